@@ -0,0 +1,557 @@
+use std::io;
+use toml_edit::{Array, DocumentMut, Item, Table, Value};
+
+fn parse(content: &str) -> io::Result<DocumentMut> {
+    content
+        .parse::<DocumentMut>()
+        .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))
+}
+
+fn parse_value(value: &str) -> io::Result<Item> {
+    let mut document = parse(&format!("value = {}", value))?;
+
+    document
+        .remove("value")
+        .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, "Missing value"))
+}
+
+/// Adds each `(name, specification)` pair to the given dependency table, leaving
+/// dependencies that are already declared untouched.
+pub fn add_dependencies(
+    content: &str,
+    table: &str,
+    dependencies: &[(&str, &str)],
+) -> io::Result<String> {
+    let mut document = parse(content)?;
+
+    if dependencies.is_empty() {
+        return Ok(document.to_string());
+    }
+
+    let table = document
+        .entry(table)
+        .or_insert_with(|| Item::Table(Table::new()))
+        .as_table_mut()
+        .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, "Expected a table"))?;
+
+    for (name, specification) in dependencies {
+        if !table.contains_key(name) {
+            table.insert(name, parse_value(specification)?);
+        }
+    }
+
+    Ok(document.to_string())
+}
+
+/// Reads a string value from the `[workspace.metadata.cargo-project]` table.
+pub fn get_metadata(content: &str, key: &str) -> io::Result<Option<String>> {
+    let document = parse(content)?;
+
+    let value = document
+        .get("workspace")
+        .and_then(|workspace| workspace.get("metadata"))
+        .and_then(|metadata| metadata.get("cargo-project"))
+        .and_then(|table| table.get(key))
+        .and_then(|value| value.as_str())
+        .map(String::from);
+
+    Ok(value)
+}
+
+fn invalid_data(message: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message)
+}
+
+/// Pushes `value` unless already present, laying it out like the array's last item.
+fn push_unique(array: &mut Array, value: &str) {
+    if array.iter().any(|item| item.as_str() == Some(value)) {
+        return;
+    }
+
+    let mut value = Value::from(value);
+
+    if let Some(last) = array.iter_mut().last() {
+        let decor = last.decor().clone();
+        let prefix = match decor.prefix().and_then(|prefix| prefix.as_str()) {
+            Some(prefix) if !prefix.is_empty() => prefix.to_string(),
+            _ => String::from(" "),
+        };
+        let suffix = decor.suffix().cloned().unwrap_or_default();
+
+        last.decor_mut().set_suffix("");
+        value.decor_mut().set_prefix(prefix);
+        value.decor_mut().set_suffix(suffix);
+    }
+
+    array.push_formatted(value);
+}
+
+/// Adds a crate to the `members` of the `[workspace]` table.
+pub fn add_workspace_member(content: &str, member: &str) -> io::Result<String> {
+    let mut document = parse(content)?;
+
+    let members = document
+        .get_mut("workspace")
+        .and_then(|workspace| workspace.get_mut("members"))
+        .and_then(|members| members.as_array_mut())
+        .ok_or_else(|| invalid_data("Missing workspace members"))?;
+
+    push_unique(members, member);
+
+    Ok(document.to_string())
+}
+
+/// Whether the `[workspace]` table declares `lints` for its members to inherit.
+pub fn has_workspace_lints(content: &str) -> io::Result<bool> {
+    let document = parse(content)?;
+
+    Ok(document
+        .get("workspace")
+        .and_then(|workspace| workspace.get("lints"))
+        .is_some())
+}
+
+/// Makes a member crate inherit the lints of the workspace.
+pub fn inherit_workspace_lints(content: &str) -> io::Result<String> {
+    let mut document = parse(content)?;
+
+    let lints = document
+        .entry("lints")
+        .or_insert_with(|| Item::Table(Table::new()))
+        .as_table_mut()
+        .ok_or_else(|| invalid_data("Expected a lints table"))?;
+    lints.insert("workspace", toml_edit::value(true));
+
+    Ok(document.to_string())
+}
+
+/// Reads the `members` of the `[workspace]` table.
+pub fn get_workspace_members(content: &str) -> io::Result<Vec<String>> {
+    let document = parse(content)?;
+
+    let members = document
+        .get("workspace")
+        .and_then(|workspace| workspace.get("members"))
+        .and_then(|members| members.as_array())
+        .map(|array| {
+            array
+                .iter()
+                .filter_map(|value| value.as_str())
+                .map(String::from)
+                .collect()
+        })
+        .unwrap_or_default();
+
+    Ok(members)
+}
+
+/// Reads the name of the package and the names of its `[dependencies]`.
+pub fn get_package_dependencies(content: &str) -> io::Result<(String, Vec<String>)> {
+    let document = parse(content)?;
+
+    let name = document
+        .get("package")
+        .and_then(|package| package.get("name"))
+        .and_then(|name| name.as_str())
+        .ok_or_else(|| invalid_data("Missing package name"))?;
+    let dependencies = document
+        .get("dependencies")
+        .and_then(|dependencies| dependencies.as_table_like())
+        .map(|table| table.iter().map(|(name, _)| name.to_string()).collect())
+        .unwrap_or_default();
+
+    Ok((name.to_string(), dependencies))
+}
+
+/// Turns the manifest of a single package into the root of a workspace listing
+/// `members`, keeping the package as it is.
+pub fn init_workspace(content: &str, members: &[&str]) -> io::Result<String> {
+    let mut document = parse(content)?;
+
+    if document.contains_key("workspace") {
+        return Err(invalid_data("Already a workspace"));
+    }
+    if !document.contains_key("package") {
+        return Err(invalid_data("Missing package table"));
+    }
+
+    let mut array = Array::new();
+    for member in members {
+        push_unique(&mut array, member);
+    }
+
+    let mut workspace = Table::new();
+    workspace.insert("members", Item::Value(Value::Array(array)));
+    document.insert("workspace", Item::Table(workspace));
+
+    Ok(document.to_string())
+}
+
+/// Reads a list of strings from the `[workspace.metadata.cargo-project]` table.
+pub fn get_metadata_list(content: &str, key: &str) -> io::Result<Vec<String>> {
+    let document = parse(content)?;
+
+    let values = document
+        .get("workspace")
+        .and_then(|workspace| workspace.get("metadata"))
+        .and_then(|metadata| metadata.get("cargo-project"))
+        .and_then(|table| table.get(key))
+        .and_then(|value| value.as_array())
+        .map(|array| {
+            array
+                .iter()
+                .filter_map(|value| value.as_str())
+                .map(String::from)
+                .collect()
+        })
+        .unwrap_or_default();
+
+    Ok(values)
+}
+
+/// The `[workspace.metadata.cargo-project]` table, created when missing.
+fn metadata_table_mut(document: &mut DocumentMut) -> io::Result<&mut Table> {
+    let workspace = document
+        .get_mut("workspace")
+        .and_then(|workspace| workspace.as_table_mut())
+        .ok_or_else(|| invalid_data("Missing workspace table"))?;
+
+    workspace
+        .entry("metadata")
+        .or_insert_with(|| {
+            let mut table = Table::new();
+            table.set_implicit(true);
+            Item::Table(table)
+        })
+        .as_table_mut()
+        .and_then(|metadata| {
+            metadata
+                .entry("cargo-project")
+                .or_insert_with(|| Item::Table(Table::new()))
+                .as_table_mut()
+        })
+        .ok_or_else(|| invalid_data("Expected a table"))
+}
+
+/// Sets a string value in the `[workspace.metadata.cargo-project]` table, creating the
+/// table when needed.
+pub fn set_metadata(content: &str, key: &str, value: &str) -> io::Result<String> {
+    let mut document = parse(content)?;
+    let table = metadata_table_mut(&mut document)?;
+
+    table.insert(key, toml_edit::value(value));
+
+    Ok(document.to_string())
+}
+
+/// Appends a string to a list in the `[workspace.metadata.cargo-project]` table,
+/// creating the table and the list when needed.
+pub fn add_metadata_list_item(content: &str, key: &str, value: &str) -> io::Result<String> {
+    let mut document = parse(content)?;
+    let table = metadata_table_mut(&mut document)?;
+
+    let list = table
+        .entry(key)
+        .or_insert_with(|| Item::Value(Value::Array(Array::new())))
+        .as_array_mut()
+        .ok_or_else(|| invalid_data("Expected an array"))?;
+
+    push_unique(list, value);
+
+    Ok(document.to_string())
+}
+
+/// Reads the named tables nested under `key` in the `[workspace.metadata.cargo-project]`
+/// table, in the order they appear.
+pub fn get_metadata_tables(content: &str, key: &str) -> io::Result<Vec<(String, Table)>> {
+    let document = parse(content)?;
+
+    let tables = match document
+        .get("workspace")
+        .and_then(|workspace| workspace.get("metadata"))
+        .and_then(|metadata| metadata.get("cargo-project"))
+        .and_then(|table| table.get(key))
+    {
+        Some(item) => item
+            .as_table()
+            .ok_or_else(|| invalid_data("Expected a table"))?,
+        None => return Ok(vec![]),
+    };
+
+    tables
+        .iter()
+        .map(|(name, item)| match item.as_table() {
+            Some(table) => Ok((name.to_string(), table.clone())),
+            None => Err(invalid_data("Expected a table")),
+        })
+        .collect()
+}
+
+/// Nests `table` as `name` under `key` in the `[workspace.metadata.cargo-project]`
+/// table, replacing any table of the same name.
+pub fn set_metadata_table(
+    content: &str,
+    key: &str,
+    name: &str,
+    table: Table,
+) -> io::Result<String> {
+    let mut document = parse(content)?;
+    let metadata = metadata_table_mut(&mut document)?;
+
+    let tables = metadata
+        .entry(key)
+        .or_insert_with(|| {
+            let mut table = Table::new();
+            table.set_implicit(true);
+            Item::Table(table)
+        })
+        .as_table_mut()
+        .ok_or_else(|| invalid_data("Expected a table"))?;
+
+    tables.insert(name, Item::Table(table));
+
+    Ok(document.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_add_dependencies() {
+        let content = "[package]
+name = \"test\"
+
+[dependencies]
+serde = \"1.0\"
+";
+
+        let expect = "[package]
+name = \"test\"
+
+[dependencies]
+serde = \"1.0\"
+async-trait = \"0.1.48\"
+
+[dev-dependencies]
+tempfile = { version = \"3\" }
+";
+        let got = add_dependencies(
+            content,
+            "dependencies",
+            &[("serde", "\"2.0\""), ("async-trait", "\"0.1.48\"")],
+        )
+        .and_then(|content| {
+            add_dependencies(
+                &content,
+                "dev-dependencies",
+                &[("tempfile", "{ version = \"3\" }")],
+            )
+        })
+        .unwrap();
+
+        assert_eq!(expect, got)
+    }
+
+    #[test]
+    fn test_get_metadata() {
+        let content = "[workspace]
+members = [\"test\"]
+
+[workspace.metadata.cargo-project]
+runtime = \"tokio\"
+";
+
+        assert_eq!(
+            Some(String::from("tokio")),
+            get_metadata(content, "runtime").unwrap()
+        );
+        assert_eq!(None, get_metadata(content, "missing").unwrap());
+        assert_eq!(None, get_metadata("[workspace]", "runtime").unwrap());
+    }
+
+    #[test]
+    fn test_add_workspace_member() {
+        let content = "[workspace]
+members = [
+    \"test\",
+    \"test-domain\",
+    \"test-data\"
+]
+";
+
+        let got = add_workspace_member(content, "test-web")
+            .and_then(|content| add_workspace_member(&content, "test-web"))
+            .unwrap();
+
+        assert_eq!(
+            "[workspace]
+members = [
+    \"test\",
+    \"test-domain\",
+    \"test-data\",
+    \"test-web\"
+]
+",
+            got
+        );
+        assert!(add_workspace_member("[package]", "test-web").is_err());
+    }
+
+    #[test]
+    fn test_workspace_lints() {
+        let workspace = "[workspace]
+members = [\"test\"]
+
+[workspace.lints.rust]
+unsafe_code = \"forbid\"
+";
+        let member = "[package]
+name = \"test\"
+";
+
+        assert!(has_workspace_lints(workspace).unwrap());
+        assert!(!has_workspace_lints(member).unwrap());
+        assert_eq!(
+            "[package]
+name = \"test\"
+
+[lints]
+workspace = true
+",
+            inherit_workspace_lints(member).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_metadata_list() {
+        let content = "[workspace]
+members = [\"test\"]
+
+[workspace.metadata.cargo-project]
+runtime = \"tokio\"
+";
+
+        let got = add_metadata_list_item(content, "presentations", "web")
+            .and_then(|content| add_metadata_list_item(&content, "presentations", "web"))
+            .and_then(|content| add_metadata_list_item(&content, "presentations", "grpc"))
+            .unwrap();
+
+        assert!(got.contains("presentations = [\"web\", \"grpc\"]"));
+        assert_eq!(
+            vec![String::from("web"), String::from("grpc")],
+            get_metadata_list(&got, "presentations").unwrap()
+        );
+        assert!(get_metadata_list(content, "presentations")
+            .unwrap()
+            .is_empty());
+    }
+
+    #[test]
+    fn test_add_metadata_list_item_without_metadata() {
+        let got =
+            add_metadata_list_item("[workspace]\nmembers = []\n", "presentations", "web").unwrap();
+
+        assert_eq!(
+            "[workspace]\nmembers = []\n\n[workspace.metadata.cargo-project]\npresentations = [\"web\"]\n",
+            got
+        );
+    }
+
+    #[test]
+    fn test_metadata_tables() {
+        let content = "[workspace]
+members = [\"test\"]
+
+[workspace.metadata.cargo-project]
+runtime = \"tokio\"
+";
+
+        let mut table = Table::new();
+        table.insert("suffix", toml_edit::value("infra"));
+
+        let got = set_metadata_table(content, "layers", "infrastructure", table).unwrap();
+
+        assert_eq!(
+            "[workspace]
+members = [\"test\"]
+
+[workspace.metadata.cargo-project]
+runtime = \"tokio\"
+
+[workspace.metadata.cargo-project.layers.infrastructure]
+suffix = \"infra\"
+",
+            got
+        );
+
+        let tables = get_metadata_tables(&got, "layers").unwrap();
+        assert_eq!(1, tables.len());
+        assert_eq!("infrastructure", tables[0].0);
+        assert_eq!(
+            Some("infra"),
+            tables[0].1.get("suffix").and_then(|item| item.as_str())
+        );
+        assert!(get_metadata_tables(content, "layers").unwrap().is_empty());
+        assert!(get_metadata_tables(content, "runtime").is_err());
+    }
+
+    #[test]
+    fn test_package_dependencies() {
+        let content = "[workspace]
+members = [\".\", \"demo-domain\"]
+
+[package]
+name = \"demo\"
+
+[dependencies]
+demo-domain = { path = \"demo-domain\" }
+structopt = \"0.3\"
+";
+
+        assert_eq!(
+            vec![".", "demo-domain"],
+            get_workspace_members(content).unwrap()
+        );
+        assert_eq!(
+            (
+                String::from("demo"),
+                vec![String::from("demo-domain"), String::from("structopt")]
+            ),
+            get_package_dependencies(content).unwrap()
+        );
+        assert!(get_package_dependencies("[workspace]\n").is_err());
+    }
+
+    #[test]
+    fn test_init_workspace() {
+        let content = "[package]
+name = \"test\"
+
+[dependencies]
+serde = \"1.0\"
+";
+
+        let got = init_workspace(content, &[".", "test-domain", "test-data"])
+            .and_then(|content| set_metadata(&content, "runtime", "tokio"))
+            .unwrap();
+
+        assert_eq!(
+            "[package]
+name = \"test\"
+
+[dependencies]
+serde = \"1.0\"
+
+[workspace]
+members = [\".\", \"test-domain\", \"test-data\"]
+
+[workspace.metadata.cargo-project]
+runtime = \"tokio\"
+",
+            got
+        );
+        assert!(init_workspace(&got, &["."]).is_err());
+        assert!(init_workspace("[dependencies]", &["."]).is_err());
+    }
+}
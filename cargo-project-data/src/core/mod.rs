@@ -0,0 +1,3 @@
+pub mod manifest;
+pub mod modules;
+pub mod stamps;
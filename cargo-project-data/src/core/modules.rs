@@ -0,0 +1,88 @@
+/// Name of the module declared by `line`, if it only declares a public module.
+fn declared_module(line: &str) -> Option<&str> {
+    line.trim()
+        .strip_prefix("pub mod ")?
+        .strip_suffix(';')
+        .map(str::trim)
+}
+
+/// Adds the module declarations in `declarations` to the mod file `content`, each before
+/// the first declaration sorting after it so the file stays as rustfmt orders it.
+/// Modules that are already declared are left untouched.
+pub fn add_mod_declarations(content: &str, declarations: &str) -> String {
+    let mut lines: Vec<&str> = match content.trim().is_empty() {
+        true => Vec::new(),
+        false => content.lines().collect(),
+    };
+
+    for declaration in declarations.lines() {
+        let module = match declared_module(declaration) {
+            Some(module) => module,
+            None => continue,
+        };
+        if lines
+            .iter()
+            .any(|line| declared_module(line) == Some(module))
+        {
+            continue;
+        }
+
+        let declared = lines
+            .iter()
+            .enumerate()
+            .filter_map(|(index, line)| declared_module(line).map(|name| (index, name)));
+        let mut position = 0;
+        for (index, name) in declared {
+            if name > module {
+                position = index;
+                break;
+            }
+            position = index + 1;
+        }
+
+        lines.insert(position, declaration);
+    }
+
+    format!("{}\n", lines.join("\n"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_add_mod_declarations() {
+        let content = "pub mod notes;\npub mod store;\n";
+
+        let got = add_mod_declarations(content, "pub mod audio;\npub mod settings;\n");
+
+        assert_eq!(
+            "pub mod audio;\npub mod notes;\npub mod settings;\npub mod store;\n",
+            got
+        );
+    }
+
+    #[test]
+    fn test_add_mod_declarations_empty() {
+        assert_eq!(
+            "pub mod notes;\n",
+            add_mod_declarations("\n", "pub mod notes;\n")
+        );
+        assert_eq!(
+            "pub mod notes;\n",
+            add_mod_declarations("", "pub mod notes;\n")
+        );
+    }
+
+    #[test]
+    fn test_add_mod_declarations_keeps_other_items() {
+        let content = "pub mod notes;\n\npub fn routes() {}\n";
+
+        let got = add_mod_declarations(content, "pub mod store;\npub mod notes;\n");
+
+        assert_eq!(
+            "pub mod notes;\npub mod store;\n\npub fn routes() {}\n",
+            got
+        );
+    }
+}
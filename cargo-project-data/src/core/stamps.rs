@@ -0,0 +1,101 @@
+use std::io;
+use toml_edit::{value, DocumentMut, Item, Table};
+
+/// File, relative to the workspace root, recording how every generated file was produced.
+pub const STAMPS_FILE: &str = ".cargo-project/stamps.toml";
+
+/// Template, template version and content hash a file was generated with, keyed by the
+/// file's path in the stamps file.
+#[derive(Debug, Clone, PartialEq)]
+pub struct StampModel {
+    pub path: String,
+    pub template: String,
+    pub version: i64,
+    pub hash: String,
+}
+
+impl StampModel {
+    fn from_table(path: &str, table: &Table) -> io::Result<Self> {
+        let invalid = |key: &str| {
+            io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("Missing `{}` in the stamp of {}", key, path),
+            )
+        };
+        let string = |key: &str| {
+            table
+                .get(key)
+                .and_then(|item| item.as_str())
+                .map(String::from)
+                .ok_or_else(|| invalid(key))
+        };
+
+        Ok(Self {
+            path: path.to_string(),
+            template: string("template")?,
+            version: table
+                .get("version")
+                .and_then(|item| item.as_integer())
+                .ok_or_else(|| invalid("version"))?,
+            hash: string("hash")?,
+        })
+    }
+
+    fn to_table(&self) -> Table {
+        let mut table = Table::new();
+        table.insert("template", value(&self.template));
+        table.insert("version", value(self.version));
+        table.insert("hash", value(&self.hash));
+        table
+    }
+}
+
+fn parse(content: &str) -> io::Result<DocumentMut> {
+    content
+        .parse::<DocumentMut>()
+        .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))
+}
+
+pub fn read_stamps(content: &str) -> io::Result<Vec<StampModel>> {
+    let document = parse(content)?;
+
+    document
+        .iter()
+        .filter_map(|(path, item)| item.as_table().map(|table| (path, table)))
+        .map(|(path, table)| StampModel::from_table(path, table))
+        .collect()
+}
+
+/// Records `stamps`, replacing the ones previously recorded for the same paths.
+pub fn add_stamps(content: &str, stamps: &[StampModel]) -> io::Result<String> {
+    let mut document = parse(content)?;
+
+    for stamp in stamps {
+        document.insert(&stamp.path, Item::Table(stamp.to_table()));
+    }
+
+    Ok(document.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_add_stamps() {
+        let stamp = StampModel {
+            path: String::from("test-domain/src/lib.rs"),
+            template: String::from("LibFileTemplate"),
+            version: 1,
+            hash: String::from("af63bd4c8601b7df"),
+        };
+
+        let content = add_stamps("", std::slice::from_ref(&stamp)).unwrap();
+
+        assert_eq!(
+            "[\"test-domain/src/lib.rs\"]\ntemplate = \"LibFileTemplate\"\nversion = 1\nhash = \"af63bd4c8601b7df\"\n",
+            content
+        );
+        assert_eq!(vec![stamp], read_stamps(&content).unwrap());
+    }
+}
@@ -1,17 +1,28 @@
 use async_std::prelude::*;
 use async_std::{
-    fs::{DirBuilder, File, OpenOptions},
+    fs::{self, DirBuilder, File},
     io,
     path::Path,
 };
 use async_trait::async_trait;
 use futures::try_join;
 
+use super::models::{FeatureModel, LayerModel};
+
+use crate::core::modules::add_mod_declarations;
+use crate::core::stamps::{add_stamps, read_stamps, StampModel, STAMPS_FILE};
+
+use crate::core::manifest::{
+    add_dependencies, add_metadata_list_item, add_workspace_member, get_metadata,
+    get_metadata_list, get_metadata_tables, has_workspace_lints, inherit_workspace_lints,
+    set_metadata_table,
+};
+
 #[async_trait]
 pub trait Datasource {
     async fn update_cli(
         &self,
-        project: &str,
+        crate_path: &str,
         name: &str,
         cli_file_content: &[u8],
         parent_mod_file_content: &[u8],
@@ -35,6 +46,28 @@ pub trait Datasource {
         models_file_content: &[u8],
         parent_mod_file_content: &[u8],
     ) -> io::Result<()>;
+    async fn update_manifest(
+        &self,
+        crate_name: &str,
+        dependencies: &[(&str, &str)],
+        dev_dependencies: &[(&str, &str)],
+    ) -> io::Result<()>;
+    async fn exists(&self, path: &str) -> bool;
+    async fn read_file(&self, path: &str) -> io::Result<String>;
+    async fn write_file(&self, path: &str, content: &[u8]) -> io::Result<()>;
+    async fn read_metadata(&self, key: &str) -> io::Result<Option<String>>;
+    async fn read_metadata_list(&self, key: &str) -> io::Result<Vec<String>>;
+    async fn add_metadata_item(&self, key: &str, value: &str) -> io::Result<()>;
+    async fn add_workspace_member(&self, member: &str) -> io::Result<()>;
+    async fn read_layers(&self) -> io::Result<Vec<LayerModel>>;
+    async fn add_layer(&self, layer: &LayerModel) -> io::Result<()>;
+    async fn read_features(&self) -> io::Result<Vec<FeatureModel>>;
+    async fn record_feature(&self, feature: &FeatureModel) -> io::Result<()>;
+    async fn read_stamps(&self) -> io::Result<Vec<StampModel>>;
+    async fn record_stamps(&self, stamps: &[StampModel]) -> io::Result<()>;
+    async fn create_directory(&self, path: &str) -> io::Result<()>;
+    async fn list_directories(&self, path: &str) -> io::Result<Vec<String>>;
+    async fn list_files(&self, path: &str) -> io::Result<Vec<String>>;
 }
 
 pub struct AddDatasource {}
@@ -56,15 +89,13 @@ impl AddDatasource {
         Ok(())
     }
 
-    async fn append_mod_file(&self, path: &Path, content: &[u8]) -> io::Result<()> {
-        let mut file = OpenOptions::new()
-            .write(true)
-            .append(true)
-            .open(path)
+    async fn add_to_mod_file(&self, path: &Path, content: &[u8]) -> io::Result<()> {
+        let existing = fs::read_to_string(path)
             .await
-            .expect(&format!("Error writing to file: {:?}", path));
+            .expect(&format!("Error reading file: {:?}", path));
+        let content = add_mod_declarations(&existing, &String::from_utf8_lossy(content));
 
-        file.write_all(content)
+        fs::write(path, content)
             .await
             .expect(&format!("Error writing to file: {:?}", path));
 
@@ -76,16 +107,16 @@ impl AddDatasource {
 impl Datasource for AddDatasource {
     async fn update_cli(
         &self,
-        project: &str,
+        crate_path: &str,
         name: &str,
         cli_file_content: &[u8],
         parent_mod_file_content: &[u8],
     ) -> io::Result<()> {
-        let path = format!("{}/src/features/mod.rs", project);
+        let path = format!("{}/src/features/mod.rs", crate_path);
         let path = Path::new(&path);
-        let future_parent_mod_file = self.append_mod_file(path, parent_mod_file_content);
+        let future_parent_mod_file = self.add_to_mod_file(path, parent_mod_file_content);
 
-        let path = format!("{}/src/features/{}.rs", project, name);
+        let path = format!("{}/src/features/{}.rs", crate_path, name);
         let path = Path::new(&path);
         let future_cli_file = self.create_and_write_file(path, cli_file_content);
 
@@ -130,7 +161,7 @@ impl Datasource for AddDatasource {
 
         let path = format!("{}-domain/src/features/mod.rs", project);
         let path = Path::new(&path);
-        let future_parent_mod_file = self.append_mod_file(path, parent_mod_file_content);
+        let future_parent_mod_file = self.add_to_mod_file(path, parent_mod_file_content);
 
         try_join!(
             future_entities_file,
@@ -174,7 +205,7 @@ impl Datasource for AddDatasource {
 
         let path = format!("{}-data/src/features/mod.rs", project);
         let path = Path::new(&path);
-        let future_parent_mod_file = self.append_mod_file(path, parent_mod_file_content);
+        let future_parent_mod_file = self.add_to_mod_file(path, parent_mod_file_content);
 
         try_join!(
             future_datasource_file,
@@ -185,4 +216,168 @@ impl Datasource for AddDatasource {
 
         Ok(())
     }
+
+    async fn update_manifest(
+        &self,
+        crate_name: &str,
+        dependencies: &[(&str, &str)],
+        dev_dependencies: &[(&str, &str)],
+    ) -> io::Result<()> {
+        let path = format!("{}/Cargo.toml", crate_name);
+        let path = Path::new(&path);
+
+        let content = fs::read_to_string(path).await?;
+        let content = add_dependencies(&content, "dependencies", dependencies)?;
+        let content = add_dependencies(&content, "dev-dependencies", dev_dependencies)?;
+
+        fs::write(path, content).await
+    }
+
+    async fn exists(&self, path: &str) -> bool {
+        Path::new(path).exists().await
+    }
+
+    async fn read_file(&self, path: &str) -> io::Result<String> {
+        fs::read_to_string(path).await
+    }
+
+    async fn write_file(&self, path: &str, content: &[u8]) -> io::Result<()> {
+        fs::write(path, content).await
+    }
+
+    async fn read_metadata(&self, key: &str) -> io::Result<Option<String>> {
+        let content = fs::read_to_string("Cargo.toml").await?;
+
+        get_metadata(&content, key)
+    }
+
+    async fn read_metadata_list(&self, key: &str) -> io::Result<Vec<String>> {
+        let content = fs::read_to_string("Cargo.toml").await?;
+
+        get_metadata_list(&content, key)
+    }
+
+    async fn add_metadata_item(&self, key: &str, value: &str) -> io::Result<()> {
+        let content = fs::read_to_string("Cargo.toml").await?;
+        let content = add_metadata_list_item(&content, key, value)?;
+
+        fs::write("Cargo.toml", content).await
+    }
+
+    /// Adds the member, making it inherit the workspace lints when there are any.
+    async fn add_workspace_member(&self, member: &str) -> io::Result<()> {
+        let content = fs::read_to_string("Cargo.toml").await?;
+        let lints = has_workspace_lints(&content)?;
+        let content = add_workspace_member(&content, member)?;
+
+        fs::write("Cargo.toml", content).await?;
+
+        if lints {
+            let path = format!("{}/Cargo.toml", member);
+            let content = fs::read_to_string(&path).await?;
+            fs::write(&path, inherit_workspace_lints(&content)?).await?;
+        }
+
+        Ok(())
+    }
+
+    async fn read_layers(&self) -> io::Result<Vec<LayerModel>> {
+        let content = fs::read_to_string("Cargo.toml").await?;
+
+        get_metadata_tables(&content, "layers")?
+            .iter()
+            .map(|(name, table)| LayerModel::from_table(name, table))
+            .collect()
+    }
+
+    async fn add_layer(&self, layer: &LayerModel) -> io::Result<()> {
+        let content = fs::read_to_string("Cargo.toml").await?;
+        let content = set_metadata_table(&content, "layers", &layer.name, layer.to_table())?;
+
+        fs::write("Cargo.toml", content).await
+    }
+
+    async fn read_features(&self) -> io::Result<Vec<FeatureModel>> {
+        let content = fs::read_to_string("Cargo.toml").await?;
+
+        get_metadata_tables(&content, "features")?
+            .iter()
+            .map(|(name, table)| FeatureModel::from_table(name, table))
+            .collect()
+    }
+
+    async fn record_feature(&self, feature: &FeatureModel) -> io::Result<()> {
+        let content = fs::read_to_string("Cargo.toml").await?;
+        let content = set_metadata_table(&content, "features", &feature.name, feature.to_table())?;
+
+        fs::write("Cargo.toml", content).await
+    }
+
+    async fn read_stamps(&self) -> io::Result<Vec<StampModel>> {
+        if !Path::new(STAMPS_FILE).exists().await {
+            return Ok(vec![]);
+        }
+
+        let content = fs::read_to_string(STAMPS_FILE).await?;
+        read_stamps(&content)
+    }
+
+    async fn record_stamps(&self, stamps: &[StampModel]) -> io::Result<()> {
+        let content = match fs::read_to_string(STAMPS_FILE).await {
+            Ok(content) => content,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => String::new(),
+            Err(err) => return Err(err),
+        };
+        let content = add_stamps(&content, stamps)?;
+
+        if let Some(parent) = Path::new(STAMPS_FILE).parent() {
+            DirBuilder::new().recursive(true).create(parent).await?;
+        }
+        fs::write(STAMPS_FILE, content).await
+    }
+
+    async fn create_directory(&self, path: &str) -> io::Result<()> {
+        DirBuilder::new().recursive(true).create(path).await
+    }
+
+    async fn list_directories(&self, path: &str) -> io::Result<Vec<String>> {
+        let mut entries = fs::read_dir(path).await?;
+        let mut directories = Vec::new();
+
+        while let Some(entry) = entries.next().await {
+            let entry = entry?;
+
+            if entry.file_type().await?.is_dir() {
+                directories.push(entry.file_name().to_string_lossy().to_string());
+            }
+        }
+
+        directories.sort();
+
+        Ok(directories)
+    }
+
+    async fn list_files(&self, path: &str) -> io::Result<Vec<String>> {
+        let mut directories = vec![path.to_string()];
+        let mut files = Vec::new();
+
+        while let Some(directory) = directories.pop() {
+            let mut entries = fs::read_dir(&directory).await?;
+
+            while let Some(entry) = entries.next().await {
+                let entry = entry?;
+                let path = format!("{}/{}", directory, entry.file_name().to_string_lossy());
+
+                if entry.file_type().await?.is_dir() {
+                    directories.push(path);
+                } else {
+                    files.push(path);
+                }
+            }
+        }
+
+        files.sort();
+
+        Ok(files)
+    }
 }
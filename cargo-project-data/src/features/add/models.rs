@@ -1,3 +1,7 @@
+use std::io;
+
+use toml_edit::{value, Array, Table};
+
 #[derive(Debug, PartialEq)]
 pub struct UpdateSuccess(String);
 
@@ -23,3 +27,165 @@ impl UpdateError {
         self.0
     }
 }
+
+/// A layer configured in `[workspace.metadata.cargo-project.layers.<name>]`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LayerModel {
+    pub name: String,
+    pub suffix: String,
+    pub kind: String,
+    pub depends_on: Vec<String>,
+    pub files: Vec<String>,
+}
+
+fn strings(table: &Table, key: &str) -> io::Result<Vec<String>> {
+    match table.get(key) {
+        Some(item) => item
+            .as_array()
+            .map(|array| {
+                array
+                    .iter()
+                    .filter_map(|value| value.as_str())
+                    .map(String::from)
+                    .collect()
+            })
+            .ok_or_else(|| {
+                io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!("Expected `{}` to be an array", key),
+                )
+            }),
+        None => Ok(vec![]),
+    }
+}
+
+impl LayerModel {
+    pub fn from_table(name: &str, table: &Table) -> io::Result<Self> {
+        let string = |key: &str, default: &str| {
+            table
+                .get(key)
+                .and_then(|item| item.as_str())
+                .unwrap_or(default)
+                .to_string()
+        };
+
+        Ok(Self {
+            name: name.to_string(),
+            suffix: string("suffix", name),
+            kind: string("kind", "lib"),
+            depends_on: strings(table, "depends-on")?,
+            files: strings(table, "files")?,
+        })
+    }
+
+    pub fn to_table(&self) -> Table {
+        let mut table = Table::new();
+        table.insert("suffix", value(&self.suffix));
+        table.insert("kind", value(&self.kind));
+        table.insert(
+            "depends-on",
+            value(self.depends_on.iter().collect::<Array>()),
+        );
+        table.insert("files", value(self.files.iter().collect::<Array>()));
+        table
+    }
+}
+
+/// A feature generated by `add feature`, recorded in
+/// `[workspace.metadata.cargo-project.features.<name>]` so its files can be rendered again.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FeatureModel {
+    pub name: String,
+    pub datasource: String,
+    pub format: Option<String>,
+    /// Usecase methods added to the feature with `add usecase`.
+    pub usecases: Vec<String>,
+}
+
+impl FeatureModel {
+    pub fn from_table(name: &str, table: &Table) -> io::Result<Self> {
+        let string = |key: &str| {
+            table
+                .get(key)
+                .and_then(|item| item.as_str())
+                .map(String::from)
+        };
+
+        Ok(Self {
+            name: name.to_string(),
+            datasource: string("datasource").unwrap_or_else(|| String::from("empty")),
+            format: string("format"),
+            usecases: strings(table, "usecases")?,
+        })
+    }
+
+    pub fn to_table(&self) -> Table {
+        let mut table = Table::new();
+        table.insert("datasource", value(&self.datasource));
+        if let Some(format) = &self.format {
+            table.insert("format", value(format));
+        }
+        if !self.usecases.is_empty() {
+            table.insert("usecases", value(self.usecases.iter().collect::<Array>()));
+        }
+        table
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_layer_table() {
+        let layer = LayerModel {
+            name: String::from("infrastructure"),
+            suffix: String::from("infra"),
+            kind: String::from("lib"),
+            depends_on: vec![String::from("domain")],
+            files: vec![String::from("adapter")],
+        };
+
+        let table = layer.to_table();
+
+        assert_eq!(
+            "suffix = \"infra\"\nkind = \"lib\"\ndepends-on = [\"domain\"]\nfiles = [\"adapter\"]\n",
+            table.to_string()
+        );
+        assert_eq!(
+            layer,
+            LayerModel::from_table("infrastructure", &table).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_layer_table_defaults() {
+        let got = LayerModel::from_table("adapters", &Table::new()).unwrap();
+
+        assert_eq!("adapters", got.suffix);
+        assert_eq!("lib", got.kind);
+        assert!(got.depends_on.is_empty() && got.files.is_empty());
+    }
+
+    #[test]
+    fn test_feature_table() {
+        let feature = FeatureModel {
+            name: String::from("notes"),
+            datasource: String::from("file"),
+            format: Some(String::from("json")),
+            usecases: vec![String::from("archive")],
+        };
+
+        let table = feature.to_table();
+
+        assert_eq!(
+            "datasource = \"file\"\nformat = \"json\"\nusecases = [\"archive\"]\n",
+            table.to_string()
+        );
+        assert_eq!(feature, FeatureModel::from_table("notes", &table).unwrap());
+
+        let feature = FeatureModel::from_table("notes", &Table::new()).unwrap();
+        assert_eq!("empty", feature.datasource);
+        assert!(feature.usecases.is_empty());
+    }
+}
@@ -0,0 +1,142 @@
+use async_std::prelude::*;
+use async_std::{fs, io, path::Path};
+use async_trait::async_trait;
+use std::env;
+
+use super::models::{SnapshotModel, SpecModel};
+
+use crate::core::manifest::get_metadata;
+
+#[async_trait]
+pub trait Datasource {
+    async fn read_spec(&self, path: &str) -> io::Result<SpecModel>;
+    async fn exists(&self, path: &str) -> bool;
+    async fn read_file(&self, path: &str) -> io::Result<String>;
+    async fn read_metadata(&self, key: &str) -> io::Result<Option<String>>;
+    async fn enter_directory(&self, path: &str) -> io::Result<()>;
+    async fn list_files(&self, path: &str) -> io::Result<Vec<String>>;
+    async fn read_snapshot(&self, path: &str, files: &[String]) -> io::Result<SnapshotModel>;
+    async fn write_file(&self, path: &str, content: &[u8]) -> io::Result<()>;
+    async fn remove_file(&self, path: &str) -> io::Result<()>;
+    async fn remove_empty_directory(&self, path: &str) -> io::Result<()>;
+    async fn remove_directory(&self, path: &str) -> io::Result<()>;
+}
+
+/// Entries left out of snapshots, as applying a spec doesn't touch them.
+const UNSNAPSHOTTED: [&str; 2] = [".git", "target"];
+
+/// Works relative to the current directory, which `enter_directory` changes.
+pub struct ApplyDatasource {}
+
+impl ApplyDatasource {
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+impl ApplyDatasource {
+    /// Directories and files under `path`, relative to it.
+    async fn read_tree(&self, path: &str) -> io::Result<(Vec<String>, Vec<String>)> {
+        let mut pending = vec![String::new()];
+        let mut directories = Vec::new();
+        let mut files = Vec::new();
+
+        while let Some(directory) = pending.pop() {
+            let mut entries = fs::read_dir(format!("{}/{}", path, directory)).await?;
+
+            while let Some(entry) = entries.next().await {
+                let entry = entry?;
+                let name = entry.file_name().to_string_lossy().to_string();
+                if directory.is_empty() && UNSNAPSHOTTED.contains(&name.as_str()) {
+                    continue;
+                }
+
+                let relative = match directory.is_empty() {
+                    true => name,
+                    false => format!("{}/{}", directory, name),
+                };
+                if entry.file_type().await?.is_dir() {
+                    directories.push(relative.clone());
+                    pending.push(relative);
+                } else {
+                    files.push(relative);
+                }
+            }
+        }
+
+        directories.sort();
+        files.sort();
+
+        Ok((directories, files))
+    }
+}
+
+impl Default for ApplyDatasource {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl Datasource for ApplyDatasource {
+    async fn read_spec(&self, path: &str) -> io::Result<SpecModel> {
+        let content = fs::read_to_string(path).await?;
+
+        SpecModel::parse(&content)
+    }
+
+    async fn exists(&self, path: &str) -> bool {
+        Path::new(path).exists().await
+    }
+
+    async fn read_file(&self, path: &str) -> io::Result<String> {
+        fs::read_to_string(path).await
+    }
+
+    async fn read_metadata(&self, key: &str) -> io::Result<Option<String>> {
+        let content = fs::read_to_string("Cargo.toml").await?;
+
+        get_metadata(&content, key)
+    }
+
+    async fn enter_directory(&self, path: &str) -> io::Result<()> {
+        env::set_current_dir(path)
+    }
+
+    async fn list_files(&self, path: &str) -> io::Result<Vec<String>> {
+        let (_, files) = self.read_tree(path).await?;
+        Ok(files)
+    }
+
+    /// Every directory under `path`, along with the content of the given `files` only.
+    async fn read_snapshot(&self, path: &str, files: &[String]) -> io::Result<SnapshotModel> {
+        let (directories, _) = self.read_tree(path).await?;
+        let mut contents = Vec::new();
+
+        for file in files {
+            let content = fs::read(format!("{}/{}", path, file)).await?;
+            contents.push((file.clone(), content));
+        }
+
+        Ok(SnapshotModel {
+            directories,
+            files: contents,
+        })
+    }
+
+    async fn write_file(&self, path: &str, content: &[u8]) -> io::Result<()> {
+        fs::write(path, content).await
+    }
+
+    async fn remove_file(&self, path: &str) -> io::Result<()> {
+        fs::remove_file(path).await
+    }
+
+    async fn remove_empty_directory(&self, path: &str) -> io::Result<()> {
+        fs::remove_dir(path).await
+    }
+
+    async fn remove_directory(&self, path: &str) -> io::Result<()> {
+        fs::remove_dir_all(path).await
+    }
+}
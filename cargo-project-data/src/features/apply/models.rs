@@ -0,0 +1,271 @@
+use std::io;
+
+use toml_edit::{value, Array, DocumentMut, Item, Table};
+
+/// The `[project]` table of a spec.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ProjectSpecModel {
+    pub name: String,
+    pub runtime: Option<String>,
+    pub kind: Option<String>,
+}
+
+/// A `[features.<name>]` table of a spec.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FeatureSpecModel {
+    pub name: String,
+    pub datasource: Option<String>,
+    pub format: Option<String>,
+    pub usecases: Vec<String>,
+    /// Fields of the feature's entity from its `entity` table, as `(name, type)` pairs.
+    pub entity: Vec<(String, String)>,
+}
+
+/// Declarative description of a project and its features, applied by `apply`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SpecModel {
+    pub project: ProjectSpecModel,
+    pub features: Vec<FeatureSpecModel>,
+}
+
+/// Directories of a project and the content of some of its files, as read before applying
+/// a spec to undo it if it fails. Paths are relative to the directory the snapshot was read
+/// from.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SnapshotModel {
+    pub directories: Vec<String>,
+    pub files: Vec<(String, Vec<u8>)>,
+}
+
+fn invalid(message: String) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message)
+}
+
+fn string(table: &Table, key: &str) -> io::Result<Option<String>> {
+    match table.get(key) {
+        Some(item) => item
+            .as_str()
+            .map(|value| Some(value.to_string()))
+            .ok_or_else(|| invalid(format!("Expected `{}` to be a string", key))),
+        None => Ok(None),
+    }
+}
+
+fn table<'a>(item: &'a Item, key: &str) -> io::Result<&'a Table> {
+    item.as_table()
+        .ok_or_else(|| invalid(format!("Expected `{}` to be a table", key)))
+}
+
+impl FeatureSpecModel {
+    fn to_table(&self) -> Table {
+        let mut table = Table::new();
+
+        if let Some(datasource) = &self.datasource {
+            table.insert("datasource", value(datasource));
+        }
+        if let Some(format) = &self.format {
+            table.insert("format", value(format));
+        }
+        if !self.usecases.is_empty() {
+            table.insert("usecases", value(self.usecases.iter().collect::<Array>()));
+        }
+        if !self.entity.is_empty() {
+            let mut entity = Table::new();
+            for (field, ty) in &self.entity {
+                entity.insert(field, value(ty));
+            }
+            table.insert("entity", Item::Table(entity));
+        }
+
+        table
+    }
+
+    fn from_table(name: &str, table: &Table) -> io::Result<Self> {
+        let usecases = match table.get("usecases") {
+            Some(item) => item
+                .as_array()
+                .ok_or_else(|| {
+                    invalid(format!(
+                        "Expected `features.{}.usecases` to be an array",
+                        name
+                    ))
+                })?
+                .iter()
+                .map(|value| {
+                    value.as_str().map(String::from).ok_or_else(|| {
+                        invalid(format!(
+                            "Expected `features.{}.usecases` to hold strings",
+                            name
+                        ))
+                    })
+                })
+                .collect::<io::Result<_>>()?,
+            None => vec![],
+        };
+
+        let entity = match table.get("entity") {
+            Some(item) => item
+                .as_table_like()
+                .ok_or_else(|| {
+                    invalid(format!("Expected `features.{}.entity` to be a table", name))
+                })?
+                .iter()
+                .map(|(field, ty)| {
+                    ty.as_str()
+                        .map(|ty| (field.to_string(), ty.to_string()))
+                        .ok_or_else(|| {
+                            invalid(format!("Expected the type of `{}` to be a string", field))
+                        })
+                })
+                .collect::<io::Result<_>>()?,
+            None => vec![],
+        };
+
+        Ok(Self {
+            name: name.to_string(),
+            datasource: string(table, "datasource")?,
+            format: string(table, "format")?,
+            usecases,
+            entity,
+        })
+    }
+}
+
+impl SpecModel {
+    pub fn parse(content: &str) -> io::Result<Self> {
+        let document = content
+            .parse::<DocumentMut>()
+            .map_err(|err| invalid(err.to_string()))?;
+
+        let project = document
+            .get("project")
+            .ok_or_else(|| invalid(String::from("Missing `project` table")))?;
+        let project = table(project, "project")?;
+        let project = ProjectSpecModel {
+            name: string(project, "name")?
+                .ok_or_else(|| invalid(String::from("Missing `project.name`")))?,
+            runtime: string(project, "runtime")?,
+            kind: string(project, "kind")?,
+        };
+
+        let features = match document.get("features") {
+            Some(features) => table(features, "features")?
+                .iter()
+                .map(|(name, item)| FeatureSpecModel::from_table(name, table(item, name)?))
+                .collect::<io::Result<_>>()?,
+            None => vec![],
+        };
+
+        Ok(Self { project, features })
+    }
+
+    /// Writes the spec in the format `parse` reads.
+    pub fn to_toml(&self) -> String {
+        let mut document = DocumentMut::new();
+
+        let mut project = Table::new();
+        project.insert("name", value(&self.project.name));
+        if let Some(runtime) = &self.project.runtime {
+            project.insert("runtime", value(runtime));
+        }
+        if let Some(kind) = &self.project.kind {
+            project.insert("kind", value(kind));
+        }
+        document.insert("project", Item::Table(project));
+
+        let mut features = Table::new();
+        features.set_implicit(true);
+        for feature in &self.features {
+            features.insert(&feature.name, Item::Table(feature.to_table()));
+        }
+        document.insert("features", Item::Table(features));
+
+        document.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        let content = r#"[project]
+name = "demo"
+runtime = "tokio"
+
+[features.notes]
+datasource = "file"
+format = "json"
+usecases = ["archive"]
+
+[features.notes.entity]
+title = "String"
+done = "bool"
+
+[features.tags]
+"#;
+
+        let got = SpecModel::parse(content).unwrap();
+
+        assert_eq!(
+            SpecModel {
+                project: ProjectSpecModel {
+                    name: String::from("demo"),
+                    runtime: Some(String::from("tokio")),
+                    kind: None,
+                },
+                features: vec![
+                    FeatureSpecModel {
+                        name: String::from("notes"),
+                        datasource: Some(String::from("file")),
+                        format: Some(String::from("json")),
+                        usecases: vec![String::from("archive")],
+                        entity: vec![
+                            (String::from("title"), String::from("String")),
+                            (String::from("done"), String::from("bool")),
+                        ],
+                    },
+                    FeatureSpecModel {
+                        name: String::from("tags"),
+                        datasource: None,
+                        format: None,
+                        usecases: vec![],
+                        entity: vec![],
+                    },
+                ],
+            },
+            got
+        );
+    }
+
+    #[test]
+    fn test_to_toml() {
+        let content = r#"[project]
+name = "demo"
+kind = "lib"
+
+[features.notes]
+datasource = "file"
+format = "toml"
+usecases = ["archive", "restore"]
+
+[features.notes.entity]
+title = "String"
+
+[features.tags]
+datasource = "empty"
+"#;
+
+        let spec = SpecModel::parse(content).unwrap();
+
+        assert_eq!(content, spec.to_toml());
+    }
+
+    #[test]
+    fn test_parse_invalid() {
+        assert!(SpecModel::parse("[features.notes]\n").is_err());
+        assert!(SpecModel::parse("[project]\nname = 1\n").is_err());
+        assert!(SpecModel::parse("[project]\nname = \"demo\"\n[features]\nnotes = 1\n").is_err());
+    }
+}
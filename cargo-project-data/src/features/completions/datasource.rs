@@ -0,0 +1,48 @@
+use async_std::prelude::*;
+use async_std::{fs, io, path::Path};
+use async_trait::async_trait;
+
+#[async_trait]
+pub trait Datasource {
+    async fn exists(&self, path: &str) -> bool;
+    async fn list_directories(&self, path: &str) -> io::Result<Vec<String>>;
+}
+
+/// Works on the workspace rooted in the current directory.
+pub struct CompletionsDatasource {}
+
+impl CompletionsDatasource {
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+impl Default for CompletionsDatasource {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl Datasource for CompletionsDatasource {
+    async fn exists(&self, path: &str) -> bool {
+        Path::new(path).exists().await
+    }
+
+    async fn list_directories(&self, path: &str) -> io::Result<Vec<String>> {
+        let mut entries = fs::read_dir(path).await?;
+        let mut directories = Vec::new();
+
+        while let Some(entry) = entries.next().await {
+            let entry = entry?;
+
+            if entry.file_type().await?.is_dir() {
+                directories.push(entry.file_name().to_string_lossy().to_string());
+            }
+        }
+
+        directories.sort();
+
+        Ok(directories)
+    }
+}
@@ -0,0 +1,112 @@
+use async_std::prelude::*;
+use async_std::{
+    fs::{self, DirBuilder},
+    io,
+    path::Path,
+};
+use async_trait::async_trait;
+
+use crate::core::manifest::{get_metadata, get_metadata_tables};
+use crate::core::stamps::{add_stamps, StampModel, STAMPS_FILE};
+use crate::features::add::models::{FeatureModel, LayerModel};
+
+#[async_trait]
+pub trait Datasource {
+    async fn exists(&self, path: &str) -> bool;
+    async fn read_file(&self, path: &str) -> io::Result<String>;
+    async fn write_file(&self, path: &str, content: &[u8]) -> io::Result<()>;
+    async fn read_metadata(&self, key: &str) -> io::Result<Option<String>>;
+    async fn read_layers(&self) -> io::Result<Vec<LayerModel>>;
+    async fn read_features(&self) -> io::Result<Vec<FeatureModel>>;
+    async fn list_directories(&self, path: &str) -> io::Result<Vec<String>>;
+    async fn record_stamps(&self, stamps: &[StampModel]) -> io::Result<()>;
+}
+
+/// Works on the workspace rooted in the current directory.
+pub struct DocsDatasource {}
+
+impl DocsDatasource {
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+impl Default for DocsDatasource {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl Datasource for DocsDatasource {
+    async fn exists(&self, path: &str) -> bool {
+        Path::new(path).exists().await
+    }
+
+    async fn read_file(&self, path: &str) -> io::Result<String> {
+        fs::read_to_string(path).await
+    }
+
+    async fn write_file(&self, path: &str, content: &[u8]) -> io::Result<()> {
+        if let Some(parent) = Path::new(path).parent() {
+            DirBuilder::new().recursive(true).create(parent).await?;
+        }
+
+        fs::write(path, content).await
+    }
+
+    async fn read_metadata(&self, key: &str) -> io::Result<Option<String>> {
+        let content = fs::read_to_string("Cargo.toml").await?;
+
+        get_metadata(&content, key)
+    }
+
+    async fn read_layers(&self) -> io::Result<Vec<LayerModel>> {
+        let content = fs::read_to_string("Cargo.toml").await?;
+
+        get_metadata_tables(&content, "layers")?
+            .iter()
+            .map(|(name, table)| LayerModel::from_table(name, table))
+            .collect()
+    }
+
+    async fn read_features(&self) -> io::Result<Vec<FeatureModel>> {
+        let content = fs::read_to_string("Cargo.toml").await?;
+
+        get_metadata_tables(&content, "features")?
+            .iter()
+            .map(|(name, table)| FeatureModel::from_table(name, table))
+            .collect()
+    }
+
+    async fn list_directories(&self, path: &str) -> io::Result<Vec<String>> {
+        let mut entries = fs::read_dir(path).await?;
+        let mut directories = Vec::new();
+
+        while let Some(entry) = entries.next().await {
+            let entry = entry?;
+
+            if entry.file_type().await?.is_dir() {
+                directories.push(entry.file_name().to_string_lossy().to_string());
+            }
+        }
+
+        directories.sort();
+
+        Ok(directories)
+    }
+
+    async fn record_stamps(&self, stamps: &[StampModel]) -> io::Result<()> {
+        let content = match fs::read_to_string(STAMPS_FILE).await {
+            Ok(content) => content,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => String::new(),
+            Err(err) => return Err(err),
+        };
+        let content = add_stamps(&content, stamps)?;
+
+        if let Some(parent) = Path::new(STAMPS_FILE).parent() {
+            DirBuilder::new().recursive(true).create(parent).await?;
+        }
+        fs::write(STAMPS_FILE, content).await
+    }
+}
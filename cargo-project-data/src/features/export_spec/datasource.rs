@@ -0,0 +1,79 @@
+use async_std::prelude::*;
+use async_std::{fs, io, path::Path};
+use async_trait::async_trait;
+
+use crate::core::manifest::{get_metadata, get_metadata_tables};
+use crate::features::add::models::FeatureModel;
+use crate::features::apply::models::SpecModel;
+
+#[async_trait]
+pub trait Datasource {
+    async fn exists(&self, path: &str) -> bool;
+    async fn read_file(&self, path: &str) -> io::Result<String>;
+    async fn read_metadata(&self, key: &str) -> io::Result<Option<String>>;
+    async fn read_features(&self) -> io::Result<Vec<FeatureModel>>;
+    async fn list_directories(&self, path: &str) -> io::Result<Vec<String>>;
+    async fn write_spec(&self, path: &str, spec: &SpecModel) -> io::Result<()>;
+}
+
+/// Works on the workspace rooted in the current directory.
+pub struct ExportSpecDatasource {}
+
+impl ExportSpecDatasource {
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+impl Default for ExportSpecDatasource {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl Datasource for ExportSpecDatasource {
+    async fn exists(&self, path: &str) -> bool {
+        Path::new(path).exists().await
+    }
+
+    async fn read_file(&self, path: &str) -> io::Result<String> {
+        fs::read_to_string(path).await
+    }
+
+    async fn read_metadata(&self, key: &str) -> io::Result<Option<String>> {
+        let content = fs::read_to_string("Cargo.toml").await?;
+
+        get_metadata(&content, key)
+    }
+
+    async fn read_features(&self) -> io::Result<Vec<FeatureModel>> {
+        let content = fs::read_to_string("Cargo.toml").await?;
+
+        get_metadata_tables(&content, "features")?
+            .iter()
+            .map(|(name, table)| FeatureModel::from_table(name, table))
+            .collect()
+    }
+
+    async fn list_directories(&self, path: &str) -> io::Result<Vec<String>> {
+        let mut entries = fs::read_dir(path).await?;
+        let mut directories = Vec::new();
+
+        while let Some(entry) = entries.next().await {
+            let entry = entry?;
+
+            if entry.file_type().await?.is_dir() {
+                directories.push(entry.file_name().to_string_lossy().to_string());
+            }
+        }
+
+        directories.sort();
+
+        Ok(directories)
+    }
+
+    async fn write_spec(&self, path: &str, spec: &SpecModel) -> io::Result<()> {
+        fs::write(path, spec.to_toml()).await
+    }
+}
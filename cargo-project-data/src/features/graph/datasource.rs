@@ -0,0 +1,96 @@
+use async_std::prelude::*;
+use async_std::{fs, io, path::Path};
+use async_trait::async_trait;
+
+use super::models::CrateModel;
+
+use crate::core::manifest::{
+    get_metadata, get_metadata_tables, get_package_dependencies, get_workspace_members,
+};
+use crate::features::add::models::LayerModel;
+
+#[async_trait]
+pub trait Datasource {
+    async fn exists(&self, path: &str) -> bool;
+    async fn read_file(&self, path: &str) -> io::Result<String>;
+    async fn read_metadata(&self, key: &str) -> io::Result<Option<String>>;
+    async fn read_layers(&self) -> io::Result<Vec<LayerModel>>;
+    async fn read_crates(&self) -> io::Result<Vec<CrateModel>>;
+    async fn list_directories(&self, path: &str) -> io::Result<Vec<String>>;
+}
+
+/// Works on the workspace rooted in the current directory.
+pub struct GraphDatasource {}
+
+impl GraphDatasource {
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+impl Default for GraphDatasource {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl Datasource for GraphDatasource {
+    async fn exists(&self, path: &str) -> bool {
+        Path::new(path).exists().await
+    }
+
+    async fn read_file(&self, path: &str) -> io::Result<String> {
+        fs::read_to_string(path).await
+    }
+
+    async fn read_metadata(&self, key: &str) -> io::Result<Option<String>> {
+        let content = fs::read_to_string("Cargo.toml").await?;
+
+        get_metadata(&content, key)
+    }
+
+    async fn read_layers(&self) -> io::Result<Vec<LayerModel>> {
+        let content = fs::read_to_string("Cargo.toml").await?;
+
+        get_metadata_tables(&content, "layers")?
+            .iter()
+            .map(|(name, table)| LayerModel::from_table(name, table))
+            .collect()
+    }
+
+    async fn read_crates(&self) -> io::Result<Vec<CrateModel>> {
+        let content = fs::read_to_string("Cargo.toml").await?;
+        let mut crates = Vec::new();
+
+        for path in get_workspace_members(&content)? {
+            let manifest = fs::read_to_string(format!("{}/Cargo.toml", path)).await?;
+            let (name, dependencies) = get_package_dependencies(&manifest)?;
+
+            crates.push(CrateModel {
+                name,
+                path,
+                dependencies,
+            });
+        }
+
+        Ok(crates)
+    }
+
+    async fn list_directories(&self, path: &str) -> io::Result<Vec<String>> {
+        let mut entries = fs::read_dir(path).await?;
+        let mut directories = Vec::new();
+
+        while let Some(entry) = entries.next().await {
+            let entry = entry?;
+
+            if entry.file_type().await?.is_dir() {
+                directories.push(entry.file_name().to_string_lossy().to_string());
+            }
+        }
+
+        directories.sort();
+
+        Ok(directories)
+    }
+}
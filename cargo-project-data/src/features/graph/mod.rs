@@ -0,0 +1,2 @@
+pub mod datasource;
+pub mod models;
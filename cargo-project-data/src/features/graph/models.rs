@@ -0,0 +1,8 @@
+/// A crate of the workspace and the names of the crates it depends on.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CrateModel {
+    pub name: String,
+    /// Directory of the crate, relative to the workspace root.
+    pub path: String,
+    pub dependencies: Vec<String>,
+}
@@ -0,0 +1,93 @@
+use async_std::{
+    fs::{self, DirBuilder},
+    io,
+    path::Path,
+};
+use async_trait::async_trait;
+
+use crate::core::manifest::{add_dependencies, init_workspace, set_metadata};
+use crate::core::stamps::{add_stamps, StampModel, STAMPS_FILE};
+
+#[async_trait]
+pub trait Datasource {
+    async fn exists(&self, path: &str) -> bool;
+    async fn write_file(&self, path: &str, content: &[u8]) -> io::Result<()>;
+    async fn init_workspace(&self, members: &[&str], metadata: &[(&str, &str)]) -> io::Result<()>;
+    async fn update_manifest(
+        &self,
+        crate_path: &str,
+        dependencies: &[(&str, &str)],
+        dev_dependencies: &[(&str, &str)],
+    ) -> io::Result<()>;
+    async fn record_stamps(&self, stamps: &[StampModel]) -> io::Result<()>;
+}
+
+/// Works on the package found in the current directory.
+pub struct InitDatasource {}
+
+impl InitDatasource {
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+impl Default for InitDatasource {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl Datasource for InitDatasource {
+    async fn exists(&self, path: &str) -> bool {
+        Path::new(path).exists().await
+    }
+
+    async fn write_file(&self, path: &str, content: &[u8]) -> io::Result<()> {
+        if let Some(parent) = Path::new(path).parent() {
+            DirBuilder::new().recursive(true).create(parent).await?;
+        }
+
+        fs::write(path, content).await
+    }
+
+    async fn init_workspace(&self, members: &[&str], metadata: &[(&str, &str)]) -> io::Result<()> {
+        let mut content = fs::read_to_string("Cargo.toml").await?;
+        content = init_workspace(&content, members)?;
+
+        for (key, value) in metadata {
+            content = set_metadata(&content, key, value)?;
+        }
+
+        fs::write("Cargo.toml", content).await
+    }
+
+    async fn update_manifest(
+        &self,
+        crate_path: &str,
+        dependencies: &[(&str, &str)],
+        dev_dependencies: &[(&str, &str)],
+    ) -> io::Result<()> {
+        let path = format!("{}/Cargo.toml", crate_path);
+
+        let content = fs::read_to_string(&path).await?;
+        let content = add_dependencies(&content, "dependencies", dependencies)?;
+        let content = add_dependencies(&content, "dev-dependencies", dev_dependencies)?;
+
+        fs::write(&path, content).await
+    }
+
+    async fn record_stamps(&self, stamps: &[StampModel]) -> io::Result<()> {
+        let content = match fs::read_to_string(STAMPS_FILE).await {
+            Ok(content) => content,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => String::new(),
+            Err(err) => return Err(err),
+        };
+        let content = add_stamps(&content, stamps)?;
+
+        if let Some(parent) = Path::new(STAMPS_FILE).parent() {
+            DirBuilder::new().recursive(true).create(parent).await?;
+        }
+        fs::write(STAMPS_FILE, content).await
+    }
+}
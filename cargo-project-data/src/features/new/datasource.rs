@@ -1,5 +1,5 @@
 use async_std::{
-    fs::{DirBuilder, File, OpenOptions},
+    fs::{self, DirBuilder, File, OpenOptions},
     io::prelude::WriteExt,
 };
 use async_trait::async_trait;
@@ -8,14 +8,32 @@ use std::io;
 use std::path::Path;
 use std::process::Command;
 
+use crate::core::manifest::{add_dependencies, inherit_workspace_lints};
+use crate::core::stamps::{add_stamps, StampModel, STAMPS_FILE};
+
 #[async_trait]
 pub trait Datasource {
+    async fn enclosing_repository(&self) -> io::Result<Option<String>>;
+    async fn create_project_directory(&self) -> io::Result<()>;
     async fn create_git_repo(&self) -> io::Result<()>;
     async fn create_gitignore(&self, content: &[u8]) -> io::Result<()>;
     async fn create_cargo_file(&self, content: &[u8]) -> io::Result<()>;
-    async fn create_presentation_layer(&self, main_file_content: &[u8]) -> io::Result<()>;
+    async fn create_presentation_layer(
+        &self,
+        root_file_content: &[u8],
+        library: bool,
+    ) -> io::Result<()>;
     async fn create_domain_layer(&self, lib_file_content: &[u8]) -> io::Result<()>;
     async fn create_data_layer(&self, lib_file_content: &[u8]) -> io::Result<()>;
+    async fn update_manifest(
+        &self,
+        crate_name: &str,
+        dependencies: &[(&str, &str)],
+        dev_dependencies: &[(&str, &str)],
+    ) -> io::Result<()>;
+    async fn inherit_workspace_lints(&self, crate_name: &str) -> io::Result<()>;
+    async fn create_file(&self, path: &str, content: &[u8]) -> io::Result<()>;
+    async fn record_stamps(&self, stamps: &[StampModel]) -> io::Result<()>;
 }
 
 pub struct NewDatasource<'a> {
@@ -41,8 +59,7 @@ impl<'a> NewDatasource<'a> {
         );
         let path = Path::new(&path);
 
-        println!("{:?}", path);
-        File::create(path).await?;
+        fs::write(path, "\n").await?;
 
         Ok(())
     }
@@ -60,6 +77,30 @@ impl<'a> NewDatasource<'a> {
 
 #[async_trait]
 impl<'a> Datasource for NewDatasource<'a> {
+    /// Root of the git repository the project gets created in, if any.
+    async fn enclosing_repository(&self) -> io::Result<Option<String>> {
+        if !self.project_path.exists() {
+            return Ok(None);
+        }
+
+        let output = Command::new("git")
+            .arg("-C")
+            .arg(self.project_path)
+            .args(["rev-parse", "--show-toplevel"])
+            .output()?;
+
+        match output.status.success() {
+            true => Ok(Some(
+                String::from_utf8_lossy(&output.stdout).trim().to_string(),
+            )),
+            false => Ok(None),
+        }
+    }
+
+    async fn create_project_directory(&self) -> io::Result<()> {
+        fs::create_dir_all(self.project_path.join(self.project_name)).await
+    }
+
     async fn create_git_repo(&self) -> io::Result<()> {
         let path = format!(
             "{}/{}",
@@ -112,7 +153,11 @@ impl<'a> Datasource for NewDatasource<'a> {
         file.write_all(content).await
     }
 
-    async fn create_presentation_layer(&self, main_file_content: &[u8]) -> io::Result<()> {
+    async fn create_presentation_layer(
+        &self,
+        root_file_content: &[u8],
+        library: bool,
+    ) -> io::Result<()> {
         let path = format!(
             "{}/{}/{}",
             self.project_path
@@ -123,7 +168,13 @@ impl<'a> Datasource for NewDatasource<'a> {
         );
 
         let path = Path::new(&path);
-        let output = Command::new("cargo").arg("new").arg(path).output()?;
+        let mut command = Command::new("cargo");
+        // Layer crates live in the project's repository, if it has any
+        command.arg("new").arg(path).args(["--vcs", "none"]);
+        if library {
+            command.arg("--lib");
+        }
+        let output = command.output()?;
 
         if !output.status.success() {
             return Err(io::Error::new(
@@ -155,17 +206,18 @@ impl<'a> Datasource for NewDatasource<'a> {
         let future_features_module = self.create_module(path);
 
         let path = format!(
-            "{}/{}/{}/src/main.rs",
+            "{}/{}/{}/src/{}",
             self.project_path
                 .to_str()
                 .expect(&format!("Error parsing path: {:?}", self.project_path)),
             self.project_name,
-            self.project_name
+            self.project_name,
+            if library { "lib.rs" } else { "main.rs" }
         );
         let path = Path::new(&path);
-        let future_main_file = self.update_file(path, main_file_content);
+        let future_root_file = self.update_file(path, root_file_content);
 
-        try_join!(future_core_module, future_features_module, future_main_file)?;
+        try_join!(future_core_module, future_features_module, future_root_file)?;
 
         Ok(())
     }
@@ -184,6 +236,7 @@ impl<'a> Datasource for NewDatasource<'a> {
             .arg("new")
             .arg(path)
             .arg("--lib")
+            .args(["--vcs", "none"])
             .output()?;
 
         if !output.status.success() {
@@ -245,6 +298,7 @@ impl<'a> Datasource for NewDatasource<'a> {
             .arg("new")
             .arg(path)
             .arg("--lib")
+            .args(["--vcs", "none"])
             .output()?;
 
         if !output.status.success() {
@@ -291,6 +345,58 @@ impl<'a> Datasource for NewDatasource<'a> {
 
         Ok(())
     }
+
+    async fn update_manifest(
+        &self,
+        crate_name: &str,
+        dependencies: &[(&str, &str)],
+        dev_dependencies: &[(&str, &str)],
+    ) -> io::Result<()> {
+        let path = self
+            .project_path
+            .join(self.project_name)
+            .join(crate_name)
+            .join("Cargo.toml");
+
+        let content = fs::read_to_string(&path).await?;
+        let content = add_dependencies(&content, "dependencies", dependencies)?;
+        let content = add_dependencies(&content, "dev-dependencies", dev_dependencies)?;
+
+        fs::write(&path, content).await
+    }
+
+    async fn inherit_workspace_lints(&self, crate_name: &str) -> io::Result<()> {
+        let path = self
+            .project_path
+            .join(self.project_name)
+            .join(crate_name)
+            .join("Cargo.toml");
+
+        let content = fs::read_to_string(&path).await?;
+        fs::write(&path, inherit_workspace_lints(&content)?).await
+    }
+
+    /// Writes a file at `path` relative to the project root, creating its directories.
+    async fn create_file(&self, path: &str, content: &[u8]) -> io::Result<()> {
+        let path = self.project_path.join(self.project_name).join(path);
+
+        if let Some(parent) = path.parent() {
+            DirBuilder::new().recursive(true).create(parent).await?;
+        }
+
+        fs::write(&path, content).await
+    }
+
+    async fn record_stamps(&self, stamps: &[StampModel]) -> io::Result<()> {
+        let path = self.project_path.join(self.project_name).join(STAMPS_FILE);
+
+        if let Some(parent) = path.parent() {
+            DirBuilder::new().recursive(true).create(parent).await?;
+        }
+
+        let content = add_stamps("", stamps)?;
+        fs::write(&path, content).await
+    }
 }
 
 #[cfg(test)]
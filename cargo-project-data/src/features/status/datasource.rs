@@ -0,0 +1,46 @@
+use async_std::{fs, io, path::Path};
+use async_trait::async_trait;
+
+use crate::core::stamps::{read_stamps, StampModel, STAMPS_FILE};
+
+#[async_trait]
+pub trait Datasource {
+    async fn exists(&self, path: &str) -> bool;
+    async fn read_file(&self, path: &str) -> io::Result<String>;
+    async fn read_stamps(&self) -> io::Result<Vec<StampModel>>;
+}
+
+/// Works on the workspace rooted in the current directory.
+pub struct StatusDatasource {}
+
+impl StatusDatasource {
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+impl Default for StatusDatasource {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl Datasource for StatusDatasource {
+    async fn exists(&self, path: &str) -> bool {
+        Path::new(path).exists().await
+    }
+
+    async fn read_file(&self, path: &str) -> io::Result<String> {
+        fs::read_to_string(path).await
+    }
+
+    async fn read_stamps(&self) -> io::Result<Vec<StampModel>> {
+        if !Path::new(STAMPS_FILE).exists().await {
+            return Ok(vec![]);
+        }
+
+        let content = fs::read_to_string(STAMPS_FILE).await?;
+        read_stamps(&content)
+    }
+}
@@ -0,0 +1,113 @@
+use async_std::path::Path;
+use async_trait::async_trait;
+use std::io;
+use std::process::{Command, Output};
+
+#[async_trait]
+pub trait Datasource {
+    async fn exists(&self, path: &str) -> bool;
+    async fn dirty_files(&self) -> io::Result<Option<Vec<String>>>;
+    async fn commit(&self, paths: &[String], message: &str) -> io::Result<()>;
+}
+
+/// Works on the git repository containing `root`, through the `git` CLI.
+pub struct VcsDatasource {
+    root: String,
+}
+
+impl VcsDatasource {
+    pub fn new(root: &str) -> Self {
+        Self {
+            root: root.to_string(),
+        }
+    }
+
+    fn git(&self, args: &[&str]) -> io::Result<Output> {
+        Command::new("git")
+            .args(args)
+            .current_dir(&self.root)
+            .output()
+    }
+}
+
+/// Paths in the NUL separated entries of `git status --porcelain=v1 -z`, renamed and copied
+/// files giving both their new and original path.
+fn status_paths(output: &str) -> Vec<String> {
+    let mut entries = output.split('\0').filter(|entry| !entry.is_empty());
+    let mut paths = Vec::new();
+
+    while let Some(entry) = entries.next() {
+        let (status, path) = match (entry.get(..2), entry.get(3..)) {
+            (Some(status), Some(path)) => (status, path),
+            _ => continue,
+        };
+        paths.push(path.to_string());
+
+        // The original path follows as an entry of its own
+        if status.contains('R') || status.contains('C') {
+            paths.extend(entries.next().map(String::from));
+        }
+    }
+
+    paths
+}
+
+fn checked(output: Output) -> io::Result<Output> {
+    match output.status.success() {
+        true => Ok(output),
+        false => Err(io::Error::other(
+            String::from_utf8_lossy(&output.stderr).trim().to_string(),
+        )),
+    }
+}
+
+#[async_trait]
+impl Datasource for VcsDatasource {
+    async fn exists(&self, path: &str) -> bool {
+        Path::new(&self.root).join(path).exists().await
+    }
+
+    /// Files with uncommitted changes, untracked ones included, or `None` outside a git
+    /// repository or when `git` isn't installed.
+    async fn dirty_files(&self) -> io::Result<Option<Vec<String>>> {
+        let output = match self.git(&["status", "--porcelain=v1", "-z", "--untracked-files=all"]) {
+            Ok(output) => output,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(None),
+            Err(err) => return Err(err),
+        };
+
+        if !output.status.success() {
+            return Ok(None);
+        }
+
+        Ok(Some(status_paths(&String::from_utf8_lossy(&output.stdout))))
+    }
+
+    /// Stages `paths` and commits only them, leaving anything else staged as it was.
+    async fn commit(&self, paths: &[String], message: &str) -> io::Result<()> {
+        let mut add = vec!["add", "--"];
+        add.extend(paths.iter().map(String::as_str));
+        checked(self.git(&add)?)?;
+
+        let mut commit = vec!["commit", "--quiet", "--message", message, "--"];
+        commit.extend(paths.iter().map(String::as_str));
+        checked(self.git(&commit)?)?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_status_paths() {
+        let output = " M src/main.rs\0R  src/new name.rs\0src/old.rs\0?? notes\n.md\0";
+
+        assert_eq!(
+            vec!["src/main.rs", "src/new name.rs", "src/old.rs", "notes\n.md"],
+            status_paths(output)
+        );
+    }
+}
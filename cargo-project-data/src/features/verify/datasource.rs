@@ -0,0 +1,68 @@
+use async_std::{fs, path::Path};
+use async_trait::async_trait;
+use std::io;
+use std::process::Command;
+
+use super::models::CargoOutputModel;
+use crate::core::stamps::{read_stamps, StampModel, STAMPS_FILE};
+
+#[async_trait]
+pub trait Datasource {
+    async fn exists(&self, path: &str) -> bool;
+    async fn read_file(&self, path: &str) -> io::Result<String>;
+    async fn read_stamps(&self) -> io::Result<Vec<StampModel>>;
+    async fn cargo(&self, args: &[&str]) -> io::Result<CargoOutputModel>;
+}
+
+/// Works on the workspace rooted in `root`, building it through the `cargo` CLI.
+pub struct VerifyDatasource {
+    root: String,
+}
+
+impl VerifyDatasource {
+    pub fn new(root: &str) -> Self {
+        Self {
+            root: root.to_string(),
+        }
+    }
+
+    fn path(&self, path: &str) -> String {
+        format!("{}/{}", self.root, path)
+    }
+}
+
+#[async_trait]
+impl Datasource for VerifyDatasource {
+    async fn exists(&self, path: &str) -> bool {
+        Path::new(&self.path(path)).exists().await
+    }
+
+    async fn read_file(&self, path: &str) -> io::Result<String> {
+        fs::read_to_string(self.path(path)).await
+    }
+
+    async fn read_stamps(&self) -> io::Result<Vec<StampModel>> {
+        let path = self.path(STAMPS_FILE);
+        if !Path::new(&path).exists().await {
+            return Ok(vec![]);
+        }
+
+        let content = fs::read_to_string(path).await?;
+        read_stamps(&content)
+    }
+
+    /// Runs `cargo` with `args` in the workspace, printing diagnostics one per line with
+    /// paths relative to the workspace root.
+    async fn cargo(&self, args: &[&str]) -> io::Result<CargoOutputModel> {
+        let output = Command::new("cargo")
+            .args(args)
+            .args(["--message-format", "short", "--color", "never"])
+            .current_dir(&self.root)
+            .output()?;
+
+        Ok(CargoOutputModel {
+            success: output.status.success(),
+            messages: String::from_utf8_lossy(&output.stderr).to_string(),
+        })
+    }
+}
@@ -0,0 +1,6 @@
+/// Outcome of a cargo command, with the diagnostics it printed in the short message format.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CargoOutputModel {
+    pub success: bool,
+    pub messages: String,
+}
@@ -1,3 +1,4 @@
+pub mod core;
 pub mod features;
 
 #[cfg(test)]
@@ -0,0 +1,163 @@
+/// Directory, relative to the workspace root, keeping the rendered baseline of every
+/// generated file.
+pub const BASELINE_DIRECTORY: &str = ".cargo-project/baselines";
+
+/// Path of the baseline recorded for the generated file at `path`.
+pub fn baseline_path(path: &str) -> String {
+    format!("{}/{}", BASELINE_DIRECTORY, path.trim_start_matches("./"))
+}
+
+/// Result of a three-way merge.
+#[derive(Debug, PartialEq)]
+pub struct Merge {
+    pub content: String,
+    /// Number of regions changed on both sides, written between conflict markers.
+    pub conflicts: usize,
+}
+
+/// For every line of `a`, the index of the line of `b` it is matched with in a longest
+/// common subsequence of both.
+fn matches(a: &[&str], b: &[&str]) -> Vec<Option<usize>> {
+    let mut lengths = vec![vec![0_usize; b.len() + 1]; a.len() + 1];
+
+    for i in (0..a.len()).rev() {
+        for j in (0..b.len()).rev() {
+            lengths[i][j] = if a[i] == b[j] {
+                lengths[i + 1][j + 1] + 1
+            } else {
+                lengths[i + 1][j].max(lengths[i][j + 1])
+            };
+        }
+    }
+
+    let mut matched = vec![None; a.len()];
+    let (mut i, mut j) = (0, 0);
+    while i < a.len() && j < b.len() {
+        if a[i] == b[j] {
+            matched[i] = Some(j);
+            i += 1;
+            j += 1;
+        } else if lengths[i + 1][j] >= lengths[i][j + 1] {
+            i += 1;
+        } else {
+            j += 1;
+        }
+    }
+
+    matched
+}
+
+/// Merges the changes made to `base` in `ours` and in `theirs` line by line. Regions
+/// changed differently on both sides keep both versions between conflict markers.
+pub fn merge(base: &str, ours: &str, theirs: &str) -> Merge {
+    let base = base.lines().collect::<Vec<_>>();
+    let ours = ours.lines().collect::<Vec<_>>();
+    let theirs = theirs.lines().collect::<Vec<_>>();
+
+    let ours_matches = matches(&base, &ours);
+    let theirs_matches = matches(&base, &theirs);
+
+    let mut lines = Vec::new();
+    let mut conflicts = 0;
+    let (mut i, mut j, mut k) = (0, 0, 0);
+
+    loop {
+        // Next line of the base left unchanged on both sides
+        let stable = (i..base.len())
+            .find_map(|index| Some((index, ours_matches[index]?, theirs_matches[index]?)));
+        let (next_i, next_j, next_k) = stable.unwrap_or((base.len(), ours.len(), theirs.len()));
+
+        let base_chunk = &base[i..next_i];
+        let ours_chunk = &ours[j..next_j];
+        let theirs_chunk = &theirs[k..next_k];
+
+        if ours_chunk == base_chunk {
+            lines.extend_from_slice(theirs_chunk);
+        } else if theirs_chunk == base_chunk || theirs_chunk == ours_chunk {
+            lines.extend_from_slice(ours_chunk);
+        } else {
+            conflicts += 1;
+            lines.push("<<<<<<< current");
+            lines.extend_from_slice(ours_chunk);
+            lines.push("=======");
+            lines.extend_from_slice(theirs_chunk);
+            lines.push(">>>>>>> upgraded");
+        }
+
+        match stable {
+            Some(_) => {
+                lines.push(base[next_i]);
+                i = next_i + 1;
+                j = next_j + 1;
+                k = next_k + 1;
+            }
+            None => break,
+        }
+    }
+
+    let content = match lines.is_empty() {
+        true => String::new(),
+        false => format!("{}\n", lines.join("\n")),
+    };
+
+    Merge { content, conflicts }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_baseline_path() {
+        assert_eq!(
+            ".cargo-project/baselines/src/features/notes.rs",
+            baseline_path("./src/features/notes.rs")
+        );
+        assert_eq!(
+            ".cargo-project/baselines/test-domain/src/lib.rs",
+            baseline_path("test-domain/src/lib.rs")
+        );
+    }
+
+    #[test]
+    fn test_merge() {
+        let base = "use a;\n\nfn one() {}\n\nfn two() {}\n";
+        let ours = "use a;\n\nfn one() { edited(); }\n\nfn two() {}\n";
+        let theirs = "use a;\nuse b;\n\nfn one() {}\n\nfn two() {}\n\nfn three() {}\n";
+
+        let got = merge(base, ours, theirs);
+
+        assert_eq!(
+            Merge {
+                content: String::from(
+                    "use a;\nuse b;\n\nfn one() { edited(); }\n\nfn two() {}\n\nfn three() {}\n"
+                ),
+                conflicts: 0,
+            },
+            got
+        );
+    }
+
+    #[test]
+    fn test_merge_conflict() {
+        let base = "fn one() {}\nfn two() {}\n";
+        let ours = "fn one() { ours(); }\nfn two() {}\n";
+        let theirs = "fn one() { theirs(); }\nfn two() {}\n";
+
+        let got = merge(base, ours, theirs);
+
+        assert_eq!(1, got.conflicts);
+        assert_eq!(
+            "<<<<<<< current\nfn one() { ours(); }\n=======\nfn one() { theirs(); }\n>>>>>>> upgraded\nfn two() {}\n",
+            got.content
+        );
+    }
+
+    #[test]
+    fn test_merge_same_change() {
+        let got = merge("a\nb\n", "a\nc\n", "a\nc\n");
+
+        assert_eq!("a\nc\n", got.content);
+        assert_eq!(0, got.conflicts);
+    }
+}
@@ -0,0 +1,282 @@
+use std::convert::TryFrom;
+use std::error::Error;
+
+use cargo_project_data::features::add::models::LayerModel;
+
+use super::project::ProjectKind;
+use super::runtime::Runtime;
+use super::templates::layer::cargo::LayerCargoTemplate;
+use super::templates::layer::file::LayerFileTemplate;
+use super::templates::lib::LibFileTemplate;
+use super::templates::main::MainFileTemplate;
+use super::templates::mod_file::ModFileTemplate;
+use super::templates::{Rendered, Template};
+
+/// A crate of the workspace and the files every feature gets in it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Layer {
+    pub name: String,
+    /// Appended to the project name to form the crate name, the project crate itself when empty.
+    pub suffix: String,
+    pub kind: ProjectKind,
+    /// Names of the layers this one may depend on.
+    pub depends_on: Vec<String>,
+    pub files: Vec<String>,
+}
+
+impl Layer {
+    /// The presentation, domain and data layers every project is created with.
+    pub fn defaults(kind: ProjectKind) -> Vec<Layer> {
+        let layer = |name: &str, suffix: &str, kind, depends_on: &[&str], files: &[&str]| Layer {
+            name: name.to_string(),
+            suffix: suffix.to_string(),
+            kind,
+            depends_on: depends_on.iter().map(|name| name.to_string()).collect(),
+            files: files.iter().map(|file| file.to_string()).collect(),
+        };
+
+        vec![
+            layer("presentation", "", kind, &["domain"], &[]),
+            layer(
+                "domain",
+                "domain",
+                ProjectKind::Library,
+                &["data"],
+                &["entities", "repository", "usecase"],
+            ),
+            layer(
+                "data",
+                "data",
+                ProjectKind::Library,
+                &[],
+                &["datasource", "models"],
+            ),
+        ]
+    }
+
+    pub fn crate_name(&self, project: &str) -> String {
+        if self.suffix.is_empty() {
+            project.to_string()
+        } else {
+            format!("{}-{}", project, self.suffix)
+        }
+    }
+
+    /// Path dependencies on the crates of the layers this one depends on, looked up in
+    /// `layers`.
+    pub fn dependencies(
+        &self,
+        project: &str,
+        layers: &[Layer],
+    ) -> Result<Vec<(String, String)>, String> {
+        self.depends_on
+            .iter()
+            .map(|name| {
+                let layer = layers
+                    .iter()
+                    .find(|layer| &layer.name == name)
+                    .ok_or_else(|| format!("Unknown layer: {}", name))?;
+                let crate_name = layer.crate_name(project);
+                let dependency = format!("{{ path = \"../{}\" }}", crate_name);
+
+                Ok((crate_name, dependency))
+            })
+            .collect()
+    }
+
+    /// Every file of the layer's crate, apart from its dependencies, as `(path, rendered)`
+    /// pairs relative to the workspace root.
+    pub fn files(
+        &self,
+        project: &str,
+        features: &[&str],
+        runtime: Runtime,
+    ) -> Result<Vec<(String, Rendered)>, Box<dyn Error>> {
+        let crate_name = self.crate_name(project);
+
+        let mut files = vec![
+            (
+                String::from("Cargo.toml"),
+                LayerCargoTemplate::new(&crate_name).rendered()?,
+            ),
+            match self.kind {
+                ProjectKind::Binary => (
+                    String::from("src/main.rs"),
+                    MainFileTemplate::new(runtime).rendered()?,
+                ),
+                ProjectKind::Library => (
+                    String::from("src/lib.rs"),
+                    LibFileTemplate::new().rendered()?,
+                ),
+            },
+            (
+                String::from("src/core/mod.rs"),
+                ModFileTemplate::new(vec![]).rendered()?,
+            ),
+            self.features_file(features)?,
+        ];
+
+        for feature in features {
+            files.extend(self.feature_module_files(feature)?);
+        }
+
+        Ok(self.located(project, files))
+    }
+
+    /// Files to write so the layer gets a newly added `feature`, which is expected to be
+    /// part of `features`.
+    pub fn feature_files(
+        &self,
+        project: &str,
+        features: &[&str],
+        feature: &str,
+    ) -> Result<Vec<(String, Rendered)>, Box<dyn Error>> {
+        let mut files = vec![self.features_file(features)?];
+        files.extend(self.feature_module_files(feature)?);
+
+        Ok(self.located(project, files))
+    }
+
+    fn located(&self, project: &str, files: Vec<(String, Rendered)>) -> Vec<(String, Rendered)> {
+        let crate_name = self.crate_name(project);
+
+        files
+            .into_iter()
+            .map(|(path, rendered)| (format!("{}/{}", crate_name, path), rendered))
+            .collect()
+    }
+
+    fn features_file(&self, features: &[&str]) -> Result<(String, Rendered), Box<dyn Error>> {
+        let rendered = ModFileTemplate::new(features.to_vec()).rendered()?;
+
+        Ok((String::from("src/features/mod.rs"), rendered))
+    }
+
+    fn feature_module_files(
+        &self,
+        feature: &str,
+    ) -> Result<Vec<(String, Rendered)>, Box<dyn Error>> {
+        let directory = format!("src/features/{}", feature);
+        let files = self.files.iter().map(String::as_str).collect();
+
+        let mut module_files = vec![(
+            format!("{}/mod.rs", directory),
+            ModFileTemplate::new(files).rendered()?,
+        )];
+
+        for file in &self.files {
+            module_files.push((
+                format!("{}/{}.rs", directory, file),
+                LayerFileTemplate::new(&self.name, feature, file).rendered()?,
+            ));
+        }
+
+        Ok(module_files)
+    }
+}
+
+impl TryFrom<LayerModel> for Layer {
+    type Error = String;
+
+    fn try_from(model: LayerModel) -> Result<Self, Self::Error> {
+        Ok(Self {
+            kind: model.kind.parse()?,
+            name: model.name,
+            suffix: model.suffix,
+            depends_on: model.depends_on,
+            files: model.files,
+        })
+    }
+}
+
+impl From<&Layer> for LayerModel {
+    fn from(layer: &Layer) -> Self {
+        Self {
+            name: layer.name.clone(),
+            suffix: layer.suffix.clone(),
+            kind: layer.kind.name().to_string(),
+            depends_on: layer.depends_on.clone(),
+            files: layer.files.clone(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn infrastructure() -> Layer {
+        Layer {
+            name: String::from("infrastructure"),
+            suffix: String::from("infra"),
+            kind: ProjectKind::Library,
+            depends_on: vec![String::from("domain")],
+            files: vec![String::from("adapter")],
+        }
+    }
+
+    #[test]
+    fn test_crate_name() {
+        let layers = Layer::defaults(ProjectKind::Binary);
+
+        let got = layers
+            .iter()
+            .map(|layer| layer.crate_name("test"))
+            .collect::<Vec<_>>();
+
+        assert_eq!(vec!["test", "test-domain", "test-data"], got);
+        assert_eq!("test-infra", infrastructure().crate_name("test"));
+    }
+
+    #[test]
+    fn test_dependencies() {
+        let layers = Layer::defaults(ProjectKind::Binary);
+
+        let got = infrastructure().dependencies("test", &layers).unwrap();
+
+        assert_eq!(
+            vec![(
+                String::from("test-domain"),
+                String::from("{ path = \"../test-domain\" }")
+            )],
+            got
+        );
+        assert_eq!(
+            Err(String::from("Unknown layer: domain")),
+            infrastructure().dependencies("test", &[])
+        );
+    }
+
+    #[test]
+    fn test_files() {
+        let got = infrastructure()
+            .files("test", &["notes"], Runtime::Tokio)
+            .unwrap();
+
+        assert_eq!(
+            vec![
+                "test-infra/Cargo.toml",
+                "test-infra/src/lib.rs",
+                "test-infra/src/core/mod.rs",
+                "test-infra/src/features/mod.rs",
+                "test-infra/src/features/notes/mod.rs",
+                "test-infra/src/features/notes/adapter.rs",
+            ],
+            got.iter()
+                .map(|(path, _)| path.as_str())
+                .collect::<Vec<_>>()
+        );
+        assert_eq!("pub mod notes;\n", got[3].1.content);
+        assert_eq!("pub mod adapter;\n", got[4].1.content);
+        assert_eq!("LayerFileTemplate", got[5].1.template);
+    }
+
+    #[test]
+    fn test_model_conversion() {
+        let layer = infrastructure();
+
+        let got = Layer::try_from(LayerModel::from(&layer)).unwrap();
+
+        assert_eq!(layer, got);
+    }
+}
@@ -1,2 +1,10 @@
+pub mod baseline;
+pub mod layer;
+pub mod presentation;
+pub mod project;
+pub mod report;
+pub mod runtime;
+pub mod source;
+pub mod stamp;
 pub mod templates;
 pub mod utils;
@@ -0,0 +1,355 @@
+use std::error::Error;
+use std::str::FromStr;
+
+use titlecase::titlecase;
+
+use super::layer::Layer;
+use super::project::ProjectKind;
+use super::runtime::Runtime;
+use super::source::{StructField, UsecaseMethod};
+use super::templates::grpc::build::GrpcBuildFileTemplate;
+use super::templates::grpc::cargo::GrpcCargoTemplate;
+use super::templates::grpc::feature::GrpcFeatureTemplate;
+use super::templates::grpc::features::GrpcFeaturesTemplate;
+use super::templates::grpc::main::GrpcMainFileTemplate;
+use super::templates::grpc::proto::GrpcProtoTemplate;
+use super::templates::mod_file::ModFileTemplate;
+use super::templates::tui::app::TuiAppTemplate;
+use super::templates::tui::cargo::TuiCargoTemplate;
+use super::templates::tui::feature::TuiFeatureTemplate;
+use super::templates::tui::features::TuiFeaturesTemplate;
+use super::templates::tui::main::TuiMainFileTemplate;
+use super::templates::wasm::cargo::WasmCargoTemplate;
+use super::templates::wasm::feature::WasmFeatureTemplate;
+use super::templates::web::cargo::WebCargoTemplate;
+use super::templates::web::feature::WebFeatureTemplate;
+use super::templates::web::features::WebFeaturesTemplate;
+use super::templates::web::main::WebMainFileTemplate;
+use super::templates::{Rendered, Template};
+
+/// A domain feature as seen by a presentation crate.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PresentationFeature {
+    pub name: String,
+    pub methods: Vec<UsecaseMethod>,
+    pub fields: Vec<StructField>,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub enum WebFramework {
+    #[default]
+    Axum,
+}
+
+impl FromStr for WebFramework {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "axum" => Ok(WebFramework::Axum),
+            _ => Err(format!("Unknown web framework: {}", s)),
+        }
+    }
+}
+
+/// Presentation crates that can be added next to the domain and data layers.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Presentation {
+    Web(WebFramework),
+    Grpc,
+    Tui,
+    Wasm,
+}
+
+impl Presentation {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Presentation::Web(_) => "web",
+            Presentation::Grpc => "grpc",
+            Presentation::Tui => "tui",
+            Presentation::Wasm => "wasm",
+        }
+    }
+
+    pub fn crate_name(&self, project: &str) -> String {
+        format!("{}-{}", project, self.name())
+    }
+
+    /// The presentation crate as a layer depending on the domain only, for checks covering
+    /// every crate of the workspace.
+    pub fn layer(&self) -> Layer {
+        let kind = match self {
+            Presentation::Wasm => ProjectKind::Library,
+            _ => ProjectKind::Binary,
+        };
+
+        Layer {
+            name: self.name().to_string(),
+            suffix: self.name().to_string(),
+            kind,
+            depends_on: vec![String::from("domain")],
+            files: vec![],
+        }
+    }
+
+    /// Every file of a new presentation crate, as `(path, rendered)` pairs relative to
+    /// the workspace root.
+    pub fn files(
+        &self,
+        project: &str,
+        features: &[PresentationFeature],
+        runtime: Runtime,
+    ) -> Result<Vec<(String, Rendered)>, Box<dyn Error>> {
+        let mut files = match self {
+            Presentation::Web(WebFramework::Axum) => vec![
+                ("Cargo.toml", WebCargoTemplate::new(project).rendered()?),
+                ("src/main.rs", WebMainFileTemplate::new().rendered()?),
+            ],
+            Presentation::Grpc => vec![
+                ("Cargo.toml", GrpcCargoTemplate::new(project).rendered()?),
+                ("build.rs", GrpcBuildFileTemplate::new().rendered()?),
+                ("src/main.rs", GrpcMainFileTemplate::new().rendered()?),
+            ],
+            Presentation::Tui => vec![
+                (
+                    "Cargo.toml",
+                    TuiCargoTemplate::new(project, runtime).rendered()?,
+                ),
+                ("src/main.rs", TuiMainFileTemplate::new().rendered()?),
+                (
+                    "src/app.rs",
+                    TuiAppTemplate::new(project, runtime).rendered()?,
+                ),
+            ],
+            Presentation::Wasm => vec![
+                (
+                    "Cargo.toml",
+                    WasmCargoTemplate::new(project, runtime).rendered()?,
+                ),
+                (
+                    "src/lib.rs",
+                    ModFileTemplate::new(vec!["features"]).rendered()?,
+                ),
+            ],
+        }
+        .into_iter()
+        .map(|(path, rendered)| (path.to_string(), rendered))
+        .collect::<Vec<_>>();
+
+        files.push(self.features_file(features)?);
+
+        for feature in features {
+            files.extend(self.feature_module_files(project, feature, runtime)?);
+        }
+
+        Ok(self.located(project, files))
+    }
+
+    /// Files to write so the presentation exposes a newly added `feature`, which is
+    /// expected to be part of `features`.
+    pub fn feature_files(
+        &self,
+        project: &str,
+        features: &[PresentationFeature],
+        feature: &PresentationFeature,
+        runtime: Runtime,
+    ) -> Result<Vec<(String, Rendered)>, Box<dyn Error>> {
+        let mut files = vec![self.features_file(features)?];
+        files.extend(self.feature_module_files(project, feature, runtime)?);
+
+        Ok(self.located(project, files))
+    }
+
+    fn located(&self, project: &str, files: Vec<(String, Rendered)>) -> Vec<(String, Rendered)> {
+        let crate_name = self.crate_name(project);
+
+        files
+            .into_iter()
+            .map(|(path, rendered)| (format!("{}/{}", crate_name, path), rendered))
+            .collect()
+    }
+
+    fn features_file(
+        &self,
+        features: &[PresentationFeature],
+    ) -> Result<(String, Rendered), Box<dyn Error>> {
+        let names = features
+            .iter()
+            .map(|feature| feature.name.as_str())
+            .collect();
+
+        let rendered = match self {
+            Presentation::Web(WebFramework::Axum) => WebFeaturesTemplate::new(names).rendered()?,
+            Presentation::Grpc => GrpcFeaturesTemplate::new(names).rendered()?,
+            Presentation::Tui => TuiFeaturesTemplate::new(names).rendered()?,
+            Presentation::Wasm => ModFileTemplate::new(names).rendered()?,
+        };
+
+        Ok((String::from("src/features/mod.rs"), rendered))
+    }
+
+    fn feature_module_files(
+        &self,
+        project: &str,
+        feature: &PresentationFeature,
+        runtime: Runtime,
+    ) -> Result<Vec<(String, Rendered)>, Box<dyn Error>> {
+        let name = feature.name.as_str();
+        let name_title_case = titlecase(name);
+        let module = format!("src/features/{}.rs", name);
+
+        let files = match self {
+            Presentation::Web(WebFramework::Axum) => vec![(
+                module,
+                WebFeatureTemplate::new(project, name, &name_title_case, &feature.methods, runtime)
+                    .rendered()?,
+            )],
+            Presentation::Grpc => vec![
+                (
+                    format!("proto/{}.proto", name),
+                    GrpcProtoTemplate::new(name, &feature.methods, &feature.fields).rendered()?,
+                ),
+                (
+                    module,
+                    GrpcFeatureTemplate::new(
+                        project,
+                        name,
+                        &name_title_case,
+                        &feature.methods,
+                        &feature.fields,
+                        runtime,
+                    )
+                    .rendered()?,
+                ),
+            ],
+            Presentation::Tui => vec![(
+                module,
+                TuiFeatureTemplate::new(project, name, &name_title_case, &feature.methods, runtime)
+                    .rendered()?,
+            )],
+            Presentation::Wasm => vec![(
+                module,
+                WasmFeatureTemplate::new(
+                    project,
+                    name,
+                    &name_title_case,
+                    &feature.methods,
+                    runtime,
+                )
+                .rendered()?,
+            )],
+        };
+
+        Ok(files)
+    }
+}
+
+impl FromStr for Presentation {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "web" => Ok(Presentation::Web(WebFramework::default())),
+            "grpc" => Ok(Presentation::Grpc),
+            "tui" => Ok(Presentation::Tui),
+            "wasm" => Ok(Presentation::Wasm),
+            _ => Err(format!("Unknown presentation: {}", s)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::core::source::UsecaseMethodKind;
+
+    fn feature(name: &str) -> PresentationFeature {
+        PresentationFeature {
+            name: name.to_string(),
+            methods: vec![UsecaseMethod {
+                name: String::from("list"),
+                kind: UsecaseMethodKind::List,
+            }],
+            fields: vec![],
+        }
+    }
+
+    #[test]
+    fn test_from_str() {
+        assert_eq!(
+            Presentation::Web(WebFramework::Axum),
+            "web".parse().unwrap()
+        );
+        assert_eq!(Presentation::Grpc, "grpc".parse().unwrap());
+        assert_eq!(Presentation::Tui, "tui".parse().unwrap());
+        assert_eq!(Presentation::Wasm, "wasm".parse().unwrap());
+        assert!("desktop".parse::<Presentation>().is_err());
+        assert!("rocket".parse::<WebFramework>().is_err());
+    }
+
+    #[test]
+    fn test_files() {
+        let presentation = Presentation::Web(WebFramework::Axum);
+        let features = vec![feature("notes"), feature("tags")];
+
+        let got = presentation
+            .files("test", &features, Runtime::Tokio)
+            .unwrap()
+            .into_iter()
+            .map(|(path, _)| path)
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            vec![
+                "test-web/Cargo.toml",
+                "test-web/src/main.rs",
+                "test-web/src/features/mod.rs",
+                "test-web/src/features/notes.rs",
+                "test-web/src/features/tags.rs",
+            ],
+            got
+        );
+    }
+
+    #[test]
+    fn test_feature_files() {
+        let presentation = Presentation::Web(WebFramework::Axum);
+        let features = vec![feature("notes"), feature("tags")];
+
+        let got = presentation
+            .feature_files("test", &features, &features[1], Runtime::Tokio)
+            .unwrap();
+
+        assert_eq!("test-web/src/features/mod.rs", got[0].0);
+        assert!(got[0].1.content.contains("pub mod notes;\npub mod tags;\n"));
+        assert_eq!("WebFeaturesTemplate", got[0].1.template);
+        assert_eq!("test-web/src/features/tags.rs", got[1].0);
+        assert!(got[1]
+            .1
+            .content
+            .contains(".route(\"/tags\", routing::get(list))"));
+    }
+
+    #[test]
+    fn test_grpc_feature_files() {
+        let presentation = Presentation::Grpc;
+        let features = vec![feature("notes")];
+
+        let got = presentation
+            .feature_files("test", &features, &features[0], Runtime::Tokio)
+            .unwrap()
+            .into_iter()
+            .map(|(path, _)| path)
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            vec![
+                "test-grpc/src/features/mod.rs",
+                "test-grpc/proto/notes.proto",
+                "test-grpc/src/features/notes.rs",
+            ],
+            got
+        );
+    }
+}
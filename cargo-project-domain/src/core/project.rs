@@ -0,0 +1,104 @@
+use std::io;
+use std::str::FromStr;
+
+/// Kind of crate generated for the project itself, next to its domain and data layers.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub enum ProjectKind {
+    /// A CLI binary with a module per feature.
+    #[default]
+    Binary,
+    /// A facade library re-exporting the public API of the domain crate.
+    Library,
+}
+
+impl ProjectKind {
+    pub fn name(&self) -> &'static str {
+        match self {
+            ProjectKind::Binary => "bin",
+            ProjectKind::Library => "lib",
+        }
+    }
+
+    pub fn is_library(&self) -> bool {
+        *self == ProjectKind::Library
+    }
+}
+
+impl FromStr for ProjectKind {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "bin" => Ok(ProjectKind::Binary),
+            "lib" => Ok(ProjectKind::Library),
+            _ => Err(format!("Unknown project kind: {}", s)),
+        }
+    }
+}
+
+/// Version control set up for a new project, mirroring `cargo new --vcs`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Vcs {
+    Git,
+    NoVcs,
+}
+
+impl Vcs {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Vcs::Git => "git",
+            Vcs::NoVcs => "none",
+        }
+    }
+}
+
+impl FromStr for Vcs {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "git" => Ok(Vcs::Git),
+            "none" => Ok(Vcs::NoVcs),
+            _ => Err(format!("Unknown version control system: {}", s)),
+        }
+    }
+}
+
+/// Parses a setting recorded in `[workspace.metadata.cargo-project]`, like the project's
+/// kind or runtime, defaulting for projects created before it was recorded.
+pub fn metadata_setting<T>(metadata: io::Result<Option<String>>) -> Result<T, String>
+where
+    T: FromStr<Err = String> + Default,
+{
+    match metadata {
+        Ok(Some(value)) => value.parse(),
+        Ok(None) => Ok(T::default()),
+        Err(err) => Err(err.to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_str() {
+        assert_eq!(ProjectKind::Binary, "bin".parse().unwrap());
+        assert_eq!(ProjectKind::Library, "lib".parse().unwrap());
+        assert!("dylib".parse::<ProjectKind>().is_err());
+        assert_eq!(Vcs::Git, "git".parse().unwrap());
+        assert_eq!(Vcs::NoVcs, "none".parse().unwrap());
+        assert!("hg".parse::<Vcs>().is_err());
+    }
+
+    #[test]
+    fn test_metadata_setting() {
+        assert_eq!(
+            Ok(ProjectKind::Library),
+            metadata_setting(Ok(Some(String::from("lib"))))
+        );
+        assert_eq!(Ok(ProjectKind::Binary), metadata_setting(Ok(None)));
+        assert!(metadata_setting::<ProjectKind>(Ok(Some(String::from("dylib")))).is_err());
+        assert!(metadata_setting::<ProjectKind>(Err(io::Error::other("unreadable"))).is_err());
+    }
+}
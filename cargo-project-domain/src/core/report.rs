@@ -0,0 +1,188 @@
+use std::fmt;
+
+use serde::Serialize;
+
+/// Why a command failed, so tools reading the JSON output can tell failures apart.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum ErrorCode {
+    /// The command has to be run from the root of a project or package.
+    NotInProject,
+    /// A name or other argument given to the command is invalid.
+    InvalidArgument,
+    /// What the command adds is already there.
+    AlreadyExists,
+    /// The command was cancelled before changing anything.
+    Cancelled,
+    /// The worktree has uncommitted changes the command could mix its own with.
+    DirtyWorktree,
+    /// The project, as generated or changed, doesn't compile.
+    CompileFailed,
+    /// Reading, rendering or writing the project failed.
+    Failed,
+}
+
+/// Outcome of a command: the message shown to people along with what it changed,
+/// printed as is for tools with `--message-format json`.
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct Report {
+    pub success: bool,
+    /// First line of the text output.
+    pub message: String,
+    /// Further lines of the text output, like the steps taken.
+    pub details: Vec<String>,
+    /// Files and directories created, relative to the directory the command ran in.
+    pub created: Vec<String>,
+    pub modified: Vec<String>,
+    /// Files or items left alone, like those already up to date.
+    pub skipped: Vec<String>,
+    pub warnings: Vec<String>,
+    pub notes: Vec<String>,
+    pub error: Option<ErrorCode>,
+}
+
+impl Report {
+    pub fn success(message: impl Into<String>) -> Self {
+        Self {
+            success: true,
+            message: message.into(),
+            ..Self::default()
+        }
+    }
+
+    pub fn failure(error: ErrorCode, message: impl Into<String>) -> Self {
+        Self {
+            success: false,
+            message: message.into(),
+            error: Some(error),
+            ..Self::default()
+        }
+    }
+
+    /// Turns the report into a failure, keeping the files written and the warnings given
+    /// before it failed, so they can be undone.
+    pub fn failed(self, error: ErrorCode, message: impl Into<String>) -> Self {
+        Self {
+            created: self.created,
+            modified: self.modified,
+            warnings: self.warnings,
+            ..Self::failure(error, message)
+        }
+    }
+
+    pub fn not_in_project() -> Self {
+        Self::failure(
+            ErrorCode::NotInProject,
+            "Not currently in the root of a project",
+        )
+    }
+
+    /// Records the file written at `path` as created, or as modified when it `existed`
+    /// before the command first wrote it.
+    pub fn written(&mut self, path: &str, existed: bool) {
+        if self
+            .created
+            .iter()
+            .chain(&self.modified)
+            .any(|written| written == path)
+        {
+            return;
+        }
+
+        match existed {
+            true => self.modified.push(path.to_string()),
+            false => self.created.push(path.to_string()),
+        }
+    }
+
+    /// Adds the changes and warnings of a command run as part of this one.
+    pub fn merge(&mut self, other: &Report) {
+        for path in &other.created {
+            self.written(path, false);
+        }
+        for path in &other.modified {
+            self.written(path, true);
+        }
+        self.skipped.extend(other.skipped.iter().cloned());
+        self.warnings.extend(other.warnings.iter().cloned());
+    }
+}
+
+impl fmt::Display for Report {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.message)?;
+
+        for detail in &self.details {
+            write!(f, "\n  {}", detail)?;
+        }
+        for warning in &self.warnings {
+            write!(f, "\nwarning: {}", warning)?;
+        }
+        for note in &self.notes {
+            write!(f, "\nnote: {}", note)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_display() {
+        let mut report = Report::success("Successfully applied spec: spec.toml");
+        report.details.push(String::from("added feature: notes"));
+        report.warnings.push(String::from("runtime differs"));
+        report.notes.push(String::from("nothing else to do"));
+
+        assert_eq!(
+            "Successfully applied spec: spec.toml\n  added feature: notes\nwarning: runtime differs\nnote: nothing else to do",
+            report.to_string()
+        );
+        assert_eq!(
+            "Not currently in the root of a project",
+            Report::not_in_project().to_string()
+        );
+    }
+
+    #[test]
+    fn test_merge() {
+        let mut report = Report::success("Successfully applied spec: spec.toml");
+        report.written("test/Cargo.toml", false);
+
+        let mut added = Report::success("Successfully added feature: notes");
+        added.written("test/Cargo.toml", true);
+        added.written("test/src/features/notes.rs", false);
+        added.warnings.push(String::from("uses async_std"));
+        report.merge(&added);
+
+        assert_eq!(
+            vec![
+                String::from("test/Cargo.toml"),
+                String::from("test/src/features/notes.rs"),
+            ],
+            report.created
+        );
+        assert!(report.modified.is_empty());
+        assert_eq!(vec![String::from("uses async_std")], report.warnings);
+    }
+
+    #[test]
+    fn test_failed() {
+        let mut report = Report::success("Successfully added feature: notes");
+        report.written("test/src/features/notes.rs", false);
+        report.details.push(String::from("added feature: notes"));
+
+        let got = report.failed(ErrorCode::Failed, "Failed to add feature: notes");
+
+        assert!(!got.success);
+        assert_eq!(Some(ErrorCode::Failed), got.error);
+        assert_eq!("Failed to add feature: notes", got.to_string());
+        assert_eq!(
+            vec![String::from("test/src/features/notes.rs")],
+            got.created
+        );
+    }
+}
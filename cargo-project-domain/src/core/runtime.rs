@@ -0,0 +1,142 @@
+use serde::Serialize;
+use std::str::FromStr;
+
+/// Async runtime the generated project is built around.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub enum Runtime {
+    Tokio,
+    #[default]
+    AsyncStd,
+    Sync,
+}
+
+/// Snippets templates interpolate to stay agnostic of the chosen runtime.
+#[derive(Debug, Serialize)]
+pub struct RuntimeContext {
+    pub is_async: bool,
+    pub is_tokio: bool,
+    pub is_async_std: bool,
+    pub async_keyword: &'static str,
+    pub await_keyword: &'static str,
+    pub async_trait: &'static str,
+    pub main_attribute: &'static str,
+    pub test_attribute: &'static str,
+}
+
+impl Runtime {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Runtime::Tokio => "tokio",
+            Runtime::AsyncStd => "async-std",
+            Runtime::Sync => "sync",
+        }
+    }
+
+    pub fn is_async(&self) -> bool {
+        *self != Runtime::Sync
+    }
+
+    pub fn context(&self) -> RuntimeContext {
+        let is_async = self.is_async();
+
+        RuntimeContext {
+            is_async,
+            is_tokio: *self == Runtime::Tokio,
+            is_async_std: *self == Runtime::AsyncStd,
+            async_keyword: if is_async { "async " } else { "" },
+            await_keyword: if is_async { ".await" } else { "" },
+            async_trait: if is_async { "#[async_trait]\n" } else { "" },
+            main_attribute: match self {
+                Runtime::Tokio => "#[tokio::main]\n",
+                Runtime::AsyncStd => "#[async_std::main]\n",
+                Runtime::Sync => "",
+            },
+            test_attribute: match self {
+                Runtime::Tokio => "#[tokio::test]",
+                Runtime::AsyncStd => "#[async_std::test]",
+                Runtime::Sync => "#[test]",
+            },
+        }
+    }
+
+    /// Dependencies of the binary crate hosting `main`.
+    pub fn main_dependencies(&self) -> Vec<(&'static str, &'static str)> {
+        match self {
+            Runtime::Tokio => vec![(
+                "tokio",
+                "{ version = \"1\", features = [\"macros\", \"rt-multi-thread\"] }",
+            )],
+            Runtime::AsyncStd => vec![(
+                "async-std",
+                "{ version = \"1.9.0\", features = [\"attributes\"] }",
+            )],
+            Runtime::Sync => vec![],
+        }
+    }
+
+    /// Dependencies of every library layer declaring async traits.
+    pub fn dependencies(&self) -> Vec<(&'static str, &'static str)> {
+        match self {
+            Runtime::Sync => vec![],
+            _ => vec![("async-trait", "\"0.1.48\"")],
+        }
+    }
+
+    /// Dependencies needed to run the async tests generated into a layer.
+    pub fn dev_dependencies(&self) -> Vec<(&'static str, &'static str)> {
+        match self {
+            Runtime::Tokio => vec![(
+                "tokio",
+                "{ version = \"1\", features = [\"macros\", \"rt\"] }",
+            )],
+            Runtime::AsyncStd => vec![(
+                "async-std",
+                "{ version = \"1.9.0\", features = [\"attributes\"] }",
+            )],
+            Runtime::Sync => vec![],
+        }
+    }
+}
+
+impl FromStr for Runtime {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "tokio" => Ok(Runtime::Tokio),
+            "async-std" => Ok(Runtime::AsyncStd),
+            "sync" => Ok(Runtime::Sync),
+            _ => Err(format!("Unknown runtime: {}", s)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_str() {
+        assert_eq!(Runtime::Tokio, "tokio".parse().unwrap());
+        assert_eq!(Runtime::AsyncStd, "async-std".parse().unwrap());
+        assert_eq!(Runtime::Sync, "sync".parse().unwrap());
+        assert!("smol".parse::<Runtime>().is_err());
+    }
+
+    #[test]
+    fn test_name_round_trip() {
+        for runtime in &[Runtime::Tokio, Runtime::AsyncStd, Runtime::Sync] {
+            assert_eq!(*runtime, runtime.name().parse().unwrap());
+        }
+    }
+
+    #[test]
+    fn test_sync_context() {
+        let context = Runtime::Sync.context();
+
+        assert!(!context.is_async);
+        assert_eq!("", context.async_keyword);
+        assert_eq!("", context.await_keyword);
+        assert_eq!("#[test]", context.test_attribute);
+    }
+}
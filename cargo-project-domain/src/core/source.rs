@@ -0,0 +1,750 @@
+use super::utils::block_range;
+
+/// Shape of a usecase method, used to decide how presentations expose it.
+#[derive(Debug, Clone, PartialEq)]
+pub enum UsecaseMethodKind {
+    Get,
+    Save,
+    List,
+    Action,
+    Other,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct UsecaseMethod {
+    pub name: String,
+    pub kind: UsecaseMethodKind,
+}
+
+impl UsecaseMethod {
+    fn new(name: &str, signature: &str) -> Self {
+        let signature: String = signature.split_whitespace().collect::<Vec<_>>().join(" ");
+
+        let kind = match name {
+            "get" if signature.contains("(&self, id: &str)") => UsecaseMethodKind::Get,
+            "save" if signature.contains("(&self, id: &str, entity: &") => UsecaseMethodKind::Save,
+            "list" if signature.contains("(&self)") => UsecaseMethodKind::List,
+            _ if signature.contains("(&self) -> Result<(), String>") => UsecaseMethodKind::Action,
+            _ => UsecaseMethodKind::Other,
+        };
+
+        Self {
+            name: name.to_string(),
+            kind,
+        }
+    }
+}
+
+/// Modules tied to a runtime or an operating system, which aren't usable from targets
+/// like `wasm32-unknown-unknown`.
+const RUNTIME_SPECIFIC_MODULES: &[&str] = &[
+    "async_std::fs",
+    "async_std::net",
+    "async_std::os",
+    "async_std::process",
+    "tokio::fs",
+    "tokio::net",
+    "tokio::process",
+    "tokio::runtime",
+    "std::fs",
+    "std::net",
+    "std::process",
+    "std::thread",
+];
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct StructField {
+    pub name: String,
+    pub ty: String,
+}
+
+pub fn has_method(methods: &[UsecaseMethod], kind: UsecaseMethodKind) -> bool {
+    methods.iter().any(|method| method.kind == kind)
+}
+
+pub fn method_names(methods: &[UsecaseMethod], kind: UsecaseMethodKind) -> Vec<&str> {
+    methods
+        .iter()
+        .filter(|method| method.kind == kind)
+        .map(|method| method.name.as_str())
+        .collect()
+}
+
+/// Lists the methods declared by the `Usecase` trait of a generated usecase file.
+pub fn usecase_methods(source: &str) -> Vec<UsecaseMethod> {
+    let body = match block_range(source, "pub trait Usecase {") {
+        Some(range) => &source[range],
+        None => return Vec::new(),
+    };
+
+    body.split(';')
+        .filter_map(|declaration| {
+            let start = declaration.find("fn ")? + "fn ".len();
+            let declaration = &declaration[start..];
+            let end = declaration.find('(')?;
+
+            Some(UsecaseMethod::new(
+                declaration[..end].trim(),
+                &declaration[end..],
+            ))
+        })
+        .collect()
+}
+
+/// Lists the named fields of `pub struct {name}`, with whitespace removed from their types.
+pub fn struct_fields(source: &str, name: &str) -> Vec<StructField> {
+    let body = match block_range(source, &format!("pub struct {} {{", name)) {
+        Some(range) => &source[range],
+        None => return Vec::new(),
+    };
+
+    let body = body
+        .lines()
+        .map(|line| line.split("//").next().unwrap_or_default().trim())
+        .filter(|line| !line.starts_with("#["))
+        .collect::<String>();
+
+    let mut declarations = Vec::new();
+    let mut depth = 0;
+    let mut start = 0;
+
+    for (index, character) in body.char_indices() {
+        match character {
+            '<' | '(' | '[' => depth += 1,
+            '>' | ')' | ']' => depth -= 1,
+            ',' if depth == 0 => {
+                declarations.push(&body[start..index]);
+                start = index + 1;
+            }
+            _ => (),
+        }
+    }
+    declarations.push(&body[start..]);
+
+    declarations
+        .into_iter()
+        .filter_map(|declaration| {
+            let (name, ty) = declaration.split_once(':')?;
+            let name = name.trim();
+            let name = name.strip_prefix("pub(crate)").unwrap_or(name);
+            let name = name.strip_prefix("pub").unwrap_or(name);
+
+            Some(StructField {
+                name: name.trim().to_string(),
+                ty: ty.split_whitespace().collect(),
+            })
+        })
+        .collect()
+}
+
+fn is_identifier(character: char) -> bool {
+    character.is_alphanumeric() || character == '_'
+}
+
+/// Whether `path` appears in `source` as a whole path, so `std::fs` doesn't match
+/// `async_std::fs` nor `std::fsx`.
+fn contains_path(source: &str, path: &str) -> bool {
+    source.match_indices(path).any(|(index, _)| {
+        let before = source[..index].chars().next_back();
+        let after = source[index + path.len()..].chars().next();
+
+        !before.is_some_and(|c| is_identifier(c) || c == ':') && !after.is_some_and(is_identifier)
+    })
+}
+
+fn uses_module(source: &str, module: &str) -> bool {
+    if contains_path(source, module) {
+        return true;
+    }
+
+    // Also catch grouped imports like `use async_std::{fs, io};`
+    let (parent, item) = match module.rsplit_once("::") {
+        Some(split) => split,
+        None => return false,
+    };
+    let prefix = format!("use {}::{{", parent);
+
+    source
+        .lines()
+        .filter_map(|line| line.trim().strip_prefix(prefix.as_str()))
+        .flat_map(|group| group.split([',', '}']))
+        .map(str::trim)
+        .any(|path| path == item || path.starts_with(&format!("{}::", item)))
+}
+
+/// Lists the runtime specific modules used by a source file.
+pub fn runtime_specific_modules(source: &str) -> Vec<&'static str> {
+    RUNTIME_SPECIFIC_MODULES
+        .iter()
+        .copied()
+        .filter(|module| uses_module(source, module))
+        .collect()
+}
+
+/// Kind of a top-level item of a module.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ItemKind {
+    Use,
+    Struct,
+    Enum,
+    Trait,
+    Fn,
+    Impl,
+    Mod,
+    Other,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct SourceItem {
+    pub kind: ItemKind,
+    /// Declared name, or the implementing type of an impl block.
+    pub name: String,
+    /// Source of the item, including its doc comments and attributes.
+    pub text: String,
+}
+
+/// Index right after the literal or comment starting at `index`, if there is one.
+fn skip_literal(bytes: &[u8], index: usize) -> Option<usize> {
+    let find = |from: usize, pattern: &[u8]| {
+        bytes[from.min(bytes.len())..]
+            .windows(pattern.len())
+            .position(|window| window == pattern)
+            .map(|position| from + position + pattern.len())
+            .unwrap_or(bytes.len())
+    };
+
+    match (bytes[index], bytes.get(index + 1)) {
+        (b'/', Some(b'/')) => Some(find(index, b"\n")),
+        (b'/', Some(b'*')) => Some(find(index + 2, b"*/")),
+        (b'"', _) => {
+            let mut end = index + 1;
+            while end < bytes.len() && bytes[end] != b'"' {
+                end += if bytes[end] == b'\\' { 2 } else { 1 };
+            }
+            Some(end + 1)
+        }
+        (b'r', Some(b'"' | b'#')) if index == 0 || !is_identifier(bytes[index - 1] as char) => {
+            let hashes = bytes[index + 1..]
+                .iter()
+                .take_while(|byte| **byte == b'#')
+                .count();
+            if bytes.get(index + 1 + hashes) != Some(&b'"') {
+                return None;
+            }
+            let closing = [vec![b'"'], vec![b'#'; hashes]].concat();
+            Some(find(index + 2 + hashes, &closing))
+        }
+        // Char literals, as opposed to lifetimes
+        (b'\'', Some(b'\\')) => Some(find(index + 3, b"'")),
+        (b'\'', Some(_)) if bytes.get(index + 2) == Some(&b'\'') => Some(index + 3),
+        _ => None,
+    }
+}
+
+/// Strips the leading comments and attributes of an item.
+fn item_header(text: &str) -> &str {
+    let mut header = text.trim_start();
+
+    loop {
+        if header.starts_with("//") {
+            header = header.split_once('\n').map_or("", |(_, rest)| rest);
+        } else if header.starts_with("/*") {
+            header = header.split_once("*/").map_or("", |(_, rest)| rest);
+        } else if header.starts_with('#') {
+            let mut depth = 0;
+            let end = header
+                .char_indices()
+                .find(|(_, character)| {
+                    match character {
+                        '[' => depth += 1,
+                        ']' => depth -= 1,
+                        _ => return false,
+                    }
+                    depth == 0
+                })
+                .map_or(header.len(), |(index, _)| index + 1);
+            header = &header[end..];
+        } else {
+            return header;
+        }
+
+        header = header.trim_start();
+    }
+}
+
+fn leading_identifier(text: &str) -> &str {
+    let end = text
+        .find(|character| !is_identifier(character))
+        .unwrap_or(text.len());
+    &text[..end]
+}
+
+/// The implementing type of an impl block header, `Test` for `impl<T> From<T> for Test<T>`.
+fn impl_type(header: &str) -> String {
+    let header = header.split(['{', ';']).next().unwrap_or_default();
+    let header = header.split(" where").next().unwrap_or_default();
+    let mut header = header.trim_start().trim_start_matches("impl");
+
+    if header.starts_with('<') {
+        let mut depth = 0;
+        let end = header
+            .char_indices()
+            .find(|(_, character)| {
+                match character {
+                    '<' => depth += 1,
+                    '>' => depth -= 1,
+                    _ => return false,
+                }
+                depth == 0
+            })
+            .map_or(header.len(), |(index, _)| index + 1);
+        header = &header[end..];
+    }
+
+    let ty = header.rsplit(" for ").next().unwrap_or_default().trim();
+    let ty = ty.trim_start_matches('&').trim_start_matches("mut ");
+    let ty = ty
+        .trim_start_matches("dyn ")
+        .split('<')
+        .next()
+        .unwrap_or_default();
+    let ty = ty.rsplit("::").next().unwrap_or_default();
+
+    leading_identifier(ty).to_string()
+}
+
+impl SourceItem {
+    fn new(text: &str) -> Self {
+        let header = item_header(text);
+        let mut words = header.split_whitespace().peekable();
+        let mut kind = ItemKind::Other;
+        let mut name = "";
+
+        while let Some(word) = words.next() {
+            kind = match word {
+                "pub" | "async" | "unsafe" | "default" | "extern" => continue,
+                _ if word.starts_with("pub(") || word.starts_with('"') => continue,
+                "const" if words.peek() == Some(&"fn") => continue,
+                "use" => ItemKind::Use,
+                "struct" | "union" => ItemKind::Struct,
+                "enum" => ItemKind::Enum,
+                "trait" => ItemKind::Trait,
+                "fn" => ItemKind::Fn,
+                "impl" => ItemKind::Impl,
+                _ if word.starts_with("impl<") => ItemKind::Impl,
+                "mod" => ItemKind::Mod,
+                "const" | "static" | "type" => ItemKind::Other,
+                _ => break,
+            };
+
+            name = match kind {
+                ItemKind::Use | ItemKind::Impl => "",
+                _ => leading_identifier(words.next().unwrap_or_default()),
+            };
+            break;
+        }
+
+        Self {
+            name: match kind {
+                ItemKind::Impl => impl_type(header),
+                _ => name.to_string(),
+            },
+            kind,
+            text: text.to_string(),
+        }
+    }
+
+    /// The item without its leading doc comments and attributes.
+    pub fn header(&self) -> &str {
+        item_header(&self.text)
+    }
+
+    /// Visibility the item is declared with, empty when private.
+    pub fn visibility(&self) -> &str {
+        match self.header().split_whitespace().next() {
+            Some(word) if word == "pub" || word.starts_with("pub(") => word,
+            _ => "",
+        }
+    }
+
+    /// The item's source declared with `visibility` instead.
+    pub fn with_visibility(&self, visibility: &str) -> String {
+        let header = self.header();
+        let attributes = &self.text[..self.text.len() - header.len()];
+        let declaration = header
+            .strip_prefix(self.visibility())
+            .unwrap_or(header)
+            .trim_start();
+
+        match visibility {
+            "" => format!("{}{}", attributes, declaration),
+            _ => format!("{}{} {}", attributes, visibility, declaration),
+        }
+    }
+}
+
+/// Splits a module into its top-level items. Braces in comments and literals are skipped,
+/// which is enough for the code rustfmt lays out.
+pub fn source_items(source: &str) -> Vec<SourceItem> {
+    let bytes = source.as_bytes();
+    let mut items = Vec::new();
+    let mut start = 0;
+    let mut depth = 0;
+    let mut index = 0;
+
+    while index < bytes.len() {
+        if let Some(end) = skip_literal(bytes, index) {
+            index = end;
+            continue;
+        }
+
+        let end = match bytes[index] {
+            b'{' | b'(' | b'[' => {
+                depth += 1;
+                None
+            }
+            b')' | b']' => {
+                depth -= 1;
+                None
+            }
+            b'}' => {
+                depth -= 1;
+                let next = source[index + 1..].trim_start().chars().next();
+                (depth == 0 && next != Some(';')).then_some(index + 1)
+            }
+            b';' if depth == 0 => Some(index + 1),
+            _ => None,
+        };
+
+        if let Some(end) = end {
+            let text = source[start..end].trim();
+            if !text.is_empty() {
+                items.push(SourceItem::new(text));
+            }
+            start = end;
+        }
+
+        index += 1;
+    }
+
+    let text = source[start..].trim();
+    if !text.is_empty() {
+        items.push(SourceItem::new(text));
+    }
+
+    items
+}
+
+/// Every identifier-like word of `source`.
+pub fn identifiers(source: &str) -> Vec<&str> {
+    source
+        .split(|character: char| !is_identifier(character))
+        .filter(|word| !word.is_empty())
+        .collect()
+}
+
+fn use_tree_names(tree: &str, parent: &str, names: &mut Vec<String>) {
+    let tree = tree.trim();
+
+    if let Some((prefix, group)) = tree.split_once('{') {
+        let group = group.rsplit_once('}').map_or(group, |(group, _)| group);
+        let prefix = prefix.trim_end_matches("::");
+        let parent = prefix.rsplit("::").next().unwrap_or(parent);
+        let mut depth = 0;
+        let mut start = 0;
+
+        for (index, character) in group.char_indices() {
+            match character {
+                '{' => depth += 1,
+                '}' => depth -= 1,
+                ',' if depth == 0 => {
+                    use_tree_names(&group[start..index], parent, names);
+                    start = index + 1;
+                }
+                _ => (),
+            }
+        }
+        use_tree_names(&group[start..], parent, names);
+    } else if let Some((_, alias)) = tree.split_once(" as ") {
+        names.push(alias.trim().to_string());
+    } else if !tree.is_empty() {
+        let name = tree.rsplit("::").next().unwrap_or(tree);
+        names.push(if name == "self" { parent } else { name }.to_string());
+    }
+}
+
+/// Names a use declaration brings into scope, `*` for glob imports.
+pub fn use_names(text: &str) -> Vec<String> {
+    let header = item_header(text);
+    let tree = header
+        .split_once("use ")
+        .map_or("", |(_, tree)| tree)
+        .trim_end()
+        .trim_end_matches(';');
+    let mut names = Vec::new();
+
+    use_tree_names(tree, "", &mut names);
+
+    names
+}
+
+fn use_tree_paths(tree: &str, prefix: &str, paths: &mut Vec<String>) {
+    let tree = tree.trim();
+    let join = |path: &str| match prefix.is_empty() {
+        true => path.to_string(),
+        false => format!("{}::{}", prefix, path),
+    };
+
+    if let Some((head, group)) = tree.split_once('{') {
+        let group = group.rsplit_once('}').map_or(group, |(group, _)| group);
+        let prefix = join(head.trim().trim_end_matches("::"));
+        let mut depth = 0;
+        let mut start = 0;
+
+        for (index, character) in group.char_indices() {
+            match character {
+                '{' => depth += 1,
+                '}' => depth -= 1,
+                ',' if depth == 0 => {
+                    use_tree_paths(&group[start..index], &prefix, paths);
+                    start = index + 1;
+                }
+                _ => (),
+            }
+        }
+        use_tree_paths(&group[start..], &prefix, paths);
+    } else if tree == "self" {
+        paths.push(prefix.to_string());
+    } else if !tree.is_empty() {
+        let path = tree.split_once(" as ").map_or(tree, |(path, _)| path);
+        paths.push(join(path.trim()));
+    }
+}
+
+/// Full paths a use declaration imports, with groups expanded and aliases dropped.
+pub fn use_paths(text: &str) -> Vec<String> {
+    let header = item_header(text);
+    let tree = header
+        .split_once("use ")
+        .map_or("", |(_, tree)| tree)
+        .trim_end()
+        .trim_end_matches(';');
+    let tree = tree.split_whitespace().collect::<Vec<_>>().join(" ");
+    let mut paths = Vec::new();
+
+    use_tree_paths(&tree, "", &mut paths);
+
+    paths
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_usecase_methods() {
+        let source = "use super::entities::Test;
+
+#[async_trait]
+pub trait Usecase {
+    async fn get(&self, id: &str) -> Result<Option<Test>, String>;
+    async fn save(&self, id: &str, entity: &Test) -> Result<(), String>;
+    async fn list(&self) -> Result<Vec<Test>, String>;
+    async fn archive(&self) -> Result<(), String>;
+    fn count(
+        &self,
+        filter: &str,
+    ) -> usize;
+}
+
+impl Usecase for TestUsecase {
+    fn ignored(&self) {}
+}
+";
+
+        let got = usecase_methods(source);
+
+        assert_eq!(
+            vec![
+                UsecaseMethod::new("get", "(&self, id: &str)"),
+                UsecaseMethod::new("save", "(&self, id: &str, entity: &Test)"),
+                UsecaseMethod::new("list", "(&self)"),
+                UsecaseMethod::new("archive", "(&self) -> Result<(), String>"),
+                UsecaseMethod::new("count", "(&self, filter: &str) -> usize"),
+            ],
+            got
+        );
+        assert_eq!(
+            vec![
+                UsecaseMethodKind::Get,
+                UsecaseMethodKind::Save,
+                UsecaseMethodKind::List,
+                UsecaseMethodKind::Action,
+                UsecaseMethodKind::Other,
+            ],
+            got.into_iter()
+                .map(|method| method.kind)
+                .collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn test_usecase_methods_missing_trait() {
+        assert!(usecase_methods("pub struct Test {}").is_empty());
+    }
+
+    #[test]
+    fn test_struct_fields() {
+        let source = "#[derive(Debug)]
+pub struct Test {
+    pub title: String,
+    // Tags attached by the user
+    #[serde(default)]
+    pub tags: HashMap<String, Vec<u8>>,
+    count: Option< i32 >,
+}
+
+pub struct Other {
+    pub ignored: bool,
+}
+";
+
+        let field = |name: &str, ty: &str| StructField {
+            name: name.to_string(),
+            ty: ty.to_string(),
+        };
+
+        assert_eq!(
+            vec![
+                field("title", "String"),
+                field("tags", "HashMap<String,Vec<u8>>"),
+                field("count", "Option<i32>"),
+            ],
+            struct_fields(source, "Test")
+        );
+        assert!(struct_fields("pub struct Test {}", "Test").is_empty());
+        assert!(struct_fields(source, "Missing").is_empty());
+    }
+
+    #[test]
+    fn test_runtime_specific_modules() {
+        let source = "use async_std::fs::{self, File};
+use async_std::{net, prelude::*};
+use std::{io, thread::sleep};
+use std::collections::HashMap;
+";
+
+        assert_eq!(
+            vec!["async_std::fs", "async_std::net", "std::thread"],
+            runtime_specific_modules(source)
+        );
+        assert!(runtime_specific_modules("use std::io;\nuse tokio::sync::Mutex;").is_empty());
+    }
+
+    #[test]
+    fn test_source_items() {
+        let source = "use std::fmt;
+use std::{collections::HashMap, io::{self, Read}};
+
+/// A note.
+#[derive(Debug, Clone)]
+pub struct Note {
+    text: String,
+}
+
+impl fmt::Display for Note {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, \"{} }}\", self.text)
+    }
+}
+
+impl<'a> Note {
+    pub fn new(text: &'a str) -> Self {
+        let brace = '{';
+        Self { text: format!(\"{}{}\", brace, text) }
+    }
+}
+
+const LIMIT: [u8; 2] = [1; 2];
+pub(crate) async fn load() -> Option<Note> {
+    None // }
+}
+
+#[cfg(test)]
+mod tests {}
+";
+
+        let got = source_items(source)
+            .into_iter()
+            .map(|item| (item.kind, item.name))
+            .collect::<Vec<_>>();
+
+        let expect = vec![
+            (ItemKind::Use, String::new()),
+            (ItemKind::Use, String::new()),
+            (ItemKind::Struct, String::from("Note")),
+            (ItemKind::Impl, String::from("Note")),
+            (ItemKind::Impl, String::from("Note")),
+            (ItemKind::Other, String::from("LIMIT")),
+            (ItemKind::Fn, String::from("load")),
+            (ItemKind::Mod, String::from("tests")),
+        ];
+        assert_eq!(expect, got);
+        assert!(source_items(source)[2]
+            .text
+            .starts_with("/// A note.\n#[derive"));
+    }
+
+    #[test]
+    fn test_use_names() {
+        assert_eq!(vec!["fmt"], use_names("use std::fmt;"));
+        assert_eq!(
+            vec!["HashMap", "io", "Read", "Other"],
+            use_names(
+                "pub use std::{collections::HashMap, io::{self, Read}, fmt::Write as Other};"
+            )
+        );
+        assert_eq!(vec!["*"], use_names("use super::*;"));
+    }
+
+    #[test]
+    fn test_use_paths() {
+        assert_eq!(
+            vec![
+                "demo_data::features::notes::datasource::NotesDatasource",
+                "demo_data::features::notes::models",
+                "demo_data::features::notes::models::NotesModel",
+            ],
+            use_paths(
+                "use demo_data::features::notes::{\n    datasource::{NotesDatasource as Datasource},\n    models::{self, NotesModel},\n};"
+            )
+        );
+        assert_eq!(
+            vec!["super::repository::Repository"],
+            use_paths("pub(crate) use super::repository::Repository;")
+        );
+    }
+
+    #[test]
+    fn test_identifiers() {
+        assert_eq!(
+            vec!["let", "note", "Note", "new", "a_b"],
+            identifiers("let note = Note::new(a_b);")
+        );
+    }
+
+    #[test]
+    fn test_with_visibility() {
+        let items =
+            source_items("/// Doc.\n#[derive(Debug)]\nstruct Note;\npub(crate) fn load() {}\n");
+
+        assert_eq!("", items[0].visibility());
+        assert_eq!(
+            "/// Doc.\n#[derive(Debug)]\npub struct Note;",
+            items[0].with_visibility("pub")
+        );
+        assert_eq!("pub(crate)", items[1].visibility());
+        assert_eq!("pub fn load() {}", items[1].with_visibility("pub"));
+        assert_eq!("fn load() {}", items[1].with_visibility(""));
+    }
+}
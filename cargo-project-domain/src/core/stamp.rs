@@ -0,0 +1,110 @@
+use std::convert::TryFrom;
+
+use cargo_project_data::core::stamps::StampModel;
+
+use super::templates::Rendered;
+
+/// Hash of a generated file's content, FNV-1a so it stays stable across Rust releases.
+pub fn content_hash(content: &str) -> String {
+    let hash = content
+        .bytes()
+        .fold(0xcbf2_9ce4_8422_2325_u64, |hash, byte| {
+            (hash ^ u64::from(byte)).wrapping_mul(0x0100_0000_01b3)
+        });
+
+    format!("{:016x}", hash)
+}
+
+/// Records which template, at which version, generated the file at `path`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Stamp {
+    pub path: String,
+    pub template: String,
+    pub version: u32,
+    pub hash: String,
+}
+
+impl Stamp {
+    pub fn new(path: &str, rendered: &Rendered) -> Self {
+        Self {
+            path: path.trim_start_matches("./").to_string(),
+            template: rendered.template.to_string(),
+            version: rendered.version,
+            hash: content_hash(&rendered.content),
+        }
+    }
+}
+
+impl TryFrom<StampModel> for Stamp {
+    type Error = String;
+
+    fn try_from(model: StampModel) -> Result<Self, Self::Error> {
+        Ok(Self {
+            version: u32::try_from(model.version)
+                .map_err(|_| format!("Invalid version in the stamp of {}", model.path))?,
+            path: model.path,
+            // Stamps written before templates had an explicit `ID` carry the lifetimes of
+            // the template's type, like `CliTemplate<'_>`
+            template: match model.template.find('<') {
+                Some(index) => String::from(&model.template[..index]),
+                None => model.template,
+            },
+            hash: model.hash,
+        })
+    }
+}
+
+impl From<&Stamp> for StampModel {
+    fn from(stamp: &Stamp) -> Self {
+        Self {
+            path: stamp.path.clone(),
+            template: stamp.template.clone(),
+            version: i64::from(stamp.version),
+            hash: stamp.hash.clone(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_content_hash() {
+        assert_eq!("cbf29ce484222325", content_hash(""));
+        assert_eq!("af63dc4c8601ec8c", content_hash("a"));
+        assert_ne!(content_hash("pub mod a;\n"), content_hash("pub mod b;\n"));
+    }
+
+    #[test]
+    fn test_new() {
+        let rendered = Rendered {
+            template: "ModFileTemplate",
+            version: 2,
+            content: String::from("pub mod features;\n"),
+        };
+
+        let got = Stamp::new("./src/lib.rs", &rendered);
+
+        assert_eq!("src/lib.rs", got.path);
+        assert_eq!("ModFileTemplate", got.template);
+        assert_eq!(2, got.version);
+        assert_eq!(content_hash("pub mod features;\n"), got.hash);
+        assert_eq!(got, Stamp::try_from(StampModel::from(&got)).unwrap());
+    }
+
+    #[test]
+    fn test_try_from_legacy_template() {
+        let model = StampModel {
+            path: String::from("src/cli.rs"),
+            template: String::from("CliTemplate<'_>"),
+            version: 3,
+            hash: content_hash(""),
+        };
+
+        let got = Stamp::try_from(model).unwrap();
+
+        assert_eq!("CliTemplate", got.template);
+        assert_eq!(3, got.version);
+    }
+}
@@ -4,25 +4,49 @@ use tinytemplate::TinyTemplate;
 
 use super::Template;
 
+use crate::core::project::ProjectKind;
+use crate::core::runtime::Runtime;
+
 static TEMPLATE: &str = "[workspace]
 members = [
-    \"{project}\",
-    \"{project}-domain\",
-    \"{project}-data\"
-]";
+{{ for member in members }}    \"{member}\"{{ if not @last }},{{ endif }}
+{{ endfor }}]
+
+[workspace.metadata.cargo-project]
+runtime = \"{runtime}\"
+kind = \"{kind}\"
+{{ if lints }}
+[workspace.lints.rust]
+unsafe_code = \"forbid\"
+
+[workspace.lints.clippy]
+dbg_macro = \"warn\"
+todo = \"warn\"
+{{ endif }}";
 
 #[derive(Debug, Serialize)]
 pub struct CargoTemplate<'a> {
-    project: &'a str,
+    members: Vec<String>,
+    runtime: &'a str,
+    kind: &'a str,
+    /// Whether lints are declared for the members to inherit.
+    lints: bool,
 }
 
 impl<'a> CargoTemplate<'a> {
-    pub fn new(project: &'a str) -> Self {
-        Self { project }
+    pub fn new(members: Vec<String>, runtime: Runtime, kind: ProjectKind, lints: bool) -> Self {
+        Self {
+            members,
+            runtime: runtime.name(),
+            kind: kind.name(),
+            lints,
+        }
     }
 }
 
 impl<'a> Template for CargoTemplate<'a> {
+    const ID: &'static str = "CargoTemplate";
+
     fn render(&self) -> Result<String, Box<dyn Error>> {
         let mut template = TinyTemplate::new();
         template.add_template("cargo", TEMPLATE)?;
@@ -39,14 +63,59 @@ mod tests {
 
     #[test]
     fn test_render() {
-        let template = CargoTemplate::new("test");
+        let template = CargoTemplate::new(
+            vec![
+                String::from("test"),
+                String::from("test-domain"),
+                String::from("test-data"),
+            ],
+            Runtime::Tokio,
+            ProjectKind::Library,
+            false,
+        );
 
         let expect = "[workspace]
 members = [
     \"test\",
     \"test-domain\",
     \"test-data\"
-]";
+]
+
+[workspace.metadata.cargo-project]
+runtime = \"tokio\"
+kind = \"lib\"
+";
+
+        let got = &template.render().unwrap();
+
+        assert_eq!(expect, got)
+    }
+
+    #[test]
+    fn test_render_lints() {
+        let template = CargoTemplate::new(
+            vec![String::from("test")],
+            Runtime::AsyncStd,
+            ProjectKind::Binary,
+            true,
+        );
+
+        let expect = "[workspace]
+members = [
+    \"test\"
+]
+
+[workspace.metadata.cargo-project]
+runtime = \"async-std\"
+kind = \"bin\"
+
+[workspace.lints.rust]
+unsafe_code = \"forbid\"
+
+[workspace.lints.clippy]
+dbg_macro = \"warn\"
+todo = \"warn\"
+";
 
         let got = &template.render().unwrap();
 
@@ -0,0 +1,45 @@
+use serde::Serialize;
+use std::error::Error;
+use tinytemplate::TinyTemplate;
+
+use super::super::Template;
+
+static TEMPLATE: &str =
+    "# Thresholds of the lints set up in `[workspace.lints]` of the root manifest
+too-many-arguments-threshold = 8
+type-complexity-threshold = 300
+";
+
+#[derive(Debug, Default, Serialize)]
+pub struct ClippyTemplate {}
+
+impl ClippyTemplate {
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+impl Template for ClippyTemplate {
+    const ID: &'static str = "ClippyTemplate";
+
+    fn render(&self) -> Result<String, Box<dyn Error>> {
+        let mut template = TinyTemplate::new();
+        template.add_template("clippy", TEMPLATE)?;
+
+        let rendered = template.render("clippy", self)?;
+
+        Ok(rendered)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render() {
+        let got = ClippyTemplate::new().render().unwrap();
+
+        assert!(got.contains("too-many-arguments-threshold = 8\n"));
+    }
+}
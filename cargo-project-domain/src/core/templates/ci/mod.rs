@@ -0,0 +1,3 @@
+pub mod clippy;
+pub mod rustfmt;
+pub mod workflow;
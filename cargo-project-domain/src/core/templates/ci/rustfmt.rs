@@ -0,0 +1,44 @@
+use serde::Serialize;
+use std::error::Error;
+use tinytemplate::TinyTemplate;
+
+use super::super::Template;
+
+static TEMPLATE: &str = "newline_style = \"Unix\"
+use_field_init_shorthand = true
+use_try_shorthand = true
+";
+
+#[derive(Debug, Default, Serialize)]
+pub struct RustfmtTemplate {}
+
+impl RustfmtTemplate {
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+impl Template for RustfmtTemplate {
+    const ID: &'static str = "RustfmtTemplate";
+
+    fn render(&self) -> Result<String, Box<dyn Error>> {
+        let mut template = TinyTemplate::new();
+        template.add_template("rustfmt", TEMPLATE)?;
+
+        let rendered = template.render("rustfmt", self)?;
+
+        Ok(rendered)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render() {
+        let got = RustfmtTemplate::new().render().unwrap();
+
+        assert!(got.starts_with("newline_style = \"Unix\"\n"));
+    }
+}
@@ -0,0 +1,138 @@
+use serde::Serialize;
+use std::error::Error;
+use tinytemplate::{format_unescaped, TinyTemplate};
+
+use super::super::Template;
+
+use crate::core::layer::Layer;
+
+static TEMPLATE: &str = "name: CI
+
+on: [push, pull_request]
+
+env:
+  CARGO_TERM_COLOR: always
+
+jobs:
+  check:
+    runs-on: ubuntu-latest
+    steps:
+      - uses: actions/checkout@v4
+      - uses: dtolnay/rust-toolchain@stable
+        with:
+          components: rustfmt, clippy
+      - name: Format
+        run: cargo fmt --all -- --check
+      - name: Clippy
+        run: cargo clippy --workspace --all-targets -- -D warnings
+      - name: Test
+        run: cargo test --workspace
+      - name: Layer dependencies
+        run: |
+{{ for check in checks }}          if cargo tree -p {check.crate_name} --edges normal --depth 1 --prefix none | grep -E \"^({check.forbidden}) v\"; then
+            echo \"{check.crate_name} may only depend on the layers: {check.allowed}\"
+            exit 1
+          fi
+{{ endfor }}";
+
+/// Crates of the workspace a layer crate must not depend on.
+#[derive(Debug, Serialize)]
+struct LayerCheck {
+    crate_name: String,
+    /// Names of the layers the crate may depend on.
+    allowed: String,
+    /// Alternation of the forbidden crate names, as a regular expression.
+    forbidden: String,
+}
+
+/// GitHub Actions workflow checking formatting, lints and tests of every layer crate, and
+/// that layers only depend on the ones they're declared to.
+#[derive(Debug, Serialize)]
+pub struct WorkflowTemplate {
+    checks: Vec<LayerCheck>,
+}
+
+impl WorkflowTemplate {
+    pub fn new(project: &str, layers: &[Layer]) -> Self {
+        let checks = layers
+            .iter()
+            .filter_map(|layer| {
+                let forbidden = layers
+                    .iter()
+                    .filter(|other| other.name != layer.name)
+                    .filter(|other| !layer.depends_on.contains(&other.name))
+                    .map(|other| other.crate_name(project))
+                    .collect::<Vec<_>>();
+
+                if forbidden.is_empty() {
+                    return None;
+                }
+
+                let allowed = match layer.depends_on.is_empty() {
+                    true => String::from("none"),
+                    false => layer.depends_on.join(", "),
+                };
+
+                Some(LayerCheck {
+                    crate_name: layer.crate_name(project),
+                    allowed,
+                    forbidden: forbidden.join("|"),
+                })
+            })
+            .collect();
+
+        Self { checks }
+    }
+}
+
+impl Template for WorkflowTemplate {
+    const ID: &'static str = "WorkflowTemplate";
+
+    fn render(&self) -> Result<String, Box<dyn Error>> {
+        let mut template = TinyTemplate::new();
+        template.set_default_formatter(&format_unescaped);
+        template.add_template("workflow", TEMPLATE)?;
+
+        let rendered = template.render("workflow", self)?;
+
+        Ok(rendered)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::core::presentation::Presentation;
+    use crate::core::project::ProjectKind;
+
+    #[test]
+    fn test_render() {
+        let template = WorkflowTemplate::new("test", &Layer::defaults(ProjectKind::Binary));
+
+        let got = template.render().unwrap();
+
+        assert!(
+            got.contains("        run: cargo clippy --workspace --all-targets -- -D warnings\n")
+        );
+        assert!(got.ends_with(
+            "          if cargo tree -p test-data --edges normal --depth 1 --prefix none | grep -E \"^(test|test-domain) v\"; then
+            echo \"test-data may only depend on the layers: none\"
+            exit 1
+          fi
+"
+        ));
+        assert!(got.contains("grep -E \"^(test-data) v\"; then\n            echo \"test may only depend on the layers: domain\""));
+    }
+
+    #[test]
+    fn test_render_with_presentation() {
+        let mut layers = Layer::defaults(ProjectKind::Binary);
+        layers.push(Presentation::Grpc.layer());
+
+        let got = WorkflowTemplate::new("test", &layers).render().unwrap();
+
+        assert!(got.contains("grep -E \"^(test|test-data) v\"; then\n            echo \"test-grpc may only depend on the layers: domain\""));
+        assert!(got.contains("grep -E \"^(test-data|test-grpc) v\"; then\n            echo \"test may only depend on the layers: domain\""));
+    }
+}
@@ -2,27 +2,33 @@ use serde::Serialize;
 use std::error::Error;
 use tinytemplate::TinyTemplate;
 
-use super::Template;
+use super::{use_list, Template};
 
-static TEMPLATE: &str =
-    "use project_domain::features::{name}::usecase::\\{{name_title_case}Usecase, Usecase};";
+static TEMPLATE: &str = "// Imported for the commands of the feature, which are yet to be written
+#[allow(unused_imports)]
+use {project}_domain::features::{name}::usecase::\\{{imports}};
+";
 
 #[derive(Debug, Serialize)]
 pub struct CliTemplate<'a> {
+    project: String,
     name: &'a str,
-    name_title_case: &'a str,
+    imports: String,
 }
 
 impl<'a> CliTemplate<'a> {
-    pub fn new(name: &'a str, name_title_case: &'a str) -> Self {
+    pub fn new(project: &'a str, name: &'a str, name_title_case: &'a str) -> Self {
         Self {
+            project: project.replace('-', "_"),
             name,
-            name_title_case,
+            imports: use_list(&[&format!("{}Usecase", name_title_case), "Usecase"]),
         }
     }
 }
 
 impl<'a> Template for CliTemplate<'a> {
+    const ID: &'static str = "CliTemplate";
+
     fn render(&self) -> Result<String, Box<dyn Error>> {
         let mut template = TinyTemplate::new();
         template.add_template("cli", TEMPLATE)?;
@@ -39,9 +45,12 @@ mod tests {
 
     #[test]
     fn test_render() {
-        let template = CliTemplate::new("test", "Test");
+        let template = CliTemplate::new("my-project", "test", "Test");
 
-        let expect = "use project_domain::features::test::usecase::{TestUsecase, Usecase};";
+        let expect = "// Imported for the commands of the feature, which are yet to be written
+#[allow(unused_imports)]
+use my_project_domain::features::test::usecase::{TestUsecase, Usecase};
+";
         let got = &template.render().unwrap();
 
         assert_eq!(expect, got)
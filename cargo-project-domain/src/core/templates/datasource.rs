@@ -4,7 +4,18 @@ use tinytemplate::TinyTemplate;
 
 use super::Template;
 
-static TEMPLATE: &str = "pub trait Datasource \\{}
+use crate::core::runtime::{Runtime, RuntimeContext};
+
+static TEMPLATE: &str = "{{ if runtime.is_async }}use async_trait::async_trait;
+{{ endif }}use std::io;
+
+use super::models::{name_title_case}Model;
+
+{runtime.async_trait}pub trait Datasource \\{
+    {runtime.async_keyword}fn load(&self, id: &str) -> io::Result<Option<{name_title_case}Model>>;
+    {runtime.async_keyword}fn save(&self, id: &str, model: &{name_title_case}Model) -> io::Result<()>;
+    {runtime.async_keyword}fn list(&self) -> io::Result<Vec<{name_title_case}Model>>;
+}
 
 pub struct {name_title_case}Datasource \\{}
 
@@ -12,32 +23,65 @@ impl {name_title_case}Datasource \\{
     pub fn new() -> Self \\{
         Self \\{}
     }
+
+    fn not_implemented() -> io::Error \\{
+        io::Error::other(\"{name_title_case}Datasource is not implemented\")
+    }
 }
 
-impl Datasource for {name_title_case}Datasource \\{}
+impl Default for {name_title_case}Datasource \\{
+    fn default() -> Self \\{
+        Self::new()
+    }
+}
+
+{runtime.async_trait}impl Datasource for {name_title_case}Datasource \\{
+    {runtime.async_keyword}fn load(&self, _id: &str) -> io::Result<Option<{name_title_case}Model>> \\{
+        Err(Self::not_implemented())
+    }
+
+    {runtime.async_keyword}fn save(&self, _id: &str, _model: &{name_title_case}Model) -> io::Result<()> \\{
+        Err(Self::not_implemented())
+    }
+
+    {runtime.async_keyword}fn list(&self) -> io::Result<Vec<{name_title_case}Model>> \\{
+        Err(Self::not_implemented())
+    }
+}
 
 #[cfg(test)]
 mod tests \\{
     use super::*;
 
-    #[test]
-    fn test() \\{
-        assert_eq!(1, 1)
+    {runtime.test_attribute}
+    {runtime.async_keyword}fn test_not_implemented() \\{
+        let datasource = {name_title_case}Datasource::new();
+
+        let got = datasource.list(){runtime.await_keyword}.unwrap_err();
+
+        assert_eq!(io::ErrorKind::Other, got.kind())
     }
-}";
+}
+";
 
 #[derive(Debug, Serialize)]
 pub struct DatasourceTemplate<'a> {
     name_title_case: &'a str,
+    runtime: RuntimeContext,
 }
 
 impl<'a> DatasourceTemplate<'a> {
-    pub fn new(name_title_case: &'a str) -> Self {
-        Self { name_title_case }
+    pub fn new(name_title_case: &'a str, runtime: Runtime) -> Self {
+        Self {
+            name_title_case,
+            runtime: runtime.context(),
+        }
     }
 }
 
 impl<'a> Template for DatasourceTemplate<'a> {
+    const ID: &'static str = "DatasourceTemplate";
+
     fn render(&self) -> Result<String, Box<dyn Error>> {
         let mut template = TinyTemplate::new();
         template.add_template("cli", TEMPLATE)?;
@@ -54,9 +98,19 @@ mod tests {
 
     #[test]
     fn test_render() {
-        let template = DatasourceTemplate::new("Test");
+        let template = DatasourceTemplate::new("Test", Runtime::AsyncStd);
 
-        let expect = "pub trait Datasource {}
+        let expect = "use async_trait::async_trait;
+use std::io;
+
+use super::models::TestModel;
+
+#[async_trait]
+pub trait Datasource {
+    async fn load(&self, id: &str) -> io::Result<Option<TestModel>>;
+    async fn save(&self, id: &str, model: &TestModel) -> io::Result<()>;
+    async fn list(&self) -> io::Result<Vec<TestModel>>;
+}
 
 pub struct TestDatasource {}
 
@@ -64,22 +118,64 @@ impl TestDatasource {
     pub fn new() -> Self {
         Self {}
     }
+
+    fn not_implemented() -> io::Error {
+        io::Error::other(\"TestDatasource is not implemented\")
+    }
 }
 
-impl Datasource for TestDatasource {}
+impl Default for TestDatasource {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl Datasource for TestDatasource {
+    async fn load(&self, _id: &str) -> io::Result<Option<TestModel>> {
+        Err(Self::not_implemented())
+    }
+
+    async fn save(&self, _id: &str, _model: &TestModel) -> io::Result<()> {
+        Err(Self::not_implemented())
+    }
+
+    async fn list(&self) -> io::Result<Vec<TestModel>> {
+        Err(Self::not_implemented())
+    }
+}
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
-    #[test]
-    fn test() {
-        assert_eq!(1, 1)
+    #[async_std::test]
+    async fn test_not_implemented() {
+        let datasource = TestDatasource::new();
+
+        let got = datasource.list().await.unwrap_err();
+
+        assert_eq!(io::ErrorKind::Other, got.kind())
     }
-}";
+}
+";
 
         let got = &template.render().unwrap();
 
         assert_eq!(expect, got)
     }
+
+    #[test]
+    fn test_render_sync() {
+        let template = DatasourceTemplate::new("Test", Runtime::Sync);
+
+        let got = template.render().unwrap();
+
+        assert!(got.starts_with("use std::io;"));
+        assert!(got.contains("\npub trait Datasource {\n    fn load("));
+        assert!(got.contains("\nimpl Datasource for TestDatasource {"));
+        assert!(got.contains("    #[test]\n    fn test_not_implemented() {"));
+        assert!(got.contains("datasource.list().unwrap_err();"));
+        assert!(!got.contains("async"));
+    }
 }
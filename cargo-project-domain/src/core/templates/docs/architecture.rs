@@ -0,0 +1,120 @@
+use serde::Serialize;
+use std::error::Error;
+use tinytemplate::{format_unescaped, TinyTemplate};
+
+use super::super::Template;
+
+static TEMPLATE: &str = "# Architecture of {project}
+
+The project is a workspace with a crate per layer. A layer only depends on the layers \
+listed next to it, so the domain stays free of presentation and storage concerns. Every \
+feature is a module in each layer: its presentation calls the feature's usecase, which \
+reads and writes entities through a repository backed by the feature's datasource.
+
+## Layers
+
+| Layer | Crate | Depends on | Holds |
+| --- | --- | --- | --- |
+{{ for layer in layers }}| {layer.name} | `{layer.crate_name}` | {layer.depends_on} | {layer.description} |
+{{ endfor }}
+## Features
+
+{{ if features }}| Feature | Datasource | Usecase methods |
+| --- | --- | --- |
+{{ for feature in features }}| [{feature.name}]({feature.readme}) | {feature.datasource} | {feature.methods} |
+{{ endfor }}{{ else }}No features yet, add one with `cargo project add feature <name>`.
+{{ endif }}";
+
+#[derive(Debug, Serialize)]
+pub struct LayerDoc {
+    pub name: String,
+    pub crate_name: String,
+    /// Names of the layers depended on, already joined.
+    pub depends_on: String,
+    pub description: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct FeatureDoc {
+    pub name: String,
+    /// Path of the feature's README, relative to the workspace root.
+    pub readme: String,
+    pub datasource: String,
+    /// Usecase methods, already joined.
+    pub methods: String,
+}
+
+/// Overview of the layers and features of a project, written to `ARCHITECTURE.md`.
+#[derive(Debug, Serialize)]
+pub struct ArchitectureTemplate<'a> {
+    project: &'a str,
+    layers: Vec<LayerDoc>,
+    features: Vec<FeatureDoc>,
+}
+
+impl<'a> ArchitectureTemplate<'a> {
+    pub fn new(project: &'a str, layers: Vec<LayerDoc>, features: Vec<FeatureDoc>) -> Self {
+        Self {
+            project,
+            layers,
+            features,
+        }
+    }
+}
+
+impl<'a> Template for ArchitectureTemplate<'a> {
+    const ID: &'static str = "ArchitectureTemplate";
+
+    fn render(&self) -> Result<String, Box<dyn Error>> {
+        let mut template = TinyTemplate::new();
+        template.set_default_formatter(&format_unescaped);
+        template.add_template("architecture", TEMPLATE)?;
+
+        let rendered = template.render("architecture", self)?;
+
+        Ok(rendered)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render() {
+        let template = ArchitectureTemplate::new(
+            "test",
+            vec![LayerDoc {
+                name: String::from("domain"),
+                crate_name: String::from("test-domain"),
+                depends_on: String::from("data"),
+                description: String::from("Entities and usecases."),
+            }],
+            vec![FeatureDoc {
+                name: String::from("notes"),
+                readme: String::from("test-domain/src/features/notes/README.md"),
+                datasource: String::from("empty"),
+                methods: String::from("`get`, `list`"),
+            }],
+        );
+
+        let got = template.render().unwrap();
+
+        assert!(got.starts_with("# Architecture of test\n"));
+        assert!(got.contains("| domain | `test-domain` | data | Entities and usecases. |\n"));
+        assert!(got.contains(
+            "| [notes](test-domain/src/features/notes/README.md) | empty | `get`, `list` |\n"
+        ));
+    }
+
+    #[test]
+    fn test_render_without_features() {
+        let template = ArchitectureTemplate::new("test", vec![], vec![]);
+
+        let got = template.render().unwrap();
+
+        assert!(got.ends_with(
+            "## Features\n\nNo features yet, add one with `cargo project add feature <name>`.\n"
+        ));
+    }
+}
@@ -0,0 +1,2 @@
+pub mod architecture;
+pub mod readme;
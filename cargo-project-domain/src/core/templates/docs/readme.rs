@@ -0,0 +1,119 @@
+use serde::Serialize;
+use std::error::Error;
+use tinytemplate::{format_unescaped, TinyTemplate};
+
+use super::super::Template;
+
+static TEMPLATE: &str = "# {name}
+
+## Datasource
+
+{datasource}
+
+## Entities
+
+{{ if fields }}`{name_title_case}` holds:
+
+| Field | Type |
+| --- | --- |
+{{ for field in fields }}| `{field.name}` | `{field.ty}` |
+{{ endfor }}{{ else }}`{name_title_case}` has no fields yet.
+{{ endif }}
+## Usecases
+
+`{name_title_case}Usecase` provides:
+
+{{ for method in methods }}- `{method}`
+{{ endfor }}";
+
+#[derive(Debug, Serialize)]
+pub struct FieldDoc {
+    pub name: String,
+    pub ty: String,
+}
+
+/// Description of a feature, written to the `README.md` of its domain directory.
+#[derive(Debug, Serialize)]
+pub struct FeatureReadmeTemplate<'a> {
+    name: &'a str,
+    name_title_case: &'a str,
+    datasource: &'a str,
+    fields: Vec<FieldDoc>,
+    methods: Vec<String>,
+}
+
+impl<'a> FeatureReadmeTemplate<'a> {
+    pub fn new(
+        name: &'a str,
+        name_title_case: &'a str,
+        datasource: &'a str,
+        fields: Vec<FieldDoc>,
+        methods: Vec<String>,
+    ) -> Self {
+        Self {
+            name,
+            name_title_case,
+            datasource,
+            fields,
+            methods,
+        }
+    }
+}
+
+impl<'a> Template for FeatureReadmeTemplate<'a> {
+    const ID: &'static str = "FeatureReadmeTemplate";
+
+    fn render(&self) -> Result<String, Box<dyn Error>> {
+        let mut template = TinyTemplate::new();
+        template.set_default_formatter(&format_unescaped);
+        template.add_template("readme", TEMPLATE)?;
+
+        let rendered = template.render("readme", self)?;
+
+        Ok(rendered)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render() {
+        let template = FeatureReadmeTemplate::new(
+            "notes",
+            "Notes",
+            "Stores each `Notes` as a JSON file.",
+            vec![FieldDoc {
+                name: String::from("tags"),
+                ty: String::from("Vec<String>"),
+            }],
+            vec![String::from("get"), String::from("archive")],
+        );
+
+        let expect = "# notes
+
+## Datasource
+
+Stores each `Notes` as a JSON file.
+
+## Entities
+
+`Notes` holds:
+
+| Field | Type |
+| --- | --- |
+| `tags` | `Vec<String>` |
+
+## Usecases
+
+`NotesUsecase` provides:
+
+- `get`
+- `archive`
+";
+        let got = template.render().unwrap();
+
+        assert_eq!(expect, got)
+    }
+}
@@ -4,20 +4,46 @@ use tinytemplate::TinyTemplate;
 
 use super::Template;
 
-static TEMPLATE: &str = "";
+static TEMPLATE: &str = "use serde::\\{Deserialize, Serialize};
+
+use {project}_data::features::{name}::models::{name_title_case}Model;
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct {name_title_case} \\{}
+
+impl From<{name_title_case}Model> for {name_title_case} \\{
+    fn from(_model: {name_title_case}Model) -> Self \\{
+        Self \\{}
+    }
+}
+
+impl From<{name_title_case}> for {name_title_case}Model \\{
+    fn from(_entity: {name_title_case}) -> Self \\{
+        Self \\{}
+    }
+}
+";
 
 #[derive(Debug, Serialize)]
 pub struct EntitiesTemplate<'a> {
     name: &'a str,
+    name_title_case: &'a str,
+    project: String,
 }
 
 impl<'a> EntitiesTemplate<'a> {
-    pub fn new(name: &'a str) -> Self {
-        Self { name }
+    pub fn new(name: &'a str, name_title_case: &'a str, project: &'a str) -> Self {
+        Self {
+            name,
+            name_title_case,
+            project: project.replace('-', "_"),
+        }
     }
 }
 
 impl<'a> Template for EntitiesTemplate<'a> {
+    const ID: &'static str = "EntitiesTemplate";
+
     fn render(&self) -> Result<String, Box<dyn Error>> {
         let mut template = TinyTemplate::new();
         template.add_template("cli", TEMPLATE)?;
@@ -27,3 +53,45 @@ impl<'a> Template for EntitiesTemplate<'a> {
         Ok(rendered)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render() {
+        let template = EntitiesTemplate::new("test", "Test", "project");
+
+        let expect = "use serde::{Deserialize, Serialize};
+
+use project_data::features::test::models::TestModel;
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Test {}
+
+impl From<TestModel> for Test {
+    fn from(_model: TestModel) -> Self {
+        Self {}
+    }
+}
+
+impl From<Test> for TestModel {
+    fn from(_entity: Test) -> Self {
+        Self {}
+    }
+}
+";
+        let got = &template.render().unwrap();
+
+        assert_eq!(expect, got)
+    }
+
+    #[test]
+    fn test_render_hyphenated_project() {
+        let template = EntitiesTemplate::new("test", "Test", "my-project");
+
+        let got = template.render().unwrap();
+
+        assert!(got.contains("use my_project_data::features::test::models::TestModel;"));
+    }
+}
@@ -0,0 +1,57 @@
+use serde::Serialize;
+use std::error::Error;
+use tinytemplate::TinyTemplate;
+
+use super::Template;
+
+static TEMPLATE: &str = "pub use {project}_domain::features::{name}::entities::{name_title_case};
+pub use {project}_domain::features::{name}::usecase::\\{{name_title_case}Usecase, Usecase};
+";
+
+/// Module of a library project re-exporting the public API of a domain feature.
+#[derive(Debug, Serialize)]
+pub struct FacadeTemplate<'a> {
+    project: String,
+    name: &'a str,
+    name_title_case: &'a str,
+}
+
+impl<'a> FacadeTemplate<'a> {
+    pub fn new(project: &'a str, name: &'a str, name_title_case: &'a str) -> Self {
+        Self {
+            project: project.replace('-', "_"),
+            name,
+            name_title_case,
+        }
+    }
+}
+
+impl<'a> Template for FacadeTemplate<'a> {
+    const ID: &'static str = "FacadeTemplate";
+
+    fn render(&self) -> Result<String, Box<dyn Error>> {
+        let mut template = TinyTemplate::new();
+        template.add_template("facade", TEMPLATE)?;
+
+        let rendered = template.render("facade", self)?;
+
+        Ok(rendered)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render() {
+        let template = FacadeTemplate::new("my-project", "notes", "Notes");
+
+        let expect = "pub use my_project_domain::features::notes::entities::Notes;
+pub use my_project_domain::features::notes::usecase::{NotesUsecase, Usecase};
+";
+        let got = &template.render().unwrap();
+
+        assert_eq!(expect, got)
+    }
+}
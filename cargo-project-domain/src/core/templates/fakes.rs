@@ -0,0 +1,163 @@
+use serde::Serialize;
+use std::error::Error;
+use tinytemplate::TinyTemplate;
+
+use super::Template;
+
+use crate::core::runtime::{Runtime, RuntimeContext};
+
+static TEMPLATE: &str = "{{ if runtime.is_async }}use async_trait::async_trait;
+{{ endif }}use std::collections::HashMap;
+use std::io;
+use std::sync::\\{Arc, Mutex};
+
+use {project}_data::features::{name}::datasource::Datasource;
+use {project}_data::features::{name}::models::{name_title_case}Model;
+
+use super::entities::{name_title_case};
+use super::repository::Repository;
+
+/// Datasource double keeping models in memory, keyed by id.
+#[derive(Default)]
+pub struct InMemoryDatasource \\{
+    models: Mutex<HashMap<String, {name_title_case}Model>>,
+}
+
+{runtime.async_trait}impl Datasource for InMemoryDatasource \\{
+    {runtime.async_keyword}fn load(&self, id: &str) -> io::Result<Option<{name_title_case}Model>> \\{
+        Ok(self.models.lock().unwrap().get(id).cloned())
+    }
+
+    {runtime.async_keyword}fn save(&self, id: &str, model: &{name_title_case}Model) -> io::Result<()> \\{
+        self.models
+            .lock()
+            .unwrap()
+            .insert(id.to_string(), model.clone());
+        Ok(())
+    }
+
+    {runtime.async_keyword}fn list(&self) -> io::Result<Vec<{name_title_case}Model>> \\{
+        Ok(self.models.lock().unwrap().values().cloned().collect())
+    }
+}
+
+/// Repository double recording every call and returning scripted results.
+#[derive(Default)]
+pub struct RecordingRepository \\{
+    calls: Arc<Mutex<Vec<String>>>,
+    entities: Vec<{name_title_case}>,
+    error: Option<String>,
+}
+
+impl RecordingRepository \\{
+    pub fn returning(entities: Vec<{name_title_case}>) -> Self \\{
+        Self \\{
+            entities,
+            ..Default::default()
+        }
+    }
+
+    pub fn failing(error: &str) -> Self \\{
+        Self \\{
+            error: Some(error.to_string()),
+            ..Default::default()
+        }
+    }
+
+    pub fn calls(&self) -> Arc<Mutex<Vec<String>>> \\{
+        self.calls.clone()
+    }
+
+    fn record(&self, call: String) -> Result<(), String> \\{
+        self.calls.lock().unwrap().push(call);
+
+        match &self.error \\{
+            Some(error) => Err(error.clone()),
+            None => Ok(()),
+        }
+    }
+}
+
+{runtime.async_trait}impl Repository for RecordingRepository \\{
+    {runtime.async_keyword}fn get(&self, id: &str) -> Result<Option<{name_title_case}>, String> \\{
+        self.record(format!(\"get(\\{})\", id))?;
+        Ok(self.entities.first().cloned())
+    }
+
+    {runtime.async_keyword}fn save(&self, id: &str, _entity: &{name_title_case}) -> Result<(), String> \\{
+        self.record(format!(\"save(\\{})\", id))
+    }
+
+    {runtime.async_keyword}fn list(&self) -> Result<Vec<{name_title_case}>, String> \\{
+        self.record(String::from(\"list\"))?;
+        Ok(self.entities.clone())
+    }
+}
+";
+
+#[derive(Debug, Serialize)]
+pub struct FakesTemplate<'a> {
+    name: &'a str,
+    name_title_case: &'a str,
+    project: String,
+    runtime: RuntimeContext,
+}
+
+impl<'a> FakesTemplate<'a> {
+    pub fn new(
+        name: &'a str,
+        name_title_case: &'a str,
+        project: &'a str,
+        runtime: Runtime,
+    ) -> Self {
+        Self {
+            name,
+            name_title_case,
+            project: project.replace('-', "_"),
+            runtime: runtime.context(),
+        }
+    }
+}
+
+impl<'a> Template for FakesTemplate<'a> {
+    const ID: &'static str = "FakesTemplate";
+
+    fn render(&self) -> Result<String, Box<dyn Error>> {
+        let mut template = TinyTemplate::new();
+        template.add_template("fakes", TEMPLATE)?;
+
+        let rendered = template.render("fakes", self)?;
+
+        Ok(rendered)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render() {
+        let template = FakesTemplate::new("test", "Test", "project", Runtime::AsyncStd);
+
+        let got = template.render().unwrap();
+
+        assert!(got.contains("use project_data::features::test::models::TestModel;"));
+        assert!(got.contains("    models: Mutex<HashMap<String, TestModel>>,"));
+        assert!(got.contains("impl Datasource for InMemoryDatasource {"));
+        assert!(got.contains("impl Repository for RecordingRepository {"));
+        assert!(got.contains("self.record(format!(\"get({})\", id))?;"));
+    }
+
+    #[test]
+    fn test_render_sync() {
+        let template = FakesTemplate::new("test", "Test", "project", Runtime::Sync);
+
+        let got = template.render().unwrap();
+
+        assert!(got.starts_with("use std::collections::HashMap;"));
+        assert!(got.contains("\nimpl Repository for RecordingRepository {"));
+        assert!(got.contains("    fn list(&self) -> io::Result<Vec<TestModel>> {"));
+        assert!(!got.contains("async"));
+    }
+}
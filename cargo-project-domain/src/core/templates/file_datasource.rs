@@ -0,0 +1,250 @@
+use serde::Serialize;
+use std::error::Error;
+use tinytemplate::TinyTemplate;
+
+use super::Template;
+
+use crate::core::runtime::{Runtime, RuntimeContext};
+use crate::features::add::entities::FileFormat;
+
+static TEMPLATE: &str = "{{ if runtime.is_async_std }}use async_std::fs::\\{self, File};
+use async_std::prelude::*;
+use async_trait::async_trait;
+use std::io;
+use std::path::PathBuf;
+{{ endif }}{{ if runtime.is_tokio }}use async_trait::async_trait;
+use std::io;
+use std::path::PathBuf;
+use tokio::fs::\\{self, File};
+use tokio::io::AsyncWriteExt;
+{{ endif }}{{ if not runtime.is_async }}use std::fs::\\{self, File};
+use std::io::\\{self, Write};
+use std::path::PathBuf;
+{{ endif }}
+use super::models::{name_title_case}Model;
+
+static DEFAULT_DIR: &str = \"data/{name}\";
+static EXTENSION: &str = \"{extension}\";
+
+{runtime.async_trait}pub trait Datasource \\{
+    {runtime.async_keyword}fn load(&self, id: &str) -> io::Result<Option<{name_title_case}Model>>;
+    {runtime.async_keyword}fn save(&self, id: &str, model: &{name_title_case}Model) -> io::Result<()>;
+    {runtime.async_keyword}fn list(&self) -> io::Result<Vec<{name_title_case}Model>>;
+}
+
+pub struct {name_title_case}Datasource \\{
+    dir: PathBuf,
+}
+
+impl {name_title_case}Datasource \\{
+    pub fn new() -> Self \\{
+        Self::with_dir(DEFAULT_DIR)
+    }
+
+    pub fn with_dir<P: Into<PathBuf>>(dir: P) -> Self \\{
+        Self \\{ dir: dir.into() }
+    }
+
+    fn path(&self, id: &str) -> PathBuf \\{
+        self.dir.join(format!(\"\\{}.\\{}\", id, EXTENSION))
+    }
+
+    fn serialize(model: &{name_title_case}Model) -> io::Result<String> \\{
+{{ if json }}        serde_json::to_string_pretty(model)
+            .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))
+{{ else }}        toml::to_string_pretty(model).map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))
+{{ endif }}    }
+
+    fn deserialize(content: &str) -> io::Result<{name_title_case}Model> \\{
+        {{ if json }}serde_json{{ else }}toml{{ endif }}::from_str(content).map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))
+    }
+}
+
+impl Default for {name_title_case}Datasource \\{
+    fn default() -> Self \\{
+        Self::new()
+    }
+}
+
+{runtime.async_trait}impl Datasource for {name_title_case}Datasource \\{
+    {runtime.async_keyword}fn load(&self, id: &str) -> io::Result<Option<{name_title_case}Model>> \\{
+        match fs::read_to_string(self.path(id)){runtime.await_keyword} \\{
+            Ok(content) => Ok(Some(Self::deserialize(&content)?)),
+            Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(None),
+            Err(err) => Err(err),
+        }
+    }
+
+    {runtime.async_keyword}fn save(&self, id: &str, model: &{name_title_case}Model) -> io::Result<()> \\{
+        fs::create_dir_all(&self.dir){runtime.await_keyword}?;
+
+        // Write to a temporary file first so readers never observe a partial write
+        let temp_path = self.dir.join(format!(\".\\{}.\\{}.tmp\", id, EXTENSION));
+        let mut file = File::create(&temp_path){runtime.await_keyword}?;
+        file.write_all(Self::serialize(model)?.as_bytes()){runtime.await_keyword}?;
+        file.sync_all(){runtime.await_keyword}?;
+
+        fs::rename(&temp_path, self.path(id)){runtime.await_keyword}
+    }
+
+    {runtime.async_keyword}fn list(&self) -> io::Result<Vec<{name_title_case}Model>> \\{
+        let {{ if runtime.is_async }}mut {{ endif }}entries = match fs::read_dir(&self.dir){runtime.await_keyword} \\{
+            Ok(entries) => entries,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(err) => return Err(err),
+        };
+
+        let mut models = Vec::new();
+{{ if runtime.is_async_std }}        while let Some(entry) = entries.next().await \\{
+            let path = entry?.path();
+{{ endif }}{{ if runtime.is_tokio }}        while let Some(entry) = entries.next_entry().await? \\{
+            let path = entry.path();
+{{ endif }}{{ if not runtime.is_async }}        for entry in entries \\{
+            let path = entry?.path();
+{{ endif }}            if path.extension().and_then(|extension| extension.to_str()) == Some(EXTENSION) \\{
+                let content = fs::read_to_string(&path){runtime.await_keyword}?;
+                models.push(Self::deserialize(&content)?);
+            }
+        }
+
+        Ok(models)
+    }
+}
+
+#[cfg(test)]
+mod tests \\{
+    use super::*;
+    use tempfile::tempdir;
+
+    {runtime.test_attribute}
+    {runtime.async_keyword}fn test_save_and_load() \\{
+        let dir = tempdir().unwrap();
+        let datasource = {name_title_case}Datasource::with_dir(dir.path());
+
+        let expect = {name_title_case}Model::default();
+        datasource.save(\"test\", &expect){runtime.await_keyword}.unwrap();
+        let got = datasource.load(\"test\"){runtime.await_keyword}.unwrap();
+
+        assert_eq!(Some(expect), got)
+    }
+
+    {runtime.test_attribute}
+    {runtime.async_keyword}fn test_load_missing() \\{
+        let dir = tempdir().unwrap();
+        let datasource = {name_title_case}Datasource::with_dir(dir.path());
+
+        let got = datasource.load(\"missing\"){runtime.await_keyword}.unwrap();
+
+        assert_eq!(None, got)
+    }
+
+    {runtime.test_attribute}
+    {runtime.async_keyword}fn test_list() \\{
+        let dir = tempdir().unwrap();
+        let datasource = {name_title_case}Datasource::with_dir(dir.path());
+
+        let model = {name_title_case}Model::default();
+        datasource.save(\"first\", &model){runtime.await_keyword}.unwrap();
+        datasource.save(\"second\", &model){runtime.await_keyword}.unwrap();
+        let got = datasource.list(){runtime.await_keyword}.unwrap();
+
+        assert_eq!(2, got.len())
+    }
+}
+";
+
+#[derive(Debug, Serialize)]
+pub struct FileDatasourceTemplate<'a> {
+    name: &'a str,
+    name_title_case: &'a str,
+    extension: &'a str,
+    json: bool,
+    runtime: RuntimeContext,
+}
+
+impl<'a> FileDatasourceTemplate<'a> {
+    pub fn new(
+        name: &'a str,
+        name_title_case: &'a str,
+        format: FileFormat,
+        runtime: Runtime,
+    ) -> Self {
+        Self {
+            name,
+            name_title_case,
+            extension: format.extension(),
+            json: format == FileFormat::Json,
+            runtime: runtime.context(),
+        }
+    }
+}
+
+impl<'a> Template for FileDatasourceTemplate<'a> {
+    const ID: &'static str = "FileDatasourceTemplate";
+
+    fn render(&self) -> Result<String, Box<dyn Error>> {
+        let mut template = TinyTemplate::new();
+        template.add_template("file_datasource", TEMPLATE)?;
+
+        let rendered = template.render("file_datasource", self)?;
+
+        Ok(rendered)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_json() {
+        let template =
+            FileDatasourceTemplate::new("test", "Test", FileFormat::Json, Runtime::AsyncStd);
+
+        let got = template.render().unwrap();
+
+        assert!(got.contains("static DEFAULT_DIR: &str = \"data/test\";"));
+        assert!(got.contains("static EXTENSION: &str = \"json\";"));
+        assert!(got.contains("serde_json::to_string_pretty(model)"));
+        assert!(got.contains("pub struct TestDatasource {"));
+        assert!(!got.contains("toml::"));
+    }
+
+    #[test]
+    fn test_render_toml() {
+        let template =
+            FileDatasourceTemplate::new("test", "Test", FileFormat::Toml, Runtime::AsyncStd);
+
+        let got = template.render().unwrap();
+
+        assert!(got.contains("static EXTENSION: &str = \"toml\";"));
+        assert!(got.contains("toml::from_str(content)"));
+        assert!(!got.contains("serde_json::"));
+    }
+
+    #[test]
+    fn test_render_tokio() {
+        let template =
+            FileDatasourceTemplate::new("test", "Test", FileFormat::Json, Runtime::Tokio);
+
+        let got = template.render().unwrap();
+
+        assert!(got.contains("use tokio::fs::{self, File};"));
+        assert!(got.contains("while let Some(entry) = entries.next_entry().await? {"));
+        assert!(got.contains("    #[tokio::test]\n    async fn test_list() {"));
+        assert!(!got.contains("async_std"));
+    }
+
+    #[test]
+    fn test_render_sync() {
+        let template = FileDatasourceTemplate::new("test", "Test", FileFormat::Json, Runtime::Sync);
+
+        let got = template.render().unwrap();
+
+        assert!(got.contains("use std::fs::{self, File};"));
+        assert!(got.contains("let entries = match fs::read_dir(&self.dir) {"));
+        assert!(got.contains("        for entry in entries {"));
+        assert!(!got.contains("async"));
+        assert!(!got.contains(".await"));
+    }
+}
@@ -0,0 +1,55 @@
+use serde::Serialize;
+use std::error::Error;
+use tinytemplate::TinyTemplate;
+
+use super::Template;
+
+static TEMPLATE: &str = "# Build output of every crate in the workspace
+/target
+
+# Backups written by rustfmt
+**/*.rs.bk
+
+# Editor and operating system files
+.idea/
+.vscode/
+*.swp
+.DS_Store
+";
+
+/// Ignore file of a new project's git repository. The `.cargo-project` records are kept,
+/// as `upgrade` and `status` rely on them.
+#[derive(Debug, Default, Serialize)]
+pub struct GitignoreTemplate {}
+
+impl GitignoreTemplate {
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+impl Template for GitignoreTemplate {
+    const ID: &'static str = "GitignoreTemplate";
+
+    fn render(&self) -> Result<String, Box<dyn Error>> {
+        let mut template = TinyTemplate::new();
+        template.add_template("gitignore", TEMPLATE)?;
+
+        let rendered = template.render("gitignore", self)?;
+
+        Ok(rendered)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render() {
+        let got = GitignoreTemplate::new().render().unwrap();
+
+        assert!(got.starts_with("# Build output of every crate in the workspace\n/target\n"));
+        assert!(!got.contains(".cargo-project"));
+    }
+}
@@ -0,0 +1,85 @@
+use serde::Serialize;
+use std::error::Error;
+use tinytemplate::TinyTemplate;
+
+use super::super::Template;
+
+static TEMPLATE: &str = "use std::env;
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+
+fn main() -> io::Result<()> \\{
+    println!(\"cargo:rerun-if-changed=proto\");
+
+    // Fall back to the vendored protoc when none is installed
+    if env::var_os(\"PROTOC\").is_none() \\{
+        let protoc = protoc_bin_vendored::protoc_bin_path().expect(\"Error locating protoc\");
+        env::set_var(\"PROTOC\", protoc);
+    }
+
+    let entries = match fs::read_dir(\"proto\") \\{
+        Ok(entries) => entries,
+        Err(_) => return Ok(()),
+    };
+
+    let mut protos = Vec::new();
+    for entry in entries \\{
+        let path = entry?.path();
+
+        if path
+            .extension()
+            .is_some_and(|extension| extension == \"proto\")
+        \\{
+            protos.push(path);
+        }
+    }
+    protos.sort();
+
+    if protos.is_empty() \\{
+        return Ok(());
+    }
+
+    tonic_prost_build::configure().compile_protos(&protos, &[PathBuf::from(\"proto\")])
+}
+";
+
+/// Build script compiling every `.proto` file of the crate, so new features only
+/// need to add their own file.
+#[derive(Debug, Default, Serialize)]
+pub struct GrpcBuildFileTemplate {}
+
+impl GrpcBuildFileTemplate {
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+impl Template for GrpcBuildFileTemplate {
+    const ID: &'static str = "GrpcBuildFileTemplate";
+
+    fn render(&self) -> Result<String, Box<dyn Error>> {
+        let mut template = TinyTemplate::new();
+        template.add_template("build", TEMPLATE)?;
+
+        let rendered = template.render("build", self)?;
+
+        Ok(rendered)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render() {
+        let template = GrpcBuildFileTemplate::new();
+
+        let got = template.render().unwrap();
+
+        assert!(got.contains("fn main() -> io::Result<()> {"));
+        assert!(got.contains("let entries = match fs::read_dir(\"proto\") {"));
+        assert!(got.contains("tonic_prost_build::configure().compile_protos("));
+    }
+}
@@ -0,0 +1,62 @@
+use serde::Serialize;
+use std::error::Error;
+use tinytemplate::TinyTemplate;
+
+use super::super::Template;
+
+static TEMPLATE: &str = "[package]
+name = \"{project}-grpc\"
+version = \"0.1.0\"
+edition = \"2021\"
+
+[dependencies]
+{project}-domain = \\{ path = \"../{project}-domain\" }
+prost = \"0.14\"
+tokio = \\{ version = \"1\", features = [\"macros\", \"rt-multi-thread\"] }
+tonic = \"0.14\"
+tonic-prost = \"0.14\"
+
+[build-dependencies]
+protoc-bin-vendored = \"3\"
+tonic-prost-build = \"0.14\"
+";
+
+#[derive(Debug, Serialize)]
+pub struct GrpcCargoTemplate<'a> {
+    project: &'a str,
+}
+
+impl<'a> GrpcCargoTemplate<'a> {
+    pub fn new(project: &'a str) -> Self {
+        Self { project }
+    }
+}
+
+impl<'a> Template for GrpcCargoTemplate<'a> {
+    const ID: &'static str = "GrpcCargoTemplate";
+
+    fn render(&self) -> Result<String, Box<dyn Error>> {
+        let mut template = TinyTemplate::new();
+        template.add_template("cargo", TEMPLATE)?;
+
+        let rendered = template.render("cargo", self)?;
+
+        Ok(rendered)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render() {
+        let template = GrpcCargoTemplate::new("test");
+
+        let got = template.render().unwrap();
+
+        assert!(got.starts_with("[package]\nname = \"test-grpc\"\n"));
+        assert!(got.contains("test-domain = { path = \"../test-domain\" }\n"));
+        assert!(got.contains("[build-dependencies]\nprotoc-bin-vendored = \"3\"\n"));
+    }
+}
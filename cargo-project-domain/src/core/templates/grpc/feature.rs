@@ -0,0 +1,285 @@
+use serde::Serialize;
+use std::error::Error;
+use tinytemplate::{format_unescaped, TinyTemplate};
+
+use super::super::{chain, use_list, Template};
+use super::proto::{proto_actions, proto_fields, ProtoAction, ProtoField};
+
+use crate::core::runtime::{Runtime, RuntimeContext};
+use crate::core::source::{
+    has_method, method_names, StructField, UsecaseMethod, UsecaseMethodKind,
+};
+use crate::core::utils::pascal_case;
+
+static TEMPLATE: &str = "{{ if has_handlers }}use tonic::\\{Request, Response, Status};
+
+{{ if has_entity }}use {project}_domain::features::{name}::entities::{name_title_case};
+{{ endif }}use {project}_domain::features::{name}::usecase::\\{{imports}};
+
+{{ endif }}use proto::{name}_service_server::\\{{message}Service, {message}ServiceServer};
+
+pub mod proto \\{
+    tonic::include_proto!(\"{name}\");
+}
+
+#[derive(Default)]
+pub struct Service \\{}
+
+pub fn server() -> {message}ServiceServer<Service> \\{
+    {message}ServiceServer::new(Service::default())
+}
+{{ if has_entity }}
+impl From<{name_title_case}> for proto::{message} \\{
+{{ if fields }}    fn from(entity: {name_title_case}) -> Self \\{
+        Self \\{{{ for field in fields }}
+            {field.name}: entity.{field.name},{{ endfor }}
+        }
+    }
+{{ else }}    fn from(_entity: {name_title_case}) -> Self \\{
+        Self \\{}
+    }
+{{ endif }}}
+
+impl From<proto::{message}> for {name_title_case} \\{
+{{ if fields }}    fn from(message: proto::{message}) -> Self \\{
+        Self \\{{{ for field in fields }}
+            {field.name}: message.{field.name},{{ endfor }}{{ if unsupported_fields }}
+            ..Default::default(){{ endif }}
+        }
+    }
+{{ else }}    fn from(_message: proto::{message}) -> Self \\{
+        Self::default()
+    }
+{{ endif }}}
+{{ endif }}
+#[tonic::async_trait]
+impl {message}Service for Service \\{{{ if has_get }}
+    async fn get(
+        &self,
+        request: Request<proto::Get{message}Request>,
+    ) -> Result<Response<proto::{message}>, Status> \\{
+        let id = request.into_inner().id;
+
+        match {name_title_case}Usecase::new().get(&id){runtime.await_keyword} \\{
+            Ok(Some(entity)) => Ok(Response::new(entity.into())),
+            Ok(None) => Err(Status::not_found(format!(\"{name} \\{} not found\", id))),
+            Err(err) => Err(Status::internal(err)),
+        }
+    }
+{{ endif }}{{ if has_save }}
+    async fn save(
+        &self,
+        request: Request<proto::Save{message}Request>,
+    ) -> Result<Response<proto::Save{message}Response>, Status> \\{
+        let request = request.into_inner();
+        let entity = {name_title_case}::from(request.{name}.unwrap_or_default());
+
+        {save};
+
+        Ok(Response::new(proto::Save{message}Response \\{}))
+    }
+{{ endif }}{{ if has_list }}
+    async fn list(
+        &self,
+        _request: Request<proto::List{message}Request>,
+    ) -> Result<Response<proto::List{message}Response>, Status> \\{
+        let entities = {list};
+
+        Ok(Response::new(proto::List{message}Response \\{
+            {name}: entities.into_iter().map(Into::into).collect(),
+        }))
+    }
+{{ endif }}{{ for action in actions }}
+    async fn {action.name}(
+        &self,
+        _request: Request<proto::{action.rpc}{message}Request>,
+    ) -> Result<Response<proto::{action.rpc}{message}Response>, Status> \\{
+        {action.call};
+
+        Ok(Response::new(proto::{action.rpc}{message}Response \\{}))
+    }
+{{ endfor }}}
+{{ for method in unsupported_methods }}
+// `{method}` doesn't have a signature that can be mapped to an rpc, expose it by hand.
+{{ endfor }}";
+
+/// Rpc of an action method, calling it through the usecase.
+#[derive(Debug, Serialize)]
+struct Handler<'a> {
+    name: &'a str,
+    rpc: String,
+    call: String,
+}
+
+/// Tonic service delegating each rpc of a feature's `.proto` file to its usecase.
+#[derive(Debug, Serialize)]
+pub struct GrpcFeatureTemplate<'a> {
+    project: String,
+    name: &'a str,
+    name_title_case: &'a str,
+    message: String,
+    imports: String,
+    runtime: RuntimeContext,
+    save: String,
+    list: String,
+    has_get: bool,
+    has_save: bool,
+    has_list: bool,
+    has_entity: bool,
+    has_handlers: bool,
+    actions: Vec<Handler<'a>>,
+    unsupported_methods: Vec<&'a str>,
+    fields: Vec<ProtoField<'a>>,
+    unsupported_fields: Vec<&'a str>,
+}
+
+impl<'a> GrpcFeatureTemplate<'a> {
+    pub fn new(
+        project: &str,
+        name: &'a str,
+        name_title_case: &'a str,
+        methods: &'a [UsecaseMethod],
+        fields: &'a [StructField],
+        runtime: Runtime,
+    ) -> Self {
+        let has_get = has_method(methods, UsecaseMethodKind::Get);
+        let has_save = has_method(methods, UsecaseMethodKind::Save);
+        let has_list = has_method(methods, UsecaseMethodKind::List);
+        let actions = proto_actions(methods);
+        let (fields, unsupported_fields) = proto_fields(fields);
+        let runtime = runtime.context();
+
+        let usecase = format!("{}Usecase::new()", name_title_case);
+        let call = |call: &str| {
+            let calls = [call, runtime.await_keyword, ".map_err(Status::internal)?"];
+            chain(&usecase, &calls, 12)
+        };
+
+        Self {
+            project: project.replace('-', "_"),
+            name,
+            name_title_case,
+            message: pascal_case(name),
+            imports: use_list(&[&format!("{}Usecase", name_title_case), "Usecase"]),
+            save: call(".save(&request.id, &entity)"),
+            list: call(".list()"),
+            has_get,
+            has_save,
+            has_list,
+            has_entity: has_get || has_save || has_list,
+            has_handlers: has_get || has_save || has_list || !actions.is_empty(),
+            actions: actions
+                .into_iter()
+                .map(|ProtoAction { name, rpc }| Handler {
+                    name,
+                    rpc,
+                    call: call(&format!(".{}()", name)),
+                })
+                .collect(),
+            unsupported_methods: method_names(methods, UsecaseMethodKind::Other),
+            fields,
+            unsupported_fields,
+            runtime,
+        }
+    }
+}
+
+impl<'a> Template for GrpcFeatureTemplate<'a> {
+    const ID: &'static str = "GrpcFeatureTemplate";
+
+    fn render(&self) -> Result<String, Box<dyn Error>> {
+        let mut template = TinyTemplate::new();
+        template.set_default_formatter(&format_unescaped);
+        template.add_template("feature", TEMPLATE)?;
+
+        let rendered = template.render("feature", self)?;
+
+        Ok(rendered)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn method(name: &str, kind: UsecaseMethodKind) -> UsecaseMethod {
+        UsecaseMethod {
+            name: name.to_string(),
+            kind,
+        }
+    }
+
+    fn field(name: &str, ty: &str) -> StructField {
+        StructField {
+            name: name.to_string(),
+            ty: ty.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_render() {
+        let methods = vec![
+            method("get", UsecaseMethodKind::Get),
+            method("save", UsecaseMethodKind::Save),
+            method("list", UsecaseMethodKind::List),
+            method("archive", UsecaseMethodKind::Action),
+        ];
+        let fields = vec![field("title", "String"), field("size", "usize")];
+        let template = GrpcFeatureTemplate::new(
+            "my-app",
+            "notes",
+            "Notes",
+            &methods,
+            &fields,
+            Runtime::Tokio,
+        );
+
+        let got = template.render().unwrap();
+
+        assert!(got.starts_with("use tonic::{Request, Response, Status};\n\n"));
+        assert!(got.contains("use my_app_domain::features::notes::entities::Notes;"));
+        assert!(
+            got.contains("use proto::notes_service_server::{NotesService, NotesServiceServer};")
+        );
+        assert!(got.contains("    tonic::include_proto!(\"notes\");"));
+        assert!(got.contains("        Self {\n            title: entity.title,\n        }"));
+        assert!(
+            got.contains("            title: message.title,\n            ..Default::default()\n")
+        );
+        assert!(got.contains("match NotesUsecase::new().get(&id).await {"));
+        assert!(got.contains("let entity = Notes::from(request.notes.unwrap_or_default());"));
+        assert!(got.contains("notes: entities.into_iter().map(Into::into).collect(),"));
+        assert!(got.contains("_request: Request<proto::ArchiveNotesRequest>,"));
+        assert!(got.contains("            .archive()\n            .await\n"));
+    }
+
+    #[test]
+    fn test_render_without_fields() {
+        let methods = vec![method("list", UsecaseMethodKind::List)];
+        let template =
+            GrpcFeatureTemplate::new("test", "notes", "Notes", &methods, &[], Runtime::Sync);
+
+        let got = template.render().unwrap();
+
+        assert!(got.contains("    fn from(_entity: Notes) -> Self {\n        Self {}\n"));
+        assert!(got
+            .contains("    fn from(_message: proto::Notes) -> Self {\n        Self::default()\n"));
+        assert!(
+            got.contains("let entities = NotesUsecase::new().list().map_err(Status::internal)?;")
+        );
+        assert!(!got.contains("async fn get("));
+    }
+
+    #[test]
+    fn test_render_without_handlers() {
+        let methods = vec![method("count", UsecaseMethodKind::Other)];
+        let template =
+            GrpcFeatureTemplate::new("test", "notes", "Notes", &methods, &[], Runtime::Tokio);
+
+        let got = template.render().unwrap();
+
+        assert!(got.starts_with("use proto::notes_service_server::"));
+        assert!(got.contains("impl NotesService for Service {}"));
+        assert!(got.contains("// `count` doesn't have a signature"));
+    }
+}
@@ -0,0 +1,75 @@
+use serde::Serialize;
+use std::error::Error;
+use tinytemplate::{format_unescaped, TinyTemplate};
+
+use super::super::{chain, Template};
+
+static TEMPLATE: &str = "use tonic::service::Routes;
+
+{{ for feature in features }}pub mod {feature};
+{{ endfor }}
+pub fn routes() -> Routes \\{
+    {routes}
+}
+";
+
+/// Declares every feature module and registers their services.
+#[derive(Debug, Serialize)]
+pub struct GrpcFeaturesTemplate<'a> {
+    features: Vec<&'a str>,
+    routes: String,
+}
+
+impl<'a> GrpcFeaturesTemplate<'a> {
+    pub fn new(mut features: Vec<&'a str>) -> Self {
+        features.sort_unstable();
+        let calls: Vec<String> = features
+            .iter()
+            .map(|feature| format!(".add_service({}::server())", feature))
+            .collect();
+        let calls: Vec<&str> = calls.iter().map(String::as_str).collect();
+        let routes = chain("Routes::default()", &calls, 8);
+
+        Self { features, routes }
+    }
+}
+
+impl<'a> Template for GrpcFeaturesTemplate<'a> {
+    const ID: &'static str = "GrpcFeaturesTemplate";
+
+    fn render(&self) -> Result<String, Box<dyn Error>> {
+        let mut template = TinyTemplate::new();
+        template.set_default_formatter(&format_unescaped);
+        template.add_template("features", TEMPLATE)?;
+
+        let rendered = template.render("features", self)?;
+
+        Ok(rendered)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render() {
+        let template = GrpcFeaturesTemplate::new(vec!["notes", "tags"]);
+
+        let expect = "use tonic::service::Routes;
+
+pub mod notes;
+pub mod tags;
+
+pub fn routes() -> Routes {
+    Routes::default()
+        .add_service(notes::server())
+        .add_service(tags::server())
+}
+";
+
+        let got = &template.render().unwrap();
+
+        assert_eq!(expect, got)
+    }
+}
@@ -0,0 +1,64 @@
+use serde::Serialize;
+use std::error::Error;
+use tinytemplate::TinyTemplate;
+
+use super::super::Template;
+
+static TEMPLATE: &str = "use std::env;
+
+use tonic::transport::Server;
+
+mod features;
+
+#[tokio::main]
+async fn main() \\{
+    let address = env::var(\"ADDRESS\").unwrap_or_else(|_| String::from(\"127.0.0.1:50051\"));
+    let address = address.parse().expect(\"Error parsing server address\");
+
+    println!(\"Listening on \\{}\", address);
+
+    Server::builder()
+        .add_routes(features::routes())
+        .serve(address)
+        .await
+        .expect(\"Error running server\");
+}
+";
+
+#[derive(Debug, Default, Serialize)]
+pub struct GrpcMainFileTemplate {}
+
+impl GrpcMainFileTemplate {
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+impl Template for GrpcMainFileTemplate {
+    const ID: &'static str = "GrpcMainFileTemplate";
+
+    fn render(&self) -> Result<String, Box<dyn Error>> {
+        let mut template = TinyTemplate::new();
+        template.add_template("main", TEMPLATE)?;
+
+        let rendered = template.render("main", self)?;
+
+        Ok(rendered)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render() {
+        let template = GrpcMainFileTemplate::new();
+
+        let got = template.render().unwrap();
+
+        assert!(got.contains("mod features;\n\n#[tokio::main]\nasync fn main() {"));
+        assert!(got.contains("println!(\"Listening on {}\", address);"));
+        assert!(got.contains("        .add_routes(features::routes())\n"));
+    }
+}
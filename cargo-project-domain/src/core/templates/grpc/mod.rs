@@ -0,0 +1,6 @@
+pub mod build;
+pub mod cargo;
+pub mod feature;
+pub mod features;
+pub mod main;
+pub mod proto;
@@ -0,0 +1,249 @@
+use serde::Serialize;
+use std::error::Error;
+use tinytemplate::TinyTemplate;
+
+use super::super::Template;
+
+use crate::core::source::{
+    has_method, method_names, StructField, UsecaseMethod, UsecaseMethodKind,
+};
+use crate::core::utils::pascal_case;
+
+static TEMPLATE: &str = "syntax = \"proto3\";
+
+package {name};
+
+service {message}Service \\{{{ if has_get }}
+  rpc Get(Get{message}Request) returns ({message});{{ endif }}{{ if has_save }}
+  rpc Save(Save{message}Request) returns (Save{message}Response);{{ endif }}{{ if has_list }}
+  rpc List(List{message}Request) returns (List{message}Response);{{ endif }}{{ for action in actions }}
+  rpc {action.rpc}({action.rpc}{message}Request) returns ({action.rpc}{message}Response);{{ endfor }}
+}
+
+message {message} \\{
+{{ for field in fields }}  {field.ty} {field.name} = {field.number};
+{{ endfor }}{{ for field in unsupported_fields }}  // `{field}` has no protobuf equivalent
+{{ endfor }}}
+{{ if has_get }}
+message Get{message}Request \\{
+  string id = 1;
+}
+{{ endif }}{{ if has_save }}
+message Save{message}Request \\{
+  string id = 1;
+  {message} {name} = 2;
+}
+
+message Save{message}Response \\{}
+{{ endif }}{{ if has_list }}
+message List{message}Request \\{}
+
+message List{message}Response \\{
+  repeated {message} {name} = 1;
+}
+{{ endif }}{{ for action in actions }}
+message {action.rpc}{message}Request \\{}
+
+message {action.rpc}{message}Response \\{}
+{{ endfor }}";
+
+#[derive(Debug, Serialize)]
+pub struct ProtoField<'a> {
+    pub name: &'a str,
+    pub ty: String,
+    pub number: usize,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ProtoAction<'a> {
+    pub name: &'a str,
+    pub rpc: String,
+}
+
+fn scalar_type(ty: &str) -> Option<&'static str> {
+    match ty {
+        "String" => Some("string"),
+        "bool" => Some("bool"),
+        "i32" => Some("int32"),
+        "i64" => Some("int64"),
+        "u32" => Some("uint32"),
+        "u64" => Some("uint64"),
+        "f32" => Some("float"),
+        "f64" => Some("double"),
+        "Vec<u8>" => Some("bytes"),
+        _ => None,
+    }
+}
+
+/// Maps a Rust field type to the protobuf type prost generates the same Rust type for.
+fn proto_type(ty: &str) -> Option<String> {
+    if let Some(scalar) = scalar_type(ty) {
+        return Some(scalar.to_string());
+    }
+
+    let (label, inner) = if let Some(inner) = ty.strip_prefix("Vec<") {
+        ("repeated", inner.strip_suffix('>')?)
+    } else if let Some(inner) = ty.strip_prefix("Option<") {
+        ("optional", inner.strip_suffix('>')?)
+    } else {
+        return None;
+    };
+
+    match scalar_type(inner) {
+        Some("bytes") | None => None,
+        Some(scalar) => Some(format!("{} {}", label, scalar)),
+    }
+}
+
+/// Splits entity fields into the ones carried by the protobuf message and the names
+/// of the ones that can't be.
+pub fn proto_fields(fields: &[StructField]) -> (Vec<ProtoField<'_>>, Vec<&str>) {
+    let mut supported = Vec::new();
+    let mut unsupported = Vec::new();
+
+    for field in fields {
+        match proto_type(&field.ty) {
+            Some(ty) => supported.push(ProtoField {
+                name: &field.name,
+                ty,
+                number: supported.len() + 1,
+            }),
+            None => unsupported.push(field.name.as_str()),
+        }
+    }
+
+    (supported, unsupported)
+}
+
+pub fn proto_actions(methods: &[UsecaseMethod]) -> Vec<ProtoAction<'_>> {
+    method_names(methods, UsecaseMethodKind::Action)
+        .into_iter()
+        .map(|name| ProtoAction {
+            name,
+            rpc: pascal_case(name),
+        })
+        .collect()
+}
+
+/// Protobuf service mirroring a feature's usecase, with a message for its entity.
+#[derive(Debug, Serialize)]
+pub struct GrpcProtoTemplate<'a> {
+    name: &'a str,
+    message: String,
+    has_get: bool,
+    has_save: bool,
+    has_list: bool,
+    actions: Vec<ProtoAction<'a>>,
+    fields: Vec<ProtoField<'a>>,
+    unsupported_fields: Vec<&'a str>,
+}
+
+impl<'a> GrpcProtoTemplate<'a> {
+    pub fn new(name: &'a str, methods: &'a [UsecaseMethod], fields: &'a [StructField]) -> Self {
+        let (fields, unsupported_fields) = proto_fields(fields);
+
+        Self {
+            name,
+            message: pascal_case(name),
+            has_get: has_method(methods, UsecaseMethodKind::Get),
+            has_save: has_method(methods, UsecaseMethodKind::Save),
+            has_list: has_method(methods, UsecaseMethodKind::List),
+            actions: proto_actions(methods),
+            fields,
+            unsupported_fields,
+        }
+    }
+}
+
+impl<'a> Template for GrpcProtoTemplate<'a> {
+    const ID: &'static str = "GrpcProtoTemplate";
+
+    fn render(&self) -> Result<String, Box<dyn Error>> {
+        let mut template = TinyTemplate::new();
+        template.add_template("proto", TEMPLATE)?;
+
+        let rendered = template.render("proto", self)?;
+
+        Ok(rendered)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn field(name: &str, ty: &str) -> StructField {
+        StructField {
+            name: name.to_string(),
+            ty: ty.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_proto_type() {
+        assert_eq!(Some(String::from("string")), proto_type("String"));
+        assert_eq!(Some(String::from("bytes")), proto_type("Vec<u8>"));
+        assert_eq!(Some(String::from("repeated int64")), proto_type("Vec<i64>"));
+        assert_eq!(
+            Some(String::from("optional bool")),
+            proto_type("Option<bool>")
+        );
+        assert_eq!(None, proto_type("Option<Vec<u8>>"));
+        assert_eq!(None, proto_type("HashMap<String,String>"));
+        assert_eq!(None, proto_type("usize"));
+    }
+
+    #[test]
+    fn test_render() {
+        let methods = vec![
+            UsecaseMethod {
+                name: String::from("get"),
+                kind: UsecaseMethodKind::Get,
+            },
+            UsecaseMethod {
+                name: String::from("list"),
+                kind: UsecaseMethodKind::List,
+            },
+            UsecaseMethod {
+                name: String::from("archive_all"),
+                kind: UsecaseMethodKind::Action,
+            },
+        ];
+        let fields = vec![field("title", "String"), field("size", "usize")];
+        let template = GrpcProtoTemplate::new("user_notes", &methods, &fields);
+
+        let expect = "syntax = \"proto3\";
+
+package user_notes;
+
+service UserNotesService {
+  rpc Get(GetUserNotesRequest) returns (UserNotes);
+  rpc List(ListUserNotesRequest) returns (ListUserNotesResponse);
+  rpc ArchiveAll(ArchiveAllUserNotesRequest) returns (ArchiveAllUserNotesResponse);
+}
+
+message UserNotes {
+  string title = 1;
+  // `size` has no protobuf equivalent
+}
+
+message GetUserNotesRequest {
+  string id = 1;
+}
+
+message ListUserNotesRequest {}
+
+message ListUserNotesResponse {
+  repeated UserNotes user_notes = 1;
+}
+
+message ArchiveAllUserNotesRequest {}
+
+message ArchiveAllUserNotesResponse {}
+";
+
+        let got = &template.render().unwrap();
+
+        assert_eq!(expect, got)
+    }
+}
@@ -0,0 +1,58 @@
+use serde::Serialize;
+use std::error::Error;
+use tinytemplate::TinyTemplate;
+
+use super::super::Template;
+
+static TEMPLATE: &str = "[package]
+name = \"{crate_name}\"
+version = \"0.1.0\"
+edition = \"2021\"
+
+[dependencies]
+";
+
+#[derive(Debug, Serialize)]
+pub struct LayerCargoTemplate<'a> {
+    crate_name: &'a str,
+}
+
+impl<'a> LayerCargoTemplate<'a> {
+    pub fn new(crate_name: &'a str) -> Self {
+        Self { crate_name }
+    }
+}
+
+impl<'a> Template for LayerCargoTemplate<'a> {
+    const ID: &'static str = "LayerCargoTemplate";
+
+    fn render(&self) -> Result<String, Box<dyn Error>> {
+        let mut template = TinyTemplate::new();
+        template.add_template("cargo", TEMPLATE)?;
+
+        let rendered = template.render("cargo", self)?;
+
+        Ok(rendered)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render() {
+        let template = LayerCargoTemplate::new("test-infrastructure");
+
+        let expect = "[package]
+name = \"test-infrastructure\"
+version = \"0.1.0\"
+edition = \"2021\"
+
+[dependencies]
+";
+        let got = &template.render().unwrap();
+
+        assert_eq!(expect, got)
+    }
+}
@@ -0,0 +1,50 @@
+use serde::Serialize;
+use std::error::Error;
+use tinytemplate::TinyTemplate;
+
+use super::super::Template;
+
+static TEMPLATE: &str = "//! `{file}` of the `{name}` feature in the {layer} layer.
+";
+
+/// Placeholder for a file every feature gets in a configured layer.
+#[derive(Debug, Serialize)]
+pub struct LayerFileTemplate<'a> {
+    layer: &'a str,
+    name: &'a str,
+    file: &'a str,
+}
+
+impl<'a> LayerFileTemplate<'a> {
+    pub fn new(layer: &'a str, name: &'a str, file: &'a str) -> Self {
+        Self { layer, name, file }
+    }
+}
+
+impl<'a> Template for LayerFileTemplate<'a> {
+    const ID: &'static str = "LayerFileTemplate";
+
+    fn render(&self) -> Result<String, Box<dyn Error>> {
+        let mut template = TinyTemplate::new();
+        template.add_template("file", TEMPLATE)?;
+
+        let rendered = template.render("file", self)?;
+
+        Ok(rendered)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render() {
+        let template = LayerFileTemplate::new("infrastructure", "notes", "adapter");
+
+        let expect = "//! `adapter` of the `notes` feature in the infrastructure layer.\n";
+        let got = &template.render().unwrap();
+
+        assert_eq!(expect, got)
+    }
+}
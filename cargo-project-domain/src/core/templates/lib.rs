@@ -13,7 +13,8 @@ mod tests \\{
     fn it_works() \\{
         assert_eq!(2 + 2, 4);
     }
-}";
+}
+";
 
 #[derive(Debug, Serialize)]
 pub struct LibFileTemplate {}
@@ -25,6 +26,8 @@ impl LibFileTemplate {
 }
 
 impl Template for LibFileTemplate {
+    const ID: &'static str = "LibFileTemplate";
+
     fn render(&self) -> Result<String, Box<dyn Error>> {
         let mut template = TinyTemplate::new();
         template.add_template("cargo", TEMPLATE)?;
@@ -52,7 +55,8 @@ mod tests {
     fn it_works() {
         assert_eq!(2 + 2, 4);
     }
-}";
+}
+";
 
         let got = &template.render().unwrap();
 
@@ -4,21 +4,30 @@ use tinytemplate::TinyTemplate;
 
 use super::Template;
 
+use crate::core::runtime::{Runtime, RuntimeContext};
+
 static TEMPLATE: &str = "mod core;
 mod features;
 
-fn main() \\{}";
+{runtime.main_attribute}{runtime.async_keyword}fn main() \\{}
+";
 
 #[derive(Debug, Serialize)]
-pub struct MainFileTemplate {}
+pub struct MainFileTemplate {
+    runtime: RuntimeContext,
+}
 
 impl MainFileTemplate {
-    pub fn new() -> Self {
-        Self {}
+    pub fn new(runtime: Runtime) -> Self {
+        Self {
+            runtime: runtime.context(),
+        }
     }
 }
 
 impl Template for MainFileTemplate {
+    const ID: &'static str = "MainFileTemplate";
+
     fn render(&self) -> Result<String, Box<dyn Error>> {
         let mut template = TinyTemplate::new();
         template.add_template("cargo", TEMPLATE)?;
@@ -35,12 +44,29 @@ mod tests {
 
     #[test]
     fn test_render() {
-        let template = MainFileTemplate::new();
+        let template = MainFileTemplate::new(Runtime::Sync);
+
+        let expect = "mod core;
+mod features;
+
+fn main() {}
+";
+
+        let got = &template.render().unwrap();
+
+        assert_eq!(expect, got)
+    }
+
+    #[test]
+    fn test_render_tokio() {
+        let template = MainFileTemplate::new(Runtime::Tokio);
 
         let expect = "mod core;
 mod features;
 
-fn main() {}";
+#[tokio::main]
+async fn main() {}
+";
 
         let got = &template.render().unwrap();
 
@@ -1,16 +1,186 @@
 use std::error::Error;
 
 pub mod cargo;
+pub mod ci;
 pub mod cli;
 pub mod datasource;
+pub mod docs;
 pub mod entities;
+pub mod facade;
+pub mod fakes;
+pub mod file_datasource;
+pub mod gitignore;
+pub mod grpc;
+pub mod layer;
 pub mod lib;
 pub mod main;
 pub mod mod_file;
 pub mod models;
 pub mod repository;
+pub mod tui;
 pub mod usecase;
+pub mod usecase_method;
+pub mod wasm;
+pub mod web;
+
+/// Templates whose output changed since they were introduced, along with their current
+/// version. Add or bump a template's entry with every change to what it renders, files
+/// stamped with an older version are reported as outdated by `status`.
+const VERSIONS: &[(&str, u32)] = &[
+    ("CliTemplate", 3),
+    ("DatasourceTemplate", 2),
+    ("EntitiesTemplate", 2),
+    ("FakesTemplate", 2),
+    ("FileDatasourceTemplate", 2),
+    ("GrpcBuildFileTemplate", 2),
+    ("GrpcFeatureTemplate", 2),
+    ("GrpcFeaturesTemplate", 2),
+    ("LibFileTemplate", 2),
+    ("MainFileTemplate", 2),
+    ("ModFileTemplate", 2),
+    ("RepositoryTemplate", 3),
+    ("TuiAppTemplate", 2),
+    ("TuiFeaturesTemplate", 2),
+    ("UsecaseTemplate", 2),
+    ("WebFeatureTemplate", 2),
+    ("WebFeaturesTemplate", 2),
+];
+
+/// Current version of the template with the given `ID`.
+pub fn template_version(id: &str) -> u32 {
+    VERSIONS
+        .iter()
+        .find(|(template, _)| *template == id)
+        .map(|(_, version)| *version)
+        .unwrap_or(1)
+}
+
+/// Names imported by a `use` list, joined in the order rustfmt sorts them so generated
+/// files pass `cargo fmt --check` whatever the feature is called.
+pub fn use_list(names: &[&str]) -> String {
+    let mut names = names.to_vec();
+    names.sort_unstable();
+    names.join(", ")
+}
+
+/// Widest method chain or list rustfmt keeps on a single line with its default settings,
+/// templates laying out chains and lists of generated names break them past it.
+pub const SINGLE_LINE_WIDTH: usize = 60;
+
+/// Lays out the method chain `root` followed by `calls` the way rustfmt does: on a single
+/// line when it fits in `SINGLE_LINE_WIDTH`, otherwise with each call on its own line
+/// indented by `indent` spaces. Empty calls, like the await of a sync runtime, are skipped.
+pub fn chain(root: &str, calls: &[&str], indent: usize) -> String {
+    let calls: Vec<&str> = calls
+        .iter()
+        .copied()
+        .filter(|call| !call.is_empty())
+        .collect();
+
+    let line = format!("{}{}", root, calls.concat());
+    if line.len() <= SINGLE_LINE_WIDTH {
+        return line;
+    }
+
+    let indent = " ".repeat(indent);
+    calls.iter().fold(String::from(root), |chain, call| {
+        format!("{}\n{}{}", chain, indent, call)
+    })
+}
+
+/// Output of a template along with the template and version it was rendered with.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Rendered {
+    pub template: &'static str,
+    pub version: u32,
+    pub content: String,
+}
 
 pub trait Template {
+    /// Identifies the template in generation stamps and `VERSIONS`, so it has to stay the
+    /// same when the template's type is renamed.
+    const ID: &'static str;
+
     fn render(&self) -> Result<String, Box<dyn Error>>;
+
+    fn version(&self) -> u32 {
+        template_version(Self::ID)
+    }
+
+    fn rendered(&self) -> Result<Rendered, Box<dyn Error>> {
+        Ok(Rendered {
+            template: Self::ID,
+            version: self.version(),
+            content: self.render()?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use gitignore::GitignoreTemplate;
+
+    #[test]
+    fn test_rendered() {
+        let template = GitignoreTemplate::new();
+
+        let got = template.rendered().unwrap();
+
+        assert_eq!("GitignoreTemplate", got.template);
+        assert_eq!(1, got.version);
+        assert_eq!(template.render().unwrap(), got.content);
+    }
+
+    #[test]
+    fn test_version() {
+        assert_eq!(
+            2,
+            entities::EntitiesTemplate::new("test", "Test", "project").version()
+        );
+        assert_eq!(
+            3,
+            cli::CliTemplate::new("project", "test", "Test").version()
+        );
+        assert_eq!(2, lib::LibFileTemplate::new().version());
+        assert_eq!(1, GitignoreTemplate::new().version());
+    }
+
+    #[test]
+    fn test_chain() {
+        assert_eq!(
+            "NotesUsecase::new().list().map(Json)",
+            chain("NotesUsecase::new()", &[".list()", "", ".map(Json)"], 4)
+        );
+        assert_eq!(
+            "NotesUsecase::new()
+    .list()
+    .await
+    .map(Json)
+    .map_err(internal_error)",
+            chain(
+                "NotesUsecase::new()",
+                &[
+                    ".list()",
+                    ".await",
+                    ".map(Json)",
+                    ".map_err(internal_error)"
+                ],
+                4
+            )
+        );
+    }
+
+    #[test]
+    fn test_use_list() {
+        assert_eq!(
+            "NotesUsecase, Usecase",
+            use_list(&["NotesUsecase", "Usecase"])
+        );
+        assert_eq!(
+            "Datasource, StoreDatasource",
+            use_list(&["StoreDatasource", "Datasource"])
+        );
+    }
 }
@@ -4,20 +4,48 @@ use tinytemplate::TinyTemplate;
 
 use super::Template;
 
-static TEMPLATE: &str = "{{ for resource in resources }}pub mod {resource};\n{{ endfor }}";
+static TEMPLATE: &str =
+    "{{ for module in modules }}{{ if module.test_only }}#[cfg(test)]\n{{ endif }}\
+pub mod {module.name};\n{{ endfor }}{{ if not modules }}\n{{ endif }}";
 
+#[derive(Debug, Serialize)]
+struct Module<'a> {
+    name: &'a str,
+    test_only: bool,
+}
+
+/// Declares modules in the order rustfmt sorts them, an empty file holding a single newline
+/// as rustfmt leaves it.
 #[derive(Debug, Serialize)]
 pub struct ModFileTemplate<'a> {
-    resources: Vec<&'a str>,
+    modules: Vec<Module<'a>>,
 }
 
 impl<'a> ModFileTemplate<'a> {
     pub fn new(resources: Vec<&'a str>) -> Self {
-        Self { resources }
+        Self::with_test_resources(resources, vec![])
+    }
+
+    pub fn with_test_resources(resources: Vec<&'a str>, test_resources: Vec<&'a str>) -> Self {
+        let resources = resources.into_iter().map(|name| Module {
+            name,
+            test_only: false,
+        });
+        let test_resources = test_resources.into_iter().map(|name| Module {
+            name,
+            test_only: true,
+        });
+
+        let mut modules: Vec<Module> = resources.chain(test_resources).collect();
+        modules.sort_by_key(|module| module.name);
+
+        Self { modules }
     }
 }
 
 impl<'a> Template for ModFileTemplate<'a> {
+    const ID: &'static str = "ModFileTemplate";
+
     fn render(&self) -> Result<String, Box<dyn Error>> {
         let mut template = TinyTemplate::new();
         template.add_template("cli", TEMPLATE)?;
@@ -41,4 +69,33 @@ mod tests {
 
         assert_eq!(expect, got)
     }
+
+    #[test]
+    fn test_render_with_test_resources() {
+        let template = ModFileTemplate::with_test_resources(vec!["test"], vec!["fakes"]);
+
+        let expect = "#[cfg(test)]\npub mod fakes;\npub mod test;\n";
+        let got = &template.render().unwrap();
+
+        assert_eq!(expect, got)
+    }
+
+    #[test]
+    fn test_render_sorted() {
+        let template = ModFileTemplate::new(vec!["store", "notes"]);
+
+        let expect = "pub mod notes;\npub mod store;\n";
+        let got = &template.render().unwrap();
+
+        assert_eq!(expect, got)
+    }
+
+    #[test]
+    fn test_render_empty() {
+        let template = ModFileTemplate::new(vec![]);
+
+        let got = &template.render().unwrap();
+
+        assert_eq!("\n", got)
+    }
 }
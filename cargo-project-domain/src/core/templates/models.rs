@@ -4,20 +4,31 @@ use tinytemplate::TinyTemplate;
 
 use super::Template;
 
-static TEMPLATE: &str = "";
+static TEMPLATE: &str = "{{ if serialize }}use serde::\\{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+{{ else }}#[derive(Debug, Clone, Default, PartialEq)]
+{{ endif }}pub struct {name_title_case}Model \\{}
+";
 
 #[derive(Debug, Serialize)]
 pub struct ModelsTemplate<'a> {
-    name: &'a str,
+    name_title_case: &'a str,
+    serialize: bool,
 }
 
 impl<'a> ModelsTemplate<'a> {
-    pub fn new(name: &'a str) -> Self {
-        Self { name }
+    pub fn new(name_title_case: &'a str, serialize: bool) -> Self {
+        Self {
+            name_title_case,
+            serialize,
+        }
     }
 }
 
 impl<'a> Template for ModelsTemplate<'a> {
+    const ID: &'static str = "ModelsTemplate";
+
     fn render(&self) -> Result<String, Box<dyn Error>> {
         let mut template = TinyTemplate::new();
         template.add_template("cli", TEMPLATE)?;
@@ -27,3 +38,34 @@ impl<'a> Template for ModelsTemplate<'a> {
         Ok(rendered)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render() {
+        let template = ModelsTemplate::new("Test", false);
+
+        let expect = "#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TestModel {}
+";
+        let got = &template.render().unwrap();
+
+        assert_eq!(expect, got)
+    }
+
+    #[test]
+    fn test_render_serialize() {
+        let template = ModelsTemplate::new("Test", true);
+
+        let expect = "use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct TestModel {}
+";
+        let got = &template.render().unwrap();
+
+        assert_eq!(expect, got)
+    }
+}
@@ -2,12 +2,21 @@ use serde::Serialize;
 use std::error::Error;
 use tinytemplate::TinyTemplate;
 
-use super::Template;
+use super::{use_list, Template};
 
-static TEMPLATE: &str =
-    "use {project}_data::features::{name}::datasource::\\{{name_title_case}Datasource, Datasource};
+use crate::core::runtime::{Runtime, RuntimeContext};
 
-pub trait Repository \\{}
+static TEMPLATE: &str = "{{ if runtime.is_async }}use async_trait::async_trait;
+
+{{ endif }}use {project}_data::features::{name}::datasource::\\{{imports}};
+
+use super::entities::{name_title_case};
+
+{runtime.async_trait}pub trait Repository \\{
+    {runtime.async_keyword}fn get(&self, id: &str) -> Result<Option<{name_title_case}>, String>;
+    {runtime.async_keyword}fn save(&self, id: &str, entity: &{name_title_case}) -> Result<(), String>;
+    {runtime.async_keyword}fn list(&self) -> Result<Vec<{name_title_case}>, String>;
+}
 
 pub struct {name_title_case}Repository \\{
     datasource: Box<dyn Datasource + Send + Sync>,
@@ -20,40 +29,113 @@ impl {name_title_case}Repository \\{
     }
 }
 
-impl Repository for {name_title_case}Repository \\{}
+impl Default for {name_title_case}Repository \\{
+    fn default() -> Self \\{
+        Self::new()
+    }
+}
+
+{runtime.async_trait}impl Repository for {name_title_case}Repository \\{
+    {runtime.async_keyword}fn get(&self, id: &str) -> Result<Option<{name_title_case}>, String> \\{
+{{ if runtime.is_async }}        let model = self
+            .datasource
+            .load(id)
+            .await
+            .map_err(|err| err.to_string())?;
+{{ else }}        let model = self.datasource.load(id).map_err(|err| err.to_string())?;
+{{ endif }}        Ok(model.map({name_title_case}::from))
+    }
+
+    {runtime.async_keyword}fn save(&self, id: &str, entity: &{name_title_case}) -> Result<(), String> \\{
+        let model = entity.clone().into();
+        self.datasource
+            .save(id, &model)
+{{ if runtime.is_async }}            .await
+{{ endif }}            .map_err(|err| err.to_string())
+    }
+
+    {runtime.async_keyword}fn list(&self) -> Result<Vec<{name_title_case}>, String> \\{
+{{ if runtime.is_async }}        let models = self
+            .datasource
+            .list()
+            .await
+            .map_err(|err| err.to_string())?;
+{{ else }}        let models = self.datasource.list().map_err(|err| err.to_string())?;
+{{ endif }}        Ok(models.into_iter().map({name_title_case}::from).collect())
+    }
+}
 
 #[cfg(test)]
 mod tests \\{
     use super::*;
+    use crate::features::{name}::fakes::InMemoryDatasource;
 
-    struct MockDatasource \\{}
+    {runtime.test_attribute}
+    {runtime.async_keyword}fn test_save_and_get() \\{
+        let datasource = Box::new(InMemoryDatasource::default());
+        let repository = {name_title_case}Repository \\{ datasource };
 
-    impl Datasource for MockDatasource \\{}
+        let expect = {name_title_case}::default();
+        repository.save(\"test\", &expect){runtime.await_keyword}.unwrap();
+        let got = repository.get(\"test\"){runtime.await_keyword}.unwrap();
 
-    #[test]
-    fn test() \\{
-        assert_eq!(1, 1)
+        assert_eq!(Some(expect), got)
     }
-}";
+
+    {runtime.test_attribute}
+    {runtime.async_keyword}fn test_get_missing() \\{
+        let datasource = Box::new(InMemoryDatasource::default());
+        let repository = {name_title_case}Repository \\{ datasource };
+
+        let got = repository.get(\"missing\"){runtime.await_keyword}.unwrap();
+
+        assert_eq!(None, got)
+    }
+
+    {runtime.test_attribute}
+    {runtime.async_keyword}fn test_list() \\{
+        let datasource = Box::new(InMemoryDatasource::default());
+        let repository = {name_title_case}Repository \\{ datasource };
+
+        let entity = {name_title_case}::default();
+        repository.save(\"first\", &entity){runtime.await_keyword}.unwrap();
+        repository.save(\"second\", &entity){runtime.await_keyword}.unwrap();
+        let got = repository.list(){runtime.await_keyword}.unwrap();
+
+        assert_eq!(2, got.len())
+    }
+}
+";
 
 #[derive(Debug, Serialize)]
 pub struct RepositoryTemplate<'a> {
     name: &'a str,
     name_title_case: &'a str,
-    project: &'a str,
+    project: String,
+    imports: String,
+    runtime: RuntimeContext,
 }
 
 impl<'a> RepositoryTemplate<'a> {
-    pub fn new(name: &'a str, name_title_case: &'a str, project: &'a str) -> Self {
+    pub fn new(
+        name: &'a str,
+        name_title_case: &'a str,
+        project: &'a str,
+        runtime: Runtime,
+    ) -> Self {
         Self {
             name,
             name_title_case,
-            project,
+            project: project.replace('-', "_"),
+            imports: use_list(&[&format!("{}Datasource", name_title_case), "Datasource"]),
+            runtime: runtime.context(),
         }
     }
 }
 
 impl<'a> Template for RepositoryTemplate<'a> {
+    const ID: &'static str = "RepositoryTemplate";
+
     fn render(&self) -> Result<String, Box<dyn Error>> {
         let mut template = TinyTemplate::new();
         template.add_template("cli", TEMPLATE)?;
@@ -70,11 +152,20 @@ mod tests {
 
     #[test]
     fn test_render() {
-        let template = RepositoryTemplate::new("test", "Test", "test");
+        let template = RepositoryTemplate::new("test", "Test", "test", Runtime::AsyncStd);
+
+        let expect = "use async_trait::async_trait;
 
-        let expect = "use test_data::features::test::datasource::{TestDatasource, Datasource};
+use test_data::features::test::datasource::{Datasource, TestDatasource};
 
-pub trait Repository {}
+use super::entities::Test;
+
+#[async_trait]
+pub trait Repository {
+    async fn get(&self, id: &str) -> Result<Option<Test>, String>;
+    async fn save(&self, id: &str, entity: &Test) -> Result<(), String>;
+    async fn list(&self) -> Result<Vec<Test>, String>;
+}
 
 pub struct TestRepository {
     datasource: Box<dyn Datasource + Send + Sync>,
@@ -87,24 +178,99 @@ impl TestRepository {
     }
 }
 
-impl Repository for TestRepository {}
+impl Default for TestRepository {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl Repository for TestRepository {
+    async fn get(&self, id: &str) -> Result<Option<Test>, String> {
+        let model = self
+            .datasource
+            .load(id)
+            .await
+            .map_err(|err| err.to_string())?;
+        Ok(model.map(Test::from))
+    }
+
+    async fn save(&self, id: &str, entity: &Test) -> Result<(), String> {
+        let model = entity.clone().into();
+        self.datasource
+            .save(id, &model)
+            .await
+            .map_err(|err| err.to_string())
+    }
+
+    async fn list(&self) -> Result<Vec<Test>, String> {
+        let models = self
+            .datasource
+            .list()
+            .await
+            .map_err(|err| err.to_string())?;
+        Ok(models.into_iter().map(Test::from).collect())
+    }
+}
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::features::test::fakes::InMemoryDatasource;
 
-    struct MockDatasource {}
+    #[async_std::test]
+    async fn test_save_and_get() {
+        let datasource = Box::new(InMemoryDatasource::default());
+        let repository = TestRepository { datasource };
 
-    impl Datasource for MockDatasource {}
+        let expect = Test::default();
+        repository.save(\"test\", &expect).await.unwrap();
+        let got = repository.get(\"test\").await.unwrap();
 
-    #[test]
-    fn test() {
-        assert_eq!(1, 1)
+        assert_eq!(Some(expect), got)
     }
-}";
+
+    #[async_std::test]
+    async fn test_get_missing() {
+        let datasource = Box::new(InMemoryDatasource::default());
+        let repository = TestRepository { datasource };
+
+        let got = repository.get(\"missing\").await.unwrap();
+
+        assert_eq!(None, got)
+    }
+
+    #[async_std::test]
+    async fn test_list() {
+        let datasource = Box::new(InMemoryDatasource::default());
+        let repository = TestRepository { datasource };
+
+        let entity = Test::default();
+        repository.save(\"first\", &entity).await.unwrap();
+        repository.save(\"second\", &entity).await.unwrap();
+        let got = repository.list().await.unwrap();
+
+        assert_eq!(2, got.len())
+    }
+}
+";
 
         let got = &template.render().unwrap();
 
         assert_eq!(expect, got)
     }
+
+    #[test]
+    fn test_render_sync() {
+        let template = RepositoryTemplate::new("test", "Test", "test", Runtime::Sync);
+
+        let got = template.render().unwrap();
+
+        assert!(got.starts_with("use test_data::features::test::datasource::"));
+        assert!(
+            got.contains("let model = self.datasource.load(id).map_err(|err| err.to_string())?;")
+        );
+        assert!(got.contains("    #[test]\n    fn test_list() {"));
+        assert!(!got.contains("async"));
+    }
 }
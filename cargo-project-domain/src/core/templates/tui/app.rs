@@ -0,0 +1,188 @@
+use serde::Serialize;
+use std::error::Error;
+use tinytemplate::TinyTemplate;
+
+use super::super::Template;
+
+use crate::core::runtime::{Runtime, RuntimeContext};
+
+static TEMPLATE: &str = "{{ if runtime.is_async }}use std::future::Future;
+{{ endif }}use std::io;
+{{ if runtime.is_tokio }}use std::sync::OnceLock;
+{{ endif }}
+use ratatui::crossterm::event::\\{self, Event, KeyCode, KeyEventKind};
+use ratatui::layout::\\{Constraint, Layout, Rect};
+use ratatui::text::Line;
+use ratatui::widgets::\\{Block, Paragraph, Tabs};
+use ratatui::\\{DefaultTerminal, Frame};
+{{ if runtime.is_tokio }}use tokio::runtime::Runtime;
+{{ endif }}
+use crate::features;
+
+/// A tab of the application, usually backed by one feature's usecase.
+pub trait Screen \\{
+    fn title(&self) -> &str;
+    fn help(&self) -> &str;
+    fn handle_key(&mut self, code: KeyCode);
+    fn draw(&self, frame: &mut Frame, area: Rect);
+}
+{{ if runtime.is_tokio }}
+/// Drives a usecase future to completion from the synchronous event loop.
+pub fn block_on<F: Future>(future: F) -> F::Output \\{
+    static RUNTIME: OnceLock<Runtime> = OnceLock::new();
+
+    RUNTIME
+        .get_or_init(|| Runtime::new().expect(\"Error starting runtime\"))
+        .block_on(future)
+}
+{{ endif }}{{ if runtime.is_async_std }}
+/// Drives a usecase future to completion from the synchronous event loop.
+pub fn block_on<F: Future>(future: F) -> F::Output \\{
+    async_std::task::block_on(future)
+}
+{{ endif }}
+pub struct App \\{
+    screens: Vec<Box<dyn Screen>>,
+    selected: usize,
+    running: bool,
+}
+
+impl Default for App \\{
+    fn default() -> Self \\{
+        Self \\{
+            screens: features::screens(),
+            selected: 0,
+            running: true,
+        }
+    }
+}
+
+impl App \\{
+    pub fn run(mut self, terminal: &mut DefaultTerminal) -> io::Result<()> \\{
+        while self.running \\{
+            terminal.draw(|frame| self.draw(frame))?;
+
+            if let Event::Key(key) = event::read()? \\{
+                if key.kind == KeyEventKind::Press \\{
+                    self.handle_key(key.code);
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    fn handle_key(&mut self, code: KeyCode) \\{
+        match code \\{
+            KeyCode::Char('q') | KeyCode::Esc => self.running = false,
+            KeyCode::Tab if !self.screens.is_empty() => \\{
+                self.selected = (self.selected + 1) % self.screens.len()
+            }
+            code => \\{
+                if let Some(screen) = self.screens.get_mut(self.selected) \\{
+                    screen.handle_key(code)
+                }
+            }
+        }
+    }
+
+    fn draw(&self, frame: &mut Frame) \\{
+        let [tabs_area, screen_area, help_area] = Layout::vertical([
+            Constraint::Length(3),
+            Constraint::Min(0),
+            Constraint::Length(1),
+        ])
+        .areas(frame.area());
+
+        let titles = self.screens.iter().map(|screen| screen.title());
+        let tabs = Tabs::new(titles)
+            .select(self.selected)
+            .block(Block::bordered().title(\"{project}\"));
+        frame.render_widget(tabs, tabs_area);
+
+        let mut help = String::from(\"q quit | tab next screen\");
+
+        match self.screens.get(self.selected) \\{
+            Some(screen) => \\{
+                screen.draw(frame, screen_area);
+                help = format!(\"\\{} | \\{}\", help, screen.help());
+            }
+            None => \\{
+                let empty =
+                    Paragraph::new(\"No features yet, add one with `cargo project add feature`\");
+                frame.render_widget(empty, screen_area);
+            }
+        }
+
+        frame.render_widget(Line::from(help), help_area);
+    }
+}
+";
+
+/// Event loop switching between feature screens and forwarding key presses to them.
+#[derive(Debug, Serialize)]
+pub struct TuiAppTemplate<'a> {
+    project: &'a str,
+    runtime: RuntimeContext,
+}
+
+impl<'a> TuiAppTemplate<'a> {
+    pub fn new(project: &'a str, runtime: Runtime) -> Self {
+        Self {
+            project,
+            runtime: runtime.context(),
+        }
+    }
+}
+
+impl<'a> Template for TuiAppTemplate<'a> {
+    const ID: &'static str = "TuiAppTemplate";
+
+    fn render(&self) -> Result<String, Box<dyn Error>> {
+        let mut template = TinyTemplate::new();
+        template.add_template("app", TEMPLATE)?;
+
+        let rendered = template.render("app", self)?;
+
+        Ok(rendered)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_tokio() {
+        let template = TuiAppTemplate::new("test", Runtime::Tokio);
+
+        let got = template.render().unwrap();
+
+        assert!(
+            got.starts_with("use std::future::Future;\nuse std::io;\nuse std::sync::OnceLock;\n")
+        );
+        assert!(got.contains("use tokio::runtime::Runtime;\n"));
+        assert!(got.contains("        .block_on(future)\n"));
+        assert!(got.contains(".block(Block::bordered().title(\"test\"));"));
+    }
+
+    #[test]
+    fn test_render_async_std() {
+        let template = TuiAppTemplate::new("test", Runtime::AsyncStd);
+
+        let got = template.render().unwrap();
+
+        assert!(got.contains("    async_std::task::block_on(future)\n"));
+        assert!(!got.contains("tokio"));
+    }
+
+    #[test]
+    fn test_render_sync() {
+        let template = TuiAppTemplate::new("test", Runtime::Sync);
+
+        let got = template.render().unwrap();
+
+        assert!(got.starts_with("use std::io;\n\nuse ratatui::"));
+        assert!(!got.contains("block_on"));
+    }
+}
@@ -0,0 +1,81 @@
+use serde::Serialize;
+use std::error::Error;
+use tinytemplate::TinyTemplate;
+
+use super::super::Template;
+
+use crate::core::runtime::{Runtime, RuntimeContext};
+
+static TEMPLATE: &str = "[package]
+name = \"{project}-tui\"
+version = \"0.1.0\"
+edition = \"2021\"
+
+[dependencies]
+{project}-domain = \\{ path = \"../{project}-domain\" }
+{{ if runtime.is_async_std }}async-std = \"1.9.0\"
+{{ endif }}ratatui = \"0.29\"
+{{ if runtime.is_tokio }}tokio = \\{ version = \"1\", features = [\"rt-multi-thread\"] }
+{{ endif }}";
+
+#[derive(Debug, Serialize)]
+pub struct TuiCargoTemplate<'a> {
+    project: &'a str,
+    runtime: RuntimeContext,
+}
+
+impl<'a> TuiCargoTemplate<'a> {
+    pub fn new(project: &'a str, runtime: Runtime) -> Self {
+        Self {
+            project,
+            runtime: runtime.context(),
+        }
+    }
+}
+
+impl<'a> Template for TuiCargoTemplate<'a> {
+    const ID: &'static str = "TuiCargoTemplate";
+
+    fn render(&self) -> Result<String, Box<dyn Error>> {
+        let mut template = TinyTemplate::new();
+        template.add_template("cargo", TEMPLATE)?;
+
+        let rendered = template.render("cargo", self)?;
+
+        Ok(rendered)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render() {
+        let template = TuiCargoTemplate::new("test", Runtime::Tokio);
+
+        let expect = "[package]
+name = \"test-tui\"
+version = \"0.1.0\"
+edition = \"2021\"
+
+[dependencies]
+test-domain = { path = \"../test-domain\" }
+ratatui = \"0.29\"
+tokio = { version = \"1\", features = [\"rt-multi-thread\"] }
+";
+
+        let got = &template.render().unwrap();
+
+        assert_eq!(expect, got)
+    }
+
+    #[test]
+    fn test_render_sync() {
+        let template = TuiCargoTemplate::new("test", Runtime::Sync);
+
+        let got = template.render().unwrap();
+
+        assert!(got.ends_with("test-domain = { path = \"../test-domain\" }\nratatui = \"0.29\"\n"));
+    }
+}
@@ -0,0 +1,242 @@
+use serde::Serialize;
+use std::error::Error;
+use tinytemplate::TinyTemplate;
+
+use super::super::Template;
+
+use crate::core::runtime::{Runtime, RuntimeContext};
+use crate::core::source::{has_method, method_names, UsecaseMethod, UsecaseMethodKind};
+
+static TEMPLATE: &str = "use ratatui::crossterm::event::KeyCode;
+use ratatui::layout::Rect;
+use ratatui::widgets::\\{Block, List};
+use ratatui::Frame;
+
+use {project}_domain::features::{name}::entities::{name_title_case};
+{{ if has_bindings }}use {project}_domain::features::{name}::usecase::\\{{name_title_case}Usecase, Usecase};
+{{ endif }}
+{{ if has_bindings }}{{ if runtime.is_async }}use crate::app::\\{block_on, Screen};{{ else }}use crate::app::Screen;{{ endif }}{{ else }}use crate::app::Screen;{{ endif }}
+
+pub fn screen() -> Box<dyn Screen> \\{
+    Box::new({name_title_case}Screen::default())
+}
+
+#[derive(Default)]
+pub struct {name_title_case}Screen \\{
+    entities: Vec<{name_title_case}>,
+    status: String,
+}
+{{ if has_bindings }}
+impl {name_title_case}Screen \\{{{ if has_list }}
+    fn list(&mut self) \\{
+        match {call_open}{name_title_case}Usecase::new().list(){call_close} \\{
+            Ok(entities) => \\{
+                self.status = format!(\"\\{} {name}\", entities.len());
+                self.entities = entities;
+            }
+            Err(err) => self.status = err,
+        }
+    }
+{{ endif }}{{ for action in actions }}
+    fn {action.name}(&mut self) \\{
+        self.status = match {call_open}{name_title_case}Usecase::new().{action.name}(){call_close} \\{
+            Ok(()) => String::from(\"{action.name} succeeded\"),
+            Err(err) => err,
+        };
+    }
+{{ endfor }}}
+{{ endif }}
+impl Screen for {name_title_case}Screen \\{
+    fn title(&self) -> &str \\{
+        \"{name}\"
+    }
+
+    fn help(&self) -> &str \\{
+        \"{help}\"
+    }
+
+    fn handle_key(&mut self, {{ if has_bindings }}code{{ else }}_code{{ endif }}: KeyCode) \\{{{ if has_bindings }}
+        match code \\{{{ if has_list }}
+            KeyCode::Char('r') => self.list(),{{ endif }}{{ for action in actions }}
+            KeyCode::Char('{action.key}') => self.{action.name}(),{{ endfor }}
+            code => self.status = format!(\"No binding for \\{}\", code),
+        }
+    {{ endif }}}
+
+    fn draw(&self, frame: &mut Frame, area: Rect) \\{
+        let items = self.entities.iter().map(|entity| format!(\"\\{:?}\", entity));
+        let list = List::new(items).block(Block::bordered().title(self.status.as_str()));
+
+        frame.render_widget(list, area);
+    }
+}
+{{ if unbound }}
+// Not bound to a key as they need input the screen doesn't collect yet:{{ for method in unbound }} `{method}`{{ if not @last }},{{ endif }}{{ endfor }}
+{{ endif }}";
+
+#[derive(Debug, Serialize)]
+struct KeyBinding<'a> {
+    name: &'a str,
+    key: usize,
+}
+
+/// Screen listing a feature's entities, with keys bound to its usecase methods.
+#[derive(Debug, Serialize)]
+pub struct TuiFeatureTemplate<'a> {
+    project: String,
+    name: &'a str,
+    name_title_case: &'a str,
+    runtime: RuntimeContext,
+    call_open: &'static str,
+    call_close: &'static str,
+    has_list: bool,
+    has_bindings: bool,
+    actions: Vec<KeyBinding<'a>>,
+    unbound: Vec<&'a str>,
+    help: String,
+}
+
+impl<'a> TuiFeatureTemplate<'a> {
+    pub fn new(
+        project: &str,
+        name: &'a str,
+        name_title_case: &'a str,
+        methods: &'a [UsecaseMethod],
+        runtime: Runtime,
+    ) -> Self {
+        let has_list = has_method(methods, UsecaseMethodKind::List);
+
+        // Actions are bound to the digits 1 to 9, the remaining ones are listed as unbound
+        let mut actions = Vec::new();
+        let mut unbound = Vec::new();
+
+        for (index, name) in method_names(methods, UsecaseMethodKind::Action)
+            .into_iter()
+            .enumerate()
+        {
+            if index < 9 {
+                actions.push(KeyBinding {
+                    name,
+                    key: index + 1,
+                });
+            } else {
+                unbound.push(name);
+            }
+        }
+
+        for method in methods {
+            match method.kind {
+                UsecaseMethodKind::Get | UsecaseMethodKind::Save | UsecaseMethodKind::Other => {
+                    unbound.push(method.name.as_str())
+                }
+                _ => (),
+            }
+        }
+
+        let mut help = Vec::new();
+        if has_list {
+            help.push(String::from("r refresh"));
+        }
+        for action in &actions {
+            help.push(format!("{} {}", action.key, action.name));
+        }
+
+        let (call_open, call_close) = if runtime.is_async() {
+            ("block_on(", ")")
+        } else {
+            ("", "")
+        };
+
+        Self {
+            project: project.replace('-', "_"),
+            name,
+            name_title_case,
+            runtime: runtime.context(),
+            call_open,
+            call_close,
+            has_list,
+            has_bindings: has_list || !actions.is_empty(),
+            actions,
+            unbound,
+            help: help.join(" | "),
+        }
+    }
+}
+
+impl<'a> Template for TuiFeatureTemplate<'a> {
+    const ID: &'static str = "TuiFeatureTemplate";
+
+    fn render(&self) -> Result<String, Box<dyn Error>> {
+        let mut template = TinyTemplate::new();
+        template.add_template("feature", TEMPLATE)?;
+
+        let rendered = template.render("feature", self)?;
+
+        Ok(rendered)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn method(name: &str, kind: UsecaseMethodKind) -> UsecaseMethod {
+        UsecaseMethod {
+            name: name.to_string(),
+            kind,
+        }
+    }
+
+    #[test]
+    fn test_render() {
+        let methods = vec![
+            method("get", UsecaseMethodKind::Get),
+            method("list", UsecaseMethodKind::List),
+            method("archive", UsecaseMethodKind::Action),
+        ];
+        let template =
+            TuiFeatureTemplate::new("my-app", "notes", "Notes", &methods, Runtime::Tokio);
+
+        let got = template.render().unwrap();
+
+        assert!(
+            got.contains("use my_app_domain::features::notes::usecase::{NotesUsecase, Usecase};")
+        );
+        assert!(got.contains("use crate::app::{block_on, Screen};"));
+        assert!(got.contains("match block_on(NotesUsecase::new().list()) {"));
+        assert!(got.contains("self.status = format!(\"{} notes\", entities.len());"));
+        assert!(got.contains(
+            "        match code {
+            KeyCode::Char('r') => self.list(),
+            KeyCode::Char('1') => self.archive(),
+            code => self.status = format!(\"No binding for {}\", code),
+        }
+    }"
+        ));
+        assert!(got.contains("        \"r refresh | 1 archive\"\n"));
+        assert!(got.ends_with("doesn't collect yet: `get`\n"));
+    }
+
+    #[test]
+    fn test_render_sync() {
+        let methods = vec![method("archive", UsecaseMethodKind::Action)];
+        let template = TuiFeatureTemplate::new("test", "notes", "Notes", &methods, Runtime::Sync);
+
+        let got = template.render().unwrap();
+
+        assert!(got.contains("use crate::app::Screen;\n"));
+        assert!(got.contains("self.status = match NotesUsecase::new().archive() {"));
+        assert!(!got.contains("fn list("));
+    }
+
+    #[test]
+    fn test_render_without_bindings() {
+        let template = TuiFeatureTemplate::new("test", "notes", "Notes", &[], Runtime::Tokio);
+
+        let got = template.render().unwrap();
+
+        assert!(!got.contains("usecase::"));
+        assert!(got.contains("fn handle_key(&mut self, _code: KeyCode) {}"));
+        assert!(got.contains("        \"\"\n"));
+    }
+}
@@ -0,0 +1,108 @@
+use serde::Serialize;
+use std::error::Error;
+use tinytemplate::TinyTemplate;
+
+use super::super::{Template, SINGLE_LINE_WIDTH};
+
+static TEMPLATE: &str = "use crate::app::Screen;
+
+{{ for feature in features }}pub mod {feature};
+{{ endfor }}
+pub fn screens() -> Vec<Box<dyn Screen>> \\{
+{{ if single_line }}    vec![{screens}]
+{{ else }}{{ if features }}    vec![{{ for feature in features }}
+        {feature}::screen(),{{ endfor }}
+    ]
+{{ else }}    Vec::new()
+{{ endif }}{{ endif }}}
+";
+
+/// Declares every feature module and lists their screens in tab order.
+#[derive(Debug, Serialize)]
+pub struct TuiFeaturesTemplate<'a> {
+    features: Vec<&'a str>,
+    screens: String,
+    single_line: bool,
+}
+
+impl<'a> TuiFeaturesTemplate<'a> {
+    pub fn new(mut features: Vec<&'a str>) -> Self {
+        features.sort_unstable();
+        let screens = features
+            .iter()
+            .map(|feature| format!("{}::screen()", feature))
+            .collect::<Vec<_>>()
+            .join(", ");
+        let single_line = !features.is_empty() && screens.len() <= SINGLE_LINE_WIDTH;
+
+        Self {
+            features,
+            screens,
+            single_line,
+        }
+    }
+}
+
+impl<'a> Template for TuiFeaturesTemplate<'a> {
+    const ID: &'static str = "TuiFeaturesTemplate";
+
+    fn render(&self) -> Result<String, Box<dyn Error>> {
+        let mut template = TinyTemplate::new();
+        template.add_template("features", TEMPLATE)?;
+
+        let rendered = template.render("features", self)?;
+
+        Ok(rendered)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render() {
+        let template = TuiFeaturesTemplate::new(vec!["tags", "notes"]);
+
+        let expect = "use crate::app::Screen;
+
+pub mod notes;
+pub mod tags;
+
+pub fn screens() -> Vec<Box<dyn Screen>> {
+    vec![notes::screen(), tags::screen()]
+}
+";
+
+        let got = &template.render().unwrap();
+
+        assert_eq!(expect, got)
+    }
+
+    #[test]
+    fn test_render_long() {
+        let template = TuiFeaturesTemplate::new(vec!["notes", "settings", "store", "tags"]);
+
+        let got = template.render().unwrap();
+
+        assert!(got.ends_with(
+            "    vec![
+        notes::screen(),
+        settings::screen(),
+        store::screen(),
+        tags::screen(),
+    ]
+}
+"
+        ));
+    }
+
+    #[test]
+    fn test_render_empty() {
+        let template = TuiFeaturesTemplate::new(vec![]);
+
+        let got = template.render().unwrap();
+
+        assert!(got.ends_with("pub fn screens() -> Vec<Box<dyn Screen>> {\n    Vec::new()\n}\n"));
+    }
+}
@@ -0,0 +1,58 @@
+use serde::Serialize;
+use std::error::Error;
+use tinytemplate::TinyTemplate;
+
+use super::super::Template;
+
+static TEMPLATE: &str = "use std::io;
+
+use app::App;
+
+mod app;
+mod features;
+
+fn main() -> io::Result<()> \\{
+    let mut terminal = ratatui::init();
+    let result = App::default().run(&mut terminal);
+    ratatui::restore();
+
+    result
+}
+";
+
+#[derive(Debug, Default, Serialize)]
+pub struct TuiMainFileTemplate {}
+
+impl TuiMainFileTemplate {
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+impl Template for TuiMainFileTemplate {
+    const ID: &'static str = "TuiMainFileTemplate";
+
+    fn render(&self) -> Result<String, Box<dyn Error>> {
+        let mut template = TinyTemplate::new();
+        template.add_template("main", TEMPLATE)?;
+
+        let rendered = template.render("main", self)?;
+
+        Ok(rendered)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render() {
+        let template = TuiMainFileTemplate::new();
+
+        let got = template.render().unwrap();
+
+        assert!(got.contains("mod app;\nmod features;\n"));
+        assert!(got.contains("let result = App::default().run(&mut terminal);"));
+    }
+}
@@ -0,0 +1,5 @@
+pub mod app;
+pub mod cargo;
+pub mod feature;
+pub mod features;
+pub mod main;
@@ -2,14 +2,23 @@ use serde::Serialize;
 use std::error::Error;
 use tinytemplate::TinyTemplate;
 
-use super::Template;
+use super::{use_list, Template};
 
-static TEMPLATE: &str = "use super::repository::\\{{name_title_case}Repository, Repository};
+use crate::core::runtime::{Runtime, RuntimeContext};
 
-pub trait Usecase \\{}
+static TEMPLATE: &str = "{{ if runtime.is_async }}use async_trait::async_trait;
+
+{{ endif }}use super::entities::{name_title_case};
+use super::repository::\\{{imports}};
+
+{runtime.async_trait}pub trait Usecase \\{
+    {runtime.async_keyword}fn get(&self, id: &str) -> Result<Option<{name_title_case}>, String>;
+    {runtime.async_keyword}fn save(&self, id: &str, entity: &{name_title_case}) -> Result<(), String>;
+    {runtime.async_keyword}fn list(&self) -> Result<Vec<{name_title_case}>, String>;
+}
 
 pub struct {name_title_case}Usecase \\{
-    repository: Box<dyn Repository + Send + Sync>
+    repository: Box<dyn Repository + Send + Sync>,
 }
 
 impl {name_title_case}Usecase \\{
@@ -19,34 +28,98 @@ impl {name_title_case}Usecase \\{
     }
 }
 
-impl Usecase for {name_title_case}Usecase \\{}
+impl Default for {name_title_case}Usecase \\{
+    fn default() -> Self \\{
+        Self::new()
+    }
+}
+
+{runtime.async_trait}impl Usecase for {name_title_case}Usecase \\{
+    {runtime.async_keyword}fn get(&self, id: &str) -> Result<Option<{name_title_case}>, String> \\{
+        self.repository.get(id){runtime.await_keyword}
+    }
+
+    {runtime.async_keyword}fn save(&self, id: &str, entity: &{name_title_case}) -> Result<(), String> \\{
+        self.repository.save(id, entity){runtime.await_keyword}
+    }
+
+    {runtime.async_keyword}fn list(&self) -> Result<Vec<{name_title_case}>, String> \\{
+        self.repository.list(){runtime.await_keyword}
+    }
+}
 
 #[cfg(test)]
 mod tests \\{
     use super::*;
+    use crate::features::{name}::fakes::RecordingRepository;
 
-    struct MockRepository \\{}
+    {runtime.test_attribute}
+    {runtime.async_keyword}fn test_get() \\{
+        let expect = {name_title_case}::default();
+        let repository = RecordingRepository::returning(vec![expect.clone()]);
+        let calls = repository.calls();
+        let usecase = {name_title_case}Usecase \\{
+            repository: Box::new(repository),
+        };
 
-    impl Repository for MockRepository \\{}
+        let got = usecase.get(\"test\"){runtime.await_keyword}.unwrap();
 
-    #[test]
-    fn test() \\{
-        assert_eq!(1, 1)
+        assert_eq!(Some(expect), got);
+        assert_eq!(vec![\"get(test)\"], *calls.lock().unwrap())
     }
-}";
+
+    {runtime.test_attribute}
+    {runtime.async_keyword}fn test_save() \\{
+        let entity = {name_title_case}::default();
+        let repository = RecordingRepository::default();
+        let calls = repository.calls();
+        let usecase = {name_title_case}Usecase \\{
+            repository: Box::new(repository),
+        };
+
+        usecase.save(\"test\", &entity){runtime.await_keyword}.unwrap();
+
+        assert_eq!(vec![\"save(test)\"], *calls.lock().unwrap())
+    }
+
+    {runtime.test_attribute}
+    {runtime.async_keyword}fn test_list_error() \\{
+        let repository = RecordingRepository::failing(\"error\");
+        let calls = repository.calls();
+        let usecase = {name_title_case}Usecase \\{
+            repository: Box::new(repository),
+        };
+
+        let got = usecase.list(){runtime.await_keyword};
+
+        assert_eq!(Err(String::from(\"error\")), got);
+        assert_eq!(vec![\"list\"], *calls.lock().unwrap())
+    }
+}
+";
 
 #[derive(Debug, Serialize)]
 pub struct UsecaseTemplate<'a> {
+    name: &'a str,
     name_title_case: &'a str,
+    imports: String,
+    runtime: RuntimeContext,
 }
 
 impl<'a> UsecaseTemplate<'a> {
-    pub fn new(name_title_case: &'a str) -> Self {
-        Self { name_title_case }
+    pub fn new(name: &'a str, name_title_case: &'a str, runtime: Runtime) -> Self {
+        Self {
+            name,
+            name_title_case,
+            imports: use_list(&[&format!("{}Repository", name_title_case), "Repository"]),
+            runtime: runtime.context(),
+        }
     }
 }
 
 impl<'a> Template for UsecaseTemplate<'a> {
+    const ID: &'static str = "UsecaseTemplate";
+
     fn render(&self) -> Result<String, Box<dyn Error>> {
         let mut template = TinyTemplate::new();
         template.add_template("cli", TEMPLATE)?;
@@ -63,14 +136,22 @@ mod tests {
 
     #[test]
     fn test_render() {
-        let template = UsecaseTemplate::new("Test");
+        let template = UsecaseTemplate::new("test", "Test", Runtime::AsyncStd);
+
+        let expect = "use async_trait::async_trait;
 
-        let expect = "use super::repository::{TestRepository, Repository};
+use super::entities::Test;
+use super::repository::{Repository, TestRepository};
 
-pub trait Usecase {}
+#[async_trait]
+pub trait Usecase {
+    async fn get(&self, id: &str) -> Result<Option<Test>, String>;
+    async fn save(&self, id: &str, entity: &Test) -> Result<(), String>;
+    async fn list(&self) -> Result<Vec<Test>, String>;
+}
 
 pub struct TestUsecase {
-    repository: Box<dyn Repository + Send + Sync>
+    repository: Box<dyn Repository + Send + Sync>,
 }
 
 impl TestUsecase {
@@ -80,24 +161,89 @@ impl TestUsecase {
     }
 }
 
-impl Usecase for TestUsecase {}
+impl Default for TestUsecase {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl Usecase for TestUsecase {
+    async fn get(&self, id: &str) -> Result<Option<Test>, String> {
+        self.repository.get(id).await
+    }
+
+    async fn save(&self, id: &str, entity: &Test) -> Result<(), String> {
+        self.repository.save(id, entity).await
+    }
+
+    async fn list(&self) -> Result<Vec<Test>, String> {
+        self.repository.list().await
+    }
+}
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::features::test::fakes::RecordingRepository;
 
-    struct MockRepository {}
+    #[async_std::test]
+    async fn test_get() {
+        let expect = Test::default();
+        let repository = RecordingRepository::returning(vec![expect.clone()]);
+        let calls = repository.calls();
+        let usecase = TestUsecase {
+            repository: Box::new(repository),
+        };
 
-    impl Repository for MockRepository {}
+        let got = usecase.get(\"test\").await.unwrap();
 
-    #[test]
-    fn test() {
-        assert_eq!(1, 1)
+        assert_eq!(Some(expect), got);
+        assert_eq!(vec![\"get(test)\"], *calls.lock().unwrap())
+    }
+
+    #[async_std::test]
+    async fn test_save() {
+        let entity = Test::default();
+        let repository = RecordingRepository::default();
+        let calls = repository.calls();
+        let usecase = TestUsecase {
+            repository: Box::new(repository),
+        };
+
+        usecase.save(\"test\", &entity).await.unwrap();
+
+        assert_eq!(vec![\"save(test)\"], *calls.lock().unwrap())
+    }
+
+    #[async_std::test]
+    async fn test_list_error() {
+        let repository = RecordingRepository::failing(\"error\");
+        let calls = repository.calls();
+        let usecase = TestUsecase {
+            repository: Box::new(repository),
+        };
+
+        let got = usecase.list().await;
+
+        assert_eq!(Err(String::from(\"error\")), got);
+        assert_eq!(vec![\"list\"], *calls.lock().unwrap())
     }
-}";
+}
+";
 
         let got = &template.render().unwrap();
 
         assert_eq!(expect, got)
     }
+
+    #[test]
+    fn test_render_tokio() {
+        let template = UsecaseTemplate::new("test", "Test", Runtime::Tokio);
+
+        let got = template.render().unwrap();
+
+        assert!(got.contains("    #[tokio::test]\n    async fn test_get() {"));
+        assert!(got.contains("        self.repository.list().await\n"));
+    }
 }
@@ -0,0 +1,192 @@
+use serde::Serialize;
+use std::error::Error;
+use tinytemplate::TinyTemplate;
+
+use super::Template;
+
+use crate::core::runtime::{Runtime, RuntimeContext};
+
+static USECASE_DECLARATION_TEMPLATE: &str =
+    "    {runtime.async_keyword}fn {method}(&self) -> Result<(), String>;\n";
+
+static USECASE_IMPLEMENTATION_TEMPLATE: &str = "
+    {runtime.async_keyword}fn {method}(&self) -> Result<(), String> \\{
+        self.repository.{method}(){runtime.await_keyword}
+    }
+";
+
+static USECASE_TEST_TEMPLATE: &str = "
+    {runtime.test_attribute}
+    {runtime.async_keyword}fn test_{method}() \\{
+        let repository = RecordingRepository::default();
+        let calls = repository.calls();
+        let usecase = {name_title_case}Usecase \\{
+            repository: Box::new(repository),
+        };
+
+        usecase.{method}(){runtime.await_keyword}.unwrap();
+
+        assert_eq!(vec![\"{method}\"], *calls.lock().unwrap())
+    }
+";
+
+static REPOSITORY_IMPLEMENTATION_TEMPLATE: &str = "
+    {runtime.async_keyword}fn {method}(&self) -> Result<(), String> \\{
+        Err(String::from(\"{method} is not implemented\"))
+    }
+";
+
+static FAKE_REPOSITORY_IMPLEMENTATION_TEMPLATE: &str = "
+    {runtime.async_keyword}fn {method}(&self) -> Result<(), String> \\{
+        self.record(String::from(\"{method}\"))
+    }
+";
+
+/// The places a usecase method has to be added to for it to be wired through a feature.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum UsecaseMethodPart {
+    UsecaseDeclaration,
+    UsecaseImplementation,
+    UsecaseTest,
+    RepositoryDeclaration,
+    RepositoryImplementation,
+    FakeRepositoryImplementation,
+}
+
+#[derive(Debug, Serialize)]
+pub struct UsecaseMethodTemplate<'a> {
+    #[serde(skip)]
+    part: UsecaseMethodPart,
+    method: &'a str,
+    name_title_case: &'a str,
+    runtime: RuntimeContext,
+}
+
+impl<'a> UsecaseMethodTemplate<'a> {
+    pub fn new(
+        part: UsecaseMethodPart,
+        method: &'a str,
+        name_title_case: &'a str,
+        runtime: Runtime,
+    ) -> Self {
+        Self {
+            part,
+            method,
+            name_title_case,
+            runtime: runtime.context(),
+        }
+    }
+}
+
+impl<'a> Template for UsecaseMethodTemplate<'a> {
+    const ID: &'static str = "UsecaseMethodTemplate";
+
+    fn render(&self) -> Result<String, Box<dyn Error>> {
+        let text = match self.part {
+            UsecaseMethodPart::UsecaseDeclaration => USECASE_DECLARATION_TEMPLATE,
+            UsecaseMethodPart::UsecaseImplementation => USECASE_IMPLEMENTATION_TEMPLATE,
+            UsecaseMethodPart::UsecaseTest => USECASE_TEST_TEMPLATE,
+            UsecaseMethodPart::RepositoryDeclaration => USECASE_DECLARATION_TEMPLATE,
+            UsecaseMethodPart::RepositoryImplementation => REPOSITORY_IMPLEMENTATION_TEMPLATE,
+            UsecaseMethodPart::FakeRepositoryImplementation => {
+                FAKE_REPOSITORY_IMPLEMENTATION_TEMPLATE
+            }
+        };
+
+        let mut template = TinyTemplate::new();
+        template.add_template("usecase_method", text)?;
+
+        let rendered = template.render("usecase_method", self)?;
+
+        Ok(rendered)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_declaration() {
+        let template = UsecaseMethodTemplate::new(
+            UsecaseMethodPart::UsecaseDeclaration,
+            "archive",
+            "Test",
+            Runtime::AsyncStd,
+        );
+
+        let expect = "    async fn archive(&self) -> Result<(), String>;\n";
+        let got = &template.render().unwrap();
+
+        assert_eq!(expect, got)
+    }
+
+    #[test]
+    fn test_render_usecase_implementation() {
+        let template = UsecaseMethodTemplate::new(
+            UsecaseMethodPart::UsecaseImplementation,
+            "archive",
+            "Test",
+            Runtime::AsyncStd,
+        );
+
+        let expect = "
+    async fn archive(&self) -> Result<(), String> {
+        self.repository.archive().await
+    }
+";
+        let got = &template.render().unwrap();
+
+        assert_eq!(expect, got)
+    }
+
+    #[test]
+    fn test_render_usecase_test() {
+        let template = UsecaseMethodTemplate::new(
+            UsecaseMethodPart::UsecaseTest,
+            "archive",
+            "Test",
+            Runtime::AsyncStd,
+        );
+
+        let got = template.render().unwrap();
+
+        assert!(got.contains("    async fn test_archive() {"));
+        assert!(got.contains("        let usecase = TestUsecase {"));
+        assert!(got.contains("assert_eq!(vec![\"archive\"], *calls.lock().unwrap())"));
+    }
+
+    #[test]
+    fn test_render_fake_repository_implementation() {
+        let template = UsecaseMethodTemplate::new(
+            UsecaseMethodPart::FakeRepositoryImplementation,
+            "archive",
+            "Test",
+            Runtime::AsyncStd,
+        );
+
+        let expect = "
+    async fn archive(&self) -> Result<(), String> {
+        self.record(String::from(\"archive\"))
+    }
+";
+        let got = &template.render().unwrap();
+
+        assert_eq!(expect, got)
+    }
+
+    #[test]
+    fn test_render_sync_usecase_test() {
+        let template = UsecaseMethodTemplate::new(
+            UsecaseMethodPart::UsecaseTest,
+            "archive",
+            "Test",
+            Runtime::Sync,
+        );
+
+        let got = template.render().unwrap();
+
+        assert!(got.contains("    #[test]\n    fn test_archive() {"));
+        assert!(got.contains("        usecase.archive().unwrap();"));
+    }
+}
@@ -0,0 +1,88 @@
+use serde::Serialize;
+use std::error::Error;
+use tinytemplate::TinyTemplate;
+
+use super::super::Template;
+
+use crate::core::runtime::{Runtime, RuntimeContext};
+
+static TEMPLATE: &str = "[package]
+name = \"{project}-wasm\"
+version = \"0.1.0\"
+edition = \"2021\"
+
+[lib]
+crate-type = [\"cdylib\", \"rlib\"]
+
+[dependencies]
+{project}-domain = \\{ path = \"../{project}-domain\" }
+serde-wasm-bindgen = \"0.6\"
+wasm-bindgen = \"0.2\"
+{{ if runtime.is_async }}wasm-bindgen-futures = \"0.4\"
+{{ endif }}";
+
+#[derive(Debug, Serialize)]
+pub struct WasmCargoTemplate<'a> {
+    project: &'a str,
+    runtime: RuntimeContext,
+}
+
+impl<'a> WasmCargoTemplate<'a> {
+    pub fn new(project: &'a str, runtime: Runtime) -> Self {
+        Self {
+            project,
+            runtime: runtime.context(),
+        }
+    }
+}
+
+impl<'a> Template for WasmCargoTemplate<'a> {
+    const ID: &'static str = "WasmCargoTemplate";
+
+    fn render(&self) -> Result<String, Box<dyn Error>> {
+        let mut template = TinyTemplate::new();
+        template.add_template("cargo", TEMPLATE)?;
+
+        let rendered = template.render("cargo", self)?;
+
+        Ok(rendered)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render() {
+        let template = WasmCargoTemplate::new("test", Runtime::AsyncStd);
+
+        let expect = "[package]
+name = \"test-wasm\"
+version = \"0.1.0\"
+edition = \"2021\"
+
+[lib]
+crate-type = [\"cdylib\", \"rlib\"]
+
+[dependencies]
+test-domain = { path = \"../test-domain\" }
+serde-wasm-bindgen = \"0.6\"
+wasm-bindgen = \"0.2\"
+wasm-bindgen-futures = \"0.4\"
+";
+
+        let got = &template.render().unwrap();
+
+        assert_eq!(expect, got)
+    }
+
+    #[test]
+    fn test_render_sync() {
+        let template = WasmCargoTemplate::new("test", Runtime::Sync);
+
+        let got = template.render().unwrap();
+
+        assert!(got.ends_with("wasm-bindgen = \"0.2\"\n"));
+    }
+}
@@ -0,0 +1,170 @@
+use serde::Serialize;
+use std::error::Error;
+use tinytemplate::TinyTemplate;
+
+use super::super::Template;
+
+use crate::core::runtime::{Runtime, RuntimeContext};
+use crate::core::source::{has_method, method_names, UsecaseMethod, UsecaseMethodKind};
+use crate::core::utils::{camel_case, pascal_case};
+
+static TEMPLATE: &str = "use wasm_bindgen::prelude::*;
+
+{{ if has_save }}use {project}_domain::features::{name}::entities::{name_title_case};
+{{ endif }}{{ if has_exports }}use {project}_domain::features::{name}::usecase::\\{{name_title_case}Usecase, Usecase};
+{{ endif }}{{ if has_get }}
+#[wasm_bindgen(js_name = get{js_suffix})]
+pub {runtime.async_keyword}fn get(id: String) -> Result<JsValue, JsValue> \\{
+    let entity = {name_title_case}Usecase::new().get(&id){runtime.await_keyword}?;
+
+    Ok(serde_wasm_bindgen::to_value(&entity)?)
+}
+{{ endif }}{{ if has_save }}
+#[wasm_bindgen(js_name = save{js_suffix})]
+pub {runtime.async_keyword}fn save(id: String, entity: JsValue) -> Result<(), JsValue> \\{
+    let entity: {name_title_case} = serde_wasm_bindgen::from_value(entity)?;
+
+    Ok({name_title_case}Usecase::new().save(&id, &entity){runtime.await_keyword}?)
+}
+{{ endif }}{{ if has_list }}
+#[wasm_bindgen(js_name = list{js_suffix})]
+pub {runtime.async_keyword}fn list() -> Result<JsValue, JsValue> \\{
+    let entities = {name_title_case}Usecase::new().list(){runtime.await_keyword}?;
+
+    Ok(serde_wasm_bindgen::to_value(&entities)?)
+}
+{{ endif }}{{ for action in actions }}
+#[wasm_bindgen(js_name = {action.js_name})]
+pub {runtime.async_keyword}fn {action.name}() -> Result<(), JsValue> \\{
+    Ok({name_title_case}Usecase::new().{action.name}(){runtime.await_keyword}?)
+}
+{{ endfor }}{{ for method in unsupported }}
+// `{method}` doesn't have a signature that can be exported, expose it by hand.
+{{ endfor }}";
+
+#[derive(Debug, Serialize)]
+struct WasmExport<'a> {
+    name: &'a str,
+    js_name: String,
+}
+
+/// Functions exporting a feature's usecase methods to JavaScript.
+#[derive(Debug, Serialize)]
+pub struct WasmFeatureTemplate<'a> {
+    project: String,
+    name: &'a str,
+    name_title_case: &'a str,
+    js_suffix: String,
+    runtime: RuntimeContext,
+    has_get: bool,
+    has_save: bool,
+    has_list: bool,
+    has_exports: bool,
+    actions: Vec<WasmExport<'a>>,
+    unsupported: Vec<&'a str>,
+}
+
+impl<'a> WasmFeatureTemplate<'a> {
+    pub fn new(
+        project: &str,
+        name: &'a str,
+        name_title_case: &'a str,
+        methods: &'a [UsecaseMethod],
+        runtime: Runtime,
+    ) -> Self {
+        let has_get = has_method(methods, UsecaseMethodKind::Get);
+        let has_save = has_method(methods, UsecaseMethodKind::Save);
+        let has_list = has_method(methods, UsecaseMethodKind::List);
+
+        // Exports share a single namespace, so every name carries the feature
+        let actions = method_names(methods, UsecaseMethodKind::Action)
+            .into_iter()
+            .map(|action| WasmExport {
+                name: action,
+                js_name: camel_case(&format!("{}_{}", action, name)),
+            })
+            .collect::<Vec<_>>();
+
+        Self {
+            project: project.replace('-', "_"),
+            name,
+            name_title_case,
+            js_suffix: pascal_case(name),
+            runtime: runtime.context(),
+            has_get,
+            has_save,
+            has_list,
+            has_exports: has_get || has_save || has_list || !actions.is_empty(),
+            actions,
+            unsupported: method_names(methods, UsecaseMethodKind::Other),
+        }
+    }
+}
+
+impl<'a> Template for WasmFeatureTemplate<'a> {
+    const ID: &'static str = "WasmFeatureTemplate";
+
+    fn render(&self) -> Result<String, Box<dyn Error>> {
+        let mut template = TinyTemplate::new();
+        template.add_template("feature", TEMPLATE)?;
+
+        let rendered = template.render("feature", self)?;
+
+        Ok(rendered)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn method(name: &str, kind: UsecaseMethodKind) -> UsecaseMethod {
+        UsecaseMethod {
+            name: name.to_string(),
+            kind,
+        }
+    }
+
+    #[test]
+    fn test_render() {
+        let methods = vec![
+            method("get", UsecaseMethodKind::Get),
+            method("save", UsecaseMethodKind::Save),
+            method("list", UsecaseMethodKind::List),
+            method("archive_all", UsecaseMethodKind::Action),
+        ];
+        let template = WasmFeatureTemplate::new(
+            "my-app",
+            "user_notes",
+            "UserNotes",
+            &methods,
+            Runtime::Tokio,
+        );
+
+        let got = template.render().unwrap();
+
+        assert!(got.contains("use my_app_domain::features::user_notes::entities::UserNotes;"));
+        assert!(got.contains(
+            "#[wasm_bindgen(js_name = getUserNotes)]\npub async fn get(id: String) -> Result<JsValue, JsValue> {"
+        ));
+        assert!(got.contains("let entity: UserNotes = serde_wasm_bindgen::from_value(entity)?;"));
+        assert!(got.contains("let entities = UserNotesUsecase::new().list().await?;"));
+        assert!(got.contains(
+            "#[wasm_bindgen(js_name = archiveAllUserNotes)]\npub async fn archive_all() -> Result<(), JsValue> {"
+        ));
+    }
+
+    #[test]
+    fn test_render_sync() {
+        let methods = vec![method("list", UsecaseMethodKind::List)];
+        let template = WasmFeatureTemplate::new("test", "notes", "Notes", &methods, Runtime::Sync);
+
+        let got = template.render().unwrap();
+
+        assert!(got.starts_with(
+            "use wasm_bindgen::prelude::*;\n\nuse test_domain::features::notes::usecase::"
+        ));
+        assert!(got.contains("pub fn list() -> Result<JsValue, JsValue> {"));
+        assert!(got.contains("NotesUsecase::new().list()?;"));
+    }
+}
@@ -0,0 +1,2 @@
+pub mod cargo;
+pub mod feature;
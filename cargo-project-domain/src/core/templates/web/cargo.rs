@@ -0,0 +1,65 @@
+use serde::Serialize;
+use std::error::Error;
+use tinytemplate::TinyTemplate;
+
+use super::super::Template;
+
+static TEMPLATE: &str = "[package]
+name = \"{project}-web\"
+version = \"0.1.0\"
+edition = \"2021\"
+
+[dependencies]
+{project}-domain = \\{ path = \"../{project}-domain\" }
+axum = \"0.8\"
+tokio = \\{ version = \"1\", features = [\"macros\", \"net\", \"rt-multi-thread\"] }
+";
+
+#[derive(Debug, Serialize)]
+pub struct WebCargoTemplate<'a> {
+    project: &'a str,
+}
+
+impl<'a> WebCargoTemplate<'a> {
+    pub fn new(project: &'a str) -> Self {
+        Self { project }
+    }
+}
+
+impl<'a> Template for WebCargoTemplate<'a> {
+    const ID: &'static str = "WebCargoTemplate";
+
+    fn render(&self) -> Result<String, Box<dyn Error>> {
+        let mut template = TinyTemplate::new();
+        template.add_template("cargo", TEMPLATE)?;
+
+        let rendered = template.render("cargo", self)?;
+
+        Ok(rendered)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render() {
+        let template = WebCargoTemplate::new("test");
+
+        let expect = "[package]
+name = \"test-web\"
+version = \"0.1.0\"
+edition = \"2021\"
+
+[dependencies]
+test-domain = { path = \"../test-domain\" }
+axum = \"0.8\"
+tokio = { version = \"1\", features = [\"macros\", \"net\", \"rt-multi-thread\"] }
+";
+
+        let got = &template.render().unwrap();
+
+        assert_eq!(expect, got)
+    }
+}
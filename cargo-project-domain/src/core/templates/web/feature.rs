@@ -0,0 +1,236 @@
+use serde::Serialize;
+use std::error::Error;
+use tinytemplate::{format_unescaped, TinyTemplate};
+
+use super::super::{chain, use_list, Template};
+
+use crate::core::runtime::{Runtime, RuntimeContext};
+use crate::core::source::{has_method, method_names, UsecaseMethod, UsecaseMethodKind};
+
+static TEMPLATE: &str = "{{ if has_item_route }}use axum::extract::Path;
+{{ endif }}{{ if has_handlers }}use axum::http::StatusCode;
+use axum::routing;
+use axum::\\{Json, Router};
+
+{{ if has_entity }}use {project}_domain::features::{name}::entities::{name_title_case};
+{{ endif }}use {project}_domain::features::{name}::usecase::\\{{imports}};
+
+type Response<T> = Result<Json<T>, (StatusCode, String)>;
+{{ else }}use axum::Router;
+{{ endif }}
+pub fn routes() -> Router \\{
+    {routes}
+}
+{{ if has_handlers }}
+fn internal_error(err: String) -> (StatusCode, String) \\{
+    (StatusCode::INTERNAL_SERVER_ERROR, err)
+}
+{{ endif }}{{ if has_list }}
+async fn list() -> Response<Vec<{name_title_case}>> \\{
+    {list}
+}
+{{ endif }}{{ if has_get }}
+async fn get(Path(id): Path<String>) -> Response<{name_title_case}> \\{
+    match {name_title_case}Usecase::new().get(&id){runtime.await_keyword} \\{
+        Ok(Some(entity)) => Ok(Json(entity)),
+        Ok(None) => Err((StatusCode::NOT_FOUND, format!(\"{name} \\{} not found\", id))),
+        Err(err) => Err(internal_error(err)),
+    }
+}
+{{ endif }}{{ if has_save }}
+async fn save(Path(id): Path<String>, Json(entity): Json<{name_title_case}>) -> Response<()> \\{
+    {save}
+}
+{{ endif }}{{ for action in actions }}
+async fn {action.name}() -> Response<()> \\{
+    {action.chain}
+}
+{{ endfor }}{{ for method in unsupported }}
+// `{method}` doesn't have a signature that can be mapped to a route, expose it by hand.
+{{ endfor }}";
+
+/// Handler of an action method, calling it through the usecase.
+#[derive(Debug, Serialize)]
+struct Action<'a> {
+    name: &'a str,
+    chain: String,
+}
+
+/// Route module exposing a feature's usecase methods as JSON endpoints.
+#[derive(Debug, Serialize)]
+pub struct WebFeatureTemplate<'a> {
+    project: String,
+    name: &'a str,
+    name_title_case: &'a str,
+    imports: String,
+    runtime: RuntimeContext,
+    routes: String,
+    list: String,
+    save: String,
+    has_get: bool,
+    has_save: bool,
+    has_list: bool,
+    has_entity: bool,
+    has_item_route: bool,
+    has_handlers: bool,
+    actions: Vec<Action<'a>>,
+    unsupported: Vec<&'a str>,
+}
+
+impl<'a> WebFeatureTemplate<'a> {
+    pub fn new(
+        project: &str,
+        name: &'a str,
+        name_title_case: &'a str,
+        methods: &'a [UsecaseMethod],
+        runtime: Runtime,
+    ) -> Self {
+        let has_get = has_method(methods, UsecaseMethodKind::Get);
+        let has_save = has_method(methods, UsecaseMethodKind::Save);
+        let has_list = has_method(methods, UsecaseMethodKind::List);
+        let actions = method_names(methods, UsecaseMethodKind::Action);
+        let runtime = runtime.context();
+
+        let usecase = format!("{}Usecase::new()", name_title_case);
+        let respond = |call: &str| {
+            let calls = [
+                call,
+                runtime.await_keyword,
+                ".map(Json)",
+                ".map_err(internal_error)",
+            ];
+            chain(&usecase, &calls, 8)
+        };
+
+        let mut routes = Vec::new();
+        if has_list {
+            routes.push(format!(".route(\"/{}\", routing::get(list))", name));
+        }
+        match (has_get, has_save) {
+            (true, true) => routes.push(format!(
+                ".route(\"/{}/{{id}}\", routing::get(get).put(save))",
+                name
+            )),
+            (true, false) => {
+                routes.push(format!(".route(\"/{}/{{id}}\", routing::get(get))", name))
+            }
+            (false, true) => {
+                routes.push(format!(".route(\"/{}/{{id}}\", routing::put(save))", name))
+            }
+            (false, false) => {}
+        }
+        for action in &actions {
+            routes.push(format!(
+                ".route(\"/{}/{}\", routing::post({}))",
+                name, action, action
+            ));
+        }
+        let routes: Vec<&str> = routes.iter().map(String::as_str).collect();
+
+        Self {
+            project: project.replace('-', "_"),
+            name,
+            name_title_case,
+            imports: use_list(&[&format!("{}Usecase", name_title_case), "Usecase"]),
+            routes: chain("Router::new()", &routes, 8),
+            list: respond(".list()"),
+            save: respond(".save(&id, &entity)"),
+            has_get,
+            has_save,
+            has_list,
+            has_entity: has_get || has_save || has_list,
+            has_item_route: has_get || has_save,
+            has_handlers: has_get || has_save || has_list || !actions.is_empty(),
+            actions: actions
+                .iter()
+                .map(|action| Action {
+                    name: action,
+                    chain: respond(&format!(".{}()", action)),
+                })
+                .collect(),
+            unsupported: method_names(methods, UsecaseMethodKind::Other),
+            runtime,
+        }
+    }
+}
+
+impl<'a> Template for WebFeatureTemplate<'a> {
+    const ID: &'static str = "WebFeatureTemplate";
+
+    fn render(&self) -> Result<String, Box<dyn Error>> {
+        let mut template = TinyTemplate::new();
+        template.set_default_formatter(&format_unescaped);
+        template.add_template("feature", TEMPLATE)?;
+
+        let rendered = template.render("feature", self)?;
+
+        Ok(rendered)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn method(name: &str, kind: UsecaseMethodKind) -> UsecaseMethod {
+        UsecaseMethod {
+            name: name.to_string(),
+            kind,
+        }
+    }
+
+    #[test]
+    fn test_render() {
+        let methods = vec![
+            method("get", UsecaseMethodKind::Get),
+            method("save", UsecaseMethodKind::Save),
+            method("list", UsecaseMethodKind::List),
+            method("archive", UsecaseMethodKind::Action),
+        ];
+        let template =
+            WebFeatureTemplate::new("my-app", "notes", "Notes", &methods, Runtime::Tokio);
+
+        let got = template.render().unwrap();
+
+        assert!(got.starts_with("use axum::extract::Path;\nuse axum::http::StatusCode;"));
+        assert!(got.contains("use my_app_domain::features::notes::entities::Notes;"));
+        assert!(
+            got.contains("use my_app_domain::features::notes::usecase::{NotesUsecase, Usecase};")
+        );
+        assert!(got.contains(
+            "    Router::new()
+        .route(\"/notes\", routing::get(list))
+        .route(\"/notes/{id}\", routing::get(get).put(save))
+        .route(\"/notes/archive\", routing::post(archive))
+}"
+        ));
+        assert!(got.contains("match NotesUsecase::new().get(&id).await {"));
+        assert!(got.contains("format!(\"notes {} not found\", id)"));
+        assert!(got.contains("async fn archive() -> Response<()> {"));
+    }
+
+    #[test]
+    fn test_render_sync() {
+        let methods = vec![method("save", UsecaseMethodKind::Save)];
+        let template = WebFeatureTemplate::new("test", "notes", "Notes", &methods, Runtime::Sync);
+
+        let got = template.render().unwrap();
+
+        assert!(got.contains(".route(\"/notes/{id}\", routing::put(save))"));
+        assert!(got
+            .contains("    NotesUsecase::new()\n        .save(&id, &entity)\n        .map(Json)"));
+        assert!(!got.contains("async fn list()"));
+    }
+
+    #[test]
+    fn test_render_unsupported() {
+        let methods = vec![method("count", UsecaseMethodKind::Other)];
+        let template = WebFeatureTemplate::new("test", "notes", "Notes", &methods, Runtime::Tokio);
+
+        let got = template.render().unwrap();
+
+        assert!(got.starts_with("use axum::Router;\n\npub fn routes() -> Router {"));
+        assert!(got.contains("// `count` doesn't have a signature"));
+        assert!(!got.contains("StatusCode"));
+    }
+}
@@ -0,0 +1,97 @@
+use serde::Serialize;
+use std::error::Error;
+use tinytemplate::{format_unescaped, TinyTemplate};
+
+use super::super::{chain, Template};
+
+static TEMPLATE: &str = "use axum::Router;
+
+{{ for feature in features }}pub mod {feature};
+{{ endfor }}
+pub fn routes() -> Router \\{
+    {routes}
+}
+";
+
+/// Declares every feature module and merges their routes into a single router.
+#[derive(Debug, Serialize)]
+pub struct WebFeaturesTemplate<'a> {
+    features: Vec<&'a str>,
+    routes: String,
+}
+
+impl<'a> WebFeaturesTemplate<'a> {
+    pub fn new(mut features: Vec<&'a str>) -> Self {
+        features.sort_unstable();
+        let calls: Vec<String> = features
+            .iter()
+            .map(|feature| format!(".merge({}::routes())", feature))
+            .collect();
+        let calls: Vec<&str> = calls.iter().map(String::as_str).collect();
+        let routes = chain("Router::new()", &calls, 8);
+
+        Self { features, routes }
+    }
+}
+
+impl<'a> Template for WebFeaturesTemplate<'a> {
+    const ID: &'static str = "WebFeaturesTemplate";
+
+    fn render(&self) -> Result<String, Box<dyn Error>> {
+        let mut template = TinyTemplate::new();
+        template.set_default_formatter(&format_unescaped);
+        template.add_template("features", TEMPLATE)?;
+
+        let rendered = template.render("features", self)?;
+
+        Ok(rendered)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render() {
+        let template = WebFeaturesTemplate::new(vec!["tags", "notes"]);
+
+        let expect = "use axum::Router;
+
+pub mod notes;
+pub mod tags;
+
+pub fn routes() -> Router {
+    Router::new().merge(notes::routes()).merge(tags::routes())
+}
+";
+
+        let got = &template.render().unwrap();
+
+        assert_eq!(expect, got)
+    }
+
+    #[test]
+    fn test_render_long() {
+        let template = WebFeaturesTemplate::new(vec!["notes", "settings"]);
+
+        let got = template.render().unwrap();
+
+        assert!(got.ends_with(
+            "    Router::new()
+        .merge(notes::routes())
+        .merge(settings::routes())
+}
+"
+        ));
+    }
+
+    #[test]
+    fn test_render_empty() {
+        let template = WebFeaturesTemplate::new(vec![]);
+
+        let got = template.render().unwrap();
+
+        assert!(got.contains("pub fn routes() -> Router {\n    Router::new()\n}"));
+    }
+}
@@ -0,0 +1,65 @@
+use serde::Serialize;
+use std::error::Error;
+use tinytemplate::TinyTemplate;
+
+use super::super::Template;
+
+static TEMPLATE: &str = "use std::env;
+
+use tokio::net::TcpListener;
+
+mod features;
+
+#[tokio::main]
+async fn main() \\{
+    let address = env::var(\"ADDRESS\").unwrap_or_else(|_| String::from(\"127.0.0.1:3000\"));
+
+    let listener = TcpListener::bind(&address)
+        .await
+        .expect(\"Error binding server address\");
+
+    println!(\"Listening on \\{}\", address);
+
+    axum::serve(listener, features::routes())
+        .await
+        .expect(\"Error running server\");
+}
+";
+
+#[derive(Debug, Default, Serialize)]
+pub struct WebMainFileTemplate {}
+
+impl WebMainFileTemplate {
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+impl Template for WebMainFileTemplate {
+    const ID: &'static str = "WebMainFileTemplate";
+
+    fn render(&self) -> Result<String, Box<dyn Error>> {
+        let mut template = TinyTemplate::new();
+        template.add_template("main", TEMPLATE)?;
+
+        let rendered = template.render("main", self)?;
+
+        Ok(rendered)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render() {
+        let template = WebMainFileTemplate::new();
+
+        let got = template.render().unwrap();
+
+        assert!(got.contains("mod features;\n\n#[tokio::main]\nasync fn main() {"));
+        assert!(got.contains("println!(\"Listening on {}\", address);"));
+        assert!(got.contains("axum::serve(listener, features::routes())"));
+    }
+}
@@ -0,0 +1,4 @@
+pub mod cargo;
+pub mod feature;
+pub mod features;
+pub mod main;
@@ -1,6 +1,25 @@
 use async_std::fs::read;
+use std::env;
+use std::ops::Range;
 use std::str;
 
+/// Name of the project rooted in the current directory.
+pub fn current_project() -> String {
+    let current_dir = env::current_dir().expect("Error reading current directory from environment");
+
+    let project = current_dir
+        .file_name()
+        .expect("Error reading file name from current executable")
+        .to_str()
+        .expect("Can't convert file name to str")
+        .split("/");
+
+    project
+        .last()
+        .expect("Error parsing current directory")
+        .to_string()
+}
+
 pub async fn in_root_directory(project: &str) -> bool {
     let content = read("Cargo.toml")
         .await
@@ -9,3 +28,116 @@ pub async fn in_root_directory(project: &str) -> bool {
 
     content.contains("workspace") && content.contains(&format!("{}-", project))
 }
+
+/// Returns the byte range of the body of the block opened by `header`, which is
+/// expected to end with the block's opening brace.
+pub fn block_range(source: &str, header: &str) -> Option<Range<usize>> {
+    let start = source.find(header)? + header.len();
+    let mut depth = 1;
+
+    for (index, character) in source[start..].char_indices() {
+        match character {
+            '{' => depth += 1,
+            '}' => depth -= 1,
+            _ => continue,
+        }
+
+        if depth == 0 {
+            return Some(start..start + index);
+        }
+    }
+
+    None
+}
+
+/// Inserts `item` right before the closing brace of the block opened by `header`.
+pub fn insert_into_block(source: &str, header: &str, item: &str) -> Option<String> {
+    let end = block_range(source, header)?.end;
+
+    Some(format!("{}{}{}", &source[..end], item, &source[end..]))
+}
+
+/// Converts a snake_case name to PascalCase.
+pub fn pascal_case(name: &str) -> String {
+    name.split('_')
+        .map(|word| {
+            let mut characters = word.chars();
+
+            match characters.next() {
+                Some(first) => first.to_uppercase().chain(characters).collect(),
+                None => String::new(),
+            }
+        })
+        .collect()
+}
+
+/// Converts a snake_case name to camelCase.
+pub fn camel_case(name: &str) -> String {
+    let pascal = pascal_case(name);
+    let mut characters = pascal.chars();
+
+    match characters.next() {
+        Some(first) => first.to_lowercase().chain(characters).collect(),
+        None => String::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_insert_into_block() {
+        let source = "pub trait Usecase {
+    fn get(&self);
+}
+
+impl Usecase for TestUsecase {
+    fn get(&self) {
+        println!(\"{}\", 1);
+    }
+}
+";
+
+        let expect = "pub trait Usecase {
+    fn get(&self);
+}
+
+impl Usecase for TestUsecase {
+    fn get(&self) {
+        println!(\"{}\", 1);
+    }
+
+    fn list(&self) {}
+}
+";
+        let got = insert_into_block(
+            source,
+            "impl Usecase for TestUsecase {",
+            "\n    fn list(&self) {}\n",
+        )
+        .unwrap();
+
+        assert_eq!(expect, got)
+    }
+
+    #[test]
+    fn test_insert_into_missing_block() {
+        let got = insert_into_block("pub trait Usecase {}", "impl Usecase for {", "");
+
+        assert_eq!(None, got)
+    }
+
+    #[test]
+    fn test_pascal_case() {
+        assert_eq!("Notes", pascal_case("notes"));
+        assert_eq!("UserProfiles", pascal_case("user_profiles"));
+        assert_eq!("ArchiveAll2", pascal_case("archive_all2"));
+    }
+
+    #[test]
+    fn test_camel_case() {
+        assert_eq!("notes", camel_case("notes"));
+        assert_eq!("archiveAllNotes", camel_case("archive_all_notes"));
+    }
+}
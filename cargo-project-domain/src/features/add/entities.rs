@@ -0,0 +1,528 @@
+use std::convert::TryFrom;
+use std::error::Error;
+use std::str::FromStr;
+
+use cargo_project_data::features::add::models::FeatureModel;
+use titlecase::titlecase;
+
+use crate::core::project::ProjectKind;
+use crate::core::runtime::Runtime;
+use crate::core::templates::cli::CliTemplate;
+use crate::core::templates::datasource::DatasourceTemplate;
+use crate::core::templates::entities::EntitiesTemplate;
+use crate::core::templates::facade::FacadeTemplate;
+use crate::core::templates::fakes::FakesTemplate;
+use crate::core::templates::file_datasource::FileDatasourceTemplate;
+use crate::core::templates::mod_file::ModFileTemplate;
+use crate::core::templates::models::ModelsTemplate;
+use crate::core::templates::repository::RepositoryTemplate;
+use crate::core::templates::usecase::UsecaseTemplate;
+use crate::core::templates::usecase_method::{UsecaseMethodPart, UsecaseMethodTemplate};
+use crate::core::templates::{Rendered, Template};
+use crate::core::utils::{block_range, insert_into_block};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum FileFormat {
+    Json,
+    Toml,
+}
+
+impl FileFormat {
+    pub fn extension(&self) -> &'static str {
+        match self {
+            FileFormat::Json => "json",
+            FileFormat::Toml => "toml",
+        }
+    }
+}
+
+impl FromStr for FileFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "json" => Ok(FileFormat::Json),
+            "toml" => Ok(FileFormat::Toml),
+            _ => Err(format!("Unknown file format: {}", s)),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum DatasourceKind {
+    Empty,
+    File(FileFormat),
+}
+
+impl DatasourceKind {
+    pub fn new(kind: &str, format: FileFormat) -> Result<Self, String> {
+        match kind {
+            "empty" => Ok(DatasourceKind::Empty),
+            "file" => Ok(DatasourceKind::File(format)),
+            _ => Err(format!("Unknown datasource: {}", kind)),
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            DatasourceKind::Empty => "empty",
+            DatasourceKind::File(_) => "file",
+        }
+    }
+
+    /// Guesses the datasource a feature was generated with from its datasource file, for
+    /// features not recorded in the manifest.
+    pub fn detect(content: &str) -> Self {
+        if content.contains("serde_json::") {
+            DatasourceKind::File(FileFormat::Json)
+        } else if content.contains("toml::") {
+            DatasourceKind::File(FileFormat::Toml)
+        } else {
+            DatasourceKind::Empty
+        }
+    }
+
+    pub fn dependencies(&self, runtime: Runtime) -> Vec<(&'static str, &'static str)> {
+        let mut dependencies = runtime.dependencies();
+
+        if let DatasourceKind::File(format) = self {
+            dependencies.push(("serde", "{ version = \"1.0\", features = [\"derive\"] }"));
+            dependencies.push(match format {
+                FileFormat::Json => ("serde_json", "\"1.0\""),
+                FileFormat::Toml => ("toml", "\"0.8\""),
+            });
+
+            match runtime {
+                Runtime::Tokio => dependencies.push((
+                    "tokio",
+                    "{ version = \"1\", features = [\"fs\", \"io-util\"] }",
+                )),
+                Runtime::AsyncStd => dependencies.push(("async-std", "\"1.9.0\"")),
+                Runtime::Sync => {}
+            }
+        }
+
+        dependencies
+    }
+
+    pub fn dev_dependencies(&self, runtime: Runtime) -> Vec<(&'static str, &'static str)> {
+        let mut dependencies = runtime.dev_dependencies();
+
+        if let DatasourceKind::File(_) = self {
+            dependencies.push(("tempfile", "\"3\""));
+        }
+
+        dependencies
+    }
+}
+
+/// A feature generated by `add feature`, the datasource it was generated with and the
+/// usecase methods added to it since.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FeatureRecord {
+    pub name: String,
+    pub datasource: DatasourceKind,
+    pub usecases: Vec<String>,
+}
+
+impl TryFrom<FeatureModel> for FeatureRecord {
+    type Error = String;
+
+    fn try_from(model: FeatureModel) -> Result<Self, Self::Error> {
+        let format = match &model.format {
+            Some(format) => format.parse()?,
+            None => FileFormat::Json,
+        };
+
+        Ok(Self {
+            datasource: DatasourceKind::new(&model.datasource, format)?,
+            name: model.name,
+            usecases: model.usecases,
+        })
+    }
+}
+
+impl From<&FeatureRecord> for FeatureModel {
+    fn from(feature: &FeatureRecord) -> Self {
+        let format = match feature.datasource {
+            DatasourceKind::Empty => None,
+            DatasourceKind::File(format) => Some(format.extension().to_string()),
+        };
+
+        Self {
+            name: feature.name.clone(),
+            datasource: feature.datasource.name().to_string(),
+            format,
+            usecases: feature.usecases.clone(),
+        }
+    }
+}
+
+/// Domain files of a feature that `add usecase` adds a method to.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum UsecaseFile {
+    Usecase,
+    Repository,
+    Fakes,
+}
+
+impl UsecaseFile {
+    pub const ALL: [UsecaseFile; 3] = [
+        UsecaseFile::Usecase,
+        UsecaseFile::Repository,
+        UsecaseFile::Fakes,
+    ];
+
+    pub fn file_name(&self) -> &'static str {
+        match self {
+            UsecaseFile::Usecase => "usecase.rs",
+            UsecaseFile::Repository => "repository.rs",
+            UsecaseFile::Fakes => "fakes.rs",
+        }
+    }
+
+    /// Parts of a usecase method the file gets, along with the header of the block each
+    /// one goes in.
+    fn insertions(&self, name_title_case: &str) -> Vec<(String, UsecaseMethodPart)> {
+        match self {
+            UsecaseFile::Usecase => vec![
+                (
+                    String::from("pub trait Usecase {"),
+                    UsecaseMethodPart::UsecaseDeclaration,
+                ),
+                (
+                    format!("impl Usecase for {}Usecase {{", name_title_case),
+                    UsecaseMethodPart::UsecaseImplementation,
+                ),
+                (String::from("mod tests {"), UsecaseMethodPart::UsecaseTest),
+            ],
+            UsecaseFile::Repository => vec![
+                (
+                    String::from("pub trait Repository {"),
+                    UsecaseMethodPart::RepositoryDeclaration,
+                ),
+                (
+                    format!("impl Repository for {}Repository {{", name_title_case),
+                    UsecaseMethodPart::RepositoryImplementation,
+                ),
+            ],
+            UsecaseFile::Fakes => vec![(
+                String::from("impl Repository for RecordingRepository {"),
+                UsecaseMethodPart::FakeRepositoryImplementation,
+            )],
+        }
+    }
+
+    /// The file's `content` with the usecase method `name` added. Only the block of the
+    /// first part tells whether it's there already, the others have methods like `new`.
+    pub fn with_method(
+        &self,
+        content: &str,
+        name: &str,
+        name_title_case: &str,
+        runtime: Runtime,
+    ) -> Result<String, String> {
+        let insertions = self.insertions(name_title_case);
+        let (header, _) = &insertions[0];
+        let block =
+            block_range(content, header).ok_or_else(|| format!("Unable to find `{}`", header))?;
+        if content[block].contains(&format!("fn {}(", name)) {
+            return Err(String::from("Usecase already exists"));
+        }
+
+        let mut content = content.to_string();
+        for (header, part) in insertions {
+            let item = UsecaseMethodTemplate::new(part, name, name_title_case, runtime)
+                .render()
+                .map_err(|err| err.to_string())?;
+
+            content = insert_into_block(&content, &header, &item)
+                .ok_or_else(|| format!("Unable to find `{}`", header))?;
+        }
+
+        Ok(content)
+    }
+}
+
+/// Files of a feature rendered entirely from templates, apart from the mod files
+/// declaring it.
+#[derive(Debug, PartialEq)]
+pub struct FeatureFiles {
+    pub presentation: Rendered,
+    pub entities: Rendered,
+    pub domain_mod: Rendered,
+    pub repository: Rendered,
+    pub usecase: Rendered,
+    pub fakes: Rendered,
+    pub data_mod: Rendered,
+    pub datasource: Rendered,
+    pub models: Rendered,
+}
+
+impl FeatureFiles {
+    pub fn render(
+        project: &str,
+        name: &str,
+        kind: ProjectKind,
+        runtime: Runtime,
+        datasource: DatasourceKind,
+        usecases: &[String],
+    ) -> Result<Self, Box<dyn Error>> {
+        let name_title_case = titlecase(name);
+
+        let mut files = Self {
+            presentation: match kind {
+                ProjectKind::Binary => {
+                    CliTemplate::new(project, name, &name_title_case).rendered()?
+                }
+                ProjectKind::Library => {
+                    FacadeTemplate::new(project, name, &name_title_case).rendered()?
+                }
+            },
+            entities: EntitiesTemplate::new(name, &name_title_case, project).rendered()?,
+            domain_mod: ModFileTemplate::with_test_resources(
+                vec!["entities", "repository", "usecase"],
+                vec!["fakes"],
+            )
+            .rendered()?,
+            repository: RepositoryTemplate::new(name, &name_title_case, project, runtime)
+                .rendered()?,
+            usecase: UsecaseTemplate::new(name, &name_title_case, runtime).rendered()?,
+            fakes: FakesTemplate::new(name, &name_title_case, project, runtime).rendered()?,
+            data_mod: ModFileTemplate::new(vec!["datasource", "models"]).rendered()?,
+            datasource: match datasource {
+                DatasourceKind::Empty => {
+                    DatasourceTemplate::new(&name_title_case, runtime).rendered()?
+                }
+                DatasourceKind::File(format) => {
+                    FileDatasourceTemplate::new(name, &name_title_case, format, runtime)
+                        .rendered()?
+                }
+            },
+            models: ModelsTemplate::new(&name_title_case, datasource != DatasourceKind::Empty)
+                .rendered()?,
+        };
+
+        for usecase in usecases {
+            for file in UsecaseFile::ALL {
+                let rendered = files.usecase_file(file);
+                rendered.content =
+                    file.with_method(&rendered.content, usecase, &name_title_case, runtime)?;
+            }
+        }
+
+        Ok(files)
+    }
+
+    fn usecase_file(&mut self, file: UsecaseFile) -> &mut Rendered {
+        match file {
+            UsecaseFile::Usecase => &mut self.usecase,
+            UsecaseFile::Repository => &mut self.repository,
+            UsecaseFile::Fakes => &mut self.fakes,
+        }
+    }
+
+    /// The files as `(path, rendered)` pairs relative to the workspace root.
+    pub fn paths(
+        &self,
+        project: &str,
+        presentation_path: &str,
+        name: &str,
+    ) -> Vec<(String, &Rendered)> {
+        let domain = format!("{}-domain/src/features/{}", project, name);
+        let data = format!("{}-data/src/features/{}", project, name);
+
+        vec![
+            (
+                format!("{}/src/features/{}.rs", presentation_path, name),
+                &self.presentation,
+            ),
+            (format!("{}/entities.rs", domain), &self.entities),
+            (format!("{}/mod.rs", domain), &self.domain_mod),
+            (format!("{}/repository.rs", domain), &self.repository),
+            (format!("{}/usecase.rs", domain), &self.usecase),
+            (format!("{}/fakes.rs", domain), &self.fakes),
+            (format!("{}/datasource.rs", data), &self.datasource),
+            (format!("{}/mod.rs", data), &self.data_mod),
+            (format!("{}/models.rs", data), &self.models),
+        ]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_file_format_from_str() {
+        assert_eq!(FileFormat::Json, "json".parse().unwrap());
+        assert_eq!(FileFormat::Toml, "toml".parse().unwrap());
+        assert!("yaml".parse::<FileFormat>().is_err());
+    }
+
+    #[test]
+    fn test_datasource_kind_new() {
+        assert_eq!(
+            DatasourceKind::Empty,
+            DatasourceKind::new("empty", FileFormat::Json).unwrap()
+        );
+        assert_eq!(
+            DatasourceKind::File(FileFormat::Toml),
+            DatasourceKind::new("file", FileFormat::Toml).unwrap()
+        );
+        assert!(DatasourceKind::new("sql", FileFormat::Json).is_err());
+    }
+
+    #[test]
+    fn test_datasource_kind_detect() {
+        let content =
+            FileDatasourceTemplate::new("notes", "Notes", FileFormat::Toml, Runtime::Tokio)
+                .render()
+                .unwrap();
+
+        assert_eq!(
+            DatasourceKind::File(FileFormat::Toml),
+            DatasourceKind::detect(&content)
+        );
+        assert_eq!(
+            DatasourceKind::Empty,
+            DatasourceKind::detect("pub struct NotesDatasource {}")
+        );
+    }
+
+    #[test]
+    fn test_dependencies() {
+        let datasource = DatasourceKind::File(FileFormat::Toml);
+
+        let got = datasource.dependencies(Runtime::Sync);
+
+        assert_eq!(
+            vec!["serde", "toml"],
+            got.iter().map(|(name, _)| *name).collect::<Vec<_>>()
+        );
+        assert_eq!(
+            vec!["async-trait"],
+            DatasourceKind::Empty
+                .dependencies(Runtime::Tokio)
+                .iter()
+                .map(|(name, _)| *name)
+                .collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn test_feature_record_conversion() {
+        let feature = FeatureRecord {
+            name: String::from("notes"),
+            datasource: DatasourceKind::File(FileFormat::Toml),
+            usecases: vec![String::from("archive")],
+        };
+
+        let got = FeatureRecord::try_from(FeatureModel::from(&feature)).unwrap();
+
+        assert_eq!(feature, got);
+    }
+
+    #[test]
+    fn test_feature_files_paths() {
+        let files = FeatureFiles::render(
+            "test",
+            "notes",
+            ProjectKind::Binary,
+            Runtime::Sync,
+            DatasourceKind::Empty,
+            &[],
+        )
+        .unwrap();
+
+        let got = files.paths("test", "test", "notes");
+
+        assert_eq!("test/src/features/notes.rs", got[0].0);
+        assert_eq!("test-data/src/features/notes/models.rs", got[8].0);
+        assert_eq!(&files.usecase, got[4].1);
+    }
+
+    #[test]
+    fn test_usecase_file_with_method() {
+        let content = UsecaseTemplate::new("notes", "Notes", Runtime::Sync)
+            .render()
+            .unwrap();
+
+        let got = UsecaseFile::Usecase
+            .with_method(&content, "archive", "Notes", Runtime::Sync)
+            .unwrap();
+
+        assert!(got.contains("    fn archive("));
+        assert_eq!(
+            Err(String::from("Usecase already exists")),
+            UsecaseFile::Usecase.with_method(&got, "archive", "Notes", Runtime::Sync)
+        );
+        assert_eq!(
+            Err(String::from(
+                "Unable to find `impl Repository for RecordingRepository {`"
+            )),
+            UsecaseFile::Fakes.with_method(&content, "archive", "Notes", Runtime::Sync)
+        );
+    }
+
+    #[test]
+    fn test_usecase_file_with_method_named_new() {
+        let files = FeatureFiles::render(
+            "test",
+            "notes",
+            ProjectKind::Binary,
+            Runtime::Sync,
+            DatasourceKind::Empty,
+            &[],
+        )
+        .unwrap();
+        assert!(files.usecase.content.contains("pub fn new("));
+
+        for (file, content) in [
+            (UsecaseFile::Usecase, &files.usecase.content),
+            (UsecaseFile::Repository, &files.repository.content),
+            (UsecaseFile::Fakes, &files.fakes.content),
+        ] {
+            let got = file
+                .with_method(content, "new", "Notes", Runtime::Sync)
+                .unwrap();
+
+            assert_eq!(
+                Err(String::from("Usecase already exists")),
+                file.with_method(&got, "new", "Notes", Runtime::Sync)
+            );
+        }
+    }
+
+    #[test]
+    fn test_feature_files_with_usecases() {
+        let render = |usecases: &[String]| {
+            FeatureFiles::render(
+                "test",
+                "notes",
+                ProjectKind::Binary,
+                Runtime::Tokio,
+                DatasourceKind::Empty,
+                usecases,
+            )
+            .unwrap()
+        };
+        let files = render(&[]);
+
+        let got = render(&[String::from("archive")]);
+
+        assert_eq!(
+            UsecaseFile::Repository
+                .with_method(
+                    &files.repository.content,
+                    "archive",
+                    "Notes",
+                    Runtime::Tokio
+                )
+                .unwrap(),
+            got.repository.content
+        );
+        assert_eq!(files.repository.template, got.repository.template);
+        assert_eq!(files.entities, got.entities);
+    }
+}
@@ -1,2 +1,3 @@
+pub mod entities;
 mod repository;
 pub mod usecase;
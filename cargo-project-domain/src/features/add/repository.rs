@@ -1,12 +1,20 @@
 use async_trait::async_trait;
+use cargo_project_data::core::stamps::StampModel;
 use cargo_project_data::features::add::datasource::{AddDatasource, Datasource};
+use cargo_project_data::features::add::models::{FeatureModel, LayerModel};
+use std::convert::TryFrom;
 use std::io;
 
+use super::entities::FeatureRecord;
+
+use crate::core::layer::Layer;
+use crate::core::stamp::Stamp;
+
 #[async_trait]
 pub trait Repository {
     async fn update_cli(
         &self,
-        project: &str,
+        crate_path: &str,
         name: &str,
         cli_file_content: &[u8],
         parent_mod_file_content: &[u8],
@@ -30,6 +38,28 @@ pub trait Repository {
         models_file_content: &[u8],
         parent_mod_file_content: &[u8],
     ) -> io::Result<()>;
+    async fn update_manifest(
+        &self,
+        crate_name: &str,
+        dependencies: &[(&str, &str)],
+        dev_dependencies: &[(&str, &str)],
+    ) -> io::Result<()>;
+    async fn exists(&self, path: &str) -> bool;
+    async fn read_file(&self, path: &str) -> io::Result<String>;
+    async fn write_file(&self, path: &str, content: &[u8]) -> io::Result<()>;
+    async fn read_metadata(&self, key: &str) -> io::Result<Option<String>>;
+    async fn read_metadata_list(&self, key: &str) -> io::Result<Vec<String>>;
+    async fn add_metadata_item(&self, key: &str, value: &str) -> io::Result<()>;
+    async fn add_workspace_member(&self, member: &str) -> io::Result<()>;
+    async fn read_layers(&self) -> io::Result<Vec<Layer>>;
+    async fn add_layer(&self, layer: &Layer) -> io::Result<()>;
+    async fn read_features(&self) -> io::Result<Vec<FeatureRecord>>;
+    async fn record_feature(&self, feature: &FeatureRecord) -> io::Result<()>;
+    async fn read_stamps(&self) -> io::Result<Vec<Stamp>>;
+    async fn record_stamps(&self, stamps: &[Stamp]) -> io::Result<()>;
+    async fn create_directory(&self, path: &str) -> io::Result<()>;
+    async fn list_directories(&self, path: &str) -> io::Result<Vec<String>>;
+    async fn list_files(&self, path: &str) -> io::Result<Vec<String>>;
 }
 
 pub struct AddRepository {
@@ -47,14 +77,14 @@ impl AddRepository {
 impl Repository for AddRepository {
     async fn update_cli(
         &self,
-        project: &str,
+        crate_path: &str,
         name: &str,
         cli_file_content: &[u8],
         parent_mod_file_content: &[u8],
     ) -> io::Result<()> {
         Ok(self
             .datasource
-            .update_cli(project, name, cli_file_content, parent_mod_file_content)
+            .update_cli(crate_path, name, cli_file_content, parent_mod_file_content)
             .await?)
     }
 
@@ -103,4 +133,106 @@ impl Repository for AddRepository {
             )
             .await?)
     }
+
+    async fn update_manifest(
+        &self,
+        crate_name: &str,
+        dependencies: &[(&str, &str)],
+        dev_dependencies: &[(&str, &str)],
+    ) -> io::Result<()> {
+        self.datasource
+            .update_manifest(crate_name, dependencies, dev_dependencies)
+            .await
+    }
+
+    async fn exists(&self, path: &str) -> bool {
+        self.datasource.exists(path).await
+    }
+
+    async fn read_file(&self, path: &str) -> io::Result<String> {
+        self.datasource.read_file(path).await
+    }
+
+    async fn write_file(&self, path: &str, content: &[u8]) -> io::Result<()> {
+        self.datasource.write_file(path, content).await
+    }
+
+    async fn read_metadata(&self, key: &str) -> io::Result<Option<String>> {
+        self.datasource.read_metadata(key).await
+    }
+
+    async fn read_metadata_list(&self, key: &str) -> io::Result<Vec<String>> {
+        self.datasource.read_metadata_list(key).await
+    }
+
+    async fn add_metadata_item(&self, key: &str, value: &str) -> io::Result<()> {
+        self.datasource.add_metadata_item(key, value).await
+    }
+
+    async fn add_workspace_member(&self, member: &str) -> io::Result<()> {
+        self.datasource.add_workspace_member(member).await
+    }
+
+    async fn read_layers(&self) -> io::Result<Vec<Layer>> {
+        self.datasource
+            .read_layers()
+            .await?
+            .into_iter()
+            .map(|model| {
+                Layer::try_from(model)
+                    .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))
+            })
+            .collect()
+    }
+
+    async fn add_layer(&self, layer: &Layer) -> io::Result<()> {
+        self.datasource.add_layer(&LayerModel::from(layer)).await
+    }
+
+    async fn read_features(&self) -> io::Result<Vec<FeatureRecord>> {
+        self.datasource
+            .read_features()
+            .await?
+            .into_iter()
+            .map(|model| {
+                FeatureRecord::try_from(model)
+                    .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))
+            })
+            .collect()
+    }
+
+    async fn record_feature(&self, feature: &FeatureRecord) -> io::Result<()> {
+        self.datasource
+            .record_feature(&FeatureModel::from(feature))
+            .await
+    }
+
+    async fn read_stamps(&self) -> io::Result<Vec<Stamp>> {
+        self.datasource
+            .read_stamps()
+            .await?
+            .into_iter()
+            .map(|model| {
+                Stamp::try_from(model)
+                    .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))
+            })
+            .collect()
+    }
+
+    async fn record_stamps(&self, stamps: &[Stamp]) -> io::Result<()> {
+        let stamps = stamps.iter().map(StampModel::from).collect::<Vec<_>>();
+        self.datasource.record_stamps(&stamps).await
+    }
+
+    async fn create_directory(&self, path: &str) -> io::Result<()> {
+        self.datasource.create_directory(path).await
+    }
+
+    async fn list_directories(&self, path: &str) -> io::Result<Vec<String>> {
+        self.datasource.list_directories(path).await
+    }
+
+    async fn list_files(&self, path: &str) -> io::Result<Vec<String>> {
+        self.datasource.list_files(path).await
+    }
 }
@@ -1,25 +1,175 @@
-use std::env;
+use std::io;
 
 use async_trait::async_trait;
 use futures::try_join;
 use titlecase::titlecase;
 
+use super::entities::{DatasourceKind, FeatureFiles, FeatureRecord, UsecaseFile};
 use super::repository::{AddRepository, Repository};
 
-use crate::core::templates::cli::CliTemplate;
-use crate::core::templates::datasource::DatasourceTemplate;
-use crate::core::templates::entities::EntitiesTemplate;
+use crate::core::baseline::baseline_path;
+use crate::core::layer::Layer;
+use crate::core::presentation::{Presentation, PresentationFeature};
+use crate::core::project::{metadata_setting, ProjectKind};
+use crate::core::report::{ErrorCode, Report};
+use crate::core::runtime::Runtime;
+use crate::core::source::{runtime_specific_modules, struct_fields, usecase_methods};
+use crate::core::stamp::{content_hash, Stamp};
+use crate::core::templates::ci::workflow::WorkflowTemplate;
 use crate::core::templates::mod_file::ModFileTemplate;
-use crate::core::templates::models::ModelsTemplate;
-use crate::core::templates::repository::RepositoryTemplate;
-use crate::core::templates::usecase::UsecaseTemplate;
-use crate::core::templates::Template;
+use crate::core::templates::{Rendered, Template};
 
-use crate::core::utils::in_root_directory;
+use crate::core::utils::{block_range, current_project, in_root_directory};
 
 #[async_trait]
 pub trait Usecase {
-    async fn add_feature(&self, name: &str) -> String;
+    async fn add_feature(&self, name: &str, datasource: DatasourceKind) -> Report;
+    async fn add_usecase(&self, feature: &str, name: &str) -> Report;
+    async fn add_presentation(&self, presentation: Presentation) -> Report;
+    async fn add_layer(&self, layer: Layer) -> Report;
+    async fn add_fields(&self, feature: &str, fields: &[(String, String)]) -> Report;
+}
+
+/// Widest fields of a struct literal rustfmt keeps on a single line.
+const STRUCT_LITERAL_WIDTH: usize = 18;
+
+/// Adds the `fields` to the struct `name` declared in `content`.
+fn with_fields(content: &str, name: &str, fields: &[(String, String)]) -> Option<String> {
+    let range = block_range(content, &format!("pub struct {} {{", name))?;
+    let body = content[range.clone()].trim_end();
+
+    let mut declarations = String::from(body);
+    if !body.trim().is_empty() && !body.ends_with(',') {
+        declarations.push(',');
+    }
+    declarations.push('\n');
+    for (name, ty) in fields {
+        declarations.push_str(&format!("    pub {}: {},\n", name, ty));
+    }
+
+    Some(format!(
+        "{}{}{}",
+        &content[..range.start],
+        declarations,
+        &content[range.end..]
+    ))
+}
+
+/// The generated conversion from `source` into `target`, moving the `fields` both structs
+/// have. Fields of `target` missing from `source` are defaulted unless `complete`.
+fn conversion(
+    source: &str,
+    target: &str,
+    binding: &str,
+    fields: &[&str],
+    complete: bool,
+) -> String {
+    let initializers = fields
+        .iter()
+        .map(|field| format!("{}: {}.{}", field, binding, field))
+        .collect::<Vec<_>>();
+
+    let body = match (initializers.is_empty(), complete) {
+        (true, true) => String::from("Self {}"),
+        (true, false) => String::from("Self::default()"),
+        (false, true) if initializers.join(", ").len() <= STRUCT_LITERAL_WIDTH => {
+            format!("Self {{ {} }}", initializers.join(", "))
+        }
+        (false, complete) => {
+            let mut body = String::from("Self {\n");
+            for initializer in &initializers {
+                body.push_str(&format!("            {},\n", initializer));
+            }
+            if !complete {
+                body.push_str("            ..Self::default()\n");
+            }
+            body.push_str("        }");
+            body
+        }
+    };
+    let binding = match fields.is_empty() {
+        true => format!("_{}", binding),
+        false => binding.to_string(),
+    };
+
+    format!(
+        "impl From<{}> for {} {{\n    fn from({}: {}) -> Self {{\n        {}\n    }}\n}}",
+        source, target, binding, source, body
+    )
+}
+
+/// Fields of a feature's entity and model, which its conversions move between them.
+struct ConvertedFields {
+    entity: Vec<String>,
+    model: Vec<String>,
+}
+
+impl ConvertedFields {
+    fn new(entities: &str, models: &str, name_title_case: &str) -> Self {
+        let names = |source: &str, name: &str| {
+            struct_fields(source, name)
+                .into_iter()
+                .map(|field| field.name)
+                .collect::<Vec<_>>()
+        };
+
+        Self {
+            entity: names(entities, name_title_case),
+            model: names(models, &format!("{}Model", name_title_case)),
+        }
+    }
+
+    /// Both conversions of the feature's entity, as they're generated for these fields.
+    fn conversions(&self, name_title_case: &str) -> [String; 2] {
+        let model = format!("{}Model", name_title_case);
+        let shared = self
+            .entity
+            .iter()
+            .filter(|field| self.model.contains(field))
+            .map(String::as_str)
+            .collect::<Vec<_>>();
+
+        [
+            conversion(
+                &model,
+                name_title_case,
+                "model",
+                &shared,
+                shared.len() == self.entity.len(),
+            ),
+            conversion(
+                name_title_case,
+                &model,
+                "entity",
+                &shared,
+                shared.len() == self.model.len(),
+            ),
+        ]
+    }
+}
+
+/// Replaces the conversions of an entity generated for the `before` fields with the ones
+/// for the `after` fields, returning `None` when they were changed since.
+fn with_conversions(
+    content: &str,
+    name_title_case: &str,
+    before: &ConvertedFields,
+    after: &ConvertedFields,
+) -> Option<String> {
+    let mut content = content.to_string();
+
+    for (generated, updated) in before
+        .conversions(name_title_case)
+        .iter()
+        .zip(after.conversions(name_title_case))
+    {
+        if !content.contains(generated.as_str()) {
+            return None;
+        }
+        content = content.replacen(generated.as_str(), &updated, 1);
+    }
+
+    Some(content)
 }
 
 pub struct AddUsecase {
@@ -31,80 +181,1059 @@ impl AddUsecase {
         let repository = Box::new(AddRepository::new());
         Self { repository }
     }
-}
 
-#[async_trait]
-impl Usecase for AddUsecase {
-    async fn add_feature(&self, name: &str) -> String {
-        let current_dir =
-            env::current_dir().expect("Error reading current directory from environment");
+    async fn update_manifests(
+        &self,
+        project: &str,
+        datasource: DatasourceKind,
+        runtime: Runtime,
+    ) -> io::Result<()> {
+        let data_crate = format!("{}-data", project);
+        let data_dependency = format!("{{ path = \"../{}\" }}", data_crate);
 
-        let project = current_dir
-            .file_name()
-            .expect("Error reading file name from current executable")
-            .to_str()
-            .expect("Can't convert file name to str")
-            .split("/");
+        let mut domain_dependencies = vec![
+            (data_crate.as_str(), data_dependency.as_str()),
+            ("serde", "{ version = \"1.0\", features = [\"derive\"] }"),
+        ];
+        domain_dependencies.extend(runtime.dependencies());
+        let domain_dev_dependencies = runtime.dev_dependencies();
 
-        let project = project.last().expect("Error parsing current directory");
+        let data_dependencies = datasource.dependencies(runtime);
+        let data_dev_dependencies = datasource.dev_dependencies(runtime);
 
-        let name_title_case = titlecase(name);
+        let domain_crate = format!("{}-domain", project);
+        let domain_manifest_future = self.repository.update_manifest(
+            &domain_crate,
+            &domain_dependencies,
+            &domain_dev_dependencies,
+        );
+        let data_manifest_future = self.repository.update_manifest(
+            &data_crate,
+            &data_dependencies,
+            &data_dev_dependencies,
+        );
 
-        if !in_root_directory(&project).await {
-            return String::from("Not currently in the root of a project");
+        try_join!(domain_manifest_future, data_manifest_future)?;
+
+        Ok(())
+    }
+
+    /// Directory of the project's own crate, which `init` leaves at the workspace root.
+    async fn presentation_path(&self, project: &str) -> io::Result<String> {
+        let path = self.repository.read_metadata("presentation-path").await?;
+        Ok(path.unwrap_or_else(|| project.to_string()))
+    }
+
+    async fn presentations(&self) -> io::Result<Vec<Presentation>> {
+        self.repository
+            .read_metadata_list("presentations")
+            .await?
+            .iter()
+            .map(|name| {
+                name.parse()
+                    .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))
+            })
+            .collect()
+    }
+
+    async fn presentation_features(&self, project: &str) -> io::Result<Vec<PresentationFeature>> {
+        let directory = format!("{}-domain/src/features", project);
+        let mut features = Vec::new();
+
+        for name in self.repository.list_directories(&directory).await? {
+            let usecase_path = format!("{}/{}/usecase.rs", directory, name);
+            let entities_path = format!("{}/{}/entities.rs", directory, name);
+            let (usecase, entities) = try_join!(
+                self.repository.read_file(&usecase_path),
+                self.repository.read_file(&entities_path),
+            )?;
+
+            features.push(PresentationFeature {
+                fields: struct_fields(&entities, &titlecase(&name)),
+                methods: usecase_methods(&usecase),
+                name,
+            });
         }
 
-        let cli_file_content = CliTemplate::new(name, &name_title_case).render().unwrap();
-        let cli_parent_mod_file_content = ModFileTemplate::new(vec![name]).render().unwrap();
+        Ok(features)
+    }
 
-        let entities_file_content = EntitiesTemplate::new(name).render().unwrap();
-        let domain_mod_file_content =
-            ModFileTemplate::new(vec!["entities", "repository", "usecase"])
-                .render()
-                .unwrap();
-        let domain_parent_mod_file_content = ModFileTemplate::new(vec![name]).render().unwrap();
-        let repository_file_content = RepositoryTemplate::new(name, &name_title_case, project)
-            .render()
-            .unwrap();
-        let usecase_file_content = UsecaseTemplate::new(&name_title_case).render().unwrap();
+    async fn write_files(&self, files: &[(String, String)], report: &mut Report) -> io::Result<()> {
+        for (path, content) in files {
+            if let Some((directory, _)) = path.rsplit_once('/') {
+                self.repository.create_directory(directory).await?;
+            }
 
-        let data_mod_file_content = ModFileTemplate::new(vec!["datasource", "models"])
-            .render()
-            .unwrap();
-        let data_parent_mod_file_content = ModFileTemplate::new(vec![name]).render().unwrap();
-        let datasource_file_content = DatasourceTemplate::new(&name_title_case).render().unwrap();
-        let models_file_content = ModelsTemplate::new(name).render().unwrap();
+            let existed = self.repository.exists(path).await;
+            self.repository.write_file(path, content.as_bytes()).await?;
+            report.written(path, existed);
+        }
 
-        let cli_update_future = self.repository.update_cli(
+        Ok(())
+    }
+
+    /// Contents of the files at `paths` before a command writes them, `None` for those that
+    /// don't exist yet.
+    async fn contents(&self, paths: Vec<String>) -> Vec<(String, Option<String>)> {
+        let mut contents = Vec::new();
+        for path in paths {
+            let content = self.repository.read_file(&path).await.ok();
+            contents.push((path, content));
+        }
+
+        contents
+    }
+
+    /// Adds the files whose content changed from `before` to the report, the repository
+    /// methods writing them don't tell whether they had anything to change.
+    async fn report_changed(&self, before: Vec<(String, Option<String>)>, report: &mut Report) {
+        for (path, content) in before {
+            let after = self.repository.read_file(&path).await.ok();
+            if after.is_some() && after != content {
+                report.written(&path, content.is_some());
+            }
+        }
+    }
+
+    /// Writes the rendered `files` and records their stamps, so `status`, `verify` and later
+    /// updates can tell them apart from the edits made since.
+    async fn write_generated(
+        &self,
+        files: &[(String, Rendered)],
+        report: &mut Report,
+    ) -> io::Result<()> {
+        let contents = files
+            .iter()
+            .map(|(path, rendered)| (path.clone(), rendered.content.clone()))
+            .collect::<Vec<_>>();
+        self.write_files(&contents, report).await?;
+
+        let files = files
+            .iter()
+            .map(|(path, rendered)| (path.clone(), rendered))
+            .collect::<Vec<_>>();
+        self.record_generated(&files).await
+    }
+
+    /// Records the baseline and stamp of the generated `files`, so `upgrade` can merge newer
+    /// templates into them and `status` can report on them.
+    async fn record_generated(&self, files: &[(String, &Rendered)]) -> io::Result<()> {
+        let baselines = files
+            .iter()
+            .map(|(path, rendered)| (baseline_path(path), rendered.content.clone()))
+            .collect::<Vec<_>>();
+        // Baselines are bookkeeping of the tool, left out of the report
+        self.write_files(&baselines, &mut Report::default()).await?;
+
+        let stamps = files
+            .iter()
+            .map(|(path, rendered)| Stamp::new(path, rendered))
+            .collect::<Vec<_>>();
+        self.repository.record_stamps(&stamps).await
+    }
+
+    /// Writes the rendered `files` over the ones left as they were generated. Files changed
+    /// since are skipped with a warning to make the `change` by hand.
+    async fn update_generated(
+        &self,
+        files: Vec<(String, Rendered)>,
+        change: &str,
+        report: &mut Report,
+    ) -> io::Result<()> {
+        let stamps = self.repository.read_stamps().await?;
+        let mut pristine = Vec::new();
+
+        for (path, rendered) in files {
+            if self.repository.exists(&path).await {
+                let content = self.repository.read_file(&path).await?;
+                let stamped = stamps
+                    .iter()
+                    .any(|stamp| stamp.path == path && stamp.hash == content_hash(&content));
+
+                if !stamped && content != rendered.content {
+                    report
+                        .warnings
+                        .push(format!("{} was modified, {}", path, change));
+                    report.skipped.push(path);
+                    continue;
+                }
+            }
+
+            pristine.push((path, rendered));
+        }
+
+        self.write_generated(&pristine, report).await
+    }
+
+    /// Records how a feature was generated along with the baseline and stamp of its files.
+    async fn record_feature(
+        &self,
+        project: &str,
+        presentation_path: &str,
+        feature: &FeatureRecord,
+        files: &FeatureFiles,
+    ) -> io::Result<()> {
+        let files = files.paths(project, presentation_path, &feature.name);
+        self.record_generated(&files).await?;
+
+        self.repository.record_feature(feature).await
+    }
+
+    /// Exposes a newly added feature through every registered presentation crate, leaving
+    /// the files changed since they were generated alone.
+    async fn update_presentations(
+        &self,
+        project: &str,
+        name: &str,
+        runtime: Runtime,
+        report: &mut Report,
+    ) -> io::Result<()> {
+        let presentations = self.presentations().await?;
+
+        if presentations.is_empty() {
+            return Ok(());
+        }
+
+        let features = self.presentation_features(project).await?;
+        let feature = features
+            .iter()
+            .find(|feature| feature.name == name)
+            .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, name))?;
+
+        for presentation in presentations {
+            let files = presentation
+                .feature_files(project, &features, feature, runtime)
+                .map_err(|err| io::Error::other(err.to_string()))?;
+            let change = format!("expose the {} feature in it", name);
+
+            self.update_generated(files, &change, report).await?;
+        }
+
+        Ok(())
+    }
+
+    async fn feature_names(&self, project: &str) -> io::Result<Vec<String>> {
+        let directory = format!("{}-domain/src/features", project);
+        self.repository.list_directories(&directory).await
+    }
+
+    /// Generates a newly added feature's files in every configured layer, leaving the files
+    /// changed since they were generated alone.
+    async fn update_layers(
+        &self,
+        project: &str,
+        name: &str,
+        report: &mut Report,
+    ) -> io::Result<()> {
+        let layers = self.repository.read_layers().await?;
+
+        if layers.is_empty() {
+            return Ok(());
+        }
+
+        let features = self.feature_names(project).await?;
+        let features = features.iter().map(String::as_str).collect::<Vec<_>>();
+
+        for layer in layers {
+            let files = layer
+                .feature_files(project, &features, name)
+                .map_err(|err| io::Error::other(err.to_string()))?;
+            let change = format!("declare the {} feature in it", name);
+
+            self.update_generated(files, &change, report).await?;
+        }
+
+        Ok(())
+    }
+
+    /// Regenerates the CI workflow of a project created with one, so its layer dependency
+    /// check covers newly added layers and presentations. A workflow changed since it was
+    /// generated is left alone with a warning.
+    async fn update_workflow(&self, project: &str, report: &mut Report) -> io::Result<()> {
+        let path = ".github/workflows/ci.yml";
+        if !self.repository.exists(path).await {
+            return Ok(());
+        }
+
+        let content = self.repository.read_file(path).await?;
+        let stamps = self.repository.read_stamps().await?;
+        let pristine = stamps
+            .iter()
+            .any(|stamp| stamp.path == path && stamp.hash == content_hash(&content));
+        if !pristine {
+            report.warnings.push(format!(
+                "{} was modified, update its layer dependency check for the new crate",
+                path
+            ));
+            return Ok(());
+        }
+
+        let kind: ProjectKind = metadata_setting(self.repository.read_metadata("kind").await)
+            .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))?;
+        let mut layers = Layer::defaults(kind);
+        layers.extend(self.repository.read_layers().await?);
+        layers.extend(self.presentations().await?.iter().map(Presentation::layer));
+
+        let rendered = WorkflowTemplate::new(project, &layers)
+            .rendered()
+            .map_err(|err| io::Error::other(err.to_string()))?;
+        self.write_generated(&[(path.to_string(), rendered)], report)
+            .await
+    }
+
+    /// Lists the domain and data files using runtime specific modules, which won't work
+    /// once the wasm presentation is compiled to WebAssembly.
+    async fn wasm_warnings(&self, project: &str) -> io::Result<Vec<String>> {
+        let mut warnings = Vec::new();
+
+        for layer in ["domain", "data"] {
+            let directory = format!("{}-{}/src", project, layer);
+
+            for path in self.repository.list_files(&directory).await? {
+                if !path.ends_with(".rs") {
+                    continue;
+                }
+
+                let content = self.repository.read_file(&path).await?;
+
+                for module in runtime_specific_modules(&content) {
+                    warnings.push(format!(
+                        "{} uses `{}`, which isn't available to the wasm presentation",
+                        path, module
+                    ));
+                }
+            }
+        }
+
+        Ok(warnings)
+    }
+
+    /// Writes the `files`, as `(path, original, updated)` triples, one at a time. When one
+    /// fails, the files written before it get their original content back.
+    async fn write_or_restore(&self, files: &[(String, String, String)]) -> io::Result<()> {
+        for (index, (path, _, updated)) in files.iter().enumerate() {
+            if let Err(err) = self.repository.write_file(path, updated.as_bytes()).await {
+                for (path, original, _) in &files[..index] {
+                    self.repository
+                        .write_file(path, original.as_bytes())
+                        .await?;
+                }
+                return Err(err);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Records the usecase method `name` added to a recorded feature along with the
+    /// baseline and stamp of its files, as `add feature` does.
+    async fn record_usecase(&self, project: &str, feature: &str, name: &str) -> io::Result<()> {
+        let features = self.repository.read_features().await?;
+        let mut feature = match features.into_iter().find(|record| record.name == feature) {
+            Some(feature) => feature,
+            // Features generated before they were recorded aren't upgraded either
+            None => return Ok(()),
+        };
+        feature.usecases.push(name.to_string());
+
+        let invalid = |err: String| io::Error::new(io::ErrorKind::InvalidData, err);
+        let runtime =
+            metadata_setting(self.repository.read_metadata("runtime").await).map_err(invalid)?;
+        let kind =
+            metadata_setting(self.repository.read_metadata("kind").await).map_err(invalid)?;
+        let presentation_path = self.presentation_path(project).await?;
+
+        let files = FeatureFiles::render(
             project,
+            &feature.name,
+            kind,
+            runtime,
+            feature.datasource,
+            &feature.usecases,
+        )
+        .map_err(|err| io::Error::other(err.to_string()))?;
+
+        self.record_feature(project, &presentation_path, &feature, &files)
+            .await
+    }
+
+    /// Exposes a usecase method added to the feature `name` through every registered
+    /// presentation crate, `before` being the features as they were without it. Files
+    /// changed since they were generated are left alone with a warning.
+    async fn update_presentation_usecases(
+        &self,
+        project: &str,
+        name: &str,
+        before: &[PresentationFeature],
+        runtime: Runtime,
+        report: &mut Report,
+    ) -> io::Result<()> {
+        let presentations = self.presentations().await?;
+
+        if presentations.is_empty() {
+            return Ok(());
+        }
+
+        let after = self.presentation_features(project).await?;
+        let find = |features: &'_ [PresentationFeature]| {
+            features
+                .iter()
+                .find(|feature| feature.name == name)
+                .cloned()
+                .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, name.to_string()))
+        };
+        let (feature_before, feature_after) = (find(before)?, find(&after)?);
+
+        for presentation in presentations {
+            let generated = presentation
+                .feature_files(project, before, &feature_before, runtime)
+                .map_err(|err| io::Error::other(err.to_string()))?;
+            let updated = presentation
+                .feature_files(project, &after, &feature_after, runtime)
+                .map_err(|err| io::Error::other(err.to_string()))?;
+
+            for (path, rendered) in updated {
+                let generated = generated
+                    .iter()
+                    .find(|(generated, _)| *generated == path)
+                    .map(|(_, generated)| &generated.content);
+                if generated == Some(&rendered.content) {
+                    continue;
+                }
+
+                match self.repository.read_file(&path).await {
+                    Ok(current) if Some(&current) == generated => {
+                        self.write_generated(&[(path, rendered)], report).await?
+                    }
+                    _ => report.warnings.push(format!(
+                        "{} was changed since it was generated, add the usecase to it",
+                        path
+                    )),
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    async fn with_wasm_warnings(&self, project: &str, mut report: Report) -> Report {
+        match self.presentations().await {
+            Ok(presentations) if presentations.contains(&Presentation::Wasm) => (),
+            _ => return report,
+        }
+
+        match self.wasm_warnings(project).await {
+            Ok(warnings) => report.warnings.extend(warnings),
+            Err(err) => report.warnings.push(format!(
+                "Unable to check for runtime specific code - {}",
+                err
+            )),
+        }
+
+        report
+    }
+}
+
+#[async_trait]
+impl Usecase for AddUsecase {
+    async fn add_feature(&self, name: &str, datasource: DatasourceKind) -> Report {
+        let project = current_project();
+        let project = project.as_str();
+
+        if !in_root_directory(project).await {
+            return Report::not_in_project();
+        }
+
+        let runtime = match metadata_setting(self.repository.read_metadata("runtime").await) {
+            Ok(runtime) => runtime,
+            Err(err) => {
+                return Report::failure(
+                    ErrorCode::Failed,
+                    format!("Failed to add feature: {} - {}", name, err),
+                )
+            }
+        };
+        let kind = match metadata_setting(self.repository.read_metadata("kind").await) {
+            Ok(kind) => kind,
+            Err(err) => {
+                return Report::failure(
+                    ErrorCode::Failed,
+                    format!("Failed to add feature: {} - {}", name, err),
+                )
+            }
+        };
+        let presentation_path = match self.presentation_path(project).await {
+            Ok(path) => path,
+            Err(err) => {
+                return Report::failure(
+                    ErrorCode::Failed,
+                    format!("Failed to add feature: {} - {}", name, err),
+                )
+            }
+        };
+
+        let files = match FeatureFiles::render(project, name, kind, runtime, datasource, &[]) {
+            Ok(files) => files,
+            Err(err) => {
+                return Report::failure(
+                    ErrorCode::Failed,
+                    format!("Failed to add feature: {} - {}", name, err),
+                )
+            }
+        };
+        let parent_mod_file_content = ModFileTemplate::new(vec![name]).render().unwrap();
+
+        let mut paths = files
+            .paths(project, &presentation_path, name)
+            .into_iter()
+            .map(|(path, _)| path)
+            .collect::<Vec<_>>();
+        paths.extend(vec![
+            format!("{}/src/features/mod.rs", presentation_path),
+            format!("{}-domain/src/features/mod.rs", project),
+            format!("{}-data/src/features/mod.rs", project),
+            format!("{}-domain/Cargo.toml", project),
+            format!("{}-data/Cargo.toml", project),
+            String::from("Cargo.toml"),
+        ]);
+        let before = self.contents(paths).await;
+
+        let cli_update_future = self.repository.update_cli(
+            &presentation_path,
             name,
-            cli_file_content.as_bytes(),
-            cli_parent_mod_file_content.as_bytes(),
+            files.presentation.content.as_bytes(),
+            parent_mod_file_content.as_bytes(),
         );
         let domain_update_future = self.repository.update_domain(
             project,
             name,
-            entities_file_content.as_bytes(),
-            domain_mod_file_content.as_bytes(),
-            repository_file_content.as_bytes(),
-            usecase_file_content.as_bytes(),
-            domain_parent_mod_file_content.as_bytes(),
+            files.entities.content.as_bytes(),
+            files.domain_mod.content.as_bytes(),
+            files.repository.content.as_bytes(),
+            files.usecase.content.as_bytes(),
+            parent_mod_file_content.as_bytes(),
         );
         let data_update_future = self.repository.update_data(
             project,
             name,
-            datasource_file_content.as_bytes(),
-            data_mod_file_content.as_bytes(),
-            models_file_content.as_bytes(),
-            data_parent_mod_file_content.as_bytes(),
+            files.datasource.content.as_bytes(),
+            files.data_mod.content.as_bytes(),
+            files.models.content.as_bytes(),
+            parent_mod_file_content.as_bytes(),
         );
 
         // let (cli_update_result, domain_update_response, data_update_response) =
         let result = try_join!(cli_update_future, domain_update_future, data_update_future);
 
+        let result = match result {
+            Ok(_) => {
+                let path = format!("{}-domain/src/features/{}/fakes.rs", project, name);
+                self.repository
+                    .write_file(&path, files.fakes.content.as_bytes())
+                    .await
+            }
+            Err(err) => Err(err),
+        };
+
+        let result = match result {
+            Ok(_) => self.update_manifests(project, datasource, runtime).await,
+            Err(err) => Err(err),
+        };
+
+        let mut report = Report::success(format!("Successfully added feature: {}", name));
+
+        let result = match result {
+            Ok(_) => {
+                self.update_presentations(project, name, runtime, &mut report)
+                    .await
+            }
+            Err(err) => Err(err),
+        };
+
+        let result = match result {
+            Ok(_) => self.update_layers(project, name, &mut report).await,
+            Err(err) => Err(err),
+        };
+
+        let result = match result {
+            Ok(_) => {
+                let feature = FeatureRecord {
+                    name: name.to_string(),
+                    datasource,
+                    usecases: vec![],
+                };
+                self.record_feature(project, &presentation_path, &feature, &files)
+                    .await
+            }
+            Err(err) => Err(err),
+        };
+        self.report_changed(before, &mut report).await;
+
+        match result {
+            Ok(_) => return self.with_wasm_warnings(project, report).await,
+            Err(err) => {
+                return report.failed(
+                    ErrorCode::Failed,
+                    format!("Failed to add feature: {} - {:?}", name, err),
+                )
+            }
+        }
+    }
+
+    async fn add_usecase(&self, feature: &str, name: &str) -> Report {
+        let project = current_project();
+
+        if !in_root_directory(&project).await {
+            return Report::not_in_project();
+        }
+
+        if !name
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_')
+        {
+            return Report::failure(
+                ErrorCode::InvalidArgument,
+                format!("Invalid usecase name: {}", name),
+            );
+        }
+
+        let runtime = match metadata_setting(self.repository.read_metadata("runtime").await) {
+            Ok(runtime) => runtime,
+            Err(err) => {
+                return Report::failure(
+                    ErrorCode::Failed,
+                    format!("Failed to add usecase: {}::{} - {}", feature, name, err),
+                )
+            }
+        };
+
+        let feature_title_case = titlecase(feature);
+        let directory = format!("{}-domain/src/features/{}", project, feature);
+        let failure = |err: String| {
+            Report::failure(
+                ErrorCode::Failed,
+                format!("Failed to add usecase: {}::{} - {}", feature, name, err),
+            )
+        };
+
+        // The features as presentations see them, to tell their files apart from edits
+        let before = match self.presentations().await {
+            Ok(presentations) if presentations.is_empty() => Ok(vec![]),
+            Ok(_) => self.presentation_features(&project).await,
+            Err(err) => Err(err),
+        };
+        let before = match before {
+            Ok(features) => features,
+            Err(err) => return failure(err.to_string()),
+        };
+
+        // Every file is updated in memory first, then restored if one can't be written, so
+        // a failure leaves the feature untouched
+        let mut files = Vec::new();
+        for file in UsecaseFile::ALL {
+            let path = format!("{}/{}", directory, file.file_name());
+            let original = match self.repository.read_file(&path).await {
+                Ok(content) => content,
+                Err(err) => return failure(err.to_string()),
+            };
+            let updated = match file.with_method(&original, name, &feature_title_case, runtime) {
+                Ok(content) => content,
+                Err(err) => return failure(format!("{} in {}", err, path)),
+            };
+            files.push((path, original, updated));
+        }
+
+        if let Err(err) = self.write_or_restore(&files).await {
+            return failure(err.to_string());
+        }
+
+        let mut report =
+            Report::success(format!("Successfully added usecase: {}::{}", feature, name));
+        for (path, _, _) in &files {
+            report.written(path, true);
+        }
+
+        let result = self.record_usecase(&project, feature, name).await;
+
+        let result = match result {
+            Ok(_) => {
+                self.update_presentation_usecases(&project, feature, &before, runtime, &mut report)
+                    .await
+            }
+            Err(err) => Err(err),
+        };
+
+        match result {
+            Ok(_) => report,
+            Err(err) => report.failed(
+                ErrorCode::Failed,
+                format!("Failed to add usecase: {}::{} - {}", feature, name, err),
+            ),
+        }
+    }
+
+    async fn add_presentation(&self, presentation: Presentation) -> Report {
+        let project = current_project();
+        let project = project.as_str();
+        let name = presentation.name();
+
+        if !in_root_directory(project).await {
+            return Report::not_in_project();
+        }
+
+        let runtime = match metadata_setting(self.repository.read_metadata("runtime").await) {
+            Ok(runtime) => runtime,
+            Err(err) => {
+                return Report::failure(
+                    ErrorCode::Failed,
+                    format!("Failed to add presentation: {} - {}", name, err),
+                )
+            }
+        };
+
+        match self.presentations().await {
+            Ok(presentations) if presentations.contains(&presentation) => {
+                return Report::failure(
+                    ErrorCode::AlreadyExists,
+                    format!("Presentation already exists: {}", name),
+                )
+            }
+            Ok(_) => (),
+            Err(err) => {
+                return Report::failure(
+                    ErrorCode::Failed,
+                    format!("Failed to add presentation: {} - {}", name, err),
+                )
+            }
+        }
+
+        let before = self.contents(vec![String::from("Cargo.toml")]).await;
+        let files = match self.presentation_features(project).await {
+            Ok(features) => presentation
+                .files(project, &features, runtime)
+                .map_err(|err| io::Error::other(err.to_string())),
+            Err(err) => Err(err),
+        };
+
+        let mut report = Report::success(format!("Successfully added presentation: {}", name));
+        let result = match files {
+            Ok(files) => self.write_generated(&files, &mut report).await,
+            Err(err) => Err(err),
+        };
+
+        let result = match result {
+            Ok(_) => {
+                self.repository
+                    .add_workspace_member(&presentation.crate_name(project))
+                    .await
+            }
+            Err(err) => Err(err),
+        };
+
+        let result = match result {
+            Ok(_) => {
+                self.repository
+                    .add_metadata_item("presentations", name)
+                    .await
+            }
+            Err(err) => Err(err),
+        };
+
+        let result = match result {
+            Ok(_) => self.update_workflow(project, &mut report).await,
+            Err(err) => Err(err),
+        };
+        self.report_changed(before, &mut report).await;
+
+        match result {
+            Ok(_) => self.with_wasm_warnings(project, report).await,
+            Err(err) => report.failed(
+                ErrorCode::Failed,
+                format!("Failed to add presentation: {} - {}", name, err),
+            ),
+        }
+    }
+
+    async fn add_layer(&self, layer: Layer) -> Report {
+        let project = current_project();
+        let project = project.as_str();
+        let name = layer.name.as_str();
+
+        if !in_root_directory(project).await {
+            return Report::not_in_project();
+        }
+
+        let is_valid = |name: &str, separators: &[char]| {
+            !name.is_empty()
+                && name.chars().all(|c| {
+                    c.is_ascii_lowercase() || c.is_ascii_digit() || separators.contains(&c)
+                })
+        };
+        if !is_valid(name, &['_']) || !is_valid(&layer.suffix, &['_', '-']) {
+            return Report::failure(
+                ErrorCode::InvalidArgument,
+                format!("Invalid layer name: {}", name),
+            );
+        }
+        if let Some(file) = layer.files.iter().find(|file| !is_valid(file, &['_'])) {
+            return Report::failure(
+                ErrorCode::InvalidArgument,
+                format!("Invalid layer file name: {}", file),
+            );
+        }
+
+        let runtime = metadata_setting(self.repository.read_metadata("runtime").await);
+        let kind = metadata_setting(self.repository.read_metadata("kind").await);
+        let (runtime, kind): (Runtime, ProjectKind) =
+            match runtime.and_then(|runtime| kind.map(|kind| (runtime, kind))) {
+                Ok(settings) => settings,
+                Err(err) => {
+                    return Report::failure(
+                        ErrorCode::Failed,
+                        format!("Failed to add layer: {} - {}", name, err),
+                    )
+                }
+            };
+
+        let mut layers = Layer::defaults(kind);
+        match self.repository.read_layers().await {
+            Ok(configured) => layers.extend(configured),
+            Err(err) => {
+                return Report::failure(
+                    ErrorCode::Failed,
+                    format!("Failed to add layer: {} - {}", name, err),
+                )
+            }
+        }
+
+        if layers.iter().any(|existing| {
+            existing.name == layer.name || existing.crate_name(project) == layer.crate_name(project)
+        }) {
+            return Report::failure(
+                ErrorCode::AlreadyExists,
+                format!("Layer already exists: {}", name),
+            );
+        }
+
+        let mut dependencies = match layer.dependencies(project, &layers) {
+            Ok(dependencies) => dependencies,
+            Err(err) => {
+                return Report::failure(
+                    ErrorCode::Failed,
+                    format!("Failed to add layer: {} - {}", name, err),
+                )
+            }
+        };
+        let runtime_dependencies = match layer.kind {
+            ProjectKind::Binary => runtime.main_dependencies(),
+            ProjectKind::Library => runtime.dependencies(),
+        };
+        dependencies.extend(
+            runtime_dependencies
+                .into_iter()
+                .map(|(name, value)| (name.to_string(), value.to_string())),
+        );
+        let dependencies = dependencies
+            .iter()
+            .map(|(name, value)| (name.as_str(), value.as_str()))
+            .collect::<Vec<_>>();
+
+        let before = self.contents(vec![String::from("Cargo.toml")]).await;
+        let files = match self.feature_names(project).await {
+            Ok(features) => {
+                let features = features.iter().map(String::as_str).collect::<Vec<_>>();
+                layer
+                    .files(project, &features, runtime)
+                    .map_err(|err| io::Error::other(err.to_string()))
+            }
+            Err(err) => Err(err),
+        };
+
+        let mut report = Report::success(format!("Successfully added layer: {}", name));
+        let result = match files {
+            Ok(files) => {
+                // The manifest gets the layer's dependencies once written, it isn't left as
+                // rendered so it isn't stamped
+                let (manifest, files): (Vec<_>, Vec<_>) = files
+                    .into_iter()
+                    .partition(|(path, _)| path.ends_with("/Cargo.toml"));
+                let manifest = manifest
+                    .into_iter()
+                    .map(|(path, rendered)| (path, rendered.content))
+                    .collect::<Vec<_>>();
+
+                match self.write_files(&manifest, &mut report).await {
+                    Ok(_) => self.write_generated(&files, &mut report).await,
+                    Err(err) => Err(err),
+                }
+            }
+            Err(err) => Err(err),
+        };
+
+        let crate_name = layer.crate_name(project);
+        let result = match result {
+            Ok(_) => {
+                self.repository
+                    .update_manifest(&crate_name, &dependencies, &[])
+                    .await
+            }
+            Err(err) => Err(err),
+        };
+
+        let result = match result {
+            Ok(_) => self.repository.add_workspace_member(&crate_name).await,
+            Err(err) => Err(err),
+        };
+
+        let result = match result {
+            Ok(_) => self.repository.add_layer(&layer).await,
+            Err(err) => Err(err),
+        };
+
+        let result = match result {
+            Ok(_) => self.update_workflow(project, &mut report).await,
+            Err(err) => Err(err),
+        };
+        self.report_changed(before, &mut report).await;
+
+        match result {
+            Ok(_) => report,
+            Err(err) => report.failed(
+                ErrorCode::Failed,
+                format!("Failed to add layer: {} - {}", name, err),
+            ),
+        }
+    }
+
+    async fn add_fields(&self, feature: &str, fields: &[(String, String)]) -> Report {
+        let project = current_project();
+
+        if !in_root_directory(&project).await {
+            return Report::not_in_project();
+        }
+
+        let is_valid = |name: &str| {
+            name.starts_with(|c: char| c.is_ascii_lowercase())
+                && name
+                    .chars()
+                    .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_')
+        };
+        if let Some((name, _)) = fields.iter().find(|(name, _)| !is_valid(name)) {
+            return Report::failure(
+                ErrorCode::InvalidArgument,
+                format!("Invalid field name: {}", name),
+            );
+        }
+        if let Some((name, _)) = fields.iter().find(|(_, ty)| ty.trim().is_empty()) {
+            return Report::failure(
+                ErrorCode::InvalidArgument,
+                format!("Missing type of field: {}", name),
+            );
+        }
+
+        let feature_title_case = titlecase(feature);
+        let model_name = format!("{}Model", feature_title_case);
+        let entities_path = format!("{}-domain/src/features/{}/entities.rs", project, feature);
+        let models_path = format!("{}-data/src/features/{}/models.rs", project, feature);
+        let (original_entities, original_models) = match try_join!(
+            self.repository.read_file(&entities_path),
+            self.repository.read_file(&models_path),
+        ) {
+            Ok(contents) => contents,
+            Err(err) => {
+                return Report::failure(
+                    ErrorCode::Failed,
+                    format!("Failed to add fields: {} - {}", feature, err),
+                )
+            }
+        };
+
+        let before =
+            ConvertedFields::new(&original_entities, &original_models, &feature_title_case);
+        let existing = [
+            (&feature_title_case, &before.entity),
+            (&model_name, &before.model),
+        ];
+        for (name, _) in fields {
+            if let Some((owner, _)) = existing.iter().find(|(_, names)| names.contains(name)) {
+                return Report::failure(
+                    ErrorCode::AlreadyExists,
+                    format!("Field already exists: {}::{}", owner, name),
+                );
+            }
+        }
+
+        let (entities, models) = match (
+            with_fields(&original_entities, &feature_title_case, fields),
+            with_fields(&original_models, &model_name, fields),
+        ) {
+            (Some(entities), Some(models)) => (entities, models),
+            (None, _) | (_, None) => {
+                return Report::failure(
+                    ErrorCode::Failed,
+                    format!(
+                        "Failed to add fields: {} - missing struct {} or {}",
+                        feature, feature_title_case, model_name
+                    ),
+                )
+            }
+        };
+
+        let mut report = Report::success(format!(
+            "Successfully added fields to entity: {} ({})",
+            feature_title_case,
+            fields
+                .iter()
+                .map(|(name, _)| name.as_str())
+                .collect::<Vec<_>>()
+                .join(", ")
+        ));
+
+        let after = ConvertedFields::new(&entities, &models, &feature_title_case);
+        let entities = match with_conversions(&entities, &feature_title_case, &before, &after) {
+            Some(converted) => converted,
+            None => {
+                report.warnings.push(format!(
+                    "the conversions between {} and {} in {} were changed, map the new fields in them",
+                    feature_title_case, model_name, entities_path
+                ));
+                entities
+            }
+        };
+
+        // Written one at a time, so the entity is restored if its model can't be written
+        let result = match self
+            .repository
+            .write_file(&entities_path, entities.as_bytes())
+            .await
+        {
+            Ok(_) => match self
+                .repository
+                .write_file(&models_path, models.as_bytes())
+                .await
+            {
+                Ok(_) => Ok(()),
+                Err(err) => {
+                    let _ = self
+                        .repository
+                        .write_file(&entities_path, original_entities.as_bytes())
+                        .await;
+                    Err(err)
+                }
+            },
+            Err(err) => Err(err),
+        };
+
         match result {
-            Ok(_) => return format!("Successfully added feature: {}", name),
-            Err(err) => return format!("Failed to add feature: {} - {:?}", name, err),
+            Ok(_) => {
+                report.written(&entities_path, true);
+                report.written(&models_path, true);
+                report
+            }
+            Err(err) => Report::failure(
+                ErrorCode::Failed,
+                format!("Failed to add fields: {} - {}", feature, err),
+            ),
         }
     }
 }
@@ -114,7 +1243,6 @@ mod tests {
     use super::*;
 
     use async_trait::async_trait;
-    use std::io;
 
     struct MockRepository {}
 
@@ -122,7 +1250,7 @@ mod tests {
     impl Repository for MockRepository {
         async fn update_cli(
             &self,
-            _project: &str,
+            _crate_path: &str,
             _name: &str,
             _cli_file_content: &[u8],
             _parent_mod_file_content: &[u8],
@@ -132,7 +1260,7 @@ mod tests {
 
         async fn update_domain(
             &self,
-            _project: &str,
+            _crate_path: &str,
             _name: &str,
             _entities_file_content: &[u8],
             _mod_file_content: &[u8],
@@ -145,7 +1273,7 @@ mod tests {
 
         async fn update_data(
             &self,
-            _project: &str,
+            _crate_path: &str,
             _name: &str,
             _datasource_file_content: &[u8],
             _mod_file_content: &[u8],
@@ -154,6 +1282,79 @@ mod tests {
         ) -> io::Result<()> {
             Ok(())
         }
+
+        async fn update_manifest(
+            &self,
+            _crate_name: &str,
+            _dependencies: &[(&str, &str)],
+            _dev_dependencies: &[(&str, &str)],
+        ) -> io::Result<()> {
+            Ok(())
+        }
+
+        async fn exists(&self, _path: &str) -> bool {
+            false
+        }
+
+        async fn read_file(&self, _path: &str) -> io::Result<String> {
+            Ok(String::new())
+        }
+
+        async fn write_file(&self, _path: &str, _content: &[u8]) -> io::Result<()> {
+            Ok(())
+        }
+
+        async fn read_metadata(&self, _key: &str) -> io::Result<Option<String>> {
+            Ok(None)
+        }
+
+        async fn read_metadata_list(&self, _key: &str) -> io::Result<Vec<String>> {
+            Ok(vec![])
+        }
+
+        async fn add_metadata_item(&self, _key: &str, _value: &str) -> io::Result<()> {
+            Ok(())
+        }
+
+        async fn add_workspace_member(&self, _member: &str) -> io::Result<()> {
+            Ok(())
+        }
+
+        async fn read_layers(&self) -> io::Result<Vec<Layer>> {
+            Ok(vec![])
+        }
+
+        async fn add_layer(&self, _layer: &Layer) -> io::Result<()> {
+            Ok(())
+        }
+
+        async fn read_features(&self) -> io::Result<Vec<FeatureRecord>> {
+            Ok(vec![])
+        }
+
+        async fn record_feature(&self, _feature: &FeatureRecord) -> io::Result<()> {
+            Ok(())
+        }
+
+        async fn read_stamps(&self) -> io::Result<Vec<Stamp>> {
+            Ok(vec![])
+        }
+
+        async fn record_stamps(&self, _stamps: &[Stamp]) -> io::Result<()> {
+            Ok(())
+        }
+
+        async fn create_directory(&self, _path: &str) -> io::Result<()> {
+            Ok(())
+        }
+
+        async fn list_directories(&self, _path: &str) -> io::Result<Vec<String>> {
+            Ok(vec![])
+        }
+
+        async fn list_files(&self, _path: &str) -> io::Result<Vec<String>> {
+            Ok(vec![])
+        }
     }
 
     #[async_std::test]
@@ -162,8 +1363,150 @@ mod tests {
         let usecase = AddUsecase { repository };
 
         let expect = String::from("Not currently in the root of a project");
-        let got = usecase.add_feature("test").await;
+        let got = usecase.add_feature("test", DatasourceKind::Empty).await;
+
+        assert_eq!(expect, got.to_string())
+    }
+
+    #[async_std::test]
+    async fn test_add_usecase() {
+        let repository = Box::new(MockRepository {});
+        let usecase = AddUsecase { repository };
+
+        let expect = String::from("Not currently in the root of a project");
+        let got = usecase.add_usecase("test", "archive").await;
+
+        assert_eq!(expect, got.to_string())
+    }
+
+    #[async_std::test]
+    async fn test_add_presentation() {
+        let repository = Box::new(MockRepository {});
+        let usecase = AddUsecase { repository };
+
+        let expect = String::from("Not currently in the root of a project");
+        let got = usecase.add_presentation("web".parse().unwrap()).await;
+
+        assert_eq!(expect, got.to_string())
+    }
+
+    #[async_std::test]
+    async fn test_add_layer() {
+        let repository = Box::new(MockRepository {});
+        let usecase = AddUsecase { repository };
+        let layer = Layer {
+            name: String::from("infrastructure"),
+            suffix: String::from("infrastructure"),
+            kind: ProjectKind::Library,
+            depends_on: vec![String::from("domain")],
+            files: vec![],
+        };
+
+        let expect = String::from("Not currently in the root of a project");
+        let got = usecase.add_layer(layer).await;
+
+        assert_eq!(expect, got.to_string())
+    }
+
+    #[test]
+    fn test_with_fields() {
+        let content =
+            crate::core::templates::entities::EntitiesTemplate::new("notes", "Notes", "test")
+                .render()
+                .unwrap();
+        let fields = [
+            (String::from("title"), String::from("String")),
+            (String::from("done"), String::from("bool")),
+        ];
+
+        let got = with_fields(&content, "Notes", &fields[..1]).unwrap();
+        let got = with_fields(&got, "Notes", &fields[1..]).unwrap();
+
+        assert!(got.contains("pub struct Notes {\n    pub title: String,\n    pub done: bool,\n}"));
+        assert_eq!(None, with_fields(&content, "Tags", &fields));
+    }
+
+    #[test]
+    fn test_with_conversions() {
+        let entities =
+            crate::core::templates::entities::EntitiesTemplate::new("notes", "Notes", "test")
+                .render()
+                .unwrap();
+        let models = crate::core::templates::models::ModelsTemplate::new("Notes", false)
+            .render()
+            .unwrap();
+        let title = [(String::from("title"), String::from("String"))];
+        let done = [(String::from("done"), String::from("bool"))];
+
+        let before = ConvertedFields::new(&entities, &models, "Notes");
+        let entities = with_fields(&entities, "Notes", &title).unwrap();
+        let models = with_fields(&models, "NotesModel", &title).unwrap();
+        let after = ConvertedFields::new(&entities, &models, "Notes");
+        let got = with_conversions(&entities, "Notes", &before, &after).unwrap();
+
+        assert!(got.contains(
+            "fn from(model: NotesModel) -> Self {\n        Self { title: model.title }\n    }"
+        ));
+        assert!(got.contains(
+            "fn from(entity: Notes) -> Self {
+        Self {
+            title: entity.title,
+        }
+    }"
+        ));
+
+        let before = after;
+        let entities = with_fields(&got, "Notes", &done).unwrap();
+        let models = with_fields(&models, "NotesModel", &done).unwrap();
+        let after = ConvertedFields::new(&entities, &models, "Notes");
+        let got = with_conversions(&entities, "Notes", &before, &after).unwrap();
+
+        assert!(got.contains(
+            "fn from(model: NotesModel) -> Self {
+        Self {
+            title: model.title,
+            done: model.done,
+        }
+    }"
+        ));
+
+        // Conversions changed since they were generated are left alone
+        let changed = got.replace("done: model.done", "done: !model.done");
+        assert_eq!(None, with_conversions(&changed, "Notes", &after, &after));
+    }
+
+    #[test]
+    fn test_conversion_defaults_missing_fields() {
+        assert_eq!(
+            "impl From<NotesModel> for Notes {
+    fn from(model: NotesModel) -> Self {
+        Self {
+            title: model.title,
+            ..Self::default()
+        }
+    }
+}",
+            conversion("NotesModel", "Notes", "model", &["title"], false)
+        );
+        assert_eq!(
+            "impl From<NotesModel> for Notes {
+    fn from(_model: NotesModel) -> Self {
+        Self::default()
+    }
+}",
+            conversion("NotesModel", "Notes", "model", &[], false)
+        );
+    }
+
+    #[async_std::test]
+    async fn test_add_fields() {
+        let repository = Box::new(MockRepository {});
+        let usecase = AddUsecase { repository };
+        let fields = [(String::from("title"), String::from("String"))];
+
+        let expect = String::from("Not currently in the root of a project");
+        let got = usecase.add_fields("test", &fields).await;
 
-        assert_eq!(expect, got)
+        assert_eq!(expect, got.to_string())
     }
 }
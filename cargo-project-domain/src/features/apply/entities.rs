@@ -0,0 +1,257 @@
+use std::convert::TryFrom;
+
+use cargo_project_data::features::apply::models::{
+    FeatureSpecModel, ProjectSpecModel, SnapshotModel, SpecModel,
+};
+
+use crate::core::project::ProjectKind;
+use crate::core::runtime::Runtime;
+use crate::features::add::entities::{DatasourceKind, FileFormat};
+
+/// A feature listed in a spec along with what it should contain.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FeatureSpec {
+    pub name: String,
+    pub datasource: DatasourceKind,
+    pub usecases: Vec<String>,
+    /// Fields of the feature's entity, as `(name, type)` pairs.
+    pub fields: Vec<(String, String)>,
+}
+
+/// A validated spec. Runtime and kind are only set when the spec states them.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Spec {
+    pub name: String,
+    pub runtime: Option<Runtime>,
+    pub kind: Option<ProjectKind>,
+    pub features: Vec<FeatureSpec>,
+}
+
+/// Directories of a project before a spec is applied to it, along with the content of the
+/// files its steps may change, to undo them when applying fails part way.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Snapshot {
+    pub directories: Vec<String>,
+    pub files: Vec<(String, Vec<u8>)>,
+}
+
+fn is_snake_case(name: &str) -> bool {
+    name.starts_with(|character: char| character.is_ascii_lowercase())
+        && name.chars().all(|character| {
+            character.is_ascii_lowercase() || character.is_ascii_digit() || character == '_'
+        })
+}
+
+/// Whether `name` can name the project, whose directory is created in the current one: a
+/// crate name, so a single path segment.
+fn is_crate_name(name: &str) -> bool {
+    name.starts_with(|character: char| character.is_ascii_lowercase())
+        && name.chars().all(|character| {
+            character.is_ascii_lowercase()
+                || character.is_ascii_digit()
+                || character == '_'
+                || character == '-'
+        })
+}
+
+/// Checks every name is a valid, unique snake case identifier.
+fn validate_names<'a>(
+    names: impl Iterator<Item = &'a String>,
+    what: &str,
+    prefix: &str,
+) -> Result<(), String> {
+    let mut seen = Vec::new();
+
+    for name in names {
+        if !is_snake_case(name) {
+            return Err(format!("Invalid {} name: {}{}", what, prefix, name));
+        }
+        if seen.contains(&name) {
+            return Err(format!("Duplicate {}: {}{}", what, prefix, name));
+        }
+        seen.push(name);
+    }
+
+    Ok(())
+}
+
+impl TryFrom<FeatureSpecModel> for FeatureSpec {
+    type Error = String;
+
+    fn try_from(model: FeatureSpecModel) -> Result<Self, Self::Error> {
+        let prefix = format!("{}::", model.name);
+        validate_names(model.usecases.iter(), "usecase", &prefix)?;
+        validate_names(model.entity.iter().map(|(name, _)| name), "field", &prefix)?;
+
+        if let Some((name, _)) = model.entity.iter().find(|(_, ty)| ty.trim().is_empty()) {
+            return Err(format!("Missing type of field: {}{}", prefix, name));
+        }
+
+        let format = match &model.format {
+            Some(format) => format.parse()?,
+            None => FileFormat::Json,
+        };
+        let datasource =
+            DatasourceKind::new(model.datasource.as_deref().unwrap_or("empty"), format)?;
+
+        Ok(Self {
+            name: model.name,
+            datasource,
+            usecases: model.usecases,
+            fields: model.entity,
+        })
+    }
+}
+
+impl TryFrom<SpecModel> for Spec {
+    type Error = String;
+
+    fn try_from(model: SpecModel) -> Result<Self, Self::Error> {
+        if model.project.name.is_empty() {
+            return Err(String::from("Missing project name"));
+        }
+        if !is_crate_name(&model.project.name) {
+            return Err(format!("Invalid project name: {}", model.project.name));
+        }
+        validate_names(
+            model.features.iter().map(|feature| &feature.name),
+            "feature",
+            "",
+        )?;
+
+        Ok(Self {
+            name: model.project.name,
+            runtime: model
+                .project
+                .runtime
+                .map(|runtime| runtime.parse())
+                .transpose()?,
+            kind: model.project.kind.map(|kind| kind.parse()).transpose()?,
+            features: model
+                .features
+                .into_iter()
+                .map(FeatureSpec::try_from)
+                .collect::<Result<_, _>>()?,
+        })
+    }
+}
+
+impl From<&FeatureSpec> for FeatureSpecModel {
+    fn from(feature: &FeatureSpec) -> Self {
+        Self {
+            name: feature.name.clone(),
+            datasource: Some(feature.datasource.name().to_string()),
+            format: match feature.datasource {
+                DatasourceKind::File(format) => Some(format.extension().to_string()),
+                DatasourceKind::Empty => None,
+            },
+            usecases: feature.usecases.clone(),
+            entity: feature.fields.clone(),
+        }
+    }
+}
+
+impl From<SnapshotModel> for Snapshot {
+    fn from(model: SnapshotModel) -> Self {
+        Self {
+            directories: model.directories,
+            files: model.files,
+        }
+    }
+}
+
+impl From<&Spec> for SpecModel {
+    fn from(spec: &Spec) -> Self {
+        Self {
+            project: ProjectSpecModel {
+                name: spec.name.clone(),
+                runtime: spec.runtime.map(|runtime| runtime.name().to_string()),
+                kind: spec.kind.map(|kind| kind.name().to_string()),
+            },
+            features: spec.features.iter().map(FeatureSpecModel::from).collect(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn model(features: Vec<FeatureSpecModel>) -> SpecModel {
+        SpecModel {
+            project: ProjectSpecModel {
+                name: String::from("demo"),
+                runtime: Some(String::from("sync")),
+                kind: None,
+            },
+            features,
+        }
+    }
+
+    fn feature(name: &str, usecases: &[&str]) -> FeatureSpecModel {
+        FeatureSpecModel {
+            name: name.to_string(),
+            datasource: Some(String::from("file")),
+            format: Some(String::from("toml")),
+            usecases: usecases.iter().map(|name| name.to_string()).collect(),
+            entity: vec![(String::from("title"), String::from("String"))],
+        }
+    }
+
+    #[test]
+    fn test_spec_conversion() {
+        let got = Spec::try_from(model(vec![feature("notes", &["archive"])])).unwrap();
+
+        assert_eq!(
+            Spec {
+                name: String::from("demo"),
+                runtime: Some(Runtime::Sync),
+                kind: None,
+                features: vec![FeatureSpec {
+                    name: String::from("notes"),
+                    datasource: DatasourceKind::File(FileFormat::Toml),
+                    usecases: vec![String::from("archive")],
+                    fields: vec![(String::from("title"), String::from("String"))],
+                }],
+            },
+            got
+        );
+    }
+
+    #[test]
+    fn test_spec_model() {
+        let model = model(vec![feature("notes", &["archive"])]);
+        let spec = Spec::try_from(model.clone()).unwrap();
+
+        assert_eq!(model, SpecModel::from(&spec));
+    }
+
+    #[test]
+    fn test_spec_validation() {
+        assert_eq!(
+            Err(String::from("Invalid feature name: Notes")),
+            Spec::try_from(model(vec![feature("Notes", &[])]))
+        );
+        assert_eq!(
+            Err(String::from("Duplicate usecase: notes::archive")),
+            Spec::try_from(model(vec![feature("notes", &["archive", "archive"])]))
+        );
+        assert_eq!(
+            Err(String::from("Duplicate feature: notes")),
+            Spec::try_from(model(vec![feature("notes", &[]), feature("notes", &[])]))
+        );
+
+        for name in [".", "..", "/", "../demo", "/tmp/demo", "demo/app", "Demo"] {
+            let mut model = model(vec![]);
+            model.project.name = name.to_string();
+
+            assert_eq!(
+                Err(format!("Invalid project name: {}", name)),
+                Spec::try_from(model)
+            );
+        }
+        let mut model = model(vec![]);
+        model.project.name = String::from("my-app_2");
+        assert!(Spec::try_from(model).is_ok());
+    }
+}
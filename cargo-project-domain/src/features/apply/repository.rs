@@ -0,0 +1,89 @@
+use async_trait::async_trait;
+use cargo_project_data::features::apply::datasource::{ApplyDatasource, Datasource};
+use std::convert::TryFrom;
+use std::io;
+
+use super::entities::{Snapshot, Spec};
+
+#[async_trait]
+pub trait Repository {
+    async fn read_spec(&self, path: &str) -> io::Result<Spec>;
+    async fn exists(&self, path: &str) -> bool;
+    async fn read_file(&self, path: &str) -> io::Result<String>;
+    async fn read_metadata(&self, key: &str) -> io::Result<Option<String>>;
+    async fn enter_directory(&self, path: &str) -> io::Result<()>;
+    async fn list_files(&self, path: &str) -> io::Result<Vec<String>>;
+    async fn read_snapshot(&self, path: &str, files: &[String]) -> io::Result<Snapshot>;
+    async fn write_file(&self, path: &str, content: &[u8]) -> io::Result<()>;
+    async fn remove_file(&self, path: &str) -> io::Result<()>;
+    async fn remove_empty_directory(&self, path: &str) -> io::Result<()>;
+    async fn remove_directory(&self, path: &str) -> io::Result<()>;
+}
+
+pub struct ApplyRepository {
+    datasource: Box<dyn Datasource + Send + Sync>,
+}
+
+impl ApplyRepository {
+    pub fn new() -> Self {
+        let datasource = Box::new(ApplyDatasource::new());
+        Self { datasource }
+    }
+}
+
+impl Default for ApplyRepository {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl Repository for ApplyRepository {
+    async fn read_spec(&self, path: &str) -> io::Result<Spec> {
+        let model = self.datasource.read_spec(path).await?;
+
+        Spec::try_from(model).map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))
+    }
+
+    async fn exists(&self, path: &str) -> bool {
+        self.datasource.exists(path).await
+    }
+
+    async fn read_file(&self, path: &str) -> io::Result<String> {
+        self.datasource.read_file(path).await
+    }
+
+    async fn read_metadata(&self, key: &str) -> io::Result<Option<String>> {
+        self.datasource.read_metadata(key).await
+    }
+
+    async fn enter_directory(&self, path: &str) -> io::Result<()> {
+        self.datasource.enter_directory(path).await
+    }
+
+    async fn list_files(&self, path: &str) -> io::Result<Vec<String>> {
+        self.datasource.list_files(path).await
+    }
+
+    async fn read_snapshot(&self, path: &str, files: &[String]) -> io::Result<Snapshot> {
+        let model = self.datasource.read_snapshot(path, files).await?;
+
+        Ok(Snapshot::from(model))
+    }
+
+    async fn write_file(&self, path: &str, content: &[u8]) -> io::Result<()> {
+        self.datasource.write_file(path, content).await
+    }
+
+    async fn remove_file(&self, path: &str) -> io::Result<()> {
+        self.datasource.remove_file(path).await
+    }
+
+    async fn remove_empty_directory(&self, path: &str) -> io::Result<()> {
+        self.datasource.remove_empty_directory(path).await
+    }
+
+    async fn remove_directory(&self, path: &str) -> io::Result<()> {
+        self.datasource.remove_directory(path).await
+    }
+}
@@ -0,0 +1,470 @@
+use std::env;
+use std::io;
+
+use async_trait::async_trait;
+use titlecase::titlecase;
+
+use super::entities::{FeatureSpec, Snapshot, Spec};
+use super::repository::{ApplyRepository, Repository};
+
+use crate::core::project::{metadata_setting, ProjectKind};
+use crate::core::report::{ErrorCode, Report};
+use crate::core::runtime::Runtime;
+use crate::core::source::{struct_fields, usecase_methods};
+use crate::core::utils::current_project;
+use crate::features::add::usecase::{AddUsecase, Usecase as _};
+use crate::features::new::usecase::{NewUsecase, Usecase as _};
+
+#[async_trait]
+pub trait Usecase {
+    async fn apply_spec(&self, path: &str) -> Report;
+}
+
+/// Creates the project described by a spec file and whatever it lists that the project
+/// is missing, through the same usecases as `new` and `add`.
+pub struct ApplyUsecase {
+    repository: Box<dyn Repository + Send + Sync>,
+}
+
+/// How applying a spec is undone when one of its steps fails.
+enum Rollback {
+    /// Undoes what the steps did to the existing project at `root`, `snapshot` holding the
+    /// content of the files they may change.
+    Undo { root: String, snapshot: Snapshot },
+    /// Removes the project created at `root`, from the directory it was created in.
+    Remove { parent: String, root: String },
+}
+
+/// Whether the file at `path`, relative to the project root, is bookkeeping the steps of a
+/// spec may change without reporting it, like the features and stamps they record.
+fn is_bookkeeping(path: &str) -> bool {
+    path == "Cargo.toml" || path.starts_with(".cargo-project/")
+}
+
+/// Whether a step of a spec may change the existing file at `path`: the manifests, feature
+/// modules and CI workflow the usecases update, and their bookkeeping.
+fn may_change(path: &str) -> bool {
+    is_bookkeeping(path)
+        || path.ends_with("/Cargo.toml")
+        || path.starts_with("src/features/")
+        || path.contains("/src/features/")
+        || path == ".github/workflows/ci.yml"
+}
+
+/// Adds what the usecase run for a step did to the `report`, passing on its failure.
+fn merged(report: &mut Report, step: Report) -> Result<(), Box<Report>> {
+    report.merge(&step);
+
+    match step.success {
+        true => Ok(()),
+        false => Err(Box::new(step)),
+    }
+}
+
+fn failed(err: impl ToString) -> Box<Report> {
+    Box::new(Report::failure(ErrorCode::Failed, err.to_string()))
+}
+
+impl ApplyUsecase {
+    pub fn new() -> Self {
+        let repository = Box::new(ApplyRepository::new());
+        Self { repository }
+    }
+
+    /// Reads what's needed to undo applying the spec, before anything is changed.
+    async fn prepare_rollback(&self, spec: &Spec) -> Result<Rollback, Box<Report>> {
+        let current_dir = env::current_dir().map_err(failed)?;
+        let root =
+            match current_project() == spec.name && self.repository.exists("Cargo.toml").await {
+                true => current_dir.clone(),
+                false => current_dir.join(&spec.name),
+            };
+        let root = root.display().to_string();
+
+        if !self.repository.exists(&root).await {
+            return Ok(Rollback::Remove {
+                parent: current_dir.display().to_string(),
+                root,
+            });
+        }
+
+        let snapshot = match self.repository.list_files(&root).await {
+            Ok(files) => {
+                let files = files
+                    .into_iter()
+                    .filter(|path| may_change(path))
+                    .collect::<Vec<_>>();
+                self.repository.read_snapshot(&root, &files).await
+            }
+            Err(err) => Err(err),
+        };
+        let snapshot = snapshot.map_err(|err| failed(format!("{} - {}", root, err)))?;
+
+        Ok(Rollback::Undo { root, snapshot })
+    }
+
+    /// Undoes the steps applied so far, which wrote what the `report` lists, leaving the
+    /// project as it was before the spec.
+    async fn roll_back(&self, rollback: &Rollback, report: &Report) -> io::Result<()> {
+        let (root, snapshot) = match rollback {
+            Rollback::Undo { root, snapshot } => (root, snapshot),
+            Rollback::Remove { parent, root } => {
+                self.repository.enter_directory(parent).await?;
+
+                return match self.repository.exists(root).await {
+                    true => self.repository.remove_directory(root).await,
+                    false => Ok(()),
+                };
+            }
+        };
+
+        let located = |path: &str| format!("{}/{}", root, path);
+        let relative = |path: &String| path.trim_start_matches("./").to_string();
+        let original = |path: &str| {
+            snapshot
+                .files
+                .iter()
+                .find(|(file, _)| file == path)
+                .map(|(_, content)| content)
+        };
+        let files = self.repository.list_files(root).await?;
+
+        let created = report.created.iter().map(relative).chain(
+            files
+                .iter()
+                .filter(|path| is_bookkeeping(path) && original(path).is_none())
+                .cloned(),
+        );
+        for path in created {
+            if files.contains(&path) {
+                self.repository.remove_file(&located(&path)).await?;
+            }
+        }
+
+        let modified = report.modified.iter().map(relative).chain(
+            snapshot
+                .files
+                .iter()
+                .map(|(path, _)| path.clone())
+                .filter(|path| is_bookkeeping(path)),
+        );
+        for path in modified {
+            let content = original(&path).ok_or_else(|| {
+                io::Error::new(
+                    io::ErrorKind::NotFound,
+                    format!("{} wasn't expected to change", path),
+                )
+            })?;
+            self.repository.write_file(&located(&path), content).await?;
+        }
+
+        // Deepest first, so the directories the steps created are empty once reached
+        let directories = self.repository.read_snapshot(root, &[]).await?.directories;
+        for directory in directories.iter().rev() {
+            if !snapshot.directories.contains(directory) {
+                self.repository
+                    .remove_empty_directory(&located(directory))
+                    .await?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Makes the spec's project the current directory, creating it when missing.
+    async fn enter_project(&self, spec: &Spec, report: &mut Report) -> Result<(), Box<Report>> {
+        if current_project() == spec.name && self.repository.exists("Cargo.toml").await {
+            return Ok(());
+        }
+
+        if !self.repository.exists(&spec.name).await {
+            let current_dir = env::current_dir().map_err(failed)?;
+            let usecase = NewUsecase::new(
+                &spec.name,
+                &current_dir,
+                spec.runtime.unwrap_or_default(),
+                spec.kind.unwrap_or_default(),
+                None,
+                false,
+            );
+
+            merged(report, usecase.create_project().await)?;
+            report
+                .details
+                .push(format!("created project: {}", spec.name));
+        }
+
+        self.repository
+            .enter_directory(&spec.name)
+            .await
+            .map_err(failed)
+    }
+
+    /// Warns about the settings of an existing project that differ from the spec, which
+    /// can't be changed after creation.
+    async fn settings_warnings(&self, spec: &Spec) -> Vec<String> {
+        let mut warnings = Vec::new();
+
+        let runtime: Option<Runtime> =
+            metadata_setting(self.repository.read_metadata("runtime").await).ok();
+        if let (Some(expected), Some(runtime)) = (spec.runtime, runtime) {
+            if expected != runtime {
+                warnings.push(format!(
+                    "the project uses the {} runtime, not {}",
+                    runtime.name(),
+                    expected.name()
+                ));
+            }
+        }
+
+        let kind: ProjectKind =
+            metadata_setting(self.repository.read_metadata("kind").await).unwrap_or_default();
+        if let Some(expected) = spec.kind {
+            if expected != kind {
+                warnings.push(format!(
+                    "the project is a {}, not a {}",
+                    kind.name(),
+                    expected.name()
+                ));
+            }
+        }
+
+        warnings
+    }
+
+    async fn apply_feature(
+        &self,
+        project: &str,
+        feature: &FeatureSpec,
+        report: &mut Report,
+    ) -> Result<(), Box<Report>> {
+        let name = feature.name.as_str();
+        let directory = format!("{}-domain/src/features/{}", project, name);
+        let usecase = AddUsecase::new();
+
+        if !self.repository.exists(&directory).await {
+            merged(report, usecase.add_feature(name, feature.datasource).await)?;
+            report.details.push(format!("added feature: {}", name));
+        }
+
+        let usecase_path = format!("{}/usecase.rs", directory);
+        let content = self
+            .repository
+            .read_file(&usecase_path)
+            .await
+            .map_err(|err| failed(format!("{} - {}", usecase_path, err)))?;
+        let existing = usecase_methods(&content);
+
+        for method in &feature.usecases {
+            if existing.iter().any(|existing| &existing.name == method) {
+                continue;
+            }
+
+            merged(report, usecase.add_usecase(name, method).await)?;
+            report
+                .details
+                .push(format!("added usecase: {}::{}", name, method));
+        }
+
+        let entities_path = format!("{}/entities.rs", directory);
+        let content = self
+            .repository
+            .read_file(&entities_path)
+            .await
+            .map_err(|err| failed(format!("{} - {}", entities_path, err)))?;
+        let name_title_case = titlecase(name);
+        let existing = struct_fields(&content, &name_title_case);
+        let fields = feature
+            .fields
+            .iter()
+            .filter(|(field, _)| !existing.iter().any(|existing| &existing.name == field))
+            .cloned()
+            .collect::<Vec<_>>();
+
+        if !fields.is_empty() {
+            merged(report, usecase.add_fields(name, &fields).await)?;
+
+            for (field, _) in &fields {
+                report
+                    .details
+                    .push(format!("added field: {}::{}", name_title_case, field));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl Default for ApplyUsecase {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl Usecase for ApplyUsecase {
+    async fn apply_spec(&self, path: &str) -> Report {
+        // The whole spec is validated before anything gets created
+        let spec = match self.repository.read_spec(path).await {
+            Ok(spec) => spec,
+            Err(err) => {
+                return Report::failure(
+                    ErrorCode::InvalidArgument,
+                    format!("Failed to apply spec: {} - {}", path, err),
+                )
+            }
+        };
+
+        let rollback = match self.prepare_rollback(&spec).await {
+            Ok(rollback) => rollback,
+            Err(err) => {
+                return Report::failure(
+                    ErrorCode::Failed,
+                    format!("Failed to apply spec: {} - {}", path, err.message),
+                )
+            }
+        };
+
+        let mut report = Report::success(format!("Successfully applied spec: {}", path));
+        let mut result = self.enter_project(&spec, &mut report).await;
+
+        if result.is_ok() {
+            let warnings = self.settings_warnings(&spec).await;
+            report.warnings.extend(warnings);
+        }
+
+        for feature in &spec.features {
+            if result.is_err() {
+                break;
+            }
+            result = self.apply_feature(&spec.name, feature, &mut report).await;
+        }
+
+        match result {
+            Ok(_) if report.details.is_empty() => {
+                report.message = format!("Project already matches spec: {}", path);
+            }
+            Ok(_) => (),
+            Err(err) => {
+                report.success = false;
+                report.error = err.error;
+                report.message = format!("Failed to apply spec: {} - {}", path, err.message);
+
+                let changed = !report.created.is_empty() || !report.modified.is_empty();
+                match self.roll_back(&rollback, &report).await {
+                    Ok(_) => {
+                        report.created.clear();
+                        report.modified.clear();
+                        if changed {
+                            report.notes.push(String::from(
+                                "rolled back the steps above, the project is left as it was",
+                            ));
+                        }
+                    }
+                    Err(err) => {
+                        report
+                            .warnings
+                            .push(format!("Failed to roll back the steps above - {}", err));
+                        report.notes.push(String::from(
+                            "applying the spec again skips the steps above",
+                        ));
+                    }
+                }
+            }
+        }
+
+        report
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct MockRepository {}
+
+    #[async_trait]
+    impl Repository for MockRepository {
+        async fn read_spec(&self, _path: &str) -> io::Result<Spec> {
+            Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "Invalid feature name: Notes",
+            ))
+        }
+
+        async fn exists(&self, _path: &str) -> bool {
+            false
+        }
+
+        async fn read_file(&self, _path: &str) -> io::Result<String> {
+            Ok(String::new())
+        }
+
+        async fn read_metadata(&self, _key: &str) -> io::Result<Option<String>> {
+            Ok(None)
+        }
+
+        async fn enter_directory(&self, _path: &str) -> io::Result<()> {
+            Ok(())
+        }
+
+        async fn list_files(&self, _path: &str) -> io::Result<Vec<String>> {
+            Ok(vec![])
+        }
+
+        async fn read_snapshot(&self, _path: &str, _files: &[String]) -> io::Result<Snapshot> {
+            Ok(Snapshot::default())
+        }
+
+        async fn write_file(&self, _path: &str, _content: &[u8]) -> io::Result<()> {
+            Ok(())
+        }
+
+        async fn remove_file(&self, _path: &str) -> io::Result<()> {
+            Ok(())
+        }
+
+        async fn remove_empty_directory(&self, _path: &str) -> io::Result<()> {
+            Ok(())
+        }
+
+        async fn remove_directory(&self, _path: &str) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_may_change() {
+        for path in [
+            "Cargo.toml",
+            ".cargo-project/stamps.toml",
+            "demo-domain/Cargo.toml",
+            "demo-domain/src/features/mod.rs",
+            "src/features/notes.rs",
+            ".github/workflows/ci.yml",
+        ] {
+            assert!(may_change(path), "{}", path);
+        }
+        for path in [
+            "README.md",
+            "demo/src/main.rs",
+            "demo/src/legacy/features.rs",
+        ] {
+            assert!(!may_change(path), "{}", path);
+        }
+        assert!(!is_bookkeeping("demo-domain/Cargo.toml"));
+    }
+
+    #[async_std::test]
+    async fn test_apply_invalid_spec() {
+        let usecase = ApplyUsecase {
+            repository: Box::new(MockRepository {}),
+        };
+
+        let expect = "Failed to apply spec: spec.toml - Invalid feature name: Notes";
+        let got = usecase.apply_spec("spec.toml").await;
+
+        assert_eq!(expect, got.to_string());
+        assert_eq!(Some(ErrorCode::InvalidArgument), got.error);
+    }
+}
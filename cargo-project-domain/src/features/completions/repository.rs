@@ -0,0 +1,37 @@
+use async_trait::async_trait;
+use cargo_project_data::features::completions::datasource::{CompletionsDatasource, Datasource};
+use std::io;
+
+#[async_trait]
+pub trait Repository {
+    async fn exists(&self, path: &str) -> bool;
+    async fn list_directories(&self, path: &str) -> io::Result<Vec<String>>;
+}
+
+pub struct CompletionsRepository {
+    datasource: Box<dyn Datasource + Send + Sync>,
+}
+
+impl CompletionsRepository {
+    pub fn new() -> Self {
+        let datasource = Box::new(CompletionsDatasource::new());
+        Self { datasource }
+    }
+}
+
+impl Default for CompletionsRepository {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl Repository for CompletionsRepository {
+    async fn exists(&self, path: &str) -> bool {
+        self.datasource.exists(path).await
+    }
+
+    async fn list_directories(&self, path: &str) -> io::Result<Vec<String>> {
+        self.datasource.list_directories(path).await
+    }
+}
@@ -0,0 +1,90 @@
+use async_trait::async_trait;
+
+use super::repository::{CompletionsRepository, Repository};
+
+use crate::core::report::Report;
+use crate::core::utils::{current_project, in_root_directory};
+
+#[async_trait]
+pub trait Usecase {
+    async fn feature_names(&self) -> Report;
+}
+
+/// Lists what shell completion scripts offer as values, like the project's features.
+pub struct CompletionsUsecase {
+    project: String,
+    repository: Box<dyn Repository + Send + Sync>,
+}
+
+impl CompletionsUsecase {
+    pub fn new() -> Self {
+        let project = current_project();
+        let repository = Box::new(CompletionsRepository::new());
+
+        Self {
+            project,
+            repository,
+        }
+    }
+}
+
+impl Default for CompletionsUsecase {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl Usecase for CompletionsUsecase {
+    /// Names of the features one per line, or nothing outside the root of a project as
+    /// the output is read by shells completing a command line.
+    async fn feature_names(&self) -> Report {
+        let project = self.project.as_str();
+
+        if !self.repository.exists("Cargo.toml").await || !in_root_directory(project).await {
+            return Report::success("");
+        }
+
+        let features = format!("{}-domain/src/features", project);
+        let names = self
+            .repository
+            .list_directories(&features)
+            .await
+            .unwrap_or_default();
+
+        Report::success(names.join("\n"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::io;
+
+    struct MockRepository {}
+
+    #[async_trait]
+    impl Repository for MockRepository {
+        async fn exists(&self, _path: &str) -> bool {
+            false
+        }
+
+        async fn list_directories(&self, _path: &str) -> io::Result<Vec<String>> {
+            Ok(vec![String::from("notes"), String::from("users")])
+        }
+    }
+
+    #[async_std::test]
+    async fn test_feature_names_outside_project() {
+        let usecase = CompletionsUsecase {
+            project: String::from("test"),
+            repository: Box::new(MockRepository {}),
+        };
+
+        let got = usecase.feature_names().await;
+
+        assert!(got.success);
+        assert_eq!("", got.message);
+    }
+}
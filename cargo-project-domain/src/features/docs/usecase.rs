@@ -0,0 +1,460 @@
+use std::io;
+
+use async_trait::async_trait;
+use titlecase::titlecase;
+
+use super::repository::{DocsRepository, Repository};
+
+use crate::core::baseline::baseline_path;
+use crate::core::layer::Layer;
+use crate::core::project::metadata_setting;
+use crate::core::report::{ErrorCode, Report};
+use crate::core::source::{struct_fields, usecase_methods};
+use crate::core::stamp::Stamp;
+use crate::core::templates::docs::architecture::{ArchitectureTemplate, FeatureDoc, LayerDoc};
+use crate::core::templates::docs::readme::{FeatureReadmeTemplate, FieldDoc};
+use crate::core::templates::{Rendered, Template};
+use crate::core::utils::{current_project, in_root_directory};
+use crate::features::add::entities::{DatasourceKind, FileFormat};
+
+/// Marks the start of the section `docs` regenerates, anything outside it is kept.
+const GENERATED_START: &str =
+    "<!-- Generated by `cargo project docs`, edits up to the end marker are overwritten -->";
+const GENERATED_END: &str = "<!-- End of generated section -->";
+
+const ARCHITECTURE_FILE: &str = "ARCHITECTURE.md";
+
+#[async_trait]
+pub trait Usecase {
+    async fn generate_docs(&self) -> Report;
+}
+
+/// Documents the layers of the project and each of its features in markdown files.
+pub struct DocsUsecase {
+    project: String,
+    repository: Box<dyn Repository + Send + Sync>,
+}
+
+/// Puts `generated` between the markers of the `existing` document, appending it when
+/// the document has no markers yet.
+fn with_generated(existing: Option<&str>, generated: &str) -> String {
+    let section = format!("{}\n{}{}\n", GENERATED_START, generated, GENERATED_END);
+
+    let existing = match existing {
+        Some(existing) if !existing.trim().is_empty() => existing,
+        _ => return section,
+    };
+
+    match (existing.find(GENERATED_START), existing.find(GENERATED_END)) {
+        (Some(start), Some(end)) if start < end => {
+            let end = end + GENERATED_END.len();
+            let rest = existing[end..]
+                .strip_prefix('\n')
+                .unwrap_or(&existing[end..]);
+
+            format!("{}{}{}", &existing[..start], section, rest)
+        }
+        _ => format!("{}\n\n{}", existing.trim_end(), section),
+    }
+}
+
+fn layer_description(layer: &Layer) -> String {
+    match layer.name.as_str() {
+        "presentation" => String::from("The entry point exposing each feature's usecase."),
+        "domain" => String::from(
+            "Entities, usecases and the repositories they read and write entities through.",
+        ),
+        "data" => String::from("Models and the datasources storing them."),
+        _ => format!(
+            "The {} module of every feature.",
+            layer
+                .files
+                .iter()
+                .map(|file| format!("`{}`", file))
+                .collect::<Vec<_>>()
+                .join(", ")
+        ),
+    }
+}
+
+fn datasource_name(datasource: DatasourceKind) -> String {
+    match datasource {
+        DatasourceKind::File(format) => format!("file ({})", format.extension()),
+        DatasourceKind::Empty => String::from("empty"),
+    }
+}
+
+fn datasource_description(datasource: DatasourceKind, name_title_case: &str) -> String {
+    match datasource {
+        DatasourceKind::File(format) => format!(
+            "`{0}Datasource` stores each `{0}` as a {1} file named after its id.",
+            name_title_case,
+            match format {
+                FileFormat::Json => "JSON",
+                FileFormat::Toml => "TOML",
+            }
+        ),
+        DatasourceKind::Empty => format!(
+            "`{0}Datasource` is a stub to implement, storing `{0}` wherever it belongs.",
+            name_title_case
+        ),
+    }
+}
+
+impl DocsUsecase {
+    pub fn new() -> Self {
+        let repository = Box::new(DocsRepository::new());
+        Self {
+            project: current_project(),
+            repository,
+        }
+    }
+
+    /// Writes `generated` into the document at `path`, returning whether it changed along
+    /// with the document as written.
+    async fn write_doc(
+        &self,
+        path: &str,
+        generated: &Rendered,
+        report: &mut Report,
+    ) -> io::Result<(bool, Rendered)> {
+        let existing = match self.repository.exists(path).await {
+            true => Some(self.repository.read_file(path).await?),
+            false => None,
+        };
+        let document = Rendered {
+            content: with_generated(existing.as_deref(), &generated.content),
+            ..generated.clone()
+        };
+
+        if existing.as_deref() == Some(document.content.as_str()) {
+            return Ok((false, document));
+        }
+
+        self.repository
+            .write_file(path, document.content.as_bytes())
+            .await?;
+        report.written(path, existing.is_some());
+
+        Ok((true, document))
+    }
+
+    /// Writes every document, listing the paths of those that changed and how many
+    /// didn't.
+    async fn write_docs(&self, report: &mut Report) -> io::Result<(Vec<String>, usize)> {
+        let project = self.project.as_str();
+        let mut layers = Layer::defaults(
+            metadata_setting(self.repository.read_metadata("kind").await)
+                .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))?,
+        );
+        layers.extend(self.repository.read_layers().await?);
+
+        let recorded = self.repository.read_features().await?;
+        let directory = format!("{}-domain/src/features", project);
+        let names = self.repository.list_directories(&directory).await?;
+
+        let mut docs = Vec::new();
+        let mut features = Vec::new();
+        for name in &names {
+            let name_title_case = titlecase(name);
+            let datasource = match recorded.iter().find(|feature| &feature.name == name) {
+                Some(feature) => feature.datasource,
+                None => {
+                    let path = format!("{}-data/src/features/{}/datasource.rs", project, name);
+                    match self.repository.exists(&path).await {
+                        true => DatasourceKind::detect(&self.repository.read_file(&path).await?),
+                        false => DatasourceKind::Empty,
+                    }
+                }
+            };
+
+            let usecase = format!("{}/{}/usecase.rs", directory, name);
+            let entities = format!("{}/{}/entities.rs", directory, name);
+            let methods = usecase_methods(&self.repository.read_file(&usecase).await?)
+                .into_iter()
+                .map(|method| method.name)
+                .collect::<Vec<_>>();
+            let fields = struct_fields(
+                &self.repository.read_file(&entities).await?,
+                &name_title_case,
+            )
+            .into_iter()
+            .map(|field| FieldDoc {
+                name: field.name,
+                ty: field.ty,
+            })
+            .collect();
+
+            let readme = format!("{}/{}/README.md", directory, name);
+            let description = datasource_description(datasource, &name_title_case);
+            let rendered = FeatureReadmeTemplate::new(
+                name,
+                &name_title_case,
+                &description,
+                fields,
+                methods.clone(),
+            )
+            .rendered()
+            .map_err(|err| io::Error::other(err.to_string()))?;
+            docs.push((readme.clone(), rendered));
+
+            features.push(FeatureDoc {
+                name: name.clone(),
+                readme,
+                datasource: datasource_name(datasource),
+                methods: methods
+                    .iter()
+                    .map(|method| format!("`{}`", method))
+                    .collect::<Vec<_>>()
+                    .join(", "),
+            });
+        }
+
+        let layer_docs = layers
+            .iter()
+            .map(|layer| LayerDoc {
+                name: layer.name.clone(),
+                crate_name: layer.crate_name(project),
+                depends_on: match layer.depends_on.is_empty() {
+                    true => String::from("-"),
+                    false => layer.depends_on.join(", "),
+                },
+                description: layer_description(layer),
+            })
+            .collect();
+        let rendered = ArchitectureTemplate::new(project, layer_docs, features)
+            .rendered()
+            .map_err(|err| io::Error::other(err.to_string()))?;
+        docs.insert(0, (String::from(ARCHITECTURE_FILE), rendered));
+
+        let mut updated = Vec::new();
+        let mut unchanged = 0;
+        let mut stamps = Vec::new();
+        for (path, rendered) in docs {
+            let (changed, document) = self.write_doc(&path, &rendered, report).await?;
+            // The document is stamped whole, edits outside the generated section included
+            self.repository
+                .write_file(&baseline_path(&path), document.content.as_bytes())
+                .await?;
+            stamps.push(Stamp::new(&path, &document));
+
+            match changed {
+                true => updated.push(path),
+                false => {
+                    report.skipped.push(path);
+                    unchanged += 1;
+                }
+            }
+        }
+        self.repository.record_stamps(&stamps).await?;
+
+        Ok((updated, unchanged))
+    }
+}
+
+impl Default for DocsUsecase {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl Usecase for DocsUsecase {
+    async fn generate_docs(&self) -> Report {
+        let project = self.project.as_str();
+
+        if !in_root_directory(project).await {
+            return Report::not_in_project();
+        }
+
+        let mut report = Report::success(format!(
+            "Successfully generated docs for project: {}",
+            project
+        ));
+        let (updated, unchanged) = match self.write_docs(&mut report).await {
+            Ok(result) => result,
+            Err(err) => {
+                return Report::failure(
+                    ErrorCode::Failed,
+                    format!("Failed to generate docs: {} - {}", project, err),
+                )
+            }
+        };
+
+        report
+            .details
+            .extend(updated.iter().map(|path| format!("updated: {}", path)));
+        report
+            .details
+            .push(format!("{} files unchanged", unchanged));
+
+        report
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::collections::HashMap;
+    use std::sync::{Arc, Mutex};
+
+    use crate::core::stamp::content_hash;
+    use crate::features::add::entities::FeatureRecord;
+
+    struct MockRepository {
+        files: Arc<Mutex<HashMap<String, String>>>,
+        stamps: Arc<Mutex<Vec<Stamp>>>,
+    }
+
+    #[async_trait]
+    impl Repository for MockRepository {
+        async fn exists(&self, path: &str) -> bool {
+            self.files.lock().unwrap().contains_key(path)
+        }
+
+        async fn read_file(&self, path: &str) -> io::Result<String> {
+            self.files
+                .lock()
+                .unwrap()
+                .get(path)
+                .cloned()
+                .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "No such file"))
+        }
+
+        async fn write_file(&self, path: &str, content: &[u8]) -> io::Result<()> {
+            self.files.lock().unwrap().insert(
+                path.to_string(),
+                String::from_utf8_lossy(content).to_string(),
+            );
+            Ok(())
+        }
+
+        async fn read_metadata(&self, _key: &str) -> io::Result<Option<String>> {
+            Ok(None)
+        }
+
+        async fn read_layers(&self) -> io::Result<Vec<Layer>> {
+            Ok(vec![])
+        }
+
+        async fn read_features(&self) -> io::Result<Vec<FeatureRecord>> {
+            Ok(vec![FeatureRecord {
+                name: String::from("notes"),
+                datasource: DatasourceKind::File(FileFormat::Json),
+                usecases: vec![],
+            }])
+        }
+
+        async fn list_directories(&self, _path: &str) -> io::Result<Vec<String>> {
+            Ok(vec![String::from("notes")])
+        }
+
+        async fn record_stamps(&self, stamps: &[Stamp]) -> io::Result<()> {
+            self.stamps.lock().unwrap().extend_from_slice(stamps);
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_with_generated() {
+        let section = format!("{}\ngenerated\n{}\n", GENERATED_START, GENERATED_END);
+
+        assert_eq!(section, with_generated(None, "generated\n"));
+        assert_eq!(
+            format!("# Notes\n\n{}", section),
+            with_generated(Some("# Notes\n"), "generated\n")
+        );
+
+        let existing = format!(
+            "Intro\n{}\nold\n{}\nOutro\n",
+            GENERATED_START, GENERATED_END
+        );
+        assert_eq!(
+            format!("Intro\n{}Outro\n", section),
+            with_generated(Some(&existing), "generated\n")
+        );
+    }
+
+    #[test]
+    fn test_datasource_description() {
+        assert_eq!(
+            "`NotesDatasource` stores each `Notes` as a TOML file named after its id.",
+            datasource_description(DatasourceKind::File(FileFormat::Toml), "Notes")
+        );
+        assert_eq!(
+            "file (json)",
+            datasource_name(DatasourceKind::File(FileFormat::Json))
+        );
+    }
+
+    #[async_std::test]
+    async fn test_write_docs() {
+        let files = Arc::new(Mutex::new(
+            vec![
+                (
+                    "test-domain/src/features/notes/usecase.rs",
+                    "pub trait Usecase {\n    fn archive(&self) -> Result<(), String>;\n}",
+                ),
+                (
+                    "test-domain/src/features/notes/entities.rs",
+                    "pub struct Notes {\n    pub title: String,\n}",
+                ),
+                (
+                    "test-domain/src/features/notes/README.md",
+                    "Written by hand.\n",
+                ),
+            ]
+            .into_iter()
+            .map(|(path, content)| (path.to_string(), content.to_string()))
+            .collect(),
+        ));
+        let stamps = Arc::new(Mutex::new(Vec::new()));
+        let usecase = DocsUsecase {
+            project: String::from("test"),
+            repository: Box::new(MockRepository {
+                files: files.clone(),
+                stamps: stamps.clone(),
+            }),
+        };
+
+        let mut report = Report::default();
+        let got = usecase.write_docs(&mut report).await.unwrap();
+
+        assert_eq!(
+            (
+                vec![
+                    String::from("ARCHITECTURE.md"),
+                    String::from("test-domain/src/features/notes/README.md"),
+                ],
+                0
+            ),
+            got
+        );
+        {
+            let files = files.lock().unwrap();
+            let readme = &files["test-domain/src/features/notes/README.md"];
+            assert!(readme.starts_with("Written by hand.\n\n<!-- Generated"));
+            assert!(readme.contains("| `title` | `String` |"));
+            assert!(files["ARCHITECTURE.md"].contains("| file (json) | `archive` |"));
+
+            let stamps = stamps.lock().unwrap();
+            assert_eq!("ArchitectureTemplate", stamps[0].template);
+            assert_eq!("FeatureReadmeTemplate", stamps[1].template);
+            for stamp in stamps.iter() {
+                assert_eq!(content_hash(&files[&stamp.path]), stamp.hash);
+            }
+        }
+        assert_eq!(vec![String::from("ARCHITECTURE.md")], report.created);
+        assert_eq!(
+            vec![String::from("test-domain/src/features/notes/README.md")],
+            report.modified
+        );
+
+        let mut report = Report::default();
+        let got = usecase.write_docs(&mut report).await.unwrap();
+
+        assert_eq!((vec![], 2), got);
+        assert_eq!(2, report.skipped.len());
+    }
+}
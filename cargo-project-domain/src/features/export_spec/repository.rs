@@ -0,0 +1,72 @@
+use async_trait::async_trait;
+use cargo_project_data::features::apply::models::SpecModel;
+use cargo_project_data::features::export_spec::datasource::{Datasource, ExportSpecDatasource};
+use std::convert::TryFrom;
+use std::io;
+
+use crate::features::add::entities::FeatureRecord;
+use crate::features::apply::entities::Spec;
+
+#[async_trait]
+pub trait Repository {
+    async fn exists(&self, path: &str) -> bool;
+    async fn read_file(&self, path: &str) -> io::Result<String>;
+    async fn read_metadata(&self, key: &str) -> io::Result<Option<String>>;
+    async fn read_features(&self) -> io::Result<Vec<FeatureRecord>>;
+    async fn list_directories(&self, path: &str) -> io::Result<Vec<String>>;
+    async fn write_spec(&self, path: &str, spec: &Spec) -> io::Result<()>;
+}
+
+pub struct ExportSpecRepository {
+    datasource: Box<dyn Datasource + Send + Sync>,
+}
+
+impl ExportSpecRepository {
+    pub fn new() -> Self {
+        let datasource = Box::new(ExportSpecDatasource::new());
+        Self { datasource }
+    }
+}
+
+impl Default for ExportSpecRepository {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl Repository for ExportSpecRepository {
+    async fn exists(&self, path: &str) -> bool {
+        self.datasource.exists(path).await
+    }
+
+    async fn read_file(&self, path: &str) -> io::Result<String> {
+        self.datasource.read_file(path).await
+    }
+
+    async fn read_metadata(&self, key: &str) -> io::Result<Option<String>> {
+        self.datasource.read_metadata(key).await
+    }
+
+    async fn read_features(&self) -> io::Result<Vec<FeatureRecord>> {
+        self.datasource
+            .read_features()
+            .await?
+            .into_iter()
+            .map(|model| {
+                FeatureRecord::try_from(model)
+                    .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))
+            })
+            .collect()
+    }
+
+    async fn list_directories(&self, path: &str) -> io::Result<Vec<String>> {
+        self.datasource.list_directories(path).await
+    }
+
+    async fn write_spec(&self, path: &str, spec: &Spec) -> io::Result<()> {
+        self.datasource
+            .write_spec(path, &SpecModel::from(spec))
+            .await
+    }
+}
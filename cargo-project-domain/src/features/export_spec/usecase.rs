@@ -0,0 +1,306 @@
+use async_trait::async_trait;
+use cargo_project_data::features::apply::models::SpecModel;
+use titlecase::titlecase;
+
+use super::repository::{ExportSpecRepository, Repository};
+
+use crate::core::project::metadata_setting;
+use crate::core::report::{ErrorCode, Report};
+use crate::core::source::{struct_fields, usecase_methods, UsecaseMethodKind};
+use crate::core::utils::{current_project, in_root_directory};
+use crate::features::add::entities::DatasourceKind;
+use crate::features::apply::entities::{FeatureSpec, Spec};
+
+/// Writes the spec to standard output instead of a file.
+pub const STDOUT: &str = "-";
+
+#[async_trait]
+pub trait Usecase {
+    async fn export_spec(&self, path: &str) -> Report;
+}
+
+/// Describes the features of the project in the spec format read by `apply`.
+pub struct ExportSpecUsecase {
+    project: String,
+    repository: Box<dyn Repository + Send + Sync>,
+}
+
+/// Usecases added to a feature, leaving out the methods every feature is generated with.
+fn added_usecases(content: &str) -> Vec<String> {
+    usecase_methods(content)
+        .into_iter()
+        .filter(|method| {
+            !matches!(
+                method.kind,
+                UsecaseMethodKind::Get | UsecaseMethodKind::Save | UsecaseMethodKind::List
+            )
+        })
+        .map(|method| method.name)
+        .collect()
+}
+
+impl ExportSpecUsecase {
+    pub fn new() -> Self {
+        let repository = Box::new(ExportSpecRepository::new());
+        Self {
+            project: current_project(),
+            repository,
+        }
+    }
+
+    async fn feature(
+        &self,
+        name: &str,
+        datasource: Option<DatasourceKind>,
+    ) -> Result<FeatureSpec, String> {
+        let project = self.project.as_str();
+        let read = |path: String| async move {
+            self.repository
+                .read_file(&path)
+                .await
+                .map_err(|err| format!("{} - {}", path, err))
+        };
+
+        let datasource = match datasource {
+            Some(datasource) => datasource,
+            None => {
+                let path = format!("{}-data/src/features/{}/datasource.rs", project, name);
+                match self.repository.exists(&path).await {
+                    true => DatasourceKind::detect(&read(path).await?),
+                    false => DatasourceKind::Empty,
+                }
+            }
+        };
+
+        let usecase = read(format!(
+            "{}-domain/src/features/{}/usecase.rs",
+            project, name
+        ))
+        .await?;
+        let entities = read(format!(
+            "{}-domain/src/features/{}/entities.rs",
+            project, name
+        ))
+        .await?;
+
+        Ok(FeatureSpec {
+            name: name.to_string(),
+            datasource,
+            usecases: added_usecases(&usecase),
+            fields: struct_fields(&entities, &titlecase(name))
+                .into_iter()
+                .map(|field| (field.name, field.ty))
+                .collect(),
+        })
+    }
+
+    async fn spec(&self) -> Result<Spec, String> {
+        let project = self.project.as_str();
+        let recorded = self
+            .repository
+            .read_features()
+            .await
+            .map_err(|err| err.to_string())?;
+        let names = self
+            .repository
+            .list_directories(&format!("{}-domain/src/features", project))
+            .await
+            .map_err(|err| err.to_string())?;
+
+        let mut features = Vec::new();
+        for name in names {
+            let datasource = recorded
+                .iter()
+                .find(|feature| feature.name == name)
+                .map(|feature| feature.datasource);
+
+            features.push(self.feature(&name, datasource).await?);
+        }
+
+        Ok(Spec {
+            name: project.to_string(),
+            runtime: Some(metadata_setting(
+                self.repository.read_metadata("runtime").await,
+            )?),
+            kind: Some(metadata_setting(
+                self.repository.read_metadata("kind").await,
+            )?),
+            features,
+        })
+    }
+}
+
+impl Default for ExportSpecUsecase {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl Usecase for ExportSpecUsecase {
+    async fn export_spec(&self, path: &str) -> Report {
+        let project = self.project.as_str();
+
+        if !in_root_directory(project).await {
+            return Report::not_in_project();
+        }
+
+        let spec = match self.spec().await {
+            Ok(spec) => spec,
+            Err(err) => {
+                return Report::failure(
+                    ErrorCode::Failed,
+                    format!("Failed to export spec: {} - {}", project, err),
+                )
+            }
+        };
+
+        if path == STDOUT {
+            return Report::success(SpecModel::from(&spec).to_toml().trim_end());
+        }
+
+        let existed = self.repository.exists(path).await;
+        match self.repository.write_spec(path, &spec).await {
+            Ok(_) => {
+                let mut report = Report::success(format!(
+                    "Successfully exported spec: {} ({} features)",
+                    path,
+                    spec.features.len()
+                ));
+                report.written(path, existed);
+                report
+            }
+            Err(err) => Report::failure(
+                ErrorCode::Failed,
+                format!("Failed to export spec: {} - {}", path, err),
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::collections::HashMap;
+    use std::io;
+
+    use crate::core::project::ProjectKind;
+    use crate::core::runtime::Runtime;
+    use crate::core::templates::usecase::UsecaseTemplate;
+    use crate::core::templates::Template;
+    use crate::features::add::entities::{FeatureRecord, FileFormat};
+
+    struct MockRepository {
+        files: HashMap<String, String>,
+        features: Vec<FeatureRecord>,
+    }
+
+    #[async_trait]
+    impl Repository for MockRepository {
+        async fn exists(&self, path: &str) -> bool {
+            self.files.contains_key(path)
+        }
+
+        async fn read_file(&self, path: &str) -> io::Result<String> {
+            self.files
+                .get(path)
+                .cloned()
+                .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "No such file"))
+        }
+
+        async fn read_metadata(&self, key: &str) -> io::Result<Option<String>> {
+            Ok(match key {
+                "runtime" => Some(String::from("tokio")),
+                _ => None,
+            })
+        }
+
+        async fn read_features(&self) -> io::Result<Vec<FeatureRecord>> {
+            Ok(self.features.clone())
+        }
+
+        async fn list_directories(&self, _path: &str) -> io::Result<Vec<String>> {
+            Ok(vec![String::from("notes"), String::from("tags")])
+        }
+
+        async fn write_spec(&self, _path: &str, _spec: &Spec) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_added_usecases() {
+        let content = "#[async_trait]
+pub trait Usecase {
+    async fn get(&self, id: &str) -> Result<Option<Notes>, String>;
+    async fn save(&self, id: &str, entity: &Notes) -> Result<(), String>;
+    async fn list(&self) -> Result<Vec<Notes>, String>;
+    async fn archive(&self) -> Result<(), String>;
+}";
+
+        assert_eq!(vec![String::from("archive")], added_usecases(content));
+    }
+
+    #[async_std::test]
+    async fn test_spec() {
+        let usecase = UsecaseTemplate::new("notes", "Notes", Runtime::Tokio)
+            .render()
+            .unwrap();
+        let files = vec![
+            (
+                "test-domain/src/features/notes/usecase.rs",
+                usecase.as_str(),
+            ),
+            (
+                "test-domain/src/features/notes/entities.rs",
+                "pub struct Notes {\n    pub title: String,\n}",
+            ),
+            ("test-domain/src/features/tags/usecase.rs", ""),
+            ("test-domain/src/features/tags/entities.rs", ""),
+            (
+                "test-data/src/features/tags/datasource.rs",
+                "serde_json::from_str(content)",
+            ),
+        ];
+        let usecase = ExportSpecUsecase {
+            project: String::from("test"),
+            repository: Box::new(MockRepository {
+                files: files
+                    .into_iter()
+                    .map(|(path, content)| (path.to_string(), content.to_string()))
+                    .collect(),
+                features: vec![FeatureRecord {
+                    name: String::from("notes"),
+                    datasource: DatasourceKind::Empty,
+                    usecases: vec![],
+                }],
+            }),
+        };
+
+        let got = usecase.spec().await.unwrap();
+
+        assert_eq!(
+            Spec {
+                name: String::from("test"),
+                runtime: Some(Runtime::Tokio),
+                kind: Some(ProjectKind::Binary),
+                features: vec![
+                    FeatureSpec {
+                        name: String::from("notes"),
+                        datasource: DatasourceKind::Empty,
+                        usecases: vec![],
+                        fields: vec![(String::from("title"), String::from("String"))],
+                    },
+                    FeatureSpec {
+                        name: String::from("tags"),
+                        datasource: DatasourceKind::File(FileFormat::Json),
+                        usecases: vec![],
+                        fields: vec![],
+                    },
+                ],
+            },
+            got
+        );
+    }
+}
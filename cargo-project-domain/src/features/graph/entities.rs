@@ -0,0 +1,243 @@
+use std::str::FromStr;
+
+use cargo_project_data::features::graph::models::CrateModel;
+
+/// Diagram language the graph is rendered in.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub enum GraphFormat {
+    #[default]
+    Dot,
+    Mermaid,
+}
+
+impl FromStr for GraphFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "dot" => Ok(GraphFormat::Dot),
+            "mermaid" => Ok(GraphFormat::Mermaid),
+            _ => Err(format!("Unknown graph format: {}", s)),
+        }
+    }
+}
+
+/// A crate of the workspace.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Crate {
+    pub name: String,
+    pub path: String,
+    pub dependencies: Vec<String>,
+}
+
+impl From<CrateModel> for Crate {
+    fn from(model: CrateModel) -> Self {
+        Self {
+            name: model.name,
+            path: model.path,
+            dependencies: model.dependencies,
+        }
+    }
+}
+
+/// A module of a feature in one of the layer crates.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Module {
+    pub crate_name: String,
+    /// Path of the module within its crate, like `features::notes::usecase`.
+    pub path: String,
+}
+
+impl Module {
+    fn id(&self) -> String {
+        format!("{}::{}", self.crate_name, self.path)
+    }
+}
+
+/// Layer crates, feature modules and the dependencies between them.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Graph {
+    pub name: String,
+    pub crates: Vec<Crate>,
+    pub modules: Vec<Module>,
+    /// Modules using other modules, as indices into `modules`.
+    pub edges: Vec<(usize, usize)>,
+}
+
+/// Identifier usable as a Mermaid node id.
+fn mermaid_id(id: &str) -> String {
+    id.replace("::", "__")
+        .replace(|character: char| !character.is_ascii_alphanumeric(), "_")
+}
+
+impl Graph {
+    /// Dependencies between the crates of the workspace, leaving out external crates.
+    pub fn crate_edges(&self) -> Vec<(&str, &str)> {
+        self.crates
+            .iter()
+            .flat_map(|from| {
+                from.dependencies
+                    .iter()
+                    .filter(move |name| self.crates.iter().any(|to| &&to.name == name))
+                    .map(move |name| (from.name.as_str(), name.as_str()))
+            })
+            .collect()
+    }
+
+    pub fn render(&self, format: GraphFormat) -> String {
+        match format {
+            GraphFormat::Dot => self.to_dot(),
+            GraphFormat::Mermaid => self.to_mermaid(),
+        }
+    }
+
+    fn to_dot(&self) -> String {
+        let mut lines = vec![
+            format!("digraph \"{}\" {{", self.name),
+            String::from("    node [shape=box];"),
+        ];
+
+        for krate in &self.crates {
+            lines.push(String::new());
+            lines.push(format!("    subgraph \"cluster_{}\" {{", krate.name));
+            lines.push(format!("        label=\"{}\";", krate.name));
+            lines.push(format!("        \"{}\" [shape=folder];", krate.name));
+            for module in self.modules.iter().filter(|m| m.crate_name == krate.name) {
+                lines.push(format!(
+                    "        \"{}\" [label=\"{}\"];",
+                    module.id(),
+                    module.path
+                ));
+            }
+            lines.push(String::from("    }"));
+        }
+
+        lines.push(String::new());
+        for (from, to) in self.crate_edges() {
+            lines.push(format!("    \"{}\" -> \"{}\" [style=dashed];", from, to));
+        }
+        for (from, to) in &self.edges {
+            lines.push(format!(
+                "    \"{}\" -> \"{}\";",
+                self.modules[*from].id(),
+                self.modules[*to].id()
+            ));
+        }
+        lines.push(String::from("}"));
+
+        lines.join("\n")
+    }
+
+    fn to_mermaid(&self) -> String {
+        let mut lines = vec![String::from("flowchart TD")];
+
+        for krate in &self.crates {
+            let id = mermaid_id(&krate.name);
+            lines.push(format!("    subgraph cluster_{} [\"{}\"]", id, krate.name));
+            lines.push(format!("        {}[[\"{}\"]]", id, krate.name));
+            for module in self.modules.iter().filter(|m| m.crate_name == krate.name) {
+                lines.push(format!(
+                    "        {}[\"{}\"]",
+                    mermaid_id(&module.id()),
+                    module.path
+                ));
+            }
+            lines.push(String::from("    end"));
+        }
+
+        for (from, to) in self.crate_edges() {
+            lines.push(format!("    {} -.-> {}", mermaid_id(from), mermaid_id(to)));
+        }
+        for (from, to) in &self.edges {
+            lines.push(format!(
+                "    {} --> {}",
+                mermaid_id(&self.modules[*from].id()),
+                mermaid_id(&self.modules[*to].id())
+            ));
+        }
+
+        lines.join("\n")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn graph() -> Graph {
+        let krate = |name: &str, dependencies: &[&str]| Crate {
+            name: name.to_string(),
+            path: name.to_string(),
+            dependencies: dependencies.iter().map(|name| name.to_string()).collect(),
+        };
+        let module = |crate_name: &str, path: &str| Module {
+            crate_name: crate_name.to_string(),
+            path: path.to_string(),
+        };
+
+        Graph {
+            name: String::from("demo"),
+            crates: vec![
+                krate("demo", &["demo-domain", "structopt"]),
+                krate("demo-domain", &[]),
+            ],
+            modules: vec![
+                module("demo", "features::notes"),
+                module("demo-domain", "features::notes::usecase"),
+            ],
+            edges: vec![(0, 1)],
+        }
+    }
+
+    #[test]
+    fn test_graph_format() {
+        assert_eq!(GraphFormat::Mermaid, "mermaid".parse().unwrap());
+        assert!("svg".parse::<GraphFormat>().is_err());
+    }
+
+    #[test]
+    fn test_crate_edges() {
+        assert_eq!(vec![("demo", "demo-domain")], graph().crate_edges());
+    }
+
+    #[test]
+    fn test_to_dot() {
+        let expect = r#"digraph "demo" {
+    node [shape=box];
+
+    subgraph "cluster_demo" {
+        label="demo";
+        "demo" [shape=folder];
+        "demo::features::notes" [label="features::notes"];
+    }
+
+    subgraph "cluster_demo-domain" {
+        label="demo-domain";
+        "demo-domain" [shape=folder];
+        "demo-domain::features::notes::usecase" [label="features::notes::usecase"];
+    }
+
+    "demo" -> "demo-domain" [style=dashed];
+    "demo::features::notes" -> "demo-domain::features::notes::usecase";
+}"#;
+
+        assert_eq!(expect, graph().render(GraphFormat::Dot));
+    }
+
+    #[test]
+    fn test_to_mermaid() {
+        let expect = r#"flowchart TD
+    subgraph cluster_demo ["demo"]
+        demo[["demo"]]
+        demo__features__notes["features::notes"]
+    end
+    subgraph cluster_demo_domain ["demo-domain"]
+        demo_domain[["demo-domain"]]
+        demo_domain__features__notes__usecase["features::notes::usecase"]
+    end
+    demo -.-> demo_domain
+    demo__features__notes --> demo_domain__features__notes__usecase"#;
+
+        assert_eq!(expect, graph().render(GraphFormat::Mermaid));
+    }
+}
@@ -0,0 +1,72 @@
+use async_trait::async_trait;
+use cargo_project_data::features::graph::datasource::{Datasource, GraphDatasource};
+use std::convert::TryFrom;
+use std::io;
+
+use super::entities::Crate;
+
+use crate::core::layer::Layer;
+
+#[async_trait]
+pub trait Repository {
+    async fn exists(&self, path: &str) -> bool;
+    async fn read_file(&self, path: &str) -> io::Result<String>;
+    async fn read_metadata(&self, key: &str) -> io::Result<Option<String>>;
+    async fn read_layers(&self) -> io::Result<Vec<Layer>>;
+    async fn read_crates(&self) -> io::Result<Vec<Crate>>;
+    async fn list_directories(&self, path: &str) -> io::Result<Vec<String>>;
+}
+
+pub struct GraphRepository {
+    datasource: Box<dyn Datasource + Send + Sync>,
+}
+
+impl GraphRepository {
+    pub fn new() -> Self {
+        let datasource = Box::new(GraphDatasource::new());
+        Self { datasource }
+    }
+}
+
+impl Default for GraphRepository {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl Repository for GraphRepository {
+    async fn exists(&self, path: &str) -> bool {
+        self.datasource.exists(path).await
+    }
+
+    async fn read_file(&self, path: &str) -> io::Result<String> {
+        self.datasource.read_file(path).await
+    }
+
+    async fn read_metadata(&self, key: &str) -> io::Result<Option<String>> {
+        self.datasource.read_metadata(key).await
+    }
+
+    async fn read_layers(&self) -> io::Result<Vec<Layer>> {
+        self.datasource
+            .read_layers()
+            .await?
+            .into_iter()
+            .map(|model| {
+                Layer::try_from(model)
+                    .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))
+            })
+            .collect()
+    }
+
+    async fn read_crates(&self) -> io::Result<Vec<Crate>> {
+        let crates = self.datasource.read_crates().await?;
+
+        Ok(crates.into_iter().map(Crate::from).collect())
+    }
+
+    async fn list_directories(&self, path: &str) -> io::Result<Vec<String>> {
+        self.datasource.list_directories(path).await
+    }
+}
@@ -0,0 +1,332 @@
+use async_trait::async_trait;
+
+use super::entities::{Crate, Graph, GraphFormat, Module};
+use super::repository::{GraphRepository, Repository};
+
+use crate::core::layer::Layer;
+use crate::core::project::metadata_setting;
+use crate::core::report::{ErrorCode, Report};
+use crate::core::source::{source_items, use_paths, ItemKind};
+use crate::core::utils::{current_project, in_root_directory};
+
+#[async_trait]
+pub trait Usecase {
+    async fn project_graph(&self, format: GraphFormat) -> Report;
+}
+
+/// Renders the layer crates of the project, the modules of its features and the
+/// dependencies between them.
+pub struct GraphUsecase {
+    project: String,
+    repository: Box<dyn Repository + Send + Sync>,
+}
+
+/// Feature modules of a layer shown in the graph. The default layers only show the
+/// modules holding logic, leaving out entities and models.
+fn graphed_files(layer: &Layer) -> Vec<&str> {
+    match layer.name.as_str() {
+        "domain" => vec!["usecase", "repository"],
+        "data" => vec!["datasource"],
+        _ => layer.files.iter().map(String::as_str).collect(),
+    }
+}
+
+/// Index of the module imported by the use `path` found in module `from`, if any. Paths
+/// naming a crate outside the workspace are matched against the modules of every other
+/// crate, so a module is still found through a renamed dependency.
+fn resolve(path: &str, from: &Module, crates: &[Crate], modules: &[Module]) -> Option<usize> {
+    let (first, mut rest) = path.split_once("::")?;
+    let mut parent = from.path.as_str();
+
+    let (crate_name, path) = match first {
+        "crate" => (Some(from.crate_name.as_str()), rest.to_string()),
+        "self" => (
+            Some(from.crate_name.as_str()),
+            format!("{}::{}", parent, rest),
+        ),
+        "super" => {
+            parent = parent.rsplit_once("::").map_or("", |(parent, _)| parent);
+            while let Some(remaining) = rest.strip_prefix("super::") {
+                parent = parent.rsplit_once("::").map_or("", |(parent, _)| parent);
+                rest = remaining;
+            }
+            (
+                Some(from.crate_name.as_str()),
+                format!("{}::{}", parent, rest),
+            )
+        }
+        name => (
+            crates
+                .iter()
+                .find(|krate| krate.name.replace('-', "_") == name)
+                .map(|krate| krate.name.as_str()),
+            rest.to_string(),
+        ),
+    };
+
+    let imports =
+        |module: &Module| path == module.path || path.starts_with(&format!("{}::", module.path));
+    let candidates = modules
+        .iter()
+        .enumerate()
+        .filter(|(_, module)| match crate_name {
+            Some(name) => module.crate_name == name,
+            None => module.crate_name != from.crate_name,
+        })
+        .filter(|(_, module)| imports(module))
+        .collect::<Vec<_>>();
+
+    match crate_name {
+        Some(_) => candidates
+            .into_iter()
+            .max_by_key(|(_, module)| module.path.len())
+            .map(|(index, _)| index),
+        None if candidates.len() == 1 => Some(candidates[0].0),
+        None => None,
+    }
+}
+
+impl GraphUsecase {
+    pub fn new() -> Self {
+        let repository = Box::new(GraphRepository::new());
+        Self {
+            project: current_project(),
+            repository,
+        }
+    }
+
+    /// Every existing feature module of the layer crates, along with its file.
+    async fn modules(&self, crates: &[Crate]) -> Result<Vec<(Module, String)>, String> {
+        let project = self.project.as_str();
+        let mut layers = Layer::defaults(metadata_setting(
+            self.repository.read_metadata("kind").await,
+        )?);
+        layers.extend(
+            self.repository
+                .read_layers()
+                .await
+                .map_err(|err| err.to_string())?,
+        );
+
+        let domain = format!("{}-domain", project);
+        let domain_path = crates
+            .iter()
+            .find(|krate| krate.name == domain)
+            .map_or(domain.as_str(), |krate| krate.path.as_str());
+        let features = self
+            .repository
+            .list_directories(&format!("{}/src/features", domain_path))
+            .await
+            .map_err(|err| err.to_string())?;
+
+        let mut modules = Vec::new();
+        for layer in &layers {
+            let crate_name = layer.crate_name(project);
+            let krate = match crates.iter().find(|krate| krate.name == crate_name) {
+                Some(krate) => krate,
+                None => continue,
+            };
+
+            for feature in &features {
+                let files = match layer.name.as_str() {
+                    "presentation" => vec![(
+                        format!("features::{}", feature),
+                        format!("{}/src/features/{}.rs", krate.path, feature),
+                    )],
+                    _ => graphed_files(layer)
+                        .into_iter()
+                        .map(|file| {
+                            (
+                                format!("features::{}::{}", feature, file),
+                                format!("{}/src/features/{}/{}.rs", krate.path, feature, file),
+                            )
+                        })
+                        .collect(),
+                };
+
+                for (path, file) in files {
+                    if self.repository.exists(&file).await {
+                        let crate_name = crate_name.clone();
+                        modules.push((Module { crate_name, path }, file));
+                    }
+                }
+            }
+        }
+
+        Ok(modules)
+    }
+
+    async fn graph(&self) -> Result<Graph, String> {
+        let crates = self
+            .repository
+            .read_crates()
+            .await
+            .map_err(|err| err.to_string())?;
+        let (modules, files): (Vec<_>, Vec<_>) = self.modules(&crates).await?.into_iter().unzip();
+
+        let mut edges = Vec::new();
+        for (from, file) in files.iter().enumerate() {
+            let content = self
+                .repository
+                .read_file(file)
+                .await
+                .map_err(|err| format!("{} - {}", file, err))?;
+
+            let uses = source_items(&content)
+                .into_iter()
+                .filter(|item| item.kind == ItemKind::Use)
+                .flat_map(|item| use_paths(&item.text));
+            for path in uses {
+                match resolve(&path, &modules[from], &crates, &modules) {
+                    Some(to) if to != from && !edges.contains(&(from, to)) => {
+                        edges.push((from, to))
+                    }
+                    _ => (),
+                }
+            }
+        }
+
+        Ok(Graph {
+            name: self.project.clone(),
+            crates,
+            modules,
+            edges,
+        })
+    }
+}
+
+impl Default for GraphUsecase {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl Usecase for GraphUsecase {
+    async fn project_graph(&self, format: GraphFormat) -> Report {
+        let project = self.project.as_str();
+
+        if !in_root_directory(project).await {
+            return Report::not_in_project();
+        }
+
+        match self.graph().await {
+            Ok(graph) => Report::success(graph.render(format)),
+            Err(err) => Report::failure(
+                ErrorCode::Failed,
+                format!("Failed to graph project: {} - {}", project, err),
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::collections::HashMap;
+    use std::io;
+
+    struct MockRepository {
+        files: HashMap<&'static str, &'static str>,
+    }
+
+    #[async_trait]
+    impl Repository for MockRepository {
+        async fn exists(&self, path: &str) -> bool {
+            self.files.contains_key(path)
+        }
+
+        async fn read_file(&self, path: &str) -> io::Result<String> {
+            Ok(self.files[path].to_string())
+        }
+
+        async fn read_metadata(&self, _key: &str) -> io::Result<Option<String>> {
+            Ok(None)
+        }
+
+        async fn read_layers(&self) -> io::Result<Vec<Layer>> {
+            Ok(vec![])
+        }
+
+        async fn read_crates(&self) -> io::Result<Vec<Crate>> {
+            Ok(["test", "test-domain", "test-data"]
+                .iter()
+                .map(|name| Crate {
+                    name: name.to_string(),
+                    path: name.to_string(),
+                    dependencies: vec![],
+                })
+                .collect())
+        }
+
+        async fn list_directories(&self, _path: &str) -> io::Result<Vec<String>> {
+            Ok(vec![String::from("notes")])
+        }
+    }
+
+    fn module(crate_name: &str, path: &str) -> Module {
+        Module {
+            crate_name: crate_name.to_string(),
+            path: path.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_resolve() {
+        let crates = vec![Crate {
+            name: String::from("test-data"),
+            path: String::from("test-data"),
+            dependencies: vec![],
+        }];
+        let modules = vec![
+            module("test-domain", "features::notes::usecase"),
+            module("test-domain", "features::notes::repository"),
+            module("test-data", "features::notes::datasource"),
+        ];
+        let resolve = |path: &str, from: usize| resolve(path, &modules[from], &crates, &modules);
+
+        assert_eq!(Some(1), resolve("super::repository::Repository", 0));
+        assert_eq!(Some(1), resolve("crate::features::notes::repository", 0));
+        assert_eq!(
+            Some(2),
+            resolve("test_data::features::notes::datasource::Datasource", 1)
+        );
+        assert_eq!(Some(0), resolve("renamed::features::notes::usecase", 2));
+        assert_eq!(None, resolve("super::entities::Notes", 0));
+        assert_eq!(None, resolve("async_trait::async_trait", 0));
+    }
+
+    #[async_std::test]
+    async fn test_graph() {
+        let files = vec![
+            (
+                "test/src/features/notes.rs",
+                "use test_domain::features::notes::usecase::{NotesUsecase, Usecase};",
+            ),
+            (
+                "test-domain/src/features/notes/usecase.rs",
+                "use super::entities::Notes;\nuse super::repository::{NotesRepository, Repository};",
+            ),
+            ("test-domain/src/features/notes/repository.rs", ""),
+        ];
+        let usecase = GraphUsecase {
+            project: String::from("test"),
+            repository: Box::new(MockRepository {
+                files: files.into_iter().collect(),
+            }),
+        };
+
+        let got = usecase.graph().await.unwrap();
+
+        assert_eq!(
+            vec![
+                module("test", "features::notes"),
+                module("test-domain", "features::notes::usecase"),
+                module("test-domain", "features::notes::repository"),
+            ],
+            got.modules
+        );
+        assert_eq!(vec![(0, 1), (1, 2)], got.edges);
+    }
+}
@@ -0,0 +1,68 @@
+use async_trait::async_trait;
+use cargo_project_data::core::stamps::StampModel;
+use cargo_project_data::features::init::datasource::{Datasource, InitDatasource};
+use std::io;
+
+use crate::core::stamp::Stamp;
+
+#[async_trait]
+pub trait Repository {
+    async fn exists(&self, path: &str) -> bool;
+    async fn write_file(&self, path: &str, content: &[u8]) -> io::Result<()>;
+    async fn init_workspace(&self, members: &[&str], metadata: &[(&str, &str)]) -> io::Result<()>;
+    async fn update_manifest(
+        &self,
+        crate_path: &str,
+        dependencies: &[(&str, &str)],
+        dev_dependencies: &[(&str, &str)],
+    ) -> io::Result<()>;
+    async fn record_stamps(&self, stamps: &[Stamp]) -> io::Result<()>;
+}
+
+pub struct InitRepository {
+    datasource: Box<dyn Datasource + Send + Sync>,
+}
+
+impl InitRepository {
+    pub fn new() -> Self {
+        let datasource = Box::new(InitDatasource::new());
+        Self { datasource }
+    }
+}
+
+impl Default for InitRepository {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl Repository for InitRepository {
+    async fn exists(&self, path: &str) -> bool {
+        self.datasource.exists(path).await
+    }
+
+    async fn write_file(&self, path: &str, content: &[u8]) -> io::Result<()> {
+        self.datasource.write_file(path, content).await
+    }
+
+    async fn init_workspace(&self, members: &[&str], metadata: &[(&str, &str)]) -> io::Result<()> {
+        self.datasource.init_workspace(members, metadata).await
+    }
+
+    async fn update_manifest(
+        &self,
+        crate_path: &str,
+        dependencies: &[(&str, &str)],
+        dev_dependencies: &[(&str, &str)],
+    ) -> io::Result<()> {
+        self.datasource
+            .update_manifest(crate_path, dependencies, dev_dependencies)
+            .await
+    }
+
+    async fn record_stamps(&self, stamps: &[Stamp]) -> io::Result<()> {
+        let stamps = stamps.iter().map(StampModel::from).collect::<Vec<_>>();
+        self.datasource.record_stamps(&stamps).await
+    }
+}
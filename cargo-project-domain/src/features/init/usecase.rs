@@ -0,0 +1,382 @@
+use std::io;
+
+use async_trait::async_trait;
+use futures::{join, try_join};
+
+use super::repository::{InitRepository, Repository};
+
+use crate::core::baseline::baseline_path;
+use crate::core::layer::Layer;
+use crate::core::project::ProjectKind;
+use crate::core::report::{ErrorCode, Report};
+use crate::core::runtime::Runtime;
+use crate::core::stamp::Stamp;
+use crate::core::templates::mod_file::ModFileTemplate;
+use crate::core::templates::{Rendered, Template};
+use crate::core::utils::current_project;
+
+#[async_trait]
+pub trait Usecase {
+    async fn init_project(&self) -> Report;
+}
+
+/// Converts the package in the current directory into the presentation layer of a
+/// new workspace, leaving its code untouched.
+pub struct InitUsecase {
+    project: String,
+    runtime: Runtime,
+    repository: Box<dyn Repository + Send + Sync>,
+}
+
+impl InitUsecase {
+    pub fn new(runtime: Runtime) -> Self {
+        let repository = Box::new(InitRepository::new());
+        Self {
+            project: current_project(),
+            runtime,
+            repository,
+        }
+    }
+
+    async fn kind(&self) -> ProjectKind {
+        let (main, lib) = join!(
+            self.repository.exists("src/main.rs"),
+            self.repository.exists("src/lib.rs"),
+        );
+
+        if lib && !main {
+            ProjectKind::Library
+        } else {
+            ProjectKind::Binary
+        }
+    }
+
+    async fn create_layers(&self, layers: &[Layer], report: &mut Report) -> io::Result<()> {
+        for layer in layers {
+            let files = layer
+                .files(&self.project, &[], self.runtime)
+                .map_err(|err| io::Error::other(err.to_string()))?;
+
+            for (path, rendered) in &files {
+                self.repository
+                    .write_file(path, rendered.content.as_bytes())
+                    .await?;
+                report.written(path, false);
+            }
+
+            // Manifests get the layer's dependencies once written
+            let generated = files
+                .iter()
+                .filter(|(path, _)| !path.ends_with("/Cargo.toml"))
+                .map(|(path, rendered)| (path.clone(), rendered))
+                .collect::<Vec<_>>();
+            self.record_generated(&generated).await?;
+        }
+
+        Ok(())
+    }
+
+    /// Records the baseline and stamp of the generated `files`, so `upgrade` can merge newer
+    /// templates into them and `status` can report on them.
+    async fn record_generated(&self, files: &[(String, &Rendered)]) -> io::Result<()> {
+        for (path, rendered) in files {
+            self.repository
+                .write_file(&baseline_path(path), rendered.content.as_bytes())
+                .await?;
+        }
+
+        let stamps = files
+            .iter()
+            .map(|(path, rendered)| Stamp::new(path, rendered))
+            .collect::<Vec<_>>();
+        self.repository.record_stamps(&stamps).await
+    }
+
+    async fn create_features_module(&self) -> io::Result<()> {
+        let path = "src/features/mod.rs";
+        let rendered = ModFileTemplate::new(vec![])
+            .rendered()
+            .map_err(|err| io::Error::other(err.to_string()))?;
+
+        self.repository
+            .write_file(path, rendered.content.as_bytes())
+            .await?;
+        self.record_generated(&[(path.to_string(), &rendered)])
+            .await
+    }
+
+    async fn update_manifests(&self, layers: &[Layer]) -> io::Result<()> {
+        let domain = &layers[1];
+        let data = &layers[2];
+
+        let domain_crate = domain.crate_name(&self.project);
+        let domain_dependency = format!("{{ path = \"{}\" }}", domain_crate);
+        let presentation_dependencies = [(domain_crate.as_str(), domain_dependency.as_str())];
+
+        let domain_dependencies = domain
+            .dependencies(&self.project, layers)
+            .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))?;
+        let mut domain_dependencies = domain_dependencies
+            .iter()
+            .map(|(name, value)| (name.as_str(), value.as_str()))
+            .collect::<Vec<_>>();
+        domain_dependencies.extend(self.runtime.dependencies());
+
+        let layer_dependencies = self.runtime.dependencies();
+        let layer_dev_dependencies = self.runtime.dev_dependencies();
+
+        let data_crate = data.crate_name(&self.project);
+        try_join!(
+            self.repository
+                .update_manifest(".", &presentation_dependencies, &[]),
+            self.repository.update_manifest(
+                &domain_crate,
+                &domain_dependencies,
+                &layer_dev_dependencies
+            ),
+            self.repository.update_manifest(
+                &data_crate,
+                &layer_dependencies,
+                &layer_dev_dependencies
+            ),
+        )?;
+
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl Usecase for InitUsecase {
+    async fn init_project(&self) -> Report {
+        let project = self.project.as_str();
+
+        if !self.repository.exists("Cargo.toml").await {
+            return Report::failure(
+                ErrorCode::NotInProject,
+                "Not currently in the root of a package",
+            );
+        }
+
+        let kind = self.kind().await;
+        let layers = Layer::defaults(kind);
+        let crate_names = layers[1..]
+            .iter()
+            .map(|layer| layer.crate_name(project))
+            .collect::<Vec<_>>();
+
+        for crate_name in &crate_names {
+            if self.repository.exists(crate_name).await {
+                return Report::failure(
+                    ErrorCode::AlreadyExists,
+                    format!(
+                        "Failed to initialize project: {} - {} already exists",
+                        project, crate_name
+                    ),
+                );
+            }
+        }
+
+        let mut members = vec!["."];
+        members.extend(crate_names.iter().map(String::as_str));
+        let metadata = [
+            ("runtime", self.runtime.name()),
+            ("kind", kind.name()),
+            ("presentation-path", "."),
+        ];
+
+        let mut report = Report::success(format!("Successfully initialized project: {}", project));
+        let result = self.repository.init_workspace(&members, &metadata).await;
+        report.written("Cargo.toml", true);
+
+        let result = match result {
+            Ok(_) => self.create_layers(&layers[1..], &mut report).await,
+            Err(err) => Err(err),
+        };
+
+        let result = match result {
+            Ok(_) => self.update_manifests(&layers).await,
+            Err(err) => Err(err),
+        };
+
+        // The features module only gets created, declaring it is left to the package
+        let has_features_module = self.repository.exists("src/features/mod.rs").await
+            || self.repository.exists("src/features.rs").await;
+        let result = match result {
+            Ok(_) if !has_features_module => self.create_features_module().await,
+            result => result,
+        };
+
+        match result {
+            Ok(_) if has_features_module => report,
+            Ok(_) => {
+                report.written("src/features/mod.rs", false);
+                report.notes.push(format!(
+                    "declare `mod features;` in src/{} to build the modules generated by `add feature`",
+                    match kind {
+                        ProjectKind::Binary => "main.rs",
+                        ProjectKind::Library => "lib.rs",
+                    }
+                ));
+                report
+            }
+            Err(err) => Report::failure(
+                ErrorCode::Failed,
+                format!("Failed to initialize project: {} - {}", project, err),
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::sync::{Arc, Mutex};
+
+    struct MockRepository {
+        existing: Vec<&'static str>,
+        calls: Arc<Mutex<Vec<String>>>,
+    }
+
+    #[async_trait]
+    impl Repository for MockRepository {
+        async fn exists(&self, path: &str) -> bool {
+            self.existing.contains(&path)
+        }
+
+        async fn write_file(&self, path: &str, _content: &[u8]) -> io::Result<()> {
+            self.calls.lock().unwrap().push(format!("write {}", path));
+            Ok(())
+        }
+
+        async fn init_workspace(
+            &self,
+            members: &[&str],
+            metadata: &[(&str, &str)],
+        ) -> io::Result<()> {
+            self.calls
+                .lock()
+                .unwrap()
+                .push(format!("workspace {:?} {:?}", members, metadata));
+            Ok(())
+        }
+
+        async fn update_manifest(
+            &self,
+            crate_path: &str,
+            dependencies: &[(&str, &str)],
+            _dev_dependencies: &[(&str, &str)],
+        ) -> io::Result<()> {
+            self.calls
+                .lock()
+                .unwrap()
+                .push(format!("manifest {} {:?}", crate_path, dependencies));
+            Ok(())
+        }
+
+        async fn record_stamps(&self, stamps: &[Stamp]) -> io::Result<()> {
+            let paths = stamps
+                .iter()
+                .map(|stamp| stamp.path.as_str())
+                .collect::<Vec<_>>();
+            self.calls
+                .lock()
+                .unwrap()
+                .push(format!("stamp {}", paths.join(", ")));
+            Ok(())
+        }
+    }
+
+    fn usecase(existing: Vec<&'static str>) -> (InitUsecase, Arc<Mutex<Vec<String>>>) {
+        let calls = Arc::new(Mutex::new(Vec::new()));
+        let repository = MockRepository {
+            existing,
+            calls: calls.clone(),
+        };
+        let usecase = InitUsecase {
+            project: String::from("test"),
+            runtime: Runtime::Sync,
+            repository: Box::new(repository),
+        };
+
+        (usecase, calls)
+    }
+
+    #[async_std::test]
+    async fn test_init_project() {
+        let (usecase, calls) = usecase(vec!["Cargo.toml", "src/main.rs", "src/features/mod.rs"]);
+
+        let got = usecase.init_project().await;
+
+        assert_eq!("Successfully initialized project: test", got.to_string());
+        assert_eq!(vec![String::from("Cargo.toml")], got.modified);
+        assert_eq!(8, got.created.len());
+        assert_eq!(
+            vec![
+                "workspace [\".\", \"test-domain\", \"test-data\"] [(\"runtime\", \"sync\"), (\"kind\", \"bin\"), (\"presentation-path\", \".\")]",
+                "write test-domain/Cargo.toml",
+                "write test-domain/src/lib.rs",
+                "write test-domain/src/core/mod.rs",
+                "write test-domain/src/features/mod.rs",
+                "write .cargo-project/baselines/test-domain/src/lib.rs",
+                "write .cargo-project/baselines/test-domain/src/core/mod.rs",
+                "write .cargo-project/baselines/test-domain/src/features/mod.rs",
+                "stamp test-domain/src/lib.rs, test-domain/src/core/mod.rs, test-domain/src/features/mod.rs",
+                "write test-data/Cargo.toml",
+                "write test-data/src/lib.rs",
+                "write test-data/src/core/mod.rs",
+                "write test-data/src/features/mod.rs",
+                "write .cargo-project/baselines/test-data/src/lib.rs",
+                "write .cargo-project/baselines/test-data/src/core/mod.rs",
+                "write .cargo-project/baselines/test-data/src/features/mod.rs",
+                "stamp test-data/src/lib.rs, test-data/src/core/mod.rs, test-data/src/features/mod.rs",
+                "manifest . [(\"test-domain\", \"{ path = \\\"test-domain\\\" }\")]",
+                "manifest test-domain [(\"test-data\", \"{ path = \\\"../test-data\\\" }\")]",
+                "manifest test-data []",
+            ],
+            *calls.lock().unwrap()
+        );
+    }
+
+    #[async_std::test]
+    async fn test_init_library() {
+        let (usecase, calls) = usecase(vec!["Cargo.toml", "src/lib.rs"]);
+
+        let got = usecase.init_project().await;
+
+        let got = got.to_string();
+        assert!(got.starts_with("Successfully initialized project: test\n"));
+        assert!(got.contains("note: declare `mod features;` in src/lib.rs"));
+        let calls = calls.lock().unwrap();
+        assert!(calls[0].contains("(\"kind\", \"lib\")"));
+        assert_eq!(
+            vec![
+                "write src/features/mod.rs",
+                "write .cargo-project/baselines/src/features/mod.rs",
+                "stamp src/features/mod.rs"
+            ],
+            calls[calls.len() - 3..]
+        );
+    }
+
+    #[async_std::test]
+    async fn test_init_project_existing_layer() {
+        let (usecase, calls) = usecase(vec!["Cargo.toml", "test-data"]);
+
+        let expect = "Failed to initialize project: test - test-data already exists";
+        let got = usecase.init_project().await;
+
+        assert_eq!(expect, got.to_string());
+        assert!(calls.lock().unwrap().is_empty());
+    }
+
+    #[async_std::test]
+    async fn test_init_project_without_package() {
+        let (usecase, _) = usecase(vec![]);
+
+        let expect = "Not currently in the root of a package";
+        let got = usecase.init_project().await;
+
+        assert_eq!(expect, got.to_string())
+    }
+}
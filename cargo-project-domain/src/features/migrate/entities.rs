@@ -0,0 +1,237 @@
+use std::str::FromStr;
+
+use crate::core::source::{ItemKind, SourceItem};
+
+/// Layer file an item of a migrated module is moved to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Role {
+    Entity,
+    Repository,
+    Usecase,
+    Datasource,
+    Presentation,
+}
+
+/// Name suffixes hinting at a role, checked before the content of an item.
+const NAME_HINTS: &[(Role, &[&str])] = &[
+    (
+        Role::Datasource,
+        &[
+            "Datasource",
+            "DataSource",
+            "Model",
+            "Dao",
+            "Store",
+            "Client",
+        ],
+    ),
+    (Role::Repository, &["Repository", "Repo"]),
+    (
+        Role::Usecase,
+        &["Usecase", "UseCase", "Service", "Interactor"],
+    ),
+    (
+        Role::Presentation,
+        &["Command", "Args", "Cli", "View", "Handler", "Controller"],
+    ),
+];
+
+/// Code hinting at a role when the name doesn't.
+const CONTENT_HINTS: &[(Role, &[&str])] = &[
+    (
+        Role::Presentation,
+        &[
+            "println!",
+            "eprintln!",
+            "print!",
+            "env::args",
+            "stdin()",
+            "StructOpt",
+            "clap::",
+        ],
+    ),
+    (
+        Role::Datasource,
+        &[
+            "fs::",
+            "File::",
+            "OpenOptions",
+            "TcpStream",
+            "reqwest",
+            "sqlx",
+            "diesel",
+        ],
+    ),
+];
+
+impl Role {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Role::Entity => "entity",
+            Role::Repository => "repository",
+            Role::Usecase => "usecase",
+            Role::Datasource => "datasource",
+            Role::Presentation => "presentation",
+        }
+    }
+
+    /// Module of the feature the role's items live in, the feature module itself for
+    /// the presentation.
+    fn module(&self) -> Option<&'static str> {
+        match self {
+            Role::Entity => Some("entities"),
+            Role::Repository => Some("repository"),
+            Role::Usecase => Some("usecase"),
+            Role::Datasource => Some("datasource"),
+            Role::Presentation => None,
+        }
+    }
+
+    fn is_domain(&self) -> bool {
+        matches!(self, Role::Entity | Role::Repository | Role::Usecase)
+    }
+
+    /// Crate directory, feature module directory and file name of the role's items,
+    /// relative to the workspace root.
+    pub fn location(
+        &self,
+        project: &str,
+        presentation_path: &str,
+        feature: &str,
+    ) -> (String, String, String) {
+        match self.module() {
+            Some(module) => {
+                let layer = if self.is_domain() { "domain" } else { "data" };
+                (
+                    format!("{}-{}", project, layer),
+                    format!("src/features/{}", feature),
+                    format!("{}.rs", module),
+                )
+            }
+            None => (
+                presentation_path.to_string(),
+                String::from("src/features"),
+                format!("{}.rs", feature),
+            ),
+        }
+    }
+
+    /// Path the role's items are imported from by `from` items, `None` when the layer
+    /// of `from` can't depend on this one.
+    pub fn import_path(&self, from: Role, project: &str, feature: &str) -> Option<String> {
+        let project = project.replace('-', "_");
+
+        match (from, self.module()) {
+            (from, Some(module)) if from.is_domain() && self.is_domain() => {
+                Some(format!("super::{}", module))
+            }
+            (from, Some(module)) if from.is_domain() => Some(format!(
+                "{}_data::features::{}::{}",
+                project, feature, module
+            )),
+            (Role::Presentation, Some(module)) if self.is_domain() => Some(format!(
+                "{}_domain::features::{}::{}",
+                project, feature, module
+            )),
+            (Role::Presentation, None) => Some(format!("crate::features::{}", feature)),
+            _ => None,
+        }
+    }
+
+    /// Guesses the role of a declared item from its name, then its content.
+    pub fn classify(item: &SourceItem) -> Role {
+        let hinted = |hints: &[(Role, &[&str])], matches: &dyn Fn(&str) -> bool| {
+            hints
+                .iter()
+                .find(|(_, patterns)| patterns.iter().any(|pattern| matches(pattern)))
+                .map(|(role, _)| *role)
+        };
+
+        if item.kind == ItemKind::Fn && item.name == "main" {
+            return Role::Presentation;
+        }
+
+        hinted(NAME_HINTS, &|suffix| item.name.ends_with(suffix))
+            .or_else(|| hinted(CONTENT_HINTS, &|code| item.text.contains(code)))
+            .unwrap_or(match item.kind {
+                ItemKind::Fn | ItemKind::Trait | ItemKind::Impl => Role::Usecase,
+                _ => Role::Entity,
+            })
+    }
+}
+
+impl FromStr for Role {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "entity" => Ok(Role::Entity),
+            "repository" => Ok(Role::Repository),
+            "usecase" => Ok(Role::Usecase),
+            "datasource" => Ok(Role::Datasource),
+            "presentation" => Ok(Role::Presentation),
+            _ => Err(format!("Unknown role: {}", s)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::core::source::source_items;
+
+    #[test]
+    fn test_classify() {
+        let source = "pub struct Note;
+struct NoteStore;
+trait NoteRepository {}
+fn count() -> usize { 0 }
+fn show() { println!(\"note\"); }
+fn load() { std::fs::read(\"notes\").unwrap(); }
+fn main() {}
+";
+
+        let got = source_items(source)
+            .iter()
+            .map(Role::classify)
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            vec![
+                Role::Entity,
+                Role::Datasource,
+                Role::Repository,
+                Role::Usecase,
+                Role::Presentation,
+                Role::Datasource,
+                Role::Presentation,
+            ],
+            got
+        );
+    }
+
+    #[test]
+    fn test_import_path() {
+        assert_eq!(
+            Some(String::from("super::entities")),
+            Role::Entity.import_path(Role::Usecase, "my-app", "notes")
+        );
+        assert_eq!(
+            Some(String::from("my_app_data::features::notes::datasource")),
+            Role::Datasource.import_path(Role::Repository, "my-app", "notes")
+        );
+        assert_eq!(
+            Some(String::from("my_app_domain::features::notes::usecase")),
+            Role::Usecase.import_path(Role::Presentation, "my-app", "notes")
+        );
+        assert_eq!(
+            None,
+            Role::Entity.import_path(Role::Datasource, "my-app", "notes")
+        );
+        assert_eq!(
+            None,
+            Role::Datasource.import_path(Role::Presentation, "my-app", "notes")
+        );
+    }
+}
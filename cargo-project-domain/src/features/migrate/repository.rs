@@ -0,0 +1,56 @@
+use async_trait::async_trait;
+use cargo_project_data::core::stamps::StampModel;
+use cargo_project_data::features::migrate::datasource::{Datasource, MigrateDatasource};
+use std::io;
+
+use crate::core::stamp::Stamp;
+
+#[async_trait]
+pub trait Repository {
+    async fn exists(&self, path: &str) -> bool;
+    async fn read_file(&self, path: &str) -> io::Result<String>;
+    async fn write_file(&self, path: &str, content: &[u8]) -> io::Result<()>;
+    async fn read_metadata(&self, key: &str) -> io::Result<Option<String>>;
+    async fn record_stamps(&self, stamps: &[Stamp]) -> io::Result<()>;
+}
+
+pub struct MigrateRepository {
+    datasource: Box<dyn Datasource + Send + Sync>,
+}
+
+impl MigrateRepository {
+    pub fn new() -> Self {
+        let datasource = Box::new(MigrateDatasource::new());
+        Self { datasource }
+    }
+}
+
+impl Default for MigrateRepository {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl Repository for MigrateRepository {
+    async fn exists(&self, path: &str) -> bool {
+        self.datasource.exists(path).await
+    }
+
+    async fn read_file(&self, path: &str) -> io::Result<String> {
+        self.datasource.read_file(path).await
+    }
+
+    async fn write_file(&self, path: &str, content: &[u8]) -> io::Result<()> {
+        self.datasource.write_file(path, content).await
+    }
+
+    async fn read_metadata(&self, key: &str) -> io::Result<Option<String>> {
+        self.datasource.read_metadata(key).await
+    }
+
+    async fn record_stamps(&self, stamps: &[Stamp]) -> io::Result<()> {
+        let stamps = stamps.iter().map(StampModel::from).collect::<Vec<_>>();
+        self.datasource.record_stamps(&stamps).await
+    }
+}
@@ -0,0 +1,1042 @@
+use std::collections::{BTreeMap, HashSet};
+use std::io;
+
+use async_trait::async_trait;
+
+use super::entities::Role;
+use super::repository::{MigrateRepository, Repository};
+
+use crate::core::baseline::baseline_path;
+use crate::core::report::{ErrorCode, Report};
+use crate::core::source::{identifiers, source_items, use_names, use_paths, ItemKind, SourceItem};
+use crate::core::stamp::Stamp;
+use crate::core::templates::mod_file::ModFileTemplate;
+use crate::core::templates::Template;
+use crate::core::utils::{current_project, in_root_directory};
+
+#[async_trait]
+pub trait Usecase {
+    async fn migrate_module(&self, module: &str, feature: &str, roles: &[(String, Role)])
+        -> Report;
+}
+
+/// Moves the items of an existing module into the layers of a feature.
+pub struct MigrateUsecase {
+    project: String,
+    repository: Box<dyn Repository + Send + Sync>,
+}
+
+impl MigrateUsecase {
+    pub fn new() -> Self {
+        let repository = Box::new(MigrateRepository::new());
+        Self {
+            project: current_project(),
+            repository,
+        }
+    }
+
+    async fn write_layers(
+        &self,
+        migration: &Migration,
+        presentation_path: &str,
+        feature: &str,
+        report: &mut Report,
+    ) -> io::Result<Vec<String>> {
+        let mut paths = Vec::new();
+
+        for (role, items) in &migration.items {
+            let (crate_dir, directory, file) =
+                role.location(&self.project, presentation_path, feature);
+            let path = format!("{}/{}/{}", crate_dir, directory, file);
+
+            let existed = self.repository.exists(&path).await;
+            let existing = if existed {
+                self.repository.read_file(&path).await?
+            } else {
+                String::new()
+            };
+            let content = merged(&existing, &migration.imports[role], items);
+            self.repository
+                .write_file(&path, content.as_bytes())
+                .await?;
+            report.written(&path, existed);
+
+            let mod_file = format!("{}/src/features/mod.rs", crate_dir);
+            self.declare_module(&mod_file, feature, report).await?;
+            if *role != Role::Presentation {
+                let module = file.trim_end_matches(".rs");
+                let mod_file = format!("{}/{}/mod.rs", crate_dir, directory);
+                self.declare_module(&mod_file, module, report).await?;
+            }
+
+            paths.push(path);
+        }
+
+        Ok(paths)
+    }
+
+    /// Appends a `pub mod` declaration of `module` to the mod file at `path` when missing.
+    async fn declare_module(
+        &self,
+        path: &str,
+        module: &str,
+        report: &mut Report,
+    ) -> io::Result<()> {
+        let existed = self.repository.exists(path).await;
+        let content = if existed {
+            self.repository.read_file(path).await?
+        } else {
+            String::new()
+        };
+        let declaration = ModFileTemplate::new(vec![module])
+            .render()
+            .map_err(|err| io::Error::other(err.to_string()))?;
+
+        if content
+            .lines()
+            .any(|line| line.trim() == declaration.trim())
+        {
+            return Ok(());
+        }
+
+        // A mod file declaring nothing else is rendered again, so it can be stamped
+        let rendered = match declared_modules(&content) {
+            Some(mut modules) => {
+                modules.push(module);
+                let rendered = ModFileTemplate::new(modules)
+                    .rendered()
+                    .map_err(|err| io::Error::other(err.to_string()))?;
+                Some(rendered)
+            }
+            None => None,
+        };
+        let content = match &rendered {
+            Some(rendered) => rendered.content.clone(),
+            None => format!("{}\n{}", content.trim_end(), declaration),
+        };
+        self.repository.write_file(path, content.as_bytes()).await?;
+        report.written(path, existed);
+
+        match rendered {
+            Some(rendered) => {
+                self.repository
+                    .write_file(&baseline_path(path), rendered.content.as_bytes())
+                    .await?;
+                self.repository
+                    .record_stamps(&[Stamp::new(path, &rendered)])
+                    .await
+            }
+            None => Ok(()),
+        }
+    }
+}
+
+impl Default for MigrateUsecase {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl Usecase for MigrateUsecase {
+    async fn migrate_module(
+        &self,
+        module: &str,
+        feature: &str,
+        roles: &[(String, Role)],
+    ) -> Report {
+        let project = self.project.as_str();
+
+        if !in_root_directory(project).await {
+            return Report::not_in_project();
+        }
+
+        if feature.is_empty()
+            || !feature
+                .chars()
+                .all(|character| character.is_ascii_lowercase() || character == '_')
+        {
+            return Report::failure(
+                ErrorCode::InvalidArgument,
+                format!("Invalid feature name: {}", feature),
+            );
+        }
+
+        let source = match self.repository.read_file(module).await {
+            Ok(source) => source,
+            Err(err) => {
+                return Report::failure(
+                    ErrorCode::Failed,
+                    format!("Failed to migrate module: {} - {}", module, err),
+                )
+            }
+        };
+
+        let presentation_path = match self.repository.read_metadata("presentation-path").await {
+            Ok(Some(path)) => path,
+            _ => project.to_string(),
+        };
+
+        let module_path = module_path(module, &presentation_path);
+        let migration = plan(&source, project, feature, module_path.as_deref(), roles);
+        if migration.items.is_empty() {
+            let mut report = Report::success(format!("Nothing to migrate in {}", module));
+            report.warnings.extend(migration.warnings);
+            return report;
+        }
+
+        let mut report = Report::success(format!(
+            "Successfully migrated {} to feature: {}",
+            module, feature
+        ));
+        let result = self
+            .write_layers(&migration, &presentation_path, feature, &mut report)
+            .await;
+        let result = match result {
+            Ok(paths) => self
+                .repository
+                .write_file(module, migration.remaining.as_bytes())
+                .await
+                .map(|_| paths),
+            Err(err) => Err(err),
+        };
+
+        match result {
+            Ok(paths) => {
+                report.written(module, true);
+                for (name, role) in &migration.moved {
+                    let index = migration.items.keys().position(|key| key == role).unwrap();
+                    report.details.push(format!("{} -> {}", name, paths[index]));
+                }
+                report.warnings.extend(migration.warnings);
+                report
+            }
+            Err(err) => Report::failure(
+                ErrorCode::Failed,
+                format!("Failed to migrate module: {} - {}", module, err),
+            ),
+        }
+    }
+}
+
+/// Items of a module split by the role they are moved to, and what is left of the module.
+#[derive(Debug, Default, PartialEq)]
+struct Migration {
+    imports: BTreeMap<Role, Vec<String>>,
+    items: BTreeMap<Role, Vec<String>>,
+    /// Names of the moved declarations and their role, in source order.
+    moved: Vec<(String, Role)>,
+    remaining: String,
+    warnings: Vec<String>,
+}
+
+/// Modules declared by a mod file holding nothing but `pub mod` declarations, as
+/// `ModFileTemplate` renders them.
+fn declared_modules(content: &str) -> Option<Vec<&str>> {
+    content
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| line.strip_prefix("pub mod ")?.strip_suffix(';'))
+        .collect()
+}
+
+fn declares(item: &SourceItem) -> bool {
+    !matches!(item.kind, ItemKind::Use | ItemKind::Impl | ItemKind::Mod) && !item.name.is_empty()
+}
+
+fn is_trait_impl(item: &SourceItem) -> bool {
+    let header = item.header();
+    let signature = &header[..header.find('{').unwrap_or(header.len())];
+
+    signature.contains(" for ")
+}
+
+/// Declares the methods of an inherent impl block public, they can't be called from
+/// the module they were moved out of otherwise.
+fn public_methods(text: &str) -> String {
+    let prefixes = ["fn ", "async fn ", "const fn ", "unsafe fn "];
+
+    text.lines()
+        .map(|line| {
+            let code = line.trim_start();
+            let indentation = &line[..line.len() - code.len()];
+
+            if indentation.len() == 4 && prefixes.iter().any(|prefix| code.starts_with(prefix)) {
+                format!("{}pub {}", indentation, code)
+            } else {
+                line.to_string()
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn use_statement(visibility: &str, path: &str, names: &[&str]) -> String {
+    let visibility = match visibility {
+        "" => String::new(),
+        visibility => format!("{} ", visibility),
+    };
+
+    match names {
+        [name] => format!("{}use {}::{};", visibility, path, name),
+        names => format!("{}use {}::{{{}}};", visibility, path, names.join(", ")),
+    }
+}
+
+fn joined(sections: &[String]) -> String {
+    let sections = sections
+        .iter()
+        .map(|section| section.trim())
+        .filter(|section| !section.is_empty())
+        .collect::<Vec<_>>();
+
+    match sections.is_empty() {
+        true => String::new(),
+        false => format!("{}\n", sections.join("\n\n")),
+    }
+}
+
+/// Appends `items` and the `imports` they need to the `existing` content of a layer file.
+fn merged(existing: &str, imports: &[String], items: &[String]) -> String {
+    let imports = imports
+        .iter()
+        .filter(|import| !existing.contains(import.as_str()))
+        .map(String::as_str)
+        .collect::<Vec<_>>();
+
+    joined(&[existing.to_string(), imports.join("\n"), items.join("\n\n")])
+}
+
+/// Whether the use declaration `item` brings any of `words` into scope.
+fn brings_any(item: &SourceItem, words: &HashSet<&str>) -> bool {
+    use_names(&item.text)
+        .iter()
+        .any(|name| name == "*" || words.contains(name.as_str()))
+}
+
+/// Path of the module in the file at `module` from the root of the presentation crate at
+/// `presentation_path`, `None` when the file isn't part of it.
+fn module_path(module: &str, presentation_path: &str) -> Option<String> {
+    let root = match presentation_path
+        .trim_start_matches("./")
+        .trim_end_matches('/')
+    {
+        "." | "" => String::from("src/"),
+        path => format!("{}/src/", path),
+    };
+    let file = module
+        .trim_start_matches("./")
+        .strip_prefix(&root)?
+        .strip_suffix(".rs")?;
+
+    match file.strip_suffix("/mod").unwrap_or(file) {
+        "lib" | "main" => Some(String::from("crate")),
+        file => Some(format!("crate::{}", file.replace('/', "::"))),
+    }
+}
+
+/// The use declaration `item` written for the file of `role`, `None` when it refers to the
+/// original crate in a way the file can't. Only the presentation shares the crate of the
+/// module at `module_path`, paths relative to that module get rewritten from its root.
+fn rewritten_use(item: &SourceItem, role: Role, module_path: Option<&str>) -> Option<String> {
+    let import = item.with_visibility("");
+    let relative = |path: &str| {
+        ["crate", "super", "self"]
+            .iter()
+            .any(|root| path == *root || path.starts_with(&format!("{}::", root)))
+    };
+
+    if !use_paths(&import).iter().any(|path| relative(path)) {
+        return Some(import);
+    }
+    let module_path = match (role, module_path) {
+        (Role::Presentation, Some(module_path)) => module_path,
+        _ => return None,
+    };
+
+    let (head, tree) = import.split_once("use ")?;
+    let mut segments = module_path.split("::").collect::<Vec<_>>();
+    let mut rest = tree;
+
+    if tree.starts_with("crate::") {
+        return Some(import);
+    } else if let Some(path) = tree.strip_prefix("self::") {
+        rest = path;
+    } else if tree.starts_with("super::") {
+        while let Some(path) = rest.strip_prefix("super::") {
+            segments.pop();
+            rest = path;
+        }
+    } else {
+        return None;
+    }
+
+    match segments.is_empty() {
+        true => None,
+        false => Some(format!("{}use {}::{}", head, segments.join("::"), rest)),
+    }
+}
+
+/// Items that can't move to the role `assigned` to them, with why. They either use paths
+/// the file of their role can't import or items it can't depend on, or are used by code
+/// left in the module that can't import them from their layer. Items using them stay too.
+fn staying<'a>(
+    items: &'a [SourceItem],
+    assigned: &[Option<Role>],
+    project: &str,
+    feature: &str,
+    module_path: Option<&str>,
+) -> Vec<(&'a str, String)> {
+    let uses = items
+        .iter()
+        .filter(|item| item.kind == ItemKind::Use)
+        .collect::<Vec<_>>();
+
+    // Items moving together, a declaration and its impl blocks, with the words they use
+    let mut units = Vec::<(&str, Role, HashSet<&str>)>::new();
+    let mut kept = HashSet::new();
+    for (item, role) in items.iter().zip(assigned) {
+        let role = match role {
+            Some(role) => *role,
+            None if item.kind != ItemKind::Use => {
+                kept.extend(identifiers(&item.text));
+                continue;
+            }
+            None => continue,
+        };
+
+        match units
+            .iter_mut()
+            .find(|(name, other, _)| *name == item.name && *other == role)
+        {
+            Some((_, _, words)) => words.extend(identifiers(&item.text)),
+            None => units.push((
+                &item.name,
+                role,
+                identifiers(&item.text).into_iter().collect(),
+            )),
+        }
+    }
+
+    let mut staying = Vec::<(&str, String)>::new();
+    loop {
+        let stays = |staying: &[(&str, String)], name: &str| {
+            staying.iter().any(|(staying, _)| *staying == name)
+        };
+        let mut kept = kept.clone();
+        for (name, _, words) in &units {
+            if stays(&staying, name) {
+                kept.extend(words);
+            }
+        }
+
+        let count = staying.len();
+        for (name, role, words) in &units {
+            if stays(&staying, name) {
+                continue;
+            }
+
+            let import = uses
+                .iter()
+                .filter(|item| brings_any(item, words))
+                .find(|item| rewritten_use(item, *role, module_path).is_none())
+                .map(|item| {
+                    format!(
+                        "it uses `{}`, which can't be imported in the {} file",
+                        item.with_visibility(""),
+                        role.name()
+                    )
+                });
+            let dependency = || {
+                units.iter().find_map(|(other, other_role, _)| {
+                    if other == name || !words.contains(other) {
+                        None
+                    } else if stays(&staying, other) {
+                        Some(format!("it uses {}, which stays as well", other))
+                    } else if other_role != role
+                        && other_role.import_path(*role, project, feature).is_none()
+                    {
+                        Some(format!(
+                            "the {} items can't use {}, which moves to the {} layer",
+                            role.name(),
+                            other,
+                            other_role.name()
+                        ))
+                    } else {
+                        None
+                    }
+                })
+            };
+            let user = || {
+                let declaration = items
+                    .iter()
+                    .find(|item| declares(item) && item.name == *name)?;
+                let used = !declaration.visibility().is_empty() || kept.contains(name);
+
+                (used
+                    && role
+                        .import_path(Role::Presentation, project, feature)
+                        .is_none())
+                .then(|| {
+                    format!(
+                        "the code using it can't import it from the {} layer",
+                        role.name()
+                    )
+                })
+            };
+
+            if let Some(reason) = import.or_else(dependency).or_else(user) {
+                staying.push((name, reason));
+            }
+        }
+
+        if staying.len() == count {
+            return staying;
+        }
+    }
+}
+
+/// Splits the items of `source`, the module at `module_path`, by role, `roles` overriding
+/// the guessed ones. Impl blocks follow the type they implement, nested modules, macros and
+/// the items that can't move stay where they are.
+fn plan(
+    source: &str,
+    project: &str,
+    feature: &str,
+    module_path: Option<&str>,
+    roles: &[(String, Role)],
+) -> Migration {
+    let items = source_items(source);
+    let mut migration = Migration::default();
+
+    for (name, _) in roles {
+        if !items
+            .iter()
+            .any(|item| declares(item) && &item.name == name)
+        {
+            migration
+                .warnings
+                .push(format!("{} isn't declared in the module", name));
+        }
+    }
+
+    let guessed = items
+        .iter()
+        .filter(|item| declares(item))
+        .map(|item| {
+            let role = roles
+                .iter()
+                .find(|(name, _)| name == &item.name)
+                .map(|(_, role)| *role)
+                .unwrap_or_else(|| Role::classify(item));
+
+            (item.name.as_str(), role)
+        })
+        .collect::<Vec<_>>();
+    let guessed_role = |name: &str| {
+        guessed
+            .iter()
+            .find(|(declared, _)| *declared == name)
+            .map(|(_, role)| *role)
+    };
+    let assigned = items
+        .iter()
+        .map(|item| match item.kind {
+            ItemKind::Use | ItemKind::Mod => None,
+            ItemKind::Impl => {
+                Some(guessed_role(&item.name).unwrap_or_else(|| Role::classify(item)))
+            }
+            _ if item.name.is_empty() => None,
+            _ => guessed_role(&item.name),
+        })
+        .collect::<Vec<_>>();
+
+    let staying = staying(&items, &assigned, project, feature, module_path);
+    for (name, reason) in &staying {
+        migration
+            .warnings
+            .push(format!("{} stays in the module, {}", name, reason));
+    }
+    let stays = |name: &str| staying.iter().any(|(staying, _)| *staying == name);
+
+    let declared = guessed
+        .iter()
+        .filter(|(name, _)| !stays(name))
+        .copied()
+        .collect::<Vec<_>>();
+    let role_of = |name: &str| {
+        declared
+            .iter()
+            .find(|(declared, _)| *declared == name)
+            .map(|(_, role)| *role)
+    };
+    let assigned = items
+        .iter()
+        .zip(assigned)
+        .map(|(item, role)| role.filter(|_| !stays(&item.name)))
+        .collect::<Vec<_>>();
+
+    for (item, role) in items.iter().zip(&assigned) {
+        let role = match role {
+            Some(role) => *role,
+            None => continue,
+        };
+        let text = match item.kind {
+            ItemKind::Impl if is_trait_impl(item) => item.text.clone(),
+            ItemKind::Impl => public_methods(&item.text),
+            _ => {
+                migration.moved.push((item.name.clone(), role));
+                item.with_visibility("pub")
+            }
+        };
+
+        migration.items.entry(role).or_default().push(text);
+    }
+
+    let uses = items
+        .iter()
+        .filter(|item| item.kind == ItemKind::Use)
+        .collect::<Vec<_>>();
+
+    for (role, texts) in &migration.items {
+        let body = texts.join("\n");
+        let words = identifiers(&body).into_iter().collect::<HashSet<_>>();
+
+        // The items using imports that can't be rewritten stay, so every one used is
+        let mut imports = uses
+            .iter()
+            .filter(|item| brings_any(item, &words))
+            .filter_map(|item| rewritten_use(item, *role, module_path))
+            .collect::<Vec<_>>();
+
+        let mut grouped = BTreeMap::<String, Vec<&str>>::new();
+        for (name, other) in &declared {
+            if other == role || !words.contains(name) {
+                continue;
+            }
+
+            if let Some(path) = other.import_path(*role, project, feature) {
+                grouped.entry(path).or_default().push(name);
+            }
+        }
+        for (path, names) in grouped {
+            imports.push(use_statement("", &path, &names));
+        }
+
+        migration.imports.insert(*role, imports);
+    }
+
+    let kept = items
+        .iter()
+        .zip(&assigned)
+        .filter(|(item, role)| role.is_none() && item.kind != ItemKind::Use)
+        .map(|(item, _)| item.text.as_str())
+        .collect::<Vec<_>>();
+    let kept_body = kept.join("\n");
+    let words = identifiers(&kept_body).into_iter().collect::<HashSet<_>>();
+
+    let kept_uses = uses
+        .iter()
+        .filter(|item| brings_any(item, &words))
+        .map(|item| item.text.as_str())
+        .collect::<Vec<_>>();
+
+    // Moved items stay reachable through the module, private ones only for its own code
+    let mut exports = BTreeMap::<(&str, String), Vec<&str>>::new();
+    for item in items.iter().filter(|item| declares(item)) {
+        let role = match role_of(&item.name) {
+            Some(role) => role,
+            None => continue,
+        };
+        let visibility = item.visibility();
+        if visibility.is_empty() && !words.contains(item.name.as_str()) {
+            continue;
+        }
+
+        if let Some(path) = role.import_path(Role::Presentation, project, feature) {
+            exports
+                .entry((visibility, path))
+                .or_default()
+                .push(&item.name);
+        }
+    }
+    let exports = exports
+        .iter()
+        .map(|((visibility, path), names)| use_statement(visibility, path, names))
+        .collect::<Vec<_>>();
+
+    migration.remaining = joined(&[kept_uses.join("\n"), exports.join("\n"), kept.join("\n\n")]);
+    migration.warnings.dedup();
+
+    migration
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::sync::{Arc, Mutex};
+
+    use crate::core::stamp::content_hash;
+
+    static SOURCE: &str = "use std::collections::HashMap;
+use std::fs;
+
+/// A note.
+#[derive(Debug)]
+pub struct Note {
+    pub text: String,
+}
+
+impl Note {
+    fn new(text: &str) -> Self {
+        Self { text: text.to_string() }
+    }
+}
+
+struct NoteStore {
+    path: String,
+}
+
+impl NoteStore {
+    fn load(&self) -> HashMap<String, String> {
+        let _ = fs::read_to_string(&self.path);
+        HashMap::new()
+    }
+}
+
+fn count(notes: &[Note]) -> usize {
+    notes.len()
+}
+
+pub fn print_notes(notes: &[Note]) {
+    println!(\"{}\", count(notes));
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn test_count() {}
+}
+";
+
+    #[test]
+    fn test_plan() {
+        let got = plan(SOURCE, "my-app", "notes", Some("crate::notes"), &[]);
+
+        assert_eq!(
+            vec![
+                (String::from("Note"), Role::Entity),
+                (String::from("NoteStore"), Role::Datasource),
+                (String::from("count"), Role::Usecase),
+                (String::from("print_notes"), Role::Presentation),
+            ],
+            got.moved
+        );
+        assert_eq!(
+            vec![
+                "/// A note.\n#[derive(Debug)]\npub struct Note {\n    pub text: String,\n}",
+                "impl Note {\n    pub fn new(text: &str) -> Self {\n        Self { text: text.to_string() }\n    }\n}",
+            ],
+            got.items[&Role::Entity]
+        );
+        assert_eq!(
+            vec!["use std::collections::HashMap;", "use std::fs;",],
+            got.imports[&Role::Datasource]
+        );
+        assert_eq!(
+            vec!["use super::entities::Note;"],
+            got.imports[&Role::Usecase]
+        );
+        assert_eq!(
+            vec![
+                "use my_app_domain::features::notes::entities::Note;",
+                "use my_app_domain::features::notes::usecase::count;",
+            ],
+            got.imports[&Role::Presentation]
+        );
+        assert!(got.warnings.is_empty());
+        assert_eq!(
+            "pub use crate::features::notes::print_notes;
+pub use my_app_domain::features::notes::entities::Note;
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn test_count() {}
+}
+",
+            got.remaining
+        );
+    }
+
+    #[test]
+    fn test_plan_overrides() {
+        let roles = [
+            (String::from("count"), Role::Presentation),
+            (String::from("missing"), Role::Entity),
+        ];
+
+        let got = plan(SOURCE, "my-app", "notes", Some("crate::notes"), &roles);
+
+        assert!(got
+            .moved
+            .contains(&(String::from("count"), Role::Presentation)));
+        assert!(!got.items.contains_key(&Role::Usecase));
+        assert!(got
+            .warnings
+            .contains(&String::from("missing isn't declared in the module")));
+    }
+
+    #[test]
+    fn test_plan_nothing_to_move() {
+        let got = plan(
+            "use std::fmt;\n\nmacro_rules! noop { () => {}; }\n",
+            "my-app",
+            "notes",
+            Some("crate::notes"),
+            &[],
+        );
+
+        assert!(got.items.is_empty());
+        assert_eq!("macro_rules! noop { () => {}; }\n", got.remaining);
+    }
+
+    static LEGACY_SOURCE: &str = "use super::config::Config;
+use crate::util::slug;
+
+pub struct Note {
+    pub text: String,
+}
+
+pub struct NoteStore {
+    pub path: String,
+}
+
+fn title(note: &Note) -> String {
+    slug(&note.text)
+}
+
+pub fn show(note: &Note) {
+    println!(\"{}\", title(note));
+}
+
+pub fn show_config(config: &Config) {
+    println!(\"{}\", slug(&config.name));
+}
+";
+
+    #[test]
+    fn test_plan_rewrites_imports() {
+        let got = plan(
+            LEGACY_SOURCE,
+            "my-app",
+            "notes",
+            Some("crate::legacy::notes"),
+            &[],
+        );
+
+        assert_eq!(
+            vec![
+                (String::from("Note"), Role::Entity),
+                (String::from("show_config"), Role::Presentation),
+            ],
+            got.moved
+        );
+        assert_eq!(
+            vec![
+                "use crate::legacy::config::Config;",
+                "use crate::util::slug;"
+            ],
+            got.imports[&Role::Presentation]
+        );
+        assert_eq!(
+            vec![
+                "NoteStore stays in the module, the code using it can't import it from the datasource layer",
+                "title stays in the module, it uses `use crate::util::slug;`, which can't be imported in the usecase file",
+                "show stays in the module, it uses title, which stays as well",
+            ],
+            got.warnings
+        );
+        assert_eq!(
+            "use crate::util::slug;
+
+pub use crate::features::notes::show_config;
+pub use my_app_domain::features::notes::entities::Note;
+
+pub struct NoteStore {
+    pub path: String,
+}
+
+fn title(note: &Note) -> String {
+    slug(&note.text)
+}
+
+pub fn show(note: &Note) {
+    println!(\"{}\", title(note));
+}
+",
+            got.remaining
+        );
+    }
+
+    #[test]
+    fn test_plan_outside_presentation() {
+        let got = plan(LEGACY_SOURCE, "my-app", "notes", None, &[]);
+
+        assert_eq!(vec![(String::from("Note"), Role::Entity)], got.moved);
+        assert!(got.warnings.contains(&String::from(
+            "show_config stays in the module, it uses `use super::config::Config;`, which can't be imported in the presentation file"
+        )));
+    }
+
+    #[test]
+    fn test_module_path() {
+        assert_eq!(
+            Some(String::from("crate::legacy::notes")),
+            module_path("src/legacy/notes.rs", ".")
+        );
+        assert_eq!(
+            Some(String::from("crate::legacy")),
+            module_path("./app/src/legacy/mod.rs", "app")
+        );
+        assert_eq!(Some(String::from("crate")), module_path("src/main.rs", "."));
+        assert_eq!(None, module_path("other/src/notes.rs", "."));
+    }
+
+    #[test]
+    fn test_merged() {
+        let got = merged(
+            "use std::fs;\n\npub struct Old;\n",
+            &[String::from("use std::fs;"), String::from("use std::io;")],
+            &[String::from("pub struct New;")],
+        );
+
+        assert_eq!(
+            "use std::fs;\n\npub struct Old;\n\nuse std::io;\n\npub struct New;\n",
+            got
+        );
+    }
+
+    struct MockRepository {
+        files: Arc<Mutex<BTreeMap<String, String>>>,
+        stamps: Arc<Mutex<Vec<Stamp>>>,
+    }
+
+    #[async_trait]
+    impl Repository for MockRepository {
+        async fn exists(&self, path: &str) -> bool {
+            self.files.lock().unwrap().contains_key(path)
+        }
+
+        async fn read_file(&self, path: &str) -> io::Result<String> {
+            self.files
+                .lock()
+                .unwrap()
+                .get(path)
+                .cloned()
+                .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "No such file"))
+        }
+
+        async fn write_file(&self, path: &str, content: &[u8]) -> io::Result<()> {
+            self.files.lock().unwrap().insert(
+                path.to_string(),
+                String::from_utf8_lossy(content).to_string(),
+            );
+            Ok(())
+        }
+
+        async fn read_metadata(&self, key: &str) -> io::Result<Option<String>> {
+            Ok(match key {
+                "presentation-path" => Some(String::from(".")),
+                _ => None,
+            })
+        }
+
+        async fn record_stamps(&self, stamps: &[Stamp]) -> io::Result<()> {
+            self.stamps.lock().unwrap().extend_from_slice(stamps);
+            Ok(())
+        }
+    }
+
+    #[async_std::test]
+    async fn test_write_layers() {
+        let files = Arc::new(Mutex::new(BTreeMap::new()));
+        files.lock().unwrap().insert(
+            String::from("test-domain/src/features/mod.rs"),
+            String::from("pub mod notes;\n"),
+        );
+        let stamps = Arc::new(Mutex::new(Vec::new()));
+        let usecase = MigrateUsecase {
+            project: String::from("test"),
+            repository: Box::new(MockRepository {
+                files: files.clone(),
+                stamps: stamps.clone(),
+            }),
+        };
+        let migration = plan(SOURCE, "test", "notes", Some("crate::notes"), &[]);
+
+        let mut report = Report::default();
+        let got = usecase
+            .write_layers(&migration, ".", "notes", &mut report)
+            .await
+            .unwrap();
+
+        assert_eq!(
+            vec![
+                "test-domain/src/features/notes/entities.rs",
+                "test-domain/src/features/notes/usecase.rs",
+                "test-data/src/features/notes/datasource.rs",
+                "./src/features/notes.rs",
+            ],
+            got
+        );
+        assert_eq!(
+            vec![
+                "test-domain/src/features/notes/entities.rs",
+                "test-domain/src/features/notes/mod.rs",
+                "test-domain/src/features/notes/usecase.rs",
+                "test-data/src/features/notes/datasource.rs",
+                "test-data/src/features/mod.rs",
+                "test-data/src/features/notes/mod.rs",
+                "./src/features/notes.rs",
+                "./src/features/mod.rs",
+            ],
+            report.created
+        );
+        assert!(report.modified.is_empty());
+
+        let files = files.lock().unwrap();
+        assert_eq!("pub mod notes;\n", files["test-domain/src/features/mod.rs"]);
+        assert_eq!(
+            "pub mod entities;\npub mod usecase;\n",
+            files["test-domain/src/features/notes/mod.rs"]
+        );
+        assert_eq!("pub mod notes;\n", files["./src/features/mod.rs"]);
+        let stamps = stamps
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|stamp| (stamp.path.clone(), stamp.hash.clone()))
+            .collect::<BTreeMap<_, _>>();
+        assert_eq!(
+            vec![
+                "src/features/mod.rs",
+                "test-data/src/features/mod.rs",
+                "test-data/src/features/notes/mod.rs",
+                "test-domain/src/features/notes/mod.rs",
+            ],
+            stamps.keys().collect::<Vec<_>>()
+        );
+        assert_eq!(
+            content_hash(&files["./src/features/mod.rs"]),
+            stamps["src/features/mod.rs"]
+        );
+        for (path, hash) in stamps.iter().skip(1) {
+            assert_eq!(&content_hash(&files[path]), hash);
+        }
+        assert!(files["./src/features/notes.rs"].starts_with(
+            "use test_domain::features::notes::entities::Note;\nuse test_domain::features::notes::usecase::count;\n\npub fn print_notes"
+        ));
+    }
+}
@@ -1,2 +1,13 @@
 pub mod add;
+pub mod apply;
+pub mod completions;
+pub mod docs;
+pub mod export_spec;
+pub mod graph;
+pub mod init;
+pub mod migrate;
 pub mod new;
+pub mod status;
+pub mod upgrade;
+pub mod vcs;
+pub mod verify;
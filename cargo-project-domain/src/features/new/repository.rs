@@ -2,16 +2,34 @@ use async_trait::async_trait;
 use std::io;
 use std::path::Path;
 
+use cargo_project_data::core::stamps::StampModel;
 use cargo_project_data::features::new::datasource::{Datasource, NewDatasource};
 
+use crate::core::stamp::Stamp;
+
 #[async_trait]
 pub trait Repository {
+    async fn enclosing_repository(&self) -> io::Result<Option<String>>;
+    async fn create_project_directory(&self) -> io::Result<()>;
     async fn create_git_repo(&self) -> io::Result<()>;
     async fn create_gitignore(&self, content: &[u8]) -> io::Result<()>;
     async fn create_cargo_file(&self, content: &[u8]) -> io::Result<()>;
-    async fn create_presentation_layer(&self, main_file_content: &[u8]) -> io::Result<()>;
+    async fn create_presentation_layer(
+        &self,
+        root_file_content: &[u8],
+        library: bool,
+    ) -> io::Result<()>;
     async fn create_domain_layer(&self, main_file_content: &[u8]) -> io::Result<()>;
     async fn create_data_layer(&self, main_file_content: &[u8]) -> io::Result<()>;
+    async fn update_manifest(
+        &self,
+        crate_name: &str,
+        dependencies: &[(&str, &str)],
+        dev_dependencies: &[(&str, &str)],
+    ) -> io::Result<()>;
+    async fn inherit_workspace_lints(&self, crate_name: &str) -> io::Result<()>;
+    async fn create_file(&self, path: &str, content: &[u8]) -> io::Result<()>;
+    async fn record_stamps(&self, stamps: &[Stamp]) -> io::Result<()>;
 }
 
 pub struct NewRepository<'a> {
@@ -27,6 +45,14 @@ impl<'a> NewRepository<'a> {
 
 #[async_trait]
 impl<'a> Repository for NewRepository<'a> {
+    async fn enclosing_repository(&self) -> io::Result<Option<String>> {
+        self.datasource.enclosing_repository().await
+    }
+
+    async fn create_project_directory(&self) -> io::Result<()> {
+        self.datasource.create_project_directory().await
+    }
+
     async fn create_git_repo(&self) -> io::Result<()> {
         self.datasource.create_git_repo().await
     }
@@ -39,9 +65,13 @@ impl<'a> Repository for NewRepository<'a> {
         self.datasource.create_cargo_file(content).await
     }
 
-    async fn create_presentation_layer(&self, main_file_content: &[u8]) -> io::Result<()> {
+    async fn create_presentation_layer(
+        &self,
+        root_file_content: &[u8],
+        library: bool,
+    ) -> io::Result<()> {
         self.datasource
-            .create_presentation_layer(main_file_content)
+            .create_presentation_layer(root_file_content, library)
             .await
     }
 
@@ -52,6 +82,30 @@ impl<'a> Repository for NewRepository<'a> {
     async fn create_data_layer(&self, main_file_content: &[u8]) -> io::Result<()> {
         self.datasource.create_data_layer(main_file_content).await
     }
+
+    async fn update_manifest(
+        &self,
+        crate_name: &str,
+        dependencies: &[(&str, &str)],
+        dev_dependencies: &[(&str, &str)],
+    ) -> io::Result<()> {
+        self.datasource
+            .update_manifest(crate_name, dependencies, dev_dependencies)
+            .await
+    }
+
+    async fn inherit_workspace_lints(&self, crate_name: &str) -> io::Result<()> {
+        self.datasource.inherit_workspace_lints(crate_name).await
+    }
+
+    async fn create_file(&self, path: &str, content: &[u8]) -> io::Result<()> {
+        self.datasource.create_file(path, content).await
+    }
+
+    async fn record_stamps(&self, stamps: &[Stamp]) -> io::Result<()> {
+        let stamps = stamps.iter().map(StampModel::from).collect::<Vec<_>>();
+        self.datasource.record_stamps(&stamps).await
+    }
 }
 
 #[cfg(test)]
@@ -62,6 +116,14 @@ mod tests {
 
     #[async_trait]
     impl Datasource for MockDatasource {
+        async fn enclosing_repository(&self) -> io::Result<Option<String>> {
+            Ok(Some(String::from("/monorepo")))
+        }
+
+        async fn create_project_directory(&self) -> io::Result<()> {
+            Ok(())
+        }
+
         async fn create_git_repo(&self) -> io::Result<()> {
             Ok(())
         }
@@ -74,7 +136,11 @@ mod tests {
             Ok(())
         }
 
-        async fn create_presentation_layer(&self, _main_file_content: &[u8]) -> io::Result<()> {
+        async fn create_presentation_layer(
+            &self,
+            _root_file_content: &[u8],
+            _library: bool,
+        ) -> io::Result<()> {
             Ok(())
         }
 
@@ -85,7 +151,39 @@ mod tests {
         async fn create_data_layer(&self, _main_file_content: &[u8]) -> io::Result<()> {
             Ok(())
         }
+
+        async fn update_manifest(
+            &self,
+            _crate_name: &str,
+            _dependencies: &[(&str, &str)],
+            _dev_dependencies: &[(&str, &str)],
+        ) -> io::Result<()> {
+            Ok(())
+        }
+
+        async fn inherit_workspace_lints(&self, _crate_name: &str) -> io::Result<()> {
+            Ok(())
+        }
+
+        async fn create_file(&self, _path: &str, _content: &[u8]) -> io::Result<()> {
+            Ok(())
+        }
+
+        async fn record_stamps(&self, _stamps: &[StampModel]) -> io::Result<()> {
+            Ok(())
+        }
+    }
+    #[async_std::test]
+    async fn test_enclosing_repository() {
+        let datasource = Box::new(MockDatasource {});
+        let repository = NewRepository { datasource };
+
+        let expect = Some(String::from("/monorepo"));
+        let got = repository.enclosing_repository().await.unwrap();
+
+        assert_eq!(expect, got)
     }
+
     #[async_std::test]
     async fn test_create_git_repo() {
         let datasource = Box::new(MockDatasource {});
@@ -125,7 +223,10 @@ mod tests {
         let repository = NewRepository { datasource };
 
         let expect = ();
-        let got = repository.create_presentation_layer(b"test").await.unwrap();
+        let got = repository
+            .create_presentation_layer(b"test", false)
+            .await
+            .unwrap();
 
         assert_eq!(expect, got)
     }
@@ -151,4 +252,14 @@ mod tests {
 
         assert_eq!(expect, got)
     }
+
+    #[async_std::test]
+    async fn test_update_manifest() {
+        let datasource = Box::new(MockDatasource {});
+        let repository = NewRepository { datasource };
+
+        let got = repository.update_manifest("test-data", &[], &[]).await;
+
+        assert!(got.is_ok())
+    }
 }
@@ -1,67 +1,270 @@
 use async_trait::async_trait;
 use futures::try_join;
+use std::env;
+use std::error::Error;
+use std::io;
 use std::path::Path;
 
 use super::repository::{NewRepository, Repository};
 
+use crate::core::baseline::baseline_path;
+use crate::core::layer::Layer;
+use crate::core::project::{ProjectKind, Vcs};
+use crate::core::report::{ErrorCode, Report};
+use crate::core::runtime::Runtime;
+use crate::core::stamp::Stamp;
 use crate::core::templates::cargo::CargoTemplate;
+use crate::core::templates::ci::clippy::ClippyTemplate;
+use crate::core::templates::ci::rustfmt::RustfmtTemplate;
+use crate::core::templates::ci::workflow::WorkflowTemplate;
+use crate::core::templates::gitignore::GitignoreTemplate;
 use crate::core::templates::lib::LibFileTemplate;
 use crate::core::templates::main::MainFileTemplate;
-use crate::core::templates::Template;
+use crate::core::templates::{Rendered, Template};
 
 #[async_trait]
 pub trait Usecase {
-    async fn create_project(&self) -> String;
+    async fn create_project(&self) -> Report;
 }
 
 pub struct NewUsecase<'a> {
     project_name: &'a str,
+    /// Directory the project is created in.
+    project_path: &'a Path,
+    runtime: Runtime,
+    kind: ProjectKind,
+    /// Detected from the directory the project is created in when not given.
+    vcs: Option<Vcs>,
+    /// Whether to add a CI workflow, formatting and lint configuration.
+    with_ci: bool,
     repository: Box<dyn Repository + Send + Sync + 'a>,
 }
 
 impl<'a> NewUsecase<'a> {
-    pub fn new(project_name: &'a str, project_path: &'a Path) -> Self {
+    pub fn new(
+        project_name: &'a str,
+        project_path: &'a Path,
+        runtime: Runtime,
+        kind: ProjectKind,
+        vcs: Option<Vcs>,
+        with_ci: bool,
+    ) -> Self {
         let repository = Box::new(NewRepository::new(project_name, project_path));
         Self {
             project_name,
+            project_path,
+            runtime,
+            kind,
+            vcs,
+            with_ci,
             repository,
         }
     }
+
+    /// Path of `file` in the project as reported, relative to the current directory when
+    /// the project is created under it and absolute otherwise.
+    fn project_file(&self, file: &str) -> String {
+        let path = self.project_path.join(self.project_name).join(file);
+        let current_dir = env::current_dir().unwrap_or_default();
+        let path = path.strip_prefix(&current_dir).unwrap_or(&path);
+        let path = path.strip_prefix(".").unwrap_or(path);
+
+        path.display().to_string()
+    }
+
+    /// CI workflow and configuration files, relative to the project root.
+    fn ci_files(&self) -> Result<Vec<(&'static str, Rendered)>, Box<dyn Error>> {
+        let layers = Layer::defaults(self.kind);
+
+        Ok(vec![
+            (
+                ".github/workflows/ci.yml",
+                WorkflowTemplate::new(self.project_name, &layers).rendered()?,
+            ),
+            ("rustfmt.toml", RustfmtTemplate::new().rendered()?),
+            ("clippy.toml", ClippyTemplate::new().rendered()?),
+        ])
+    }
+
+    async fn create_ci_files(&self, files: &[(&str, Rendered)]) -> io::Result<()> {
+        for (path, rendered) in files {
+            self.repository
+                .create_file(path, rendered.content.as_bytes())
+                .await?;
+        }
+
+        for layer in Layer::defaults(self.kind) {
+            self.repository
+                .inherit_workspace_lints(&layer.crate_name(self.project_name))
+                .await?;
+        }
+
+        Ok(())
+    }
+
+    /// Records the baseline and stamp of the generated `files`, so `upgrade` can merge newer
+    /// templates into them and `status` can report on them.
+    async fn record_generated(&self, files: &[(String, &Rendered)]) -> io::Result<()> {
+        for (path, rendered) in files {
+            self.repository
+                .create_file(&baseline_path(path), rendered.content.as_bytes())
+                .await?;
+        }
+
+        let stamps = files
+            .iter()
+            .map(|(path, rendered)| Stamp::new(path, rendered))
+            .collect::<Vec<_>>();
+        self.repository.record_stamps(&stamps).await
+    }
+
+    /// Version control to set up, git unless the project is created inside an existing
+    /// repository, like `cargo new` does.
+    async fn resolve_vcs(&self, report: &mut Report) -> Vcs {
+        if let Some(vcs) = self.vcs {
+            return vcs;
+        }
+
+        match self.repository.enclosing_repository().await {
+            Ok(None) => Vcs::Git,
+            Ok(Some(root)) => {
+                report.notes.push(format!(
+                    "no git repository created, the project is inside the one at {}",
+                    root
+                ));
+                Vcs::NoVcs
+            }
+            Err(err) => {
+                report.warnings.push(format!(
+                    "no git repository created, running git failed - {}",
+                    err
+                ));
+                Vcs::NoVcs
+            }
+        }
+    }
+
+    async fn update_manifests(&self) -> io::Result<()> {
+        let domain_crate = format!("{}-domain", self.project_name);
+        let domain_dependency = format!("{{ path = \"../{}\" }}", domain_crate);
+        let data_crate = format!("{}-data", self.project_name);
+        let data_dependency = format!("{{ path = \"../{}\" }}", data_crate);
+
+        let mut presentation_dependencies =
+            vec![(domain_crate.as_str(), domain_dependency.as_str())];
+        if !self.kind.is_library() {
+            presentation_dependencies.extend(self.runtime.main_dependencies());
+        }
+
+        let mut domain_dependencies = vec![(data_crate.as_str(), data_dependency.as_str())];
+        domain_dependencies.extend(self.runtime.dependencies());
+
+        let layer_dependencies = self.runtime.dependencies();
+        let layer_dev_dependencies = self.runtime.dev_dependencies();
+
+        let future_presentation_manifest =
+            self.repository
+                .update_manifest(self.project_name, &presentation_dependencies, &[]);
+        let future_domain_manifest = self.repository.update_manifest(
+            &domain_crate,
+            &domain_dependencies,
+            &layer_dev_dependencies,
+        );
+        let future_data_manifest = self.repository.update_manifest(
+            &data_crate,
+            &layer_dependencies,
+            &layer_dev_dependencies,
+        );
+
+        try_join!(
+            future_presentation_manifest,
+            future_domain_manifest,
+            future_data_manifest
+        )?;
+
+        Ok(())
+    }
 }
 
 #[async_trait]
 impl<'a> Usecase for NewUsecase<'a> {
-    async fn create_project(&self) -> String {
-        self.repository.create_git_repo().await.expect(&format!(
-            "Error creating git repo for: {}",
+    async fn create_project(&self) -> Report {
+        let mut report = Report::success(format!(
+            "Successfully created project: {}",
             self.project_name
         ));
+        let vcs = self.resolve_vcs(&mut report).await;
+
+        if let Err(err) = self.repository.create_project_directory().await {
+            return Report::failure(
+                ErrorCode::Failed,
+                format!(
+                    "Failed to create directory for project: {} - {}",
+                    self.project_name, err
+                ),
+            );
+        }
+
+        if vcs == Vcs::Git {
+            if let Err(err) = self.repository.create_git_repo().await {
+                let mut failure = Report::failure(
+                    ErrorCode::Failed,
+                    format!(
+                        "Failed to create git repository for project: {} - {}",
+                        self.project_name, err
+                    ),
+                );
+                failure
+                    .notes
+                    .push(String::from("create it without one with `--vcs none`"));
+                return failure;
+            }
+        }
 
-        let future_gitignore = self.repository.create_gitignore(b"/target");
+        let gitignore = GitignoreTemplate::new()
+            .render()
+            .expect("Error rendering Gitignore template");
+        let future_gitignore = async {
+            match vcs {
+                Vcs::Git => self.repository.create_gitignore(gitignore.as_bytes()).await,
+                Vcs::NoVcs => Ok(()),
+            }
+        };
 
-        let cargo_file_content = CargoTemplate::new(self.project_name)
+        let members = Layer::defaults(self.kind)
+            .iter()
+            .map(|layer| layer.crate_name(self.project_name))
+            .collect();
+        let cargo_file_content = CargoTemplate::new(members, self.runtime, self.kind, self.with_ci)
             .render()
             .expect("Error rendering Cargo template");
         let future_cargo_file = self
             .repository
             .create_cargo_file(cargo_file_content.as_bytes());
 
-        let main_file_content = MainFileTemplate::new()
-            .render()
-            .expect("Error rendering MainFile template");
+        let lib_file = LibFileTemplate::new()
+            .rendered()
+            .expect("Error rendering LibFile template");
+
+        let (root_file, root_file_name) = match self.kind {
+            ProjectKind::Binary => (
+                MainFileTemplate::new(self.runtime)
+                    .rendered()
+                    .expect("Error rendering MainFile template"),
+                "main.rs",
+            ),
+            ProjectKind::Library => (lib_file.clone(), "lib.rs"),
+        };
         let future_presentation_layer = self
             .repository
-            .create_presentation_layer(main_file_content.as_bytes());
+            .create_presentation_layer(root_file.content.as_bytes(), self.kind.is_library());
 
-        let lib_file_content = LibFileTemplate::new()
-            .render()
-            .expect("Error rendering LibFile template");
         let future_domain_layer = self
             .repository
-            .create_domain_layer(lib_file_content.as_bytes());
+            .create_domain_layer(lib_file.content.as_bytes());
         let future_data_layer = self
             .repository
-            .create_data_layer(lib_file_content.as_bytes());
+            .create_data_layer(lib_file.content.as_bytes());
 
         try_join!(
             future_gitignore,
@@ -72,7 +275,87 @@ impl<'a> Usecase for NewUsecase<'a> {
         )
         .expect(&format!("Error creating project: {}", self.project_name));
 
-        format!("Successfully created project: {}", self.project_name)
+        let ci_files = match self.with_ci {
+            true => self.ci_files().expect("Error rendering CI templates"),
+            false => vec![],
+        };
+        if self.with_ci {
+            if let Err(err) = self.create_ci_files(&ci_files).await {
+                return Report::failure(
+                    ErrorCode::Failed,
+                    format!(
+                        "Failed to create CI configuration for project: {} - {:?}",
+                        self.project_name, err
+                    ),
+                );
+            }
+        }
+
+        let mut generated = vec![
+            (
+                format!("{}/src/{}", self.project_name, root_file_name),
+                &root_file,
+            ),
+            (
+                format!("{}-domain/src/lib.rs", self.project_name),
+                &lib_file,
+            ),
+            (format!("{}-data/src/lib.rs", self.project_name), &lib_file),
+        ];
+        generated.extend(
+            ci_files
+                .iter()
+                .map(|(path, rendered)| (path.to_string(), rendered)),
+        );
+        if let Err(err) = self.record_generated(&generated).await {
+            return Report::failure(
+                ErrorCode::Failed,
+                format!(
+                    "Failed to record generated files for project: {} - {:?}",
+                    self.project_name, err
+                ),
+            );
+        }
+
+        if let Err(err) = self.update_manifests().await {
+            return Report::failure(
+                ErrorCode::Failed,
+                format!(
+                    "Failed to add dependencies for project: {} - {:?}",
+                    self.project_name, err
+                ),
+            );
+        }
+
+        let gitignore = match vcs {
+            Vcs::Git => Some(".gitignore"),
+            Vcs::NoVcs => None,
+        };
+        let layer_files = Layer::defaults(self.kind)
+            .iter()
+            .flat_map(|layer| {
+                let crate_name = layer.crate_name(self.project_name);
+                let root_file = match layer.kind {
+                    ProjectKind::Binary => "main.rs",
+                    ProjectKind::Library => "lib.rs",
+                };
+                vec![
+                    format!("{}/Cargo.toml", crate_name),
+                    format!("{}/src/{}", crate_name, root_file),
+                    format!("{}/src/core/mod.rs", crate_name),
+                    format!("{}/src/features/mod.rs", crate_name),
+                ]
+            })
+            .collect::<Vec<_>>();
+        report.created = gitignore
+            .into_iter()
+            .chain(Some("Cargo.toml"))
+            .chain(ci_files.iter().map(|(path, _)| *path))
+            .chain(layer_files.iter().map(String::as_str))
+            .map(|file| self.project_file(file))
+            .collect();
+
+        report
     }
 }
 
@@ -80,10 +363,22 @@ impl<'a> Usecase for NewUsecase<'a> {
 mod tests {
     use super::*;
 
-    struct MockRepository {}
+    struct MockRepository {
+        enclosing_repository: Option<String>,
+        /// Whether the workspace declares lints for the layers to inherit.
+        workspace_lints: bool,
+    }
 
     #[async_trait]
     impl Repository for MockRepository {
+        async fn enclosing_repository(&self) -> std::io::Result<Option<String>> {
+            Ok(self.enclosing_repository.clone())
+        }
+
+        async fn create_project_directory(&self) -> std::io::Result<()> {
+            Ok(())
+        }
+
         async fn create_git_repo(&self) -> std::io::Result<()> {
             Ok(())
         }
@@ -98,7 +393,8 @@ mod tests {
 
         async fn create_presentation_layer(
             &self,
-            _main_file_content: &[u8],
+            _root_file_content: &[u8],
+            _library: bool,
         ) -> std::io::Result<()> {
             Ok(())
         }
@@ -110,20 +406,121 @@ mod tests {
         async fn create_data_layer(&self, _main_file_content: &[u8]) -> std::io::Result<()> {
             Ok(())
         }
+
+        async fn update_manifest(
+            &self,
+            _crate_name: &str,
+            _dependencies: &[(&str, &str)],
+            _dev_dependencies: &[(&str, &str)],
+        ) -> std::io::Result<()> {
+            Ok(())
+        }
+
+        async fn inherit_workspace_lints(&self, crate_name: &str) -> std::io::Result<()> {
+            match self.workspace_lints {
+                true => Ok(()),
+                false => Err(std::io::Error::other(format!(
+                    "No workspace lints for {} to inherit",
+                    crate_name
+                ))),
+            }
+        }
+
+        async fn create_file(&self, _path: &str, _content: &[u8]) -> std::io::Result<()> {
+            Ok(())
+        }
+
+        async fn record_stamps(&self, _stamps: &[Stamp]) -> std::io::Result<()> {
+            Ok(())
+        }
     }
 
     #[async_std::test]
     async fn test_create_project() {
         let project_name = "test";
-        let repository = Box::new(MockRepository {});
+        let repository = Box::new(MockRepository {
+            enclosing_repository: None,
+            workspace_lints: false,
+        });
         let usecase = NewUsecase {
             project_name,
+            project_path: Path::new("projects"),
+            runtime: Runtime::Tokio,
+            kind: ProjectKind::Binary,
+            vcs: None,
+            with_ci: false,
             repository,
         };
 
         let expect = String::from("Successfully created project: test");
         let got = usecase.create_project().await;
 
-        assert_eq!(expect, got)
+        assert_eq!(expect, got.to_string());
+        assert_eq!(
+            vec![
+                "projects/test/.gitignore",
+                "projects/test/Cargo.toml",
+                "projects/test/test/Cargo.toml",
+                "projects/test/test/src/main.rs",
+                "projects/test/test/src/core/mod.rs",
+                "projects/test/test/src/features/mod.rs",
+                "projects/test/test-domain/Cargo.toml",
+                "projects/test/test-domain/src/lib.rs",
+                "projects/test/test-domain/src/core/mod.rs",
+                "projects/test/test-domain/src/features/mod.rs",
+                "projects/test/test-data/Cargo.toml",
+                "projects/test/test-data/src/lib.rs",
+                "projects/test/test-data/src/core/mod.rs",
+                "projects/test/test-data/src/features/mod.rs",
+            ],
+            got.created
+        );
+    }
+
+    #[async_std::test]
+    async fn test_create_project_with_ci_in_repository() {
+        let repository = Box::new(MockRepository {
+            enclosing_repository: Some(String::from("/monorepo")),
+            workspace_lints: true,
+        });
+        let usecase = NewUsecase {
+            project_name: "test",
+            project_path: Path::new("."),
+            runtime: Runtime::Tokio,
+            kind: ProjectKind::Library,
+            vcs: None,
+            with_ci: true,
+            repository,
+        };
+
+        let got = usecase.create_project().await;
+
+        assert_eq!(
+            vec![String::from(
+                "no git repository created, the project is inside the one at /monorepo"
+            )],
+            got.notes
+        );
+        assert_eq!(
+            vec![
+                "test/Cargo.toml",
+                "test/.github/workflows/ci.yml",
+                "test/rustfmt.toml",
+                "test/clippy.toml",
+                "test/test/Cargo.toml",
+                "test/test/src/lib.rs",
+                "test/test/src/core/mod.rs",
+                "test/test/src/features/mod.rs",
+                "test/test-domain/Cargo.toml",
+                "test/test-domain/src/lib.rs",
+                "test/test-domain/src/core/mod.rs",
+                "test/test-domain/src/features/mod.rs",
+                "test/test-data/Cargo.toml",
+                "test/test-data/src/lib.rs",
+                "test/test-data/src/core/mod.rs",
+                "test/test-data/src/features/mod.rs",
+            ],
+            got.created
+        );
     }
 }
@@ -0,0 +1,53 @@
+use async_trait::async_trait;
+use cargo_project_data::features::status::datasource::{Datasource, StatusDatasource};
+use std::convert::TryFrom;
+use std::io;
+
+use crate::core::stamp::Stamp;
+
+#[async_trait]
+pub trait Repository {
+    async fn exists(&self, path: &str) -> bool;
+    async fn read_file(&self, path: &str) -> io::Result<String>;
+    async fn read_stamps(&self) -> io::Result<Vec<Stamp>>;
+}
+
+pub struct StatusRepository {
+    datasource: Box<dyn Datasource + Send + Sync>,
+}
+
+impl StatusRepository {
+    pub fn new() -> Self {
+        let datasource = Box::new(StatusDatasource::new());
+        Self { datasource }
+    }
+}
+
+impl Default for StatusRepository {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl Repository for StatusRepository {
+    async fn exists(&self, path: &str) -> bool {
+        self.datasource.exists(path).await
+    }
+
+    async fn read_file(&self, path: &str) -> io::Result<String> {
+        self.datasource.read_file(path).await
+    }
+
+    async fn read_stamps(&self) -> io::Result<Vec<Stamp>> {
+        self.datasource
+            .read_stamps()
+            .await?
+            .into_iter()
+            .map(|model| {
+                Stamp::try_from(model)
+                    .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))
+            })
+            .collect()
+    }
+}
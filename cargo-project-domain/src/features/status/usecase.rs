@@ -0,0 +1,223 @@
+use async_trait::async_trait;
+
+use super::repository::{Repository, StatusRepository};
+
+use crate::core::report::{ErrorCode, Report};
+use crate::core::stamp::{content_hash, Stamp};
+use crate::core::templates::template_version;
+use crate::core::utils::{current_project, in_root_directory};
+
+#[async_trait]
+pub trait Usecase {
+    async fn project_status(&self) -> Report;
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum FileState {
+    /// Unchanged since it was generated.
+    Pristine,
+    Modified,
+    Removed,
+}
+
+impl FileState {
+    fn of(stamp: &Stamp, content: Option<&str>) -> Self {
+        match content {
+            Some(content) if content_hash(content) == stamp.hash => FileState::Pristine,
+            Some(_) => FileState::Modified,
+            None => FileState::Removed,
+        }
+    }
+
+    fn name(&self) -> &'static str {
+        match self {
+            FileState::Pristine => "pristine",
+            FileState::Modified => "modified",
+            FileState::Removed => "removed",
+        }
+    }
+}
+
+/// Compares every generated file with the stamp recorded when it was generated.
+pub struct StatusUsecase {
+    project: String,
+    repository: Box<dyn Repository + Send + Sync>,
+}
+
+impl StatusUsecase {
+    pub fn new() -> Self {
+        let repository = Box::new(StatusRepository::new());
+        Self {
+            project: current_project(),
+            repository,
+        }
+    }
+
+    async fn content(&self, path: &str) -> Option<String> {
+        if !self.repository.exists(path).await {
+            return None;
+        }
+
+        self.repository.read_file(path).await.ok()
+    }
+}
+
+impl Default for StatusUsecase {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// The status line of a generated file, noting when its template changed since.
+fn status_line(stamp: &Stamp, state: FileState) -> String {
+    let version = template_version(&stamp.template);
+    let line = format!("{}: {}", state.name(), stamp.path);
+
+    if version > stamp.version {
+        format!(
+            "{} (outdated: {} v{}, now v{})",
+            line, stamp.template, stamp.version, version
+        )
+    } else {
+        line
+    }
+}
+
+#[async_trait]
+impl Usecase for StatusUsecase {
+    async fn project_status(&self) -> Report {
+        let project = self.project.as_str();
+
+        if !in_root_directory(project).await {
+            return Report::not_in_project();
+        }
+
+        let mut stamps = match self.repository.read_stamps().await {
+            Ok(stamps) => stamps,
+            Err(err) => {
+                return Report::failure(
+                    ErrorCode::Failed,
+                    format!("Failed to read project status: {} - {}", project, err),
+                )
+            }
+        };
+        if stamps.is_empty() {
+            return Report::success(format!(
+                "No generated files recorded in project: {}",
+                project
+            ));
+        }
+        stamps.sort_by(|a, b| a.path.cmp(&b.path));
+
+        let mut report = Report::success(format!("Status of project: {}", project));
+        let mut counts = [0; 3];
+        let mut outdated = 0;
+
+        for stamp in &stamps {
+            let content = self.content(&stamp.path).await;
+            let state = FileState::of(stamp, content.as_deref());
+
+            counts[state as usize] += 1;
+            if template_version(&stamp.template) > stamp.version {
+                outdated += 1;
+            }
+            report.details.push(status_line(stamp, state));
+        }
+
+        report.details.push(format!(
+            "{} pristine, {} modified, {} removed, {} from an outdated template",
+            counts[0], counts[1], counts[2], outdated
+        ));
+
+        report
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::collections::HashMap;
+    use std::io;
+
+    struct MockRepository {
+        files: HashMap<&'static str, &'static str>,
+        stamps: Vec<Stamp>,
+    }
+
+    #[async_trait]
+    impl Repository for MockRepository {
+        async fn exists(&self, path: &str) -> bool {
+            self.files.contains_key(path)
+        }
+
+        async fn read_file(&self, path: &str) -> io::Result<String> {
+            Ok(self.files[path].to_string())
+        }
+
+        async fn read_stamps(&self) -> io::Result<Vec<Stamp>> {
+            Ok(self.stamps.clone())
+        }
+    }
+
+    fn stamp(path: &str, content: &str, version: u32) -> Stamp {
+        Stamp {
+            path: path.to_string(),
+            template: String::from("LibFileTemplate"),
+            version,
+            hash: content_hash(content),
+        }
+    }
+
+    #[test]
+    fn test_file_state() {
+        let stamp = stamp("src/lib.rs", "pub mod features;\n", 1);
+
+        assert_eq!(
+            FileState::Pristine,
+            FileState::of(&stamp, Some("pub mod features;\n"))
+        );
+        assert_eq!(
+            FileState::Modified,
+            FileState::of(&stamp, Some("pub mod core;\n"))
+        );
+        assert_eq!(FileState::Removed, FileState::of(&stamp, None));
+    }
+
+    #[test]
+    fn test_status_line() {
+        let stamp = stamp("src/lib.rs", "", 2);
+
+        assert_eq!(
+            "modified: src/lib.rs",
+            status_line(&stamp, FileState::Modified)
+        );
+        assert_eq!(
+            "pristine: src/lib.rs (outdated: LibFileTemplate v1, now v2)",
+            status_line(
+                &Stamp {
+                    version: 1,
+                    ..stamp
+                },
+                FileState::Pristine
+            )
+        );
+    }
+
+    #[async_std::test]
+    async fn test_project_status() {
+        let repository = MockRepository {
+            files: HashMap::new(),
+            stamps: vec![],
+        };
+        let usecase = StatusUsecase {
+            project: String::from("test"),
+            repository: Box::new(repository),
+        };
+
+        let expect = "Not currently in the root of a project";
+        let got = usecase.project_status().await;
+
+        assert_eq!(expect, got.to_string())
+    }
+}
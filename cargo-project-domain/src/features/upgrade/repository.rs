@@ -0,0 +1,108 @@
+use async_trait::async_trait;
+use cargo_project_data::core::stamps::StampModel;
+use cargo_project_data::features::upgrade::datasource::{Datasource, UpgradeDatasource};
+use std::convert::TryFrom;
+use std::io;
+
+use crate::core::layer::Layer;
+use crate::core::stamp::Stamp;
+use crate::features::add::entities::FeatureRecord;
+
+#[async_trait]
+pub trait Repository {
+    async fn exists(&self, path: &str) -> bool;
+    async fn read_file(&self, path: &str) -> io::Result<String>;
+    async fn write_file(&self, path: &str, content: &[u8]) -> io::Result<()>;
+    async fn read_metadata(&self, key: &str) -> io::Result<Option<String>>;
+    async fn read_metadata_list(&self, key: &str) -> io::Result<Vec<String>>;
+    async fn read_features(&self) -> io::Result<Vec<FeatureRecord>>;
+    async fn read_layers(&self) -> io::Result<Vec<Layer>>;
+    async fn read_stamps(&self) -> io::Result<Vec<Stamp>>;
+    async fn record_stamps(&self, stamps: &[Stamp]) -> io::Result<()>;
+    async fn list_directories(&self, path: &str) -> io::Result<Vec<String>>;
+}
+
+pub struct UpgradeRepository {
+    datasource: Box<dyn Datasource + Send + Sync>,
+}
+
+impl UpgradeRepository {
+    pub fn new() -> Self {
+        let datasource = Box::new(UpgradeDatasource::new());
+        Self { datasource }
+    }
+}
+
+impl Default for UpgradeRepository {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl Repository for UpgradeRepository {
+    async fn exists(&self, path: &str) -> bool {
+        self.datasource.exists(path).await
+    }
+
+    async fn read_file(&self, path: &str) -> io::Result<String> {
+        self.datasource.read_file(path).await
+    }
+
+    async fn write_file(&self, path: &str, content: &[u8]) -> io::Result<()> {
+        self.datasource.write_file(path, content).await
+    }
+
+    async fn read_metadata(&self, key: &str) -> io::Result<Option<String>> {
+        self.datasource.read_metadata(key).await
+    }
+
+    async fn read_metadata_list(&self, key: &str) -> io::Result<Vec<String>> {
+        self.datasource.read_metadata_list(key).await
+    }
+
+    async fn read_features(&self) -> io::Result<Vec<FeatureRecord>> {
+        self.datasource
+            .read_features()
+            .await?
+            .into_iter()
+            .map(|model| {
+                FeatureRecord::try_from(model)
+                    .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))
+            })
+            .collect()
+    }
+
+    async fn read_layers(&self) -> io::Result<Vec<Layer>> {
+        self.datasource
+            .read_layers()
+            .await?
+            .into_iter()
+            .map(|model| {
+                Layer::try_from(model)
+                    .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))
+            })
+            .collect()
+    }
+
+    async fn read_stamps(&self) -> io::Result<Vec<Stamp>> {
+        self.datasource
+            .read_stamps()
+            .await?
+            .into_iter()
+            .map(|model| {
+                Stamp::try_from(model)
+                    .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))
+            })
+            .collect()
+    }
+
+    async fn record_stamps(&self, stamps: &[Stamp]) -> io::Result<()> {
+        let stamps = stamps.iter().map(StampModel::from).collect::<Vec<_>>();
+        self.datasource.record_stamps(&stamps).await
+    }
+
+    async fn list_directories(&self, path: &str) -> io::Result<Vec<String>> {
+        self.datasource.list_directories(path).await
+    }
+}
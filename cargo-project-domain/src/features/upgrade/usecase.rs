@@ -0,0 +1,549 @@
+use std::error::Error;
+use std::io;
+
+use async_trait::async_trait;
+use titlecase::titlecase;
+
+use super::repository::{Repository, UpgradeRepository};
+
+use crate::core::baseline::{baseline_path, merge};
+use crate::core::layer::Layer;
+use crate::core::presentation::{Presentation, PresentationFeature};
+use crate::core::project::{metadata_setting, ProjectKind};
+use crate::core::report::{ErrorCode, Report};
+use crate::core::runtime::Runtime;
+use crate::core::source::{struct_fields, usecase_methods};
+use crate::core::stamp::Stamp;
+use crate::core::templates::ci::clippy::ClippyTemplate;
+use crate::core::templates::ci::rustfmt::RustfmtTemplate;
+use crate::core::templates::ci::workflow::WorkflowTemplate;
+use crate::core::templates::lib::LibFileTemplate;
+use crate::core::templates::main::MainFileTemplate;
+use crate::core::templates::{Rendered, Template};
+use crate::core::utils::{current_project, in_root_directory};
+use crate::features::add::entities::FeatureFiles;
+
+#[async_trait]
+pub trait Usecase {
+    async fn upgrade_project(&self) -> Report;
+}
+
+/// What upgrading a generated file did to it.
+#[derive(Debug, PartialEq)]
+enum FileUpgrade {
+    Unchanged,
+    /// The file wasn't edited since it was generated and got replaced.
+    Updated,
+    Merged,
+    /// Merged, with the given number of regions left between conflict markers.
+    Conflicted(usize),
+    Skipped(&'static str),
+}
+
+/// Renders every generated file again with the current templates and merges the changes
+/// into them.
+pub struct UpgradeUsecase {
+    project: String,
+    repository: Box<dyn Repository + Send + Sync>,
+}
+
+impl UpgradeUsecase {
+    pub fn new() -> Self {
+        let repository = Box::new(UpgradeRepository::new());
+        Self {
+            project: current_project(),
+            repository,
+        }
+    }
+
+    /// Three-way merges the `upgraded` rendering of the file at `path` with the edits
+    /// made since its baseline was recorded, which then becomes `upgraded`.
+    async fn upgrade_file(&self, path: &str, upgraded: &str) -> io::Result<FileUpgrade> {
+        let baseline_path = baseline_path(path);
+
+        if !self.repository.exists(path).await {
+            return Ok(FileUpgrade::Skipped("removed"));
+        }
+        if !self.repository.exists(&baseline_path).await {
+            return Ok(FileUpgrade::Skipped("no baseline recorded"));
+        }
+
+        let baseline = self.repository.read_file(&baseline_path).await?;
+        if baseline == upgraded {
+            return Ok(FileUpgrade::Unchanged);
+        }
+
+        let current = self.repository.read_file(path).await?;
+        let (content, upgrade) = if current == baseline {
+            (upgraded.to_string(), FileUpgrade::Updated)
+        } else {
+            let merge = merge(&baseline, &current, upgraded);
+            let upgrade = match merge.conflicts {
+                _ if merge.content == current => FileUpgrade::Unchanged,
+                0 => FileUpgrade::Merged,
+                conflicts => FileUpgrade::Conflicted(conflicts),
+            };
+
+            (merge.content, upgrade)
+        };
+
+        self.repository.write_file(path, content.as_bytes()).await?;
+        self.repository
+            .write_file(&baseline_path, upgraded.as_bytes())
+            .await?;
+
+        Ok(upgrade)
+    }
+
+    /// Domain features as presentation crates see them.
+    async fn presentation_features(&self) -> io::Result<Vec<PresentationFeature>> {
+        let directory = format!("{}-domain/src/features", self.project);
+        let mut features = Vec::new();
+
+        for name in self.repository.list_directories(&directory).await? {
+            let usecase = self
+                .repository
+                .read_file(&format!("{}/{}/usecase.rs", directory, name))
+                .await?;
+            let entities = self
+                .repository
+                .read_file(&format!("{}/{}/entities.rs", directory, name))
+                .await?;
+
+            features.push(PresentationFeature {
+                fields: struct_fields(&entities, &titlecase(&name)),
+                methods: usecase_methods(&usecase),
+                name,
+            });
+        }
+
+        Ok(features)
+    }
+
+    /// Every file `new`, `init` and `add` generate in the project, rendered again with the
+    /// current templates.
+    async fn rendered_files(&self) -> Result<Vec<(String, Rendered)>, Box<dyn Error>> {
+        let project = self.project.as_str();
+        let runtime: Runtime = metadata_setting(self.repository.read_metadata("runtime").await)?;
+        let kind: ProjectKind = metadata_setting(self.repository.read_metadata("kind").await)?;
+        let presentation_path = self
+            .repository
+            .read_metadata("presentation-path")
+            .await?
+            .unwrap_or_else(|| project.to_string());
+        let presentations = self
+            .repository
+            .read_metadata_list("presentations")
+            .await?
+            .iter()
+            .map(|name| name.parse())
+            .collect::<Result<Vec<Presentation>, _>>()?;
+        let layers = self.repository.read_layers().await?;
+
+        let mut files = Vec::new();
+        for feature in self.repository.read_features().await? {
+            let rendered = FeatureFiles::render(
+                project,
+                &feature.name,
+                kind,
+                runtime,
+                feature.datasource,
+                &feature.usecases,
+            )?;
+
+            files.extend(
+                rendered
+                    .paths(project, &presentation_path, &feature.name)
+                    .into_iter()
+                    .map(|(path, rendered)| (path, rendered.clone())),
+            );
+        }
+
+        let root_file = match kind {
+            ProjectKind::Binary => ("main.rs", MainFileTemplate::new(runtime).rendered()?),
+            ProjectKind::Library => ("lib.rs", LibFileTemplate::new().rendered()?),
+        };
+        files.push((
+            format!("{}/src/{}", presentation_path, root_file.0),
+            root_file.1,
+        ));
+
+        let features = self.presentation_features().await?;
+        let names = features
+            .iter()
+            .map(|feature| feature.name.as_str())
+            .collect::<Vec<_>>();
+        // The domain and data crates `init` creates are layers of their own
+        for layer in Layer::defaults(kind)[1..].iter().chain(&layers) {
+            files.extend(layer.files(project, &names, runtime)?);
+        }
+        for presentation in &presentations {
+            files.extend(presentation.files(project, &features, runtime)?);
+        }
+
+        let mut workflow_layers = Layer::defaults(kind);
+        workflow_layers.extend(layers);
+        workflow_layers.extend(presentations.iter().map(Presentation::layer));
+        files.extend(vec![
+            (
+                String::from(".github/workflows/ci.yml"),
+                WorkflowTemplate::new(project, &workflow_layers).rendered()?,
+            ),
+            (
+                String::from("rustfmt.toml"),
+                RustfmtTemplate::new().rendered()?,
+            ),
+            (
+                String::from("clippy.toml"),
+                ClippyTemplate::new().rendered()?,
+            ),
+        ]);
+
+        Ok(files)
+    }
+
+    /// Upgrades every stamped file with its rendering by the current templates.
+    async fn upgrade_files(&self) -> Result<Vec<(String, FileUpgrade)>, String> {
+        let mut stamps = self
+            .repository
+            .read_stamps()
+            .await
+            .map_err(|err| err.to_string())?;
+        stamps.sort_by(|a, b| a.path.cmp(&b.path));
+        let files = self.rendered_files().await.map_err(|err| err.to_string())?;
+
+        let mut upgrades = Vec::new();
+        let mut upgraded = Vec::new();
+        for stamp in stamps {
+            let path = stamp.path;
+            // Stamps have their `./` trimmed, unlike paths in the presentation path `.`
+            let rendered = files
+                .iter()
+                .find(|(rendered, _)| rendered.trim_start_matches("./") == path)
+                .map(|(_, rendered)| rendered);
+            let rendered = match rendered {
+                Some(rendered) => rendered,
+                None => {
+                    upgrades.push((path, FileUpgrade::Skipped("not rendered by upgrade")));
+                    continue;
+                }
+            };
+
+            let upgrade = self
+                .upgrade_file(&path, &rendered.content)
+                .await
+                .map_err(|err| format!("{} - {}", path, err))?;
+
+            if !matches!(upgrade, FileUpgrade::Skipped(_)) {
+                upgraded.push(Stamp::new(&path, rendered));
+            }
+            upgrades.push((path, upgrade));
+        }
+
+        self.repository
+            .record_stamps(&upgraded)
+            .await
+            .map_err(|err| err.to_string())?;
+
+        Ok(upgrades)
+    }
+}
+
+impl Default for UpgradeUsecase {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl Usecase for UpgradeUsecase {
+    async fn upgrade_project(&self) -> Report {
+        let project = self.project.as_str();
+
+        if !in_root_directory(project).await {
+            return Report::not_in_project();
+        }
+
+        let upgrades = match self.upgrade_files().await {
+            Ok(upgrades) => upgrades,
+            Err(err) => {
+                return Report::failure(
+                    ErrorCode::Failed,
+                    format!("Failed to upgrade project: {} - {}", project, err),
+                )
+            }
+        };
+
+        if upgrades.is_empty() {
+            return Report::success(format!(
+                "No generated files to upgrade in project: {}",
+                project
+            ));
+        }
+
+        let mut report = Report::success(format!("Successfully upgraded project: {}", project));
+        let mut unchanged = 0;
+        let mut conflicted = 0;
+
+        for (path, upgrade) in &upgrades {
+            report.details.push(match upgrade {
+                FileUpgrade::Unchanged => {
+                    unchanged += 1;
+                    continue;
+                }
+                FileUpgrade::Updated => format!("updated: {}", path),
+                FileUpgrade::Merged => format!("merged: {}", path),
+                FileUpgrade::Conflicted(conflicts) => {
+                    conflicted += 1;
+                    format!("conflicted: {} ({} conflicts)", path, conflicts)
+                }
+                FileUpgrade::Skipped(reason) => {
+                    report.skipped.push(path.clone());
+                    format!("skipped: {} ({})", path, reason)
+                }
+            });
+
+            if !matches!(upgrade, FileUpgrade::Skipped(_)) {
+                report.written(path, true);
+            }
+        }
+
+        report
+            .details
+            .push(format!("{} files unchanged", unchanged));
+        if conflicted > 0 {
+            report.warnings.push(format!(
+                "resolve the conflict markers in {} files",
+                conflicted
+            ));
+        }
+
+        report
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::collections::HashMap;
+    use std::sync::{Arc, Mutex};
+
+    use crate::features::add::entities::FeatureRecord;
+
+    struct MockRepository {
+        files: Arc<Mutex<HashMap<String, String>>>,
+        stamps: Vec<Stamp>,
+        recorded: Arc<Mutex<Vec<Stamp>>>,
+    }
+
+    #[async_trait]
+    impl Repository for MockRepository {
+        async fn exists(&self, path: &str) -> bool {
+            self.files.lock().unwrap().contains_key(path)
+        }
+
+        async fn read_file(&self, path: &str) -> io::Result<String> {
+            self.files
+                .lock()
+                .unwrap()
+                .get(path)
+                .cloned()
+                .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "No such file"))
+        }
+
+        async fn write_file(&self, path: &str, content: &[u8]) -> io::Result<()> {
+            self.files.lock().unwrap().insert(
+                path.to_string(),
+                String::from_utf8_lossy(content).to_string(),
+            );
+            Ok(())
+        }
+
+        async fn read_metadata(&self, _key: &str) -> io::Result<Option<String>> {
+            Ok(None)
+        }
+
+        async fn read_metadata_list(&self, _key: &str) -> io::Result<Vec<String>> {
+            Ok(vec![])
+        }
+
+        async fn read_features(&self) -> io::Result<Vec<FeatureRecord>> {
+            Ok(vec![])
+        }
+
+        async fn read_layers(&self) -> io::Result<Vec<Layer>> {
+            Ok(vec![])
+        }
+
+        async fn read_stamps(&self) -> io::Result<Vec<Stamp>> {
+            Ok(self.stamps.clone())
+        }
+
+        async fn record_stamps(&self, stamps: &[Stamp]) -> io::Result<()> {
+            self.recorded.lock().unwrap().extend_from_slice(stamps);
+            Ok(())
+        }
+
+        async fn list_directories(&self, _path: &str) -> io::Result<Vec<String>> {
+            Ok(vec![])
+        }
+    }
+
+    fn usecase(files: &[(&str, &str)]) -> (UpgradeUsecase, Arc<Mutex<HashMap<String, String>>>) {
+        let files = Arc::new(Mutex::new(
+            files
+                .iter()
+                .map(|(path, content)| (path.to_string(), content.to_string()))
+                .collect(),
+        ));
+        let usecase = UpgradeUsecase {
+            project: String::from("test"),
+            repository: Box::new(MockRepository {
+                files: files.clone(),
+                stamps: vec![],
+                recorded: Arc::new(Mutex::new(vec![])),
+            }),
+        };
+
+        (usecase, files)
+    }
+
+    #[async_std::test]
+    async fn test_upgrade_file_unedited() {
+        let (usecase, files) = usecase(&[
+            ("src/a.rs", "fn a() {}\n"),
+            (".cargo-project/baselines/src/a.rs", "fn a() {}\n"),
+        ]);
+
+        let got = usecase
+            .upgrade_file("src/a.rs", "fn b() {}\n")
+            .await
+            .unwrap();
+
+        assert_eq!(FileUpgrade::Updated, got);
+        let files = files.lock().unwrap();
+        assert_eq!("fn b() {}\n", files["src/a.rs"]);
+        assert_eq!("fn b() {}\n", files[".cargo-project/baselines/src/a.rs"]);
+    }
+
+    #[async_std::test]
+    async fn test_upgrade_file_edited() {
+        let (usecase, files) = usecase(&[
+            ("src/a.rs", "fn a() { edited(); }\n\nfn b() {}\n"),
+            (
+                ".cargo-project/baselines/src/a.rs",
+                "fn a() {}\n\nfn b() {}\n",
+            ),
+        ]);
+
+        let got = usecase
+            .upgrade_file("src/a.rs", "fn a() {}\n\nfn b() { upgraded(); }\n")
+            .await
+            .unwrap();
+
+        assert_eq!(FileUpgrade::Merged, got);
+        assert_eq!(
+            "fn a() { edited(); }\n\nfn b() { upgraded(); }\n",
+            files.lock().unwrap()["src/a.rs"]
+        );
+    }
+
+    #[async_std::test]
+    async fn test_upgrade_file_conflict() {
+        let (usecase, _) = usecase(&[
+            ("src/a.rs", "fn a() { edited(); }\n"),
+            (".cargo-project/baselines/src/a.rs", "fn a() {}\n"),
+        ]);
+
+        let got = usecase
+            .upgrade_file("src/a.rs", "fn a() { upgraded(); }\n")
+            .await
+            .unwrap();
+
+        assert_eq!(FileUpgrade::Conflicted(1), got);
+    }
+
+    #[async_std::test]
+    async fn test_upgrade_file_skipped() {
+        let (usecase, files) = usecase(&[("src/a.rs", "fn a() {}\n")]);
+
+        let got = usecase
+            .upgrade_file("src/a.rs", "fn b() {}\n")
+            .await
+            .unwrap();
+        assert_eq!(FileUpgrade::Skipped("no baseline recorded"), got);
+
+        let got = usecase
+            .upgrade_file("src/b.rs", "fn b() {}\n")
+            .await
+            .unwrap();
+        assert_eq!(FileUpgrade::Skipped("removed"), got);
+
+        assert_eq!("fn a() {}\n", files.lock().unwrap()["src/a.rs"]);
+    }
+
+    #[async_std::test]
+    async fn test_upgrade_files() {
+        let old = Rendered {
+            template: "MainFileTemplate",
+            version: 1,
+            content: String::from("fn main() {}\n"),
+        };
+        let files = Arc::new(Mutex::new(
+            vec![
+                ("test/src/main.rs", "fn main() {}\n"),
+                (
+                    ".cargo-project/baselines/test/src/main.rs",
+                    "fn main() {}\n",
+                ),
+                ("src/other.rs", "fn main() {}\n"),
+            ]
+            .into_iter()
+            .map(|(path, content)| (path.to_string(), content.to_string()))
+            .collect(),
+        ));
+        let recorded = Arc::new(Mutex::new(vec![]));
+        let usecase = UpgradeUsecase {
+            project: String::from("test"),
+            repository: Box::new(MockRepository {
+                files: files.clone(),
+                stamps: vec![
+                    Stamp::new("test/src/main.rs", &old),
+                    Stamp::new("src/other.rs", &old),
+                ],
+                recorded: recorded.clone(),
+            }),
+        };
+
+        let got = usecase.upgrade_files().await.unwrap();
+
+        let expect = vec![
+            (
+                String::from("src/other.rs"),
+                FileUpgrade::Skipped("not rendered by upgrade"),
+            ),
+            (String::from("test/src/main.rs"), FileUpgrade::Updated),
+        ];
+        assert_eq!(expect, got);
+
+        let rendered = MainFileTemplate::new(Runtime::default())
+            .rendered()
+            .unwrap();
+        assert_eq!(rendered.content, files.lock().unwrap()["test/src/main.rs"]);
+        assert_eq!(
+            vec![Stamp::new("test/src/main.rs", &rendered)],
+            *recorded.lock().unwrap()
+        );
+    }
+
+    #[async_std::test]
+    async fn test_upgrade_project() {
+        let (usecase, _) = usecase(&[]);
+
+        let expect = "Not currently in the root of a project";
+        let got = usecase.upgrade_project().await;
+
+        assert_eq!(expect, got.to_string())
+    }
+}
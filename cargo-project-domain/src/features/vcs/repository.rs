@@ -0,0 +1,36 @@
+use async_trait::async_trait;
+use cargo_project_data::features::vcs::datasource::{Datasource, VcsDatasource};
+use std::io;
+
+#[async_trait]
+pub trait Repository {
+    async fn exists(&self, path: &str) -> bool;
+    async fn dirty_files(&self) -> io::Result<Option<Vec<String>>>;
+    async fn commit(&self, paths: &[String], message: &str) -> io::Result<()>;
+}
+
+pub struct VcsRepository {
+    datasource: Box<dyn Datasource + Send + Sync>,
+}
+
+impl VcsRepository {
+    pub fn new(root: &str) -> Self {
+        let datasource = Box::new(VcsDatasource::new(root));
+        Self { datasource }
+    }
+}
+
+#[async_trait]
+impl Repository for VcsRepository {
+    async fn exists(&self, path: &str) -> bool {
+        self.datasource.exists(path).await
+    }
+
+    async fn dirty_files(&self) -> io::Result<Option<Vec<String>>> {
+        self.datasource.dirty_files().await
+    }
+
+    async fn commit(&self, paths: &[String], message: &str) -> io::Result<()> {
+        self.datasource.commit(paths, message).await
+    }
+}
@@ -0,0 +1,221 @@
+use async_trait::async_trait;
+use cargo_project_data::core::stamps::STAMPS_FILE;
+
+use super::repository::{Repository, VcsRepository};
+
+use crate::core::baseline::baseline_path;
+use crate::core::report::{ErrorCode, Report};
+
+#[async_trait]
+pub trait Usecase {
+    async fn check_clean(&self) -> Report;
+    async fn commit_changes(&self, subject: &str, changes: &Report) -> Report;
+}
+
+/// Guards and records the changes commands make to the git repository of the project
+/// rooted in `root`, the paths they report being relative to it.
+pub struct VcsUsecase {
+    repository: Box<dyn Repository + Send + Sync>,
+}
+
+impl VcsUsecase {
+    pub fn new(root: &str) -> Self {
+        let repository = Box::new(VcsRepository::new(root));
+        Self { repository }
+    }
+}
+
+/// Commit message made of `subject` and what the command did and wrote.
+fn commit_message(subject: &str, changes: &Report) -> String {
+    let mut message = format!("{}\n", subject);
+
+    if !changes.details.is_empty() {
+        message.push('\n');
+        for detail in &changes.details {
+            message.push_str(&format!("- {}\n", detail));
+        }
+    }
+
+    for (title, paths) in [
+        ("Created", &changes.created),
+        ("Modified", &changes.modified),
+    ] {
+        if paths.is_empty() {
+            continue;
+        }
+
+        message.push_str(&format!("\n{}:\n", title));
+        for path in paths {
+            message.push_str(&format!("  {}\n", path));
+        }
+    }
+
+    message
+}
+
+#[async_trait]
+impl Usecase for VcsUsecase {
+    /// Fails when the worktree has uncommitted changes, passing outside a git repository.
+    async fn check_clean(&self) -> Report {
+        match self.repository.dirty_files().await {
+            Ok(Some(files)) if !files.is_empty() => {
+                let mut report =
+                    Report::failure(ErrorCode::DirtyWorktree, "Worktree has uncommitted changes");
+                report.details = files;
+                report
+            }
+            Ok(_) => Report::success("Worktree is clean"),
+            Err(err) => Report::failure(
+                ErrorCode::Failed,
+                format!("Failed to read worktree status - {}", err),
+            ),
+        }
+    }
+
+    /// Commits exactly the files created and modified in `changes`, along with the stamps
+    /// and baselines recorded for them, which commands update without reporting.
+    async fn commit_changes(&self, subject: &str, changes: &Report) -> Report {
+        let mut paths = changes
+            .created
+            .iter()
+            .chain(&changes.modified)
+            .cloned()
+            .collect::<Vec<_>>();
+
+        if paths.is_empty() {
+            return Report::success("Nothing to commit");
+        }
+
+        let message = commit_message(subject, changes);
+        let count = paths.len();
+
+        let records = paths
+            .iter()
+            .map(|path| baseline_path(path))
+            .chain(Some(STAMPS_FILE.to_string()))
+            .collect::<Vec<_>>();
+        for record in records {
+            if self.repository.exists(&record).await {
+                paths.push(record);
+            }
+        }
+
+        match self.repository.commit(&paths, &message).await {
+            Ok(_) => Report::success(format!("Committed {} files: {}", count, subject)),
+            Err(err) => Report::failure(
+                ErrorCode::Failed,
+                format!("Failed to commit changes - {}", err),
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::io;
+    use std::sync::{Arc, Mutex};
+
+    struct MockRepository {
+        dirty_files: Option<Vec<String>>,
+        committed: Arc<Mutex<Vec<String>>>,
+    }
+
+    #[async_trait]
+    impl Repository for MockRepository {
+        async fn exists(&self, path: &str) -> bool {
+            path != baseline_path("README.md")
+        }
+
+        async fn dirty_files(&self) -> io::Result<Option<Vec<String>>> {
+            Ok(self.dirty_files.clone())
+        }
+
+        async fn commit(&self, paths: &[String], _message: &str) -> io::Result<()> {
+            self.committed.lock().unwrap().extend_from_slice(paths);
+            Ok(())
+        }
+    }
+
+    fn usecase(dirty_files: Option<Vec<String>>) -> VcsUsecase {
+        VcsUsecase {
+            repository: Box::new(MockRepository {
+                dirty_files,
+                committed: Arc::new(Mutex::new(vec![])),
+            }),
+        }
+    }
+
+    #[async_std::test]
+    async fn test_check_clean() {
+        let dirty = usecase(Some(vec![String::from("test/src/main.rs")]))
+            .check_clean()
+            .await;
+
+        assert_eq!(Some(ErrorCode::DirtyWorktree), dirty.error);
+        assert_eq!(vec![String::from("test/src/main.rs")], dirty.details);
+        assert!(usecase(Some(vec![])).check_clean().await.success);
+        assert!(usecase(None).check_clean().await.success);
+    }
+
+    #[test]
+    fn test_commit_message() {
+        let mut changes = Report::success("Successfully added feature: notes");
+        changes
+            .details
+            .push(String::from("added field: Notes::title"));
+        changes.written("test-domain/src/features/notes/usecase.rs", false);
+        changes.written("test-domain/src/features/mod.rs", true);
+
+        assert_eq!(
+            concat!(
+                "cargo project add feature notes\n",
+                "\n",
+                "- added field: Notes::title\n",
+                "\n",
+                "Created:\n",
+                "  test-domain/src/features/notes/usecase.rs\n",
+                "\n",
+                "Modified:\n",
+                "  test-domain/src/features/mod.rs\n",
+            ),
+            commit_message("cargo project add feature notes", &changes)
+        );
+    }
+
+    #[async_std::test]
+    async fn test_commit_changes() {
+        let committed = Arc::new(Mutex::new(vec![]));
+        let usecase = VcsUsecase {
+            repository: Box::new(MockRepository {
+                dirty_files: None,
+                committed: committed.clone(),
+            }),
+        };
+        let mut changes = Report::success("Successfully generated docs");
+        changes.written("ARCHITECTURE.md", false);
+        changes.written("README.md", true);
+
+        let got = usecase.commit_changes("cargo project docs", &changes).await;
+        let nothing = usecase
+            .commit_changes(
+                "cargo project docs",
+                &Report::success("Docs are up to date"),
+            )
+            .await;
+
+        assert_eq!("Committed 2 files: cargo project docs", got.message);
+        assert_eq!("Nothing to commit", nothing.message);
+        // README.md has no baseline to commit
+        assert_eq!(
+            vec![
+                "ARCHITECTURE.md",
+                "README.md",
+                ".cargo-project/baselines/ARCHITECTURE.md",
+                ".cargo-project/stamps.toml",
+            ],
+            *committed.lock().unwrap()
+        );
+    }
+}
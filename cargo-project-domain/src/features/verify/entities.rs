@@ -0,0 +1,115 @@
+use cargo_project_data::features::verify::models::CargoOutputModel;
+
+/// Position of a compile error in the workspace.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Location {
+    /// Path relative to the workspace root.
+    pub path: String,
+    pub line: u32,
+    pub column: u32,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct CompileError {
+    /// Where the error is, unless it isn't about the code, like a dependency failing to
+    /// resolve.
+    pub location: Option<Location>,
+    pub message: String,
+}
+
+impl CompileError {
+    /// Parses a line of cargo's short message format, skipping anything but errors.
+    fn parse(line: &str) -> Option<Self> {
+        let line = line.trim();
+
+        // Summaries of the errors printed before them
+        if line.starts_with("error: could not compile") {
+            return None;
+        }
+
+        if line.starts_with("error") {
+            return Some(Self {
+                location: None,
+                message: line.to_string(),
+            });
+        }
+
+        let index = line.find(": error")?;
+        let mut position = line[..index].rsplitn(3, ':');
+        let column = position.next()?.parse().ok()?;
+        let line_number = position.next()?.parse().ok()?;
+        let path = position.next()?;
+
+        Some(Self {
+            location: Some(Location {
+                path: path.trim_start_matches("./").to_string(),
+                line: line_number,
+                column,
+            }),
+            message: line[index + 2..].to_string(),
+        })
+    }
+}
+
+/// Outcome of a cargo command, with the errors it reported.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CargoOutput {
+    pub success: bool,
+    pub errors: Vec<CompileError>,
+}
+
+impl From<CargoOutputModel> for CargoOutput {
+    fn from(model: CargoOutputModel) -> Self {
+        Self {
+            success: model.success,
+            errors: model
+                .messages
+                .lines()
+                .filter_map(CompileError::parse)
+                .collect(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cargo_output() {
+        let model = CargoOutputModel {
+            success: false,
+            messages: String::from(concat!(
+                "    Checking test v0.1.0 (/projects/test/test)\n",
+                "test/src/main.rs:2:9: warning: unused variable: `unused`\n",
+                "test/src/main.rs:4:5: error[E0425]: cannot find value `cli` in this scope\n",
+                "warning: `test` (bin \"test\") generated 1 warning\n",
+                "error: could not compile `test` (bin \"test\") due to 1 previous error\n",
+                "error: no matching package named `missing` found\n",
+            )),
+        };
+
+        assert_eq!(
+            CargoOutput {
+                success: false,
+                errors: vec![
+                    CompileError {
+                        location: Some(Location {
+                            path: String::from("test/src/main.rs"),
+                            line: 4,
+                            column: 5,
+                        }),
+                        message: String::from(
+                            "error[E0425]: cannot find value `cli` in this scope"
+                        ),
+                    },
+                    CompileError {
+                        location: None,
+                        message: String::from("error: no matching package named `missing` found"),
+                    },
+                ],
+            },
+            CargoOutput::from(model)
+        );
+    }
+}
@@ -0,0 +1,53 @@
+use async_trait::async_trait;
+use cargo_project_data::features::verify::datasource::{Datasource, VerifyDatasource};
+use std::convert::TryFrom;
+use std::io;
+
+use super::entities::CargoOutput;
+use crate::core::stamp::Stamp;
+
+#[async_trait]
+pub trait Repository {
+    async fn exists(&self, path: &str) -> bool;
+    async fn read_file(&self, path: &str) -> io::Result<String>;
+    async fn read_stamps(&self) -> io::Result<Vec<Stamp>>;
+    async fn cargo(&self, args: &[&str]) -> io::Result<CargoOutput>;
+}
+
+pub struct VerifyRepository {
+    datasource: Box<dyn Datasource + Send + Sync>,
+}
+
+impl VerifyRepository {
+    pub fn new(root: &str) -> Self {
+        let datasource = Box::new(VerifyDatasource::new(root));
+        Self { datasource }
+    }
+}
+
+#[async_trait]
+impl Repository for VerifyRepository {
+    async fn exists(&self, path: &str) -> bool {
+        self.datasource.exists(path).await
+    }
+
+    async fn read_file(&self, path: &str) -> io::Result<String> {
+        self.datasource.read_file(path).await
+    }
+
+    async fn read_stamps(&self) -> io::Result<Vec<Stamp>> {
+        self.datasource
+            .read_stamps()
+            .await?
+            .into_iter()
+            .map(|model| {
+                Stamp::try_from(model)
+                    .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))
+            })
+            .collect()
+    }
+
+    async fn cargo(&self, args: &[&str]) -> io::Result<CargoOutput> {
+        Ok(CargoOutput::from(self.datasource.cargo(args).await?))
+    }
+}
@@ -0,0 +1,389 @@
+use async_trait::async_trait;
+
+use super::entities::{CompileError, Location};
+use super::repository::{Repository, VerifyRepository};
+
+use crate::core::report::{ErrorCode, Report};
+use crate::core::stamp::{content_hash, Stamp};
+use crate::core::utils::current_project;
+
+/// Commands building everything in the workspace, its tests included, without reaching
+/// the network.
+const COMMANDS: [&[&str]; 2] = [
+    &["check", "--offline", "--workspace"],
+    &["test", "--no-run", "--offline", "--workspace"],
+];
+
+#[async_trait]
+pub trait Usecase {
+    async fn verify(&self) -> Report;
+}
+
+/// Builds the project and traces the errors back to the templates that generated the
+/// files they're in.
+pub struct VerifyUsecase {
+    project: String,
+    root: String,
+    repository: Box<dyn Repository + Send + Sync>,
+}
+
+impl VerifyUsecase {
+    /// Works on the workspace rooted in `root`, relative to the current directory.
+    pub fn new(root: &str) -> Self {
+        let repository = Box::new(VerifyRepository::new(root));
+        let project = match root {
+            "." => current_project(),
+            root => root.trim_end_matches('/').to_string(),
+        };
+
+        Self {
+            project,
+            root: root.to_string(),
+            repository,
+        }
+    }
+
+    /// `path`, relative to the workspace root, from the current directory.
+    fn display_path(&self, path: &str) -> String {
+        match self.root.as_str() {
+            "." | "" => path.to_string(),
+            root => format!("{}/{}", root.trim_end_matches('/'), path),
+        }
+    }
+
+    /// Whether the file recorded by `stamp` was changed since it was generated.
+    async fn modified(&self, stamp: &Stamp) -> bool {
+        match self.repository.read_file(&stamp.path).await {
+            Ok(content) => content_hash(&content) != stamp.hash,
+            Err(_) => true,
+        }
+    }
+}
+
+/// The error's line of output, naming the template that generated its file.
+fn error_line(path: &str, location: &Location, error: &str, origin: Option<&str>) -> String {
+    let line = format!("{}:{}:{}: {}", path, location.line, location.column, error);
+
+    match origin {
+        Some(origin) => format!("{} ({})", line, origin),
+        None => line,
+    }
+}
+
+#[async_trait]
+impl Usecase for VerifyUsecase {
+    async fn verify(&self) -> Report {
+        if !self.repository.exists("Cargo.toml").await {
+            return Report::not_in_project();
+        }
+
+        let stamps = match self.repository.read_stamps().await {
+            Ok(stamps) => stamps,
+            Err(err) => {
+                return Report::failure(
+                    ErrorCode::Failed,
+                    format!("Failed to read the stamps of generated files - {}", err),
+                )
+            }
+        };
+
+        let mut passed = Vec::new();
+        for args in COMMANDS.iter() {
+            let command = format!("cargo {}", args.join(" "));
+            let output = match self.repository.cargo(args).await {
+                Ok(output) => output,
+                Err(err) => {
+                    return Report::failure(
+                        ErrorCode::Failed,
+                        format!("Failed to run `{}` - {}", command, err),
+                    )
+                }
+            };
+
+            if output.success {
+                passed.push(format!("passed: {}", command));
+                continue;
+            }
+
+            let located = output
+                .errors
+                .iter()
+                .filter(|error| error.location.is_some())
+                .count();
+            let mut report = match located {
+                0 => Report::failure(ErrorCode::Failed, format!("`{}` failed", command)),
+                1 => Report::failure(
+                    ErrorCode::CompileFailed,
+                    format!("`{}` failed with 1 error", command),
+                ),
+                _ => Report::failure(
+                    ErrorCode::CompileFailed,
+                    format!("`{}` failed with {} errors", command, located),
+                ),
+            };
+            report.details = passed;
+
+            let mut templates = Vec::new();
+            for CompileError { location, message } in &output.errors {
+                let location = match location {
+                    Some(location) => location,
+                    None => {
+                        report.details.push(message.clone());
+                        continue;
+                    }
+                };
+
+                let stamp = stamps.iter().find(|stamp| stamp.path == location.path);
+                let origin = match stamp {
+                    Some(stamp) => {
+                        let template = stamp.template.as_str();
+                        match self.modified(stamp).await {
+                            true => Some(format!(
+                                "generated by {} v{}, modified since",
+                                template, stamp.version
+                            )),
+                            false => {
+                                if !templates.contains(&template) {
+                                    templates.push(template);
+                                }
+                                Some(format!("generated by {} v{}", template, stamp.version))
+                            }
+                        }
+                    }
+                    None => None,
+                };
+
+                report.details.push(error_line(
+                    &self.display_path(&location.path),
+                    location,
+                    message,
+                    origin.as_deref(),
+                ));
+            }
+
+            if !templates.is_empty() {
+                report.notes.push(format!(
+                    "files left as generated fail to compile, which is a bug in {}",
+                    templates.join(", ")
+                ));
+            }
+
+            return report;
+        }
+
+        let mut report = Report::success(format!("Verified project compiles: {}", self.project));
+        report.details = passed;
+        report
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::collections::HashMap;
+    use std::io;
+
+    use crate::core::presentation::{Presentation, PresentationFeature, WebFramework};
+    use crate::core::runtime::Runtime;
+    use crate::core::templates::template_version;
+    use crate::features::verify::entities::CargoOutput;
+
+    struct MockRepository {
+        files: HashMap<String, String>,
+        stamps: Vec<Stamp>,
+        errors: Vec<CompileError>,
+    }
+
+    #[async_trait]
+    impl Repository for MockRepository {
+        async fn exists(&self, path: &str) -> bool {
+            self.files.contains_key(path)
+        }
+
+        async fn read_file(&self, path: &str) -> io::Result<String> {
+            Ok(self.files[path].clone())
+        }
+
+        async fn read_stamps(&self) -> io::Result<Vec<Stamp>> {
+            Ok(self.stamps.clone())
+        }
+
+        async fn cargo(&self, _args: &[&str]) -> io::Result<CargoOutput> {
+            Ok(CargoOutput {
+                success: self.errors.is_empty(),
+                errors: self.errors.clone(),
+            })
+        }
+    }
+
+    fn usecase(errors: Vec<CompileError>) -> VerifyUsecase {
+        let files = [
+            ("Cargo.toml", "[workspace]\n"),
+            ("test/src/main.rs", "fn main() {\n    cli();\n}\n"),
+            ("test/src/features/mod.rs", "pub mod notes;\n"),
+        ]
+        .iter()
+        .map(|(path, content)| (path.to_string(), content.to_string()))
+        .collect();
+
+        let stamp = |path: &str, template: &str, content: &str| Stamp {
+            path: path.to_string(),
+            template: template.to_string(),
+            version: 1,
+            hash: content_hash(content),
+        };
+
+        VerifyUsecase {
+            project: String::from("test"),
+            root: String::from("test"),
+            repository: Box::new(MockRepository {
+                files,
+                stamps: vec![
+                    stamp(
+                        "test/src/main.rs",
+                        "CliTemplate",
+                        "fn main() {\n    cli();\n}\n",
+                    ),
+                    stamp("test/src/features/mod.rs", "ModFileTemplate", ""),
+                ],
+                errors,
+            }),
+        }
+    }
+
+    fn error(path: &str, message: &str) -> CompileError {
+        CompileError {
+            location: Some(Location {
+                path: path.to_string(),
+                line: 2,
+                column: 5,
+            }),
+            message: message.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_error_line() {
+        let location = Location {
+            path: String::from("src/main.rs"),
+            line: 2,
+            column: 5,
+        };
+
+        assert_eq!(
+            "test/src/main.rs:2:5: error: oops (generated by CliTemplate v1)",
+            error_line(
+                "test/src/main.rs",
+                &location,
+                "error: oops",
+                Some("generated by CliTemplate v1")
+            )
+        );
+        assert_eq!(
+            "src/main.rs:2:5: error: oops",
+            error_line("src/main.rs", &location, "error: oops", None)
+        );
+    }
+
+    #[async_std::test]
+    async fn test_verify() {
+        let got = usecase(vec![]).verify().await;
+
+        assert!(got.success);
+        assert_eq!("Verified project compiles: test", got.message);
+        assert_eq!(
+            vec![
+                String::from("passed: cargo check --offline --workspace"),
+                String::from("passed: cargo test --no-run --offline --workspace"),
+            ],
+            got.details
+        );
+    }
+
+    #[async_std::test]
+    async fn test_verify_maps_errors_to_templates() {
+        let got = usecase(vec![
+            error(
+                "test/src/main.rs",
+                "error[E0425]: cannot find function `cli`",
+            ),
+            error("test/src/features/mod.rs", "error[E0583]: file not found"),
+            error("test/src/lib.rs", "error: expected item"),
+            CompileError {
+                location: None,
+                message: String::from("error: aborting"),
+            },
+        ])
+        .verify()
+        .await;
+
+        assert_eq!(Some(ErrorCode::CompileFailed), got.error);
+        assert_eq!(
+            "`cargo check --offline --workspace` failed with 3 errors",
+            got.message
+        );
+        assert_eq!(
+            vec![
+                String::from("test/test/src/main.rs:2:5: error[E0425]: cannot find function `cli` (generated by CliTemplate v1)"),
+                String::from("test/test/src/features/mod.rs:2:5: error[E0583]: file not found (generated by ModFileTemplate v1, modified since)"),
+                String::from("test/test/src/lib.rs:2:5: error: expected item"),
+                String::from("error: aborting"),
+            ],
+            got.details
+        );
+        assert_eq!(
+            vec![String::from(
+                "files left as generated fail to compile, which is a bug in CliTemplate"
+            )],
+            got.notes
+        );
+    }
+
+    #[async_std::test]
+    async fn test_verify_maps_errors_in_presentations() {
+        let features = vec![PresentationFeature {
+            name: String::from("notes"),
+            methods: vec![],
+            fields: vec![],
+        }];
+        let files = Presentation::Web(WebFramework::Axum)
+            .files("test", &features, Runtime::Tokio)
+            .unwrap();
+        let usecase = VerifyUsecase {
+            project: String::from("test"),
+            root: String::from("."),
+            repository: Box::new(MockRepository {
+                stamps: files
+                    .iter()
+                    .map(|(path, rendered)| Stamp::new(path, rendered))
+                    .collect(),
+                files: files
+                    .into_iter()
+                    .map(|(path, rendered)| (path, rendered.content))
+                    .chain([(String::from("Cargo.toml"), String::from("[workspace]\n"))])
+                    .collect(),
+                errors: vec![error(
+                    "test-web/src/features/notes.rs",
+                    "error[E0432]: unresolved import `test_domain`",
+                )],
+            }),
+        };
+
+        let got = usecase.verify().await;
+
+        assert_eq!(
+            vec![format!(
+                "test-web/src/features/notes.rs:2:5: error[E0432]: unresolved import `test_domain` (generated by WebFeatureTemplate v{})",
+                template_version("WebFeatureTemplate")
+            )],
+            got.details
+        );
+        assert_eq!(
+            vec![String::from(
+                "files left as generated fail to compile, which is a bug in WebFeatureTemplate"
+            )],
+            got.notes
+        );
+    }
+}
@@ -0,0 +1,177 @@
+//! Applies specs to sample projects, checking a spec failing part way leaves the project
+//! as it was and applying it again only adds what's missing.
+
+use std::collections::BTreeMap;
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use cargo_project_domain::core::report::{ErrorCode, Report};
+use cargo_project_domain::features::apply::usecase::{ApplyUsecase, Usecase as _};
+use futures::executor::block_on;
+
+/// Entries left out when comparing projects, as applying a spec doesn't touch them.
+const UNCOMPARED: [&str; 2] = [".git", "target"];
+
+const SPEC: &str = r#"[project]
+name = "demo"
+
+[features.store]
+datasource = "file"
+usecases = ["archive"]
+
+[features.store.entity]
+title = "String"
+"#;
+
+/// Creates a clean temporary directory holding `spec`, and makes it the current directory.
+fn enter_parent(spec: &str) -> PathBuf {
+    let parent = env::temp_dir().join("cargo-project-apply");
+    if parent.exists() {
+        fs::remove_dir_all(&parent).expect("Error removing previous sample");
+    }
+    fs::create_dir_all(&parent).expect("Error creating sample directory");
+    fs::write(parent.join("spec.toml"), spec).expect("Error writing spec");
+
+    env::set_current_dir(&parent).expect("Error entering sample directory");
+    parent
+}
+
+fn collect_files(root: &Path, dir: &Path, files: &mut BTreeMap<String, Vec<u8>>) {
+    for entry in fs::read_dir(dir).expect("Error reading project") {
+        let path = entry.expect("Error reading project").path();
+        let name = path.file_name().unwrap_or_default().to_string_lossy();
+        if UNCOMPARED.contains(&name.as_ref()) {
+            continue;
+        }
+
+        match path.is_dir() {
+            true => collect_files(root, &path, files),
+            false => {
+                let relative = path
+                    .strip_prefix(root)
+                    .expect("Error reading project path")
+                    .to_string_lossy()
+                    .to_string();
+                files.insert(relative, fs::read(&path).expect("Error reading file"));
+            }
+        }
+    }
+}
+
+fn files(root: &Path) -> BTreeMap<String, Vec<u8>> {
+    let mut files = BTreeMap::new();
+    collect_files(root, root, &mut files);
+    files
+}
+
+/// Applies the spec of the directory `parent` from it.
+fn apply(parent: &Path) -> Report {
+    env::set_current_dir(parent).expect("Error entering sample directory");
+    let spec = parent.join("spec.toml").display().to_string();
+
+    block_on(ApplyUsecase::new().apply_spec(&spec))
+}
+
+// Both scenarios change the current directory, so they run in the same test
+#[test]
+fn test_apply() {
+    assert_rolls_back_partial_failure();
+    assert_reapplies();
+    assert_rejects_paths_as_names();
+}
+
+fn assert_rolls_back_partial_failure() {
+    let parent = enter_parent(SPEC);
+    let root = parent.join("demo");
+    let report = apply(&parent);
+    assert!(report.success, "{}", report);
+
+    // A feature directory the domain is missing files of, which fails once reached
+    let broken = root.join("demo-domain/src/features/broken");
+    fs::create_dir_all(&broken).expect("Error creating broken feature");
+    let spec = format!(
+        "{}\n[features.notes]\nusecases = [\"publish\"]\n\n[features.broken]\n",
+        SPEC
+    );
+    fs::write(parent.join("spec.toml"), spec).expect("Error writing spec");
+    let before = files(&root);
+
+    let report = apply(&parent);
+
+    assert!(!report.success, "{}", report);
+    assert!(report
+        .details
+        .contains(&String::from("added feature: notes")));
+    assert_eq!(
+        vec![String::from(
+            "rolled back the steps above, the project is left as it was"
+        )],
+        report.notes
+    );
+    assert!(report.created.is_empty());
+    assert!(report.modified.is_empty());
+    assert_eq!(before, files(&root));
+    assert!(broken.is_dir());
+    assert!(!root.join("demo-domain/src/features/notes").exists());
+
+    // Once the failure is dealt with, applying again goes through every step
+    fs::remove_dir(&broken).expect("Error removing broken feature");
+    let report = apply(&parent);
+
+    assert!(report.success, "{}", report);
+    assert!(report
+        .details
+        .contains(&String::from("added feature: notes")));
+    assert!(report
+        .details
+        .contains(&String::from("added feature: broken")));
+}
+
+fn assert_reapplies() {
+    let parent = enter_parent(SPEC);
+    let root = parent.join("demo");
+
+    let report = apply(&parent);
+    assert!(report.success, "{}", report);
+    let applied = files(&root);
+
+    let report = apply(&parent);
+
+    assert!(report.success, "{}", report);
+    assert_eq!(
+        format!(
+            "Project already matches spec: {}",
+            parent.join("spec.toml").display()
+        ),
+        report.message
+    );
+    assert!(report.created.is_empty());
+    assert!(report.modified.is_empty());
+    assert_eq!(applied, files(&root));
+
+    // Applying from inside the project works the same
+    env::set_current_dir(&root).expect("Error entering project");
+    let spec = format!("{}\n[features.notes]\n", SPEC);
+    fs::write(parent.join("spec.toml"), spec).expect("Error writing spec");
+    let report = block_on(ApplyUsecase::new().apply_spec("../spec.toml"));
+
+    assert!(report.success, "{}", report);
+    assert_eq!(vec![String::from("added feature: notes")], report.details);
+}
+
+fn assert_rejects_paths_as_names() {
+    for name in ["..", ".", "/", "../demo"] {
+        let parent = enter_parent(&SPEC.replace("\"demo\"", &format!("{:?}", name)));
+        let before = files(&parent);
+
+        let report = apply(&parent);
+
+        assert!(!report.success, "{}", report);
+        assert_eq!(Some(ErrorCode::InvalidArgument), report.error);
+        assert!(report
+            .message
+            .ends_with(&format!("Invalid project name: {}", name)));
+        assert_eq!(before, files(&parent));
+    }
+}
@@ -0,0 +1,337 @@
+//! Generates sample projects with the real usecases, compares their files and the hashes of
+//! their contents with the snapshots in `tests/snapshots` and compiles them offline.
+//!
+//! Compiling needs the dependencies of the generated projects in the cargo cache. Run with
+//! `UPDATE_SNAPSHOTS=1` to record the trees after changing what gets generated.
+
+use std::env;
+use std::fs;
+use std::future::Future;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+use cargo_project_domain::core::layer::Layer;
+use cargo_project_domain::core::presentation::{Presentation, WebFramework};
+use cargo_project_domain::core::project::{ProjectKind, Vcs};
+use cargo_project_domain::core::report::Report;
+use cargo_project_domain::core::runtime::Runtime;
+use cargo_project_domain::core::stamp::content_hash;
+use cargo_project_domain::features::add::entities::{DatasourceKind, FileFormat};
+use cargo_project_domain::features::add::usecase::{AddUsecase, Usecase as _};
+use cargo_project_domain::features::new::usecase::{NewUsecase, Usecase as _};
+use cargo_project_domain::features::verify::usecase::{Usecase as _, VerifyUsecase};
+use futures::executor::block_on;
+
+/// Adding to a project works on the current directory, so samples are generated one at
+/// a time.
+static CURRENT_DIR: Mutex<()> = Mutex::new(());
+
+/// Entries left out of snapshots, as they depend on the toolchain or aren't generated.
+const UNSNAPSHOTTED: [&str; 3] = [".git", "Cargo.lock", "target"];
+
+fn assert_success(report: Report) {
+    assert!(report.success, "{}", report);
+}
+
+/// Creates the sample `name` in a clean temporary directory and makes it the current
+/// directory.
+async fn create_sample(
+    name: &str,
+    runtime: Runtime,
+    kind: ProjectKind,
+    vcs: Vcs,
+    with_ci: bool,
+) -> PathBuf {
+    let parent = env::temp_dir().join("cargo-project-e2e");
+    let root = parent.join(name);
+    if root.exists() {
+        fs::remove_dir_all(&root).expect("Error removing previous sample");
+    }
+    fs::create_dir_all(&parent).expect("Error creating sample directory");
+
+    let usecase = NewUsecase::new(name, &parent, runtime, kind, Some(vcs), with_ci);
+    assert_success(usecase.create_project().await);
+
+    env::set_current_dir(&root).expect("Error entering sample");
+    root
+}
+
+/// Adds a feature of every datasource flavour, an extra usecase method and entity fields.
+async fn add_features() {
+    let usecase = AddUsecase::new();
+
+    assert_success(usecase.add_feature("notes", DatasourceKind::Empty).await);
+    assert_success(
+        usecase
+            .add_feature("store", DatasourceKind::File(FileFormat::Json))
+            .await,
+    );
+    assert_success(
+        usecase
+            .add_feature("settings", DatasourceKind::File(FileFormat::Toml))
+            .await,
+    );
+    assert_success(usecase.add_usecase("notes", "archive").await);
+    assert_success(
+        usecase
+            .add_fields(
+                "store",
+                &[
+                    (String::from("title"), String::from("String")),
+                    (String::from("done"), String::from("bool")),
+                ],
+            )
+            .await,
+    );
+}
+
+fn collect_files(root: &Path, dir: &Path, files: &mut Vec<String>) {
+    for entry in fs::read_dir(dir).expect("Error reading sample") {
+        let path = entry.expect("Error reading sample").path();
+        let name = path.file_name().unwrap_or_default().to_string_lossy();
+        if UNSNAPSHOTTED.contains(&name.as_ref()) {
+            continue;
+        }
+
+        match path.is_dir() {
+            true => collect_files(root, &path, files),
+            false => files.push(
+                path.strip_prefix(root)
+                    .expect("Error reading sample path")
+                    .to_string_lossy()
+                    .replace('\\', "/"),
+            ),
+        }
+    }
+}
+
+/// Hash of the file at `path`, leaving out the edition `cargo new` picks for the toolchain.
+fn file_hash(path: &Path) -> String {
+    let content = fs::read_to_string(path).expect("Error reading sample file");
+    let content = content
+        .lines()
+        .filter(|line| !line.starts_with("edition = "))
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    content_hash(&content)
+}
+
+/// Compares the files of the sample at `root` and their hashes with its snapshot,
+/// recording it instead with `UPDATE_SNAPSHOTS` set.
+fn assert_snapshot(name: &str, root: &Path) {
+    let mut files = Vec::new();
+    collect_files(root, root, &mut files);
+    files.sort();
+    let tree = files
+        .iter()
+        .map(|file| format!("{}  {}\n", file_hash(&root.join(file)), file))
+        .collect::<String>();
+
+    let snapshot = Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("tests/snapshots")
+        .join(format!("{}.txt", name));
+
+    if env::var_os("UPDATE_SNAPSHOTS").is_some() {
+        fs::write(&snapshot, &tree).expect("Error writing snapshot");
+        return;
+    }
+
+    let expected = fs::read_to_string(&snapshot).unwrap_or_else(|_| {
+        panic!(
+            "Missing snapshot {}, record it with UPDATE_SNAPSHOTS=1",
+            snapshot.display()
+        )
+    });
+    assert_eq!(expected, tree, "Generated files of {} changed", name);
+}
+
+/// Checks the sample and compiles its tests, building every sample in the same target
+/// directory so dependencies are only compiled once.
+async fn assert_compiles(root: &Path) {
+    env::set_var(
+        "CARGO_TARGET_DIR",
+        Path::new(env!("CARGO_TARGET_TMPDIR")).join("e2e"),
+    );
+
+    let usecase = VerifyUsecase::new(&root.display().to_string());
+    assert_success(usecase.verify().await);
+}
+
+/// Runs `test` once no other sample is being generated.
+fn with_current_dir(test: impl Future<Output = ()>) {
+    let _current_dir = CURRENT_DIR.lock().unwrap_or_else(|err| err.into_inner());
+    block_on(test);
+}
+
+async fn assert_sample(name: &str, runtime: Runtime, kind: ProjectKind) {
+    let root = create_sample(name, runtime, kind, Vcs::NoVcs, false).await;
+    add_features().await;
+
+    assert_snapshot(name, &root);
+    assert_compiles(&root).await;
+}
+
+#[test]
+fn test_binary_tokio() {
+    with_current_dir(assert_sample(
+        "bin-tokio",
+        Runtime::Tokio,
+        ProjectKind::Binary,
+    ));
+}
+
+#[test]
+fn test_binary_sync() {
+    with_current_dir(assert_sample(
+        "bin-sync",
+        Runtime::Sync,
+        ProjectKind::Binary,
+    ));
+}
+
+#[test]
+fn test_library_async_std() {
+    with_current_dir(assert_sample(
+        "lib-async-std",
+        Runtime::AsyncStd,
+        ProjectKind::Library,
+    ));
+}
+
+#[test]
+fn test_library_sync() {
+    with_current_dir(assert_sample(
+        "lib-sync",
+        Runtime::Sync,
+        ProjectKind::Library,
+    ));
+}
+
+#[test]
+fn test_presentations_and_layers() {
+    with_current_dir(assert_presentations_and_layers());
+}
+
+async fn assert_presentations_and_layers() {
+    let name = "presentations";
+    let root = create_sample(name, Runtime::Tokio, ProjectKind::Binary, Vcs::Git, true).await;
+    let usecase = AddUsecase::new();
+
+    assert_success(usecase.add_feature("notes", DatasourceKind::Empty).await);
+    for presentation in [
+        Presentation::Web(WebFramework::Axum),
+        Presentation::Grpc,
+        Presentation::Tui,
+        Presentation::Wasm,
+    ] {
+        assert_success(usecase.add_presentation(presentation).await);
+    }
+    assert_success(
+        usecase
+            .add_layer(Layer {
+                name: String::from("infra"),
+                suffix: String::from("infra"),
+                kind: ProjectKind::Library,
+                depends_on: vec![String::from("domain")],
+                files: vec![String::from("client")],
+            })
+            .await,
+    );
+    // Added after the presentations and layers, so it reaches all of them
+    assert_success(
+        usecase
+            .add_feature("store", DatasourceKind::File(FileFormat::Json))
+            .await,
+    );
+
+    assert_snapshot(name, &root);
+    assert_compiles(&root).await;
+}
+
+#[test]
+fn test_modified_presentation() {
+    with_current_dir(assert_modified_presentation());
+}
+
+/// A presentation file changed since it was generated is left alone by `add feature`.
+async fn assert_modified_presentation() {
+    let name = "modified";
+    let root = create_sample(name, Runtime::Tokio, ProjectKind::Binary, Vcs::NoVcs, false).await;
+    let usecase = AddUsecase::new();
+    assert_success(
+        usecase
+            .add_presentation(Presentation::Web(WebFramework::Axum))
+            .await,
+    );
+
+    let path = "modified-web/src/features/mod.rs";
+    let edited = format!(
+        "{}\n// Edited by hand\n",
+        fs::read_to_string(root.join(path)).unwrap()
+    );
+    fs::write(root.join(path), &edited).unwrap();
+
+    let report = usecase.add_feature("notes", DatasourceKind::Empty).await;
+
+    assert!(report.success, "{}", report);
+    assert_eq!(
+        vec![format!(
+            "{} was modified, expose the notes feature in it",
+            path
+        )],
+        report.warnings
+    );
+    assert_eq!(vec![path.to_string()], report.skipped);
+    assert_eq!(edited, fs::read_to_string(root.join(path)).unwrap());
+    assert!(root.join("modified-web/src/features/notes.rs").exists());
+}
+
+#[test]
+fn test_reported_files() {
+    with_current_dir(assert_reported_files());
+}
+
+/// Commands report the files they wrote, and only those.
+async fn assert_reported_files() {
+    let name = "reports";
+    create_sample(name, Runtime::Tokio, ProjectKind::Binary, Vcs::NoVcs, false).await;
+    let usecase = AddUsecase::new();
+
+    assert_success(usecase.add_feature("notes", DatasourceKind::Empty).await);
+    // The data crate needs nothing new for an empty datasource, nor the domain crate for a
+    // second feature
+    let report = usecase.add_feature("tags", DatasourceKind::Empty).await;
+    assert!(report.success, "{}", report);
+    assert_eq!(
+        vec![
+            "reports/src/features/tags.rs",
+            "reports-domain/src/features/tags/entities.rs",
+            "reports-domain/src/features/tags/mod.rs",
+            "reports-domain/src/features/tags/repository.rs",
+            "reports-domain/src/features/tags/usecase.rs",
+            "reports-domain/src/features/tags/fakes.rs",
+            "reports-data/src/features/tags/datasource.rs",
+            "reports-data/src/features/tags/mod.rs",
+            "reports-data/src/features/tags/models.rs",
+        ],
+        report.created
+    );
+    assert_eq!(
+        vec![
+            "reports/src/features/mod.rs",
+            "reports-domain/src/features/mod.rs",
+            "reports-data/src/features/mod.rs",
+            "Cargo.toml",
+        ],
+        report.modified
+    );
+
+    let report = usecase.add_presentation(Presentation::Grpc).await;
+    assert!(report.success, "{}", report);
+    assert_eq!(vec!["Cargo.toml"], report.modified);
+
+    let report = usecase.add_presentation(Presentation::Grpc).await;
+    assert!(!report.success);
+    assert!(report.created.is_empty() && report.modified.is_empty());
+}
@@ -0,0 +1,47 @@
+use structopt::clap::App;
+use structopt::StructOpt;
+
+/// A command of the CLI along with the subcommands `man` and `completions` walk, as clap
+/// only exposes the help and completions of an app.
+pub struct Command {
+    pub name: &'static str,
+    /// The command's own arguments, and its subcommands as clap parses them.
+    pub app: App<'static, 'static>,
+    pub subcommands: Vec<Command>,
+}
+
+impl Command {
+    /// The command `name`, parsed into `T`.
+    pub fn new<T: StructOpt>(name: &'static str, subcommands: Vec<Command>) -> Self {
+        Self {
+            name,
+            app: T::clap().name(name),
+            subcommands,
+        }
+    }
+
+    /// The help of the command laid out by `template`.
+    fn help(&self, template: &'static str) -> String {
+        let mut help = Vec::new();
+        self.app
+            .clone()
+            .template(template)
+            .write_help(&mut help)
+            .expect("Error writing help to memory");
+
+        String::from_utf8_lossy(&help).trim().to_string()
+    }
+
+    pub fn about(&self) -> String {
+        self.help("{about}")
+    }
+
+    /// Whether the command takes the positional argument `name`.
+    pub fn takes_positional(&self, name: &str) -> bool {
+        let positional = format!("<{}>", name);
+
+        self.help("{positionals}")
+            .lines()
+            .any(|line| line.split_whitespace().next() == Some(positional.as_str()))
+    }
+}
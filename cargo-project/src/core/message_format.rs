@@ -0,0 +1,57 @@
+use std::str::FromStr;
+
+use cargo_project_domain::core::report::Report;
+
+/// How the report of a command gets printed.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub enum MessageFormat {
+    #[default]
+    Human,
+    /// A single line JSON object, for editor extensions and CI bots.
+    Json,
+}
+
+impl FromStr for MessageFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "human" => Ok(MessageFormat::Human),
+            "json" => Ok(MessageFormat::Json),
+            _ => Err(format!("Unknown message format: {}", s)),
+        }
+    }
+}
+
+impl MessageFormat {
+    pub fn render(&self, report: &Report) -> String {
+        match self {
+            MessageFormat::Human => report.to_string(),
+            MessageFormat::Json => {
+                serde_json::to_string(report).expect("Error serializing report to JSON")
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use cargo_project_domain::core::report::ErrorCode;
+
+    #[test]
+    fn test_render() {
+        let mut report = Report::failure(ErrorCode::AlreadyExists, "Layer already exists: api");
+        report.warnings.push(String::from("uses async_std"));
+
+        assert_eq!(
+            "Layer already exists: api\nwarning: uses async_std",
+            MessageFormat::Human.render(&report)
+        );
+        assert_eq!(
+            r#"{"success":false,"message":"Layer already exists: api","details":[],"created":[],"modified":[],"skipped":[],"warnings":["uses async_std"],"notes":[],"error":"already-exists"}"#,
+            MessageFormat::Json.render(&report)
+        );
+    }
+}
@@ -1 +1,4 @@
+pub mod command;
+pub mod message_format;
+pub mod prompt;
 pub mod run;
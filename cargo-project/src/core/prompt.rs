@@ -0,0 +1,202 @@
+use std::io::{self, BufRead, IsTerminal, StdinLock, Stdout, Write};
+
+/// Whether standard input and output are both terminals, so questions can be asked.
+pub fn is_interactive() -> bool {
+    io::stdin().is_terminal() && io::stdout().is_terminal()
+}
+
+/// Checks `name` is a snake case identifier, `-` being allowed as well when `dashes`.
+pub fn validate_name(name: &str, dashes: bool) -> Result<String, String> {
+    let valid = name.starts_with(|c: char| c.is_ascii_lowercase())
+        && name.chars().all(|c| {
+            c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_' || (dashes && c == '-')
+        });
+
+    match valid {
+        true => Ok(name.to_string()),
+        false if dashes => Err(String::from(
+            "use lowercase letters, digits, `_` and `-`, starting with a letter",
+        )),
+        false => Err(String::from(
+            "use lowercase letters, digits and `_`, starting with a letter",
+        )),
+    }
+}
+
+/// Entity fields as names and types.
+pub type Fields = Vec<(String, String)>;
+
+/// Asks questions on a terminal, re-asking until answers are valid.
+pub struct Prompt<R, W> {
+    input: R,
+    output: W,
+}
+
+impl Prompt<StdinLock<'static>, Stdout> {
+    pub fn stdio() -> Self {
+        Self::new(io::stdin().lock(), io::stdout())
+    }
+}
+
+impl<R: BufRead, W: Write> Prompt<R, W> {
+    pub fn new(input: R, output: W) -> Self {
+        Self { input, output }
+    }
+
+    fn answer(&mut self, question: &str) -> io::Result<String> {
+        write!(self.output, "{}: ", question)?;
+        self.output.flush()?;
+
+        let mut line = String::new();
+        if self.input.read_line(&mut line)? == 0 {
+            return Err(io::Error::new(
+                io::ErrorKind::UnexpectedEof,
+                "No answer given",
+            ));
+        }
+
+        Ok(line.trim().to_string())
+    }
+
+    /// Asks until `parse` accepts the answer, an empty answer standing for `default`.
+    pub fn ask<T>(
+        &mut self,
+        question: &str,
+        default: Option<&str>,
+        parse: impl Fn(&str) -> Result<T, String>,
+    ) -> io::Result<T> {
+        let question = match default {
+            Some(default) => format!("{} [{}]", question, default),
+            None => question.to_string(),
+        };
+
+        loop {
+            let answer = self.answer(&question)?;
+            let answer = match (answer.is_empty(), default) {
+                (true, Some(default)) => default.to_string(),
+                _ => answer,
+            };
+
+            match parse(&answer) {
+                Ok(value) => return Ok(value),
+                Err(err) => writeln!(self.output, "  Invalid answer, {}", err)?,
+            }
+        }
+    }
+
+    /// Asks to pick one of `choices`.
+    pub fn choose(
+        &mut self,
+        question: &str,
+        choices: &[&str],
+        default: &str,
+    ) -> io::Result<String> {
+        let question = format!("{} ({})", question, choices.join(", "));
+
+        self.ask(&question, Some(default), |answer| {
+            match choices.contains(&answer) {
+                true => Ok(answer.to_string()),
+                false => Err(format!("pick one of {}", choices.join(", "))),
+            }
+        })
+    }
+
+    pub fn confirm(&mut self, question: &str) -> io::Result<bool> {
+        self.ask(
+            &format!("{} (y/n)", question),
+            Some("y"),
+            |answer| match answer.to_lowercase().as_str() {
+                "y" | "yes" => Ok(true),
+                "n" | "no" => Ok(false),
+                _ => Err(String::from("answer y or n")),
+            },
+        )
+    }
+
+    /// Shows `line` to the person answering, like the plan their answers make up.
+    pub fn tell(&mut self, line: &str) -> io::Result<()> {
+        writeln!(self.output, "{}", line)
+    }
+
+    /// Asks for entity fields one at a time, until an empty answer.
+    pub fn fields(&mut self) -> io::Result<Fields> {
+        let mut fields: Fields = Vec::new();
+
+        loop {
+            let field = self.ask(
+                "Entity field as `name: Type`, empty to finish",
+                None,
+                |answer| {
+                    if answer.is_empty() {
+                        return Ok(None);
+                    }
+
+                    let (name, ty) = answer
+                        .split_once(':')
+                        .ok_or_else(|| String::from("separate the name and type with `:`"))?;
+                    let name = validate_name(name.trim(), false)?;
+                    if ty.trim().is_empty() {
+                        return Err(String::from("missing the type"));
+                    }
+                    if fields.iter().any(|(existing, _)| existing == &name) {
+                        return Err(format!("{} is already a field", name));
+                    }
+
+                    Ok(Some((name, ty.trim().to_string())))
+                },
+            )?;
+
+            match field {
+                Some(field) => fields.push(field),
+                None => return Ok(fields),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ask() {
+        let mut output = Vec::new();
+        let mut prompt = Prompt::new("Shop\nshop\n\n".as_bytes(), &mut output);
+
+        let name = prompt
+            .ask("Project name", None, |answer| validate_name(answer, true))
+            .unwrap();
+        let runtime = prompt
+            .choose("Runtime", &["tokio", "async-std"], "tokio")
+            .unwrap();
+
+        assert_eq!("shop", name);
+        assert_eq!("tokio", runtime);
+        assert_eq!(
+            concat!(
+                "Project name: ",
+                "  Invalid answer, use lowercase letters, digits, `_` and `-`, starting with a letter\n",
+                "Project name: Runtime (tokio, async-std) [tokio]: ",
+            ),
+            String::from_utf8(output).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_fields() {
+        let input = "title: String\ntitle: u8\ndone:bool\n\nn\n";
+        let mut prompt = Prompt::new(input.as_bytes(), Vec::new());
+
+        let fields = prompt.fields().unwrap();
+
+        assert_eq!(
+            vec![
+                (String::from("title"), String::from("String")),
+                (String::from("done"), String::from("bool")),
+            ],
+            fields
+        );
+        assert!(!prompt.confirm("Create it?").unwrap());
+        assert!(prompt.confirm("Create it?").is_err());
+    }
+}
@@ -1,3 +1,5 @@
+use cargo_project_domain::core::report::Report;
+
 pub trait Run {
-    fn run(&self);
+    fn run(&self) -> Report;
 }
@@ -1,9 +1,20 @@
+use std::io::{self, BufRead, Write};
+
+use cargo_project_domain::core::layer::Layer;
+use cargo_project_domain::core::presentation::{Presentation, WebFramework};
+use cargo_project_domain::core::project::ProjectKind;
+use cargo_project_domain::core::report::{ErrorCode, Report};
+use cargo_project_domain::features::add::entities::{DatasourceKind, FileFormat};
 use cargo_project_domain::features::add::usecase::{AddUsecase, Usecase};
 use futures::executor::block_on;
+use structopt::clap::{Error, ErrorKind};
 use structopt::StructOpt;
 
+use crate::core::command::Command;
+use crate::core::prompt::{is_interactive, validate_name, Fields, Prompt};
 use crate::core::run::Run;
 
+/// Adds a feature, usecase, presentation or layer to the project
 #[derive(Debug, PartialEq, StructOpt)]
 pub struct Add {
     #[structopt(subcommand)]
@@ -11,22 +22,249 @@ pub struct Add {
 }
 
 impl Run for Add {
-    fn run(&self) {
+    fn run(&self) -> Report {
         match &self.resource {
             Resource::Feature(feature) => {
+                let datasource = DatasourceKind::new(&feature.datasource, feature.format)
+                    .expect("Error parsing datasource");
+
+                let (name, datasource, fields) = match &feature.name {
+                    Some(name) => (name.clone(), datasource, vec![]),
+                    None if is_interactive() => {
+                        match feature.wizard(&mut Prompt::stdio(), datasource) {
+                            Ok(Some(settings)) => settings,
+                            Ok(None) => {
+                                return Report::failure(
+                                    ErrorCode::Cancelled,
+                                    "Cancelled adding feature",
+                                )
+                            }
+                            Err(err) => {
+                                return Report::failure(
+                                    ErrorCode::Failed,
+                                    format!("Failed to read answers - {}", err),
+                                )
+                            }
+                        }
+                    }
+                    None => Error::with_description(
+                        "The following required arguments were not provided:\n    <name>",
+                        ErrorKind::MissingRequiredArgument,
+                    )
+                    .exit(),
+                };
+
+                let usecase = AddUsecase::new();
+                let mut report = block_on(usecase.add_feature(&name, datasource));
+
+                if report.success && !fields.is_empty() {
+                    let added = block_on(usecase.add_fields(&name, &fields));
+                    report.merge(&added);
+                    report.details.push(added.message);
+                    report.success = added.success;
+                    report.error = added.error;
+                }
+
+                report
+            }
+            Resource::Usecase(resource) => {
+                let usecase = AddUsecase::new();
+                block_on(usecase.add_usecase(&resource.feature, &resource.name))
+            }
+            Resource::Presentation(kind) => {
+                let presentation = match kind {
+                    PresentationKind::Web(web) => Presentation::Web(web.framework),
+                    PresentationKind::Grpc => Presentation::Grpc,
+                    PresentationKind::Tui => Presentation::Tui,
+                    PresentationKind::Wasm => Presentation::Wasm,
+                };
+
+                let usecase = AddUsecase::new();
+                block_on(usecase.add_presentation(presentation))
+            }
+            Resource::Layer(resource) => {
+                let layer = Layer {
+                    name: resource.name.clone(),
+                    suffix: resource
+                        .suffix
+                        .clone()
+                        .unwrap_or_else(|| resource.name.replace('_', "-")),
+                    kind: resource.kind,
+                    depends_on: resource.depends_on.clone(),
+                    files: resource.files.clone(),
+                };
+
                 let usecase = AddUsecase::new();
-                println!("{}", block_on(usecase.add_feature(&feature.name)))
+                block_on(usecase.add_layer(layer))
             }
         }
     }
 }
 
+/// The subcommands of `add`, `add presentation` taking options only for the web server.
+pub fn commands() -> Vec<Command> {
+    vec![
+        Command::new::<Feature>("feature", vec![]),
+        Command::new::<UsecaseMethod>("usecase", vec![]),
+        Command::new::<PresentationKind>(
+            "presentation",
+            vec![Command::new::<WebOptions>("web", vec![])],
+        ),
+        Command::new::<LayerOptions>("layer", vec![]),
+    ]
+}
+
 #[derive(Debug, PartialEq, StructOpt)]
 enum Resource {
     Feature(Feature),
+    Usecase(UsecaseMethod),
+    Presentation(PresentationKind),
+    Layer(LayerOptions),
 }
 
+/// Generates a feature's module in every layer, with a datasource of the given kind
 #[derive(Debug, PartialEq, StructOpt)]
 struct Feature {
+    /// Name of the feature, asked for along with its entity fields when left out in a
+    /// terminal
+    name: Option<String>,
+
+    /// Kind of datasource generated for the feature
+    #[structopt(long, default_value = "empty", possible_values = &["empty", "file"])]
+    datasource: String,
+
+    /// Serialization format used by a file datasource
+    #[structopt(long, default_value = "json", possible_values = &["json", "toml"])]
+    format: FileFormat,
+}
+
+impl Feature {
+    /// Asks for the feature settings, using the given options as defaults, and returns
+    /// them once the plan is confirmed.
+    fn wizard<R: BufRead, W: Write>(
+        &self,
+        prompt: &mut Prompt<R, W>,
+        datasource: DatasourceKind,
+    ) -> io::Result<Option<(String, DatasourceKind, Fields)>> {
+        let name = prompt.ask("Feature name", None, |answer| validate_name(answer, false))?;
+        let kind = prompt.choose("Datasource", &["empty", "file"], datasource.name())?;
+        let format = match kind.as_str() {
+            "file" => prompt
+                .choose("File format", &["json", "toml"], self.format.extension())?
+                .parse()
+                .map_err(|err| io::Error::new(io::ErrorKind::InvalidInput, err))?,
+            _ => self.format,
+        };
+        let datasource = DatasourceKind::new(&kind, format)
+            .map_err(|err| io::Error::new(io::ErrorKind::InvalidInput, err))?;
+        let fields = prompt.fields()?;
+
+        prompt.tell("")?;
+        prompt.tell(&format!("Feature {} will be added with", name))?;
+        match datasource {
+            DatasourceKind::File(format) => {
+                prompt.tell(&format!("  datasource: file ({})", format.extension()))?
+            }
+            DatasourceKind::Empty => prompt.tell("  datasource: empty")?,
+        }
+        for (field, ty) in &fields {
+            prompt.tell(&format!("  field: {}: {}", field, ty))?;
+        }
+
+        match prompt.confirm("Add it?")? {
+            true => Ok(Some((name, datasource, fields))),
+            false => Ok(None),
+        }
+    }
+}
+
+/// Adds a usecase method to a feature, down to its datasource
+#[derive(Debug, PartialEq, StructOpt)]
+struct UsecaseMethod {
+    /// Feature the usecase is added to
+    feature: String,
+
+    /// Name of the usecase method
     name: String,
 }
+
+/// Adds a crate presenting every feature's usecases
+#[derive(Debug, PartialEq, StructOpt)]
+enum PresentationKind {
+    Web(WebOptions),
+    /// gRPC service generated from every feature's usecase
+    Grpc,
+    /// Terminal UI with a screen per feature
+    Tui,
+    /// WebAssembly library exporting every feature's usecase to JavaScript
+    Wasm,
+}
+
+/// HTTP API exposing every feature's usecase as JSON endpoints
+#[derive(Debug, PartialEq, StructOpt)]
+struct WebOptions {
+    /// Web framework the server is built with
+    #[structopt(long, default_value = "axum", possible_values = &["axum"])]
+    framework: WebFramework,
+}
+
+/// Adds a crate to the workspace, in which every feature gets a module
+#[derive(Debug, PartialEq, StructOpt)]
+struct LayerOptions {
+    /// Name of the layer, referenced by other layers depending on it
+    name: String,
+
+    /// Appended to the project name to form the crate name, defaults to the layer name
+    #[structopt(long)]
+    suffix: Option<String>,
+
+    /// Kind of crate generated for the layer
+    #[structopt(long, default_value = "lib", possible_values = &["lib", "bin"])]
+    kind: ProjectKind,
+
+    /// Layers the new layer may depend on
+    #[structopt(long, use_delimiter = true, default_value = "domain")]
+    depends_on: Vec<String>,
+
+    /// Files every feature gets in the layer
+    #[structopt(long, use_delimiter = true)]
+    files: Vec<String>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_wizard() {
+        let feature = Feature {
+            name: None,
+            datasource: String::from("empty"),
+            format: FileFormat::Json,
+        };
+        let input = "notes\nfile\ntoml\ntitle: String\n\ny\n";
+        let mut output = Vec::new();
+
+        let settings = feature
+            .wizard(
+                &mut Prompt::new(input.as_bytes(), &mut output),
+                DatasourceKind::Empty,
+            )
+            .unwrap();
+
+        assert_eq!(
+            Some((
+                String::from("notes"),
+                DatasourceKind::File(FileFormat::Toml),
+                vec![(String::from("title"), String::from("String"))],
+            )),
+            settings
+        );
+        assert!(String::from_utf8(output).unwrap().ends_with(concat!(
+            "\nFeature notes will be added with\n",
+            "  datasource: file (toml)\n",
+            "  field: title: String\n",
+            "Add it? (y/n) [y]: ",
+        )));
+    }
+}
@@ -0,0 +1,20 @@
+use futures::executor::block_on;
+use structopt::StructOpt;
+
+use crate::core::run::Run;
+use cargo_project_domain::core::report::Report;
+use cargo_project_domain::features::apply::usecase::{ApplyUsecase, Usecase};
+
+/// Creates the project and features described by a spec file, adding only what's missing
+#[derive(Debug, PartialEq, StructOpt)]
+pub struct Apply {
+    /// Spec file listing the project's features, usecases and entity fields
+    spec: String,
+}
+
+impl Run for Apply {
+    fn run(&self) -> Report {
+        let usecase = ApplyUsecase::new();
+        block_on(usecase.apply_spec(&self.spec))
+    }
+}
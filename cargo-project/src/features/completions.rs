@@ -0,0 +1,248 @@
+use cargo_project_domain::core::report::{ErrorCode, Report};
+use cargo_project_domain::features::completions::usecase::{CompletionsUsecase, Usecase};
+use futures::executor::block_on;
+use structopt::clap::{App, Shell};
+use structopt::StructOpt;
+
+use crate::core::command::Command;
+use crate::core::run::Run;
+
+const BIN_NAME: &str = "cargo-project";
+
+/// Command printing the features of the project, called by the completion scripts.
+const FEATURES_COMMAND: &str = "cargo-project complete-features 2>/dev/null";
+
+/// Prints a completion script for the shell, completing feature names from the project
+/// layout
+#[derive(Debug, PartialEq, StructOpt)]
+pub struct Completions {
+    /// Shell the script is written for
+    #[structopt(possible_values = &["bash", "zsh", "fish", "powershell"])]
+    shell: String,
+}
+
+/// Prints the names of the project's features one per line, for the completion scripts
+#[derive(Debug, PartialEq, StructOpt)]
+pub struct CompleteFeatures {}
+
+/// Paths of the `commands` taking a `feature` positional argument, which gets completed
+/// with the names of the project's features.
+fn feature_commands(commands: &[Command], path: &[String]) -> Vec<Vec<String>> {
+    let mut feature_commands = Vec::new();
+
+    for command in commands {
+        let mut path = path.to_vec();
+        path.push(command.name.to_string());
+
+        if command.takes_positional("feature") {
+            feature_commands.push(path.clone());
+        }
+        feature_commands.extend(self::feature_commands(&command.subcommands, &path));
+    }
+
+    feature_commands
+}
+
+fn bash(script: &str, commands: &[Vec<String>]) -> String {
+    // Words are matched with and without the `project` cargo passes on
+    let patterns = commands
+        .iter()
+        .map(|path| format!("\"{0}\"|\"project {0}\"", path.join(" ")))
+        .collect::<Vec<_>>()
+        .join("|");
+
+    let complete = format!(
+        r#"_cargo-project-features() {{
+    local i words=()
+    for i in "${{COMP_WORDS[@]:1:COMP_CWORD-1}}"; do
+        [[ ${{i}} != -* ]] && words+=("${{i}}")
+    done
+
+    case "${{words[*]}}" in
+        {patterns})
+            if [[ ${{COMP_WORDS[COMP_CWORD]}} != -* ]]; then
+                COMPREPLY=( $(compgen -W "$({features})" -- "${{COMP_WORDS[COMP_CWORD]}}") )
+                return 0
+            fi
+            ;;
+    esac
+
+    _cargo-project
+}}
+
+complete -F _cargo-project-features -o bashdefault -o default {bin}"#,
+        patterns = patterns,
+        features = FEATURES_COMMAND,
+        bin = BIN_NAME,
+    );
+
+    script.replace(
+        &format!(
+            "complete -F _cargo-project -o bashdefault -o default {}",
+            BIN_NAME
+        ),
+        &complete,
+    )
+}
+
+fn zsh(script: &str) -> String {
+    let script = script
+        .lines()
+        .map(|line| {
+            let argument = line.trim_start_matches('\'').trim_start_matches(':');
+            match argument.starts_with("feature:") || argument.starts_with("feature -- ") {
+                true => line.replace(":_files'", ":_cargo-project_features'"),
+                false => line.to_string(),
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    let features = format!(
+        r#"(( $+functions[_cargo-project_features] )) ||
+_cargo-project_features() {{
+    local features; features=(${{(f)"$({})"}})
+    _describe -t features 'features' features "$@"
+}}
+
+"#,
+        FEATURES_COMMAND
+    );
+
+    let call = format!("_{} \"$@\"", BIN_NAME);
+    script.replace(&call, &format!("{}{}", features, call))
+}
+
+fn fish(script: &str, commands: &[Vec<String>]) -> String {
+    let mut script = script.to_string();
+
+    for path in commands {
+        let subcommand = path.last().expect("Error reading subcommand name");
+        script.push_str(&format!(
+            "complete -c {bin} -n \"__fish_seen_subcommand_from {subcommand}; and not __fish_seen_subcommand_from ({features})\" -f -a \"({features})\"\n",
+            bin = BIN_NAME,
+            subcommand = subcommand,
+            features = FEATURES_COMMAND,
+        ));
+    }
+
+    script
+}
+
+fn powershell(script: &str, commands: &[Vec<String>]) -> String {
+    let mut script = script.to_string();
+
+    for path in commands {
+        let case = format!("'{};{}' {{", BIN_NAME, path.join(";"));
+        let complete = format!(
+            "{}\n            {} | ForEach-Object {{ [CompletionResult]::new($_, $_, [CompletionResultType]::ParameterValue, $_) }}",
+            case,
+            FEATURES_COMMAND.replace("2>/dev/null", "2>$null"),
+        );
+
+        script = script.replace(&case, &complete);
+    }
+
+    script
+}
+
+/// Script completing the `commands` in `shell` along with the feature names, `app` holding
+/// the arguments every command takes.
+fn completion_script(app: App<'static, 'static>, commands: &[Command], shell: Shell) -> String {
+    let mut app = app.subcommands(commands.iter().map(|command| command.app.clone()));
+    let commands = feature_commands(commands, &[]);
+
+    let mut buffer = Vec::new();
+    app.gen_completions_to(BIN_NAME, shell, &mut buffer);
+    let script = String::from_utf8_lossy(&buffer);
+
+    match shell {
+        Shell::Bash => bash(&script, &commands),
+        Shell::Zsh => zsh(&script),
+        Shell::Fish => fish(&script, &commands),
+        Shell::PowerShell => powershell(&script, &commands),
+        Shell::Elvish => script.to_string(),
+    }
+}
+
+impl Run for Completions {
+    fn run(&self) -> Report {
+        let shell = self.shell.parse::<Shell>().expect("Error parsing shell");
+        let script = completion_script(crate::Options::clap(), &crate::Cmd::commands(), shell);
+
+        match script.is_empty() {
+            true => Report::failure(ErrorCode::Failed, "Failed to generate completion script"),
+            false => Report::success(script.trim_end()),
+        }
+    }
+}
+
+impl Run for CompleteFeatures {
+    fn run(&self) -> Report {
+        let usecase = CompletionsUsecase::new();
+        block_on(usecase.feature_names())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use structopt::clap::{Arg, SubCommand};
+
+    fn command(name: &'static str, app: App<'static, 'static>) -> Command {
+        Command {
+            name,
+            app,
+            subcommands: vec![],
+        }
+    }
+
+    fn commands() -> Vec<Command> {
+        let usecase = SubCommand::with_name("usecase")
+            .arg(Arg::with_name("feature").required(true))
+            .arg(Arg::with_name("name"));
+        let feature = SubCommand::with_name("feature").arg(Arg::with_name("name"));
+
+        vec![
+            Command {
+                name: "add",
+                app: SubCommand::with_name("add")
+                    .subcommand(usecase.clone())
+                    .subcommand(feature.clone()),
+                subcommands: vec![command("usecase", usecase), command("feature", feature)],
+            },
+            command(
+                "remove",
+                SubCommand::with_name("remove").arg(Arg::with_name("feature")),
+            ),
+        ]
+    }
+
+    #[test]
+    fn test_feature_commands() {
+        let commands = feature_commands(&commands(), &[]);
+
+        assert_eq!(
+            vec![
+                vec![String::from("add"), String::from("usecase")],
+                vec![String::from("remove")],
+            ],
+            commands
+        );
+    }
+
+    #[test]
+    fn test_completion_script() {
+        let bash = completion_script(App::new("cli"), &commands(), Shell::Bash);
+        let zsh = completion_script(App::new("cli"), &commands(), Shell::Zsh);
+        let fish = completion_script(App::new("cli"), &commands(), Shell::Fish);
+
+        assert!(bash.contains(r#""add usecase"|"project add usecase"|"remove"|"project remove")"#));
+        assert!(bash.ends_with(
+            "complete -F _cargo-project-features -o bashdefault -o default cargo-project\n"
+        ));
+        assert!(zsh.contains("':feature:_cargo-project_features'"));
+        assert!(fish.contains("__fish_seen_subcommand_from remove; and not"));
+    }
+}
@@ -0,0 +1,17 @@
+use futures::executor::block_on;
+use structopt::StructOpt;
+
+use crate::core::run::Run;
+use cargo_project_domain::core::report::Report;
+use cargo_project_domain::features::docs::usecase::{DocsUsecase, Usecase};
+
+/// Writes ARCHITECTURE.md and a README.md for every feature, keeping hand-written sections
+#[derive(Debug, PartialEq, StructOpt)]
+pub struct Docs {}
+
+impl Run for Docs {
+    fn run(&self) -> Report {
+        let usecase = DocsUsecase::new();
+        block_on(usecase.generate_docs())
+    }
+}
@@ -0,0 +1,21 @@
+use futures::executor::block_on;
+use structopt::StructOpt;
+
+use crate::core::run::Run;
+use cargo_project_domain::core::report::Report;
+use cargo_project_domain::features::export_spec::usecase::{ExportSpecUsecase, Usecase};
+
+/// Writes the project's features, usecases and entity fields to a spec file
+#[derive(Debug, PartialEq, StructOpt)]
+pub struct ExportSpec {
+    /// Spec file to write, or - to print the spec
+    #[structopt(short, long, default_value = "spec.toml")]
+    output: String,
+}
+
+impl Run for ExportSpec {
+    fn run(&self) -> Report {
+        let usecase = ExportSpecUsecase::new();
+        block_on(usecase.export_spec(&self.output))
+    }
+}
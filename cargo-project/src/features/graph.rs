@@ -0,0 +1,22 @@
+use futures::executor::block_on;
+use structopt::StructOpt;
+
+use crate::core::run::Run;
+use cargo_project_domain::core::report::Report;
+use cargo_project_domain::features::graph::entities::GraphFormat;
+use cargo_project_domain::features::graph::usecase::{GraphUsecase, Usecase};
+
+/// Prints a diagram of the layer crates, feature modules and their dependencies
+#[derive(Debug, PartialEq, StructOpt)]
+pub struct Graph {
+    /// Diagram language to print the graph in
+    #[structopt(long, default_value = "dot", possible_values = &["dot", "mermaid"])]
+    format: GraphFormat,
+}
+
+impl Run for Graph {
+    fn run(&self) -> Report {
+        let usecase = GraphUsecase::new();
+        block_on(usecase.project_graph(self.format))
+    }
+}
@@ -0,0 +1,22 @@
+use futures::executor::block_on;
+use structopt::StructOpt;
+
+use crate::core::run::Run;
+use cargo_project_domain::core::report::Report;
+use cargo_project_domain::core::runtime::Runtime;
+use cargo_project_domain::features::init::usecase::{InitUsecase, Usecase};
+
+/// Converts the package in the current directory into a layered workspace
+#[derive(Debug, PartialEq, StructOpt)]
+pub struct Init {
+    /// Async runtime the generated layers are built around
+    #[structopt(long, default_value = "async-std", possible_values = &["tokio", "async-std", "sync"])]
+    runtime: Runtime,
+}
+
+impl Run for Init {
+    fn run(&self) -> Report {
+        let usecase = InitUsecase::new(self.runtime);
+        block_on(usecase.init_project())
+    }
+}
@@ -0,0 +1,133 @@
+use cargo_project_domain::core::report::{ErrorCode, Report};
+use structopt::StructOpt;
+
+use crate::core::command::Command;
+use crate::core::run::Run;
+
+/// Prints a man page of every command, to be installed as cargo-project.1
+#[derive(Debug, PartialEq, StructOpt)]
+pub struct Man {}
+
+/// Escapes `text` so roff prints it as is.
+fn escape(text: &str) -> String {
+    text.replace('\\', "\\e")
+        .lines()
+        .map(
+            |line| match line.starts_with('.') || line.starts_with('\'') {
+                true => format!("\\&{}", line),
+                false => line.to_string(),
+            },
+        )
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Writes a section for `command` invoked as `name`, then one for each of its subcommands,
+/// keeping the help output's layout in no-fill mode.
+fn write_commands(command: &Command, name: &str, page: &mut String) -> Result<(), String> {
+    let mut help = Vec::new();
+    command
+        .app
+        .clone()
+        .bin_name(name)
+        .write_long_help(&mut help)
+        .map_err(|err| err.to_string())?;
+
+    page.push_str(&format!(".SS \"{}\"\n.nf\n", name));
+    page.push_str(&escape(String::from_utf8_lossy(&help).trim_end()));
+    page.push_str("\n.fi\n");
+
+    for subcommand in &command.subcommands {
+        write_commands(subcommand, &format!("{} {}", name, subcommand.name), page)?;
+    }
+
+    Ok(())
+}
+
+/// Renders the man page of the `root` command.
+fn man_page(root: &Command) -> Result<String, String> {
+    let name = root.name;
+
+    let mut page = format!(
+        ".TH {} 1 \"\" \"{} {}\"\n.SH NAME\n{} \\- {}\n.SH SYNOPSIS\n\\fB{}\\fR [OPTIONS] <SUBCOMMAND>\n.SH COMMANDS\n",
+        name.to_uppercase(),
+        name,
+        env!("CARGO_PKG_VERSION"),
+        name,
+        escape(&root.about()),
+        name,
+    );
+
+    for subcommand in &root.subcommands {
+        page.push_str(&format!(
+            ".TP\n\\fB{}\\fR\n{}\n",
+            subcommand.name,
+            escape(&subcommand.about()),
+        ));
+    }
+
+    page.push_str(".SH DESCRIPTION\n");
+    write_commands(root, name, &mut page)?;
+
+    Ok(page)
+}
+
+impl Run for Man {
+    fn run(&self) -> Report {
+        let root = Command::new::<crate::Project>("cargo-project", crate::Cmd::commands());
+
+        match man_page(&root) {
+            Ok(page) => Report::success(page.trim_end()),
+            Err(err) => Report::failure(
+                ErrorCode::Failed,
+                format!("Failed to generate man page - {}", err),
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use structopt::clap::{App, Arg, SubCommand};
+
+    #[test]
+    fn test_escape() {
+        assert_eq!(
+            "\\&.gitignore\nC:\\eprojects\n\\&'quoted'",
+            escape(".gitignore\nC:\\projects\n'quoted'")
+        );
+    }
+
+    #[test]
+    fn test_man_page() {
+        let root = Command {
+            name: "cli",
+            app: App::new("cli")
+                .about("Creates projects")
+                .arg(Arg::with_name("verbose").long("verbose").global(true))
+                .subcommand(SubCommand::with_name("new")),
+            subcommands: vec![Command {
+                name: "new",
+                app: App::new("new")
+                    .about("Creates a new project")
+                    .arg(Arg::with_name("name").help("Name of the project")),
+                subcommands: vec![Command {
+                    name: "lib",
+                    app: App::new("lib"),
+                    subcommands: vec![],
+                }],
+            }],
+        };
+
+        let page = man_page(&root).unwrap();
+
+        assert!(page.starts_with(".TH CLI 1"));
+        assert!(page.contains(".SH NAME\ncli \\- Creates projects\n"));
+        assert!(page.contains(".TP\n\\fBnew\\fR\nCreates a new project\n"));
+        assert!(page.contains(".SS \"cli new\"\n.nf\n"));
+        assert!(page.contains(".SS \"cli new lib\"\n.nf\n"));
+        assert!(page.contains("Name of the project"));
+    }
+}
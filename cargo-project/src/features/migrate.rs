@@ -0,0 +1,61 @@
+use futures::executor::block_on;
+use structopt::StructOpt;
+
+use crate::core::run::Run;
+use cargo_project_domain::core::report::Report;
+use cargo_project_domain::features::migrate::entities::Role;
+use cargo_project_domain::features::migrate::usecase::{MigrateUsecase, Usecase};
+
+/// Moves the items of an existing module into the layers of a feature
+#[derive(Debug, PartialEq, StructOpt)]
+pub struct Migrate {
+    /// Path of the module to split, relative to the workspace root
+    #[structopt(long)]
+    module: String,
+
+    /// Feature the module's items are moved to
+    #[structopt(long)]
+    to_feature: String,
+
+    /// Items moved to the feature's entities, whatever their guessed role
+    #[structopt(long, use_delimiter = true)]
+    entity: Vec<String>,
+
+    /// Items moved to the feature's repository
+    #[structopt(long, use_delimiter = true)]
+    repository: Vec<String>,
+
+    /// Items moved to the feature's usecase
+    #[structopt(long, use_delimiter = true)]
+    usecase: Vec<String>,
+
+    /// Items moved to the feature's datasource
+    #[structopt(long, use_delimiter = true)]
+    datasource: Vec<String>,
+
+    /// Items moved to the feature's presentation module
+    #[structopt(long, use_delimiter = true)]
+    presentation: Vec<String>,
+}
+
+impl Migrate {
+    fn roles(&self) -> Vec<(String, Role)> {
+        [
+            (&self.entity, Role::Entity),
+            (&self.repository, Role::Repository),
+            (&self.usecase, Role::Usecase),
+            (&self.datasource, Role::Datasource),
+            (&self.presentation, Role::Presentation),
+        ]
+        .iter()
+        .flat_map(|(names, role)| names.iter().map(move |name| (name.clone(), *role)))
+        .collect()
+    }
+}
+
+impl Run for Migrate {
+    fn run(&self) -> Report {
+        let usecase = MigrateUsecase::new();
+        block_on(usecase.migrate_module(&self.module, &self.to_feature, &self.roles()))
+    }
+}
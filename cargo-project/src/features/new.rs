@@ -1,34 +1,179 @@
 use std::env;
-use std::path::PathBuf;
+use std::io::{self, BufRead, Write};
+use std::path::{Path, PathBuf};
 
 use futures::executor::block_on;
+use structopt::clap::{Error, ErrorKind};
 use structopt::StructOpt;
 
+use crate::core::prompt::{is_interactive, validate_name, Prompt};
 use crate::core::run::Run;
+use crate::features::verify::verify_changes;
+use cargo_project_domain::core::project::{ProjectKind, Vcs};
+use cargo_project_domain::core::report::{ErrorCode, Report};
+use cargo_project_domain::core::runtime::Runtime;
 use cargo_project_domain::features::new::usecase::{NewUsecase, Usecase};
 
+/// Creates a project with presentation, domain and data crates
 #[derive(Debug, PartialEq, StructOpt)]
 pub struct New {
-    name: String,
+    /// Name of the project, asked for when left out in a terminal
+    name: Option<String>,
 
     #[structopt(parse(from_os_str), short, long)]
     path: Option<PathBuf>,
+
+    /// Async runtime the generated layers are built around
+    #[structopt(long, default_value = "async-std", possible_values = &["tokio", "async-std", "sync"])]
+    runtime: Runtime,
+
+    /// Generate a facade library re-exporting the domain crate instead of a binary
+    #[structopt(long)]
+    lib: bool,
+
+    /// Version control to set up, git unless created inside a git repository
+    #[structopt(long, possible_values = &["git", "none"])]
+    vcs: Option<Vcs>,
+
+    /// Add a CI workflow, rustfmt and clippy configuration and workspace lints
+    #[structopt(long)]
+    with_ci: bool,
+
+    /// Set from the global `--verify`, as only `new` knows where the project it created is
+    #[structopt(skip)]
+    pub verify: bool,
+
+    /// Set from the global `--commit`, committed to the repository of the created project
+    #[structopt(skip)]
+    pub commit: bool,
+}
+
+impl New {
+    fn kind(&self) -> ProjectKind {
+        if self.lib {
+            ProjectKind::Library
+        } else {
+            ProjectKind::Binary
+        }
+    }
+
+    /// Asks for the project settings, using the given options as defaults, and returns
+    /// them once the plan is confirmed.
+    fn wizard<R: BufRead, W: Write>(
+        &self,
+        prompt: &mut Prompt<R, W>,
+        current_dir: &Path,
+    ) -> io::Result<Option<(String, PathBuf, Runtime, ProjectKind)>> {
+        let name = prompt.ask("Project name", None, |answer| validate_name(answer, true))?;
+        let path = self.path.as_deref().unwrap_or(current_dir);
+        let path = prompt.ask(
+            "Directory to create the project in",
+            Some(&path.display().to_string()),
+            |answer| Ok(PathBuf::from(answer)),
+        )?;
+        let runtime: Runtime = prompt
+            .choose(
+                "Async runtime",
+                &["tokio", "async-std", "sync"],
+                self.runtime.name(),
+            )?
+            .parse()
+            .map_err(|err| io::Error::new(io::ErrorKind::InvalidInput, err))?;
+        let kind: ProjectKind = prompt
+            .choose("Presentation", &["bin", "lib"], self.kind().name())?
+            .parse()
+            .map_err(|err| io::Error::new(io::ErrorKind::InvalidInput, err))?;
+
+        prompt.tell("")?;
+        prompt.tell(&format!(
+            "Project {} will be created in {}",
+            name,
+            path.join(&name).display()
+        ))?;
+        prompt.tell(&format!("  runtime: {}", runtime.name()))?;
+        prompt.tell(&format!("  presentation: {}", kind.name()))?;
+
+        match prompt.confirm("Create it?")? {
+            true => Ok(Some((name, path, runtime, kind))),
+            false => Ok(None),
+        }
+    }
 }
 
 impl Run for New {
-    fn run(&self) {
-        match &self.path {
-            Some(path) => {
-                let usecase = NewUsecase::new(&self.name, &path);
-                println!("{}", block_on(usecase.create_project()));
-            }
-            None => {
-                let current_dir =
-                    env::current_dir().expect("Error reading current directory from environment");
-
-                let usecase = NewUsecase::new(&self.name, &current_dir);
-                println!("{}", block_on(usecase.create_project()));
-            }
+    fn run(&self) -> Report {
+        let current_dir =
+            env::current_dir().expect("Error reading current directory from environment");
+
+        let (name, path, runtime, kind) = match &self.name {
+            Some(name) => (
+                name.clone(),
+                self.path.clone().unwrap_or_else(|| current_dir.clone()),
+                self.runtime,
+                self.kind(),
+            ),
+            None if is_interactive() => match self.wizard(&mut Prompt::stdio(), &current_dir) {
+                Ok(Some(settings)) => settings,
+                Ok(None) => {
+                    return Report::failure(ErrorCode::Cancelled, "Cancelled creating project")
+                }
+                Err(err) => {
+                    return Report::failure(
+                        ErrorCode::Failed,
+                        format!("Failed to read answers - {}", err),
+                    )
+                }
+            },
+            None => Error::with_description(
+                "The following required arguments were not provided:\n    <name>",
+                ErrorKind::MissingRequiredArgument,
+            )
+            .exit(),
+        };
+
+        let usecase = NewUsecase::new(&name, &path, runtime, kind, self.vcs, self.with_ci);
+        let report = block_on(usecase.create_project());
+
+        // Located like the paths in the report
+        let root = path.join(&name);
+        let root = root.strip_prefix(&current_dir).unwrap_or(&root);
+        let root = root.strip_prefix(".").unwrap_or(root).display().to_string();
+
+        let report = match self.verify {
+            true => verify_changes(report, &root),
+            false => report,
+        };
+
+        match self.commit {
+            true => crate::commit_changes(report, &root),
+            false => report,
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_wizard() {
+        let new = New::from_iter(&["new", "--runtime", "tokio"]);
+        let input = "shop\n\nsync\nlib\nn\n";
+        let mut output = Vec::new();
+
+        let settings = new
+            .wizard(
+                &mut Prompt::new(input.as_bytes(), &mut output),
+                Path::new("/projects"),
+            )
+            .unwrap();
+
+        assert_eq!(None, settings);
+        assert!(String::from_utf8(output).unwrap().ends_with(concat!(
+            "\nProject shop will be created in /projects/shop\n",
+            "  runtime: sync\n",
+            "  presentation: lib\n",
+            "Create it? (y/n) [y]: ",
+        )));
+    }
+}
@@ -0,0 +1,17 @@
+use futures::executor::block_on;
+use structopt::StructOpt;
+
+use crate::core::run::Run;
+use cargo_project_domain::core::report::Report;
+use cargo_project_domain::features::status::usecase::{StatusUsecase, Usecase};
+
+/// Reports which generated files are pristine, modified or from an outdated template
+#[derive(Debug, PartialEq, StructOpt)]
+pub struct Status {}
+
+impl Run for Status {
+    fn run(&self) -> Report {
+        let usecase = StatusUsecase::new();
+        block_on(usecase.project_status())
+    }
+}
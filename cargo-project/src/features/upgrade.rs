@@ -0,0 +1,17 @@
+use futures::executor::block_on;
+use structopt::StructOpt;
+
+use crate::core::run::Run;
+use cargo_project_domain::core::report::Report;
+use cargo_project_domain::features::upgrade::usecase::{UpgradeUsecase, Usecase};
+
+/// Merges the current templates into the files generated for every feature
+#[derive(Debug, PartialEq, StructOpt)]
+pub struct Upgrade {}
+
+impl Run for Upgrade {
+    fn run(&self) -> Report {
+        let usecase = UpgradeUsecase::new();
+        block_on(usecase.upgrade_project())
+    }
+}
@@ -0,0 +1,35 @@
+use futures::executor::block_on;
+use structopt::StructOpt;
+
+use crate::core::run::Run;
+use cargo_project_domain::core::report::Report;
+use cargo_project_domain::features::verify::usecase::{Usecase, VerifyUsecase};
+
+/// Checks the project and compiles its tests offline, naming the template behind every
+/// file with errors
+#[derive(Debug, PartialEq, StructOpt)]
+pub struct Verify {}
+
+impl Run for Verify {
+    fn run(&self) -> Report {
+        let usecase = VerifyUsecase::new(".");
+        block_on(usecase.verify())
+    }
+}
+
+/// Verifies the project rooted in `root` after the command that reported `report` changed
+/// it, failing the command when the project doesn't compile.
+pub fn verify_changes(mut report: Report, root: &str) -> Report {
+    if !report.success {
+        return report;
+    }
+
+    let verified = block_on(VerifyUsecase::new(root).verify());
+    report.details.push(verified.message);
+    report.details.extend(verified.details);
+    report.notes.extend(verified.notes);
+    report.success = verified.success;
+    report.error = verified.error;
+
+    report
+}
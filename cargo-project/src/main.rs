@@ -1,10 +1,22 @@
+use crate::core::command::Command;
+use crate::core::message_format::MessageFormat;
 use crate::core::run::Run;
+use crate::features::verify::verify_changes;
 
+use cargo_project_domain::core::report::Report;
+use cargo_project_domain::features::vcs::usecase::{Usecase, VcsUsecase};
+use futures::executor::block_on;
+use std::env;
+use std::path::Path;
+use std::process;
+use structopt::clap::AppSettings;
 use structopt::StructOpt;
 
 mod core;
 mod features;
 
+/// Cargo extension for building rust projects separated into presentation, domain and data
+/// layers
 #[derive(Debug, PartialEq, StructOpt)]
 struct Project {
     #[structopt(subcommand)]
@@ -13,19 +25,253 @@ struct Project {
     // Handling this binary being called on it's own: cargo-project new test
     // and being called via cargo:                    cargo project new test
     cargo_project: Option<String>,
+
+    #[structopt(flatten)]
+    options: Options,
+}
+
+// How every command is run. Not a doc comment, as flattening it would replace the about
+// of `Project`
+#[derive(Debug, PartialEq, StructOpt)]
+struct Options {
+    /// Print the outcome as human readable text or as a JSON object for tools
+    #[structopt(long, global = true, default_value = "human", possible_values = &["human", "json"])]
+    message_format: MessageFormat,
+
+    /// Run even though the worktree has uncommitted changes
+    #[structopt(long, global = true)]
+    allow_dirty: bool,
+
+    /// Commit the files the command created and modified
+    #[structopt(long, global = true)]
+    commit: bool,
+
+    /// Check the project and compile its tests offline once the command changed it
+    #[structopt(long, global = true)]
+    verify: bool,
 }
 
 #[derive(Debug, PartialEq, StructOpt)]
 enum Cmd {
     Add(features::add::Add),
+    Apply(features::apply::Apply),
+    #[structopt(setting = AppSettings::Hidden)]
+    CompleteFeatures(features::completions::CompleteFeatures),
+    Completions(features::completions::Completions),
+    Docs(features::docs::Docs),
+    ExportSpec(features::export_spec::ExportSpec),
+    Graph(features::graph::Graph),
+    Init(features::init::Init),
+    Man(features::man::Man),
+    Migrate(features::migrate::Migrate),
     New(features::new::New),
+    Status(features::status::Status),
+    Upgrade(features::upgrade::Upgrade),
+    Verify(features::verify::Verify),
+}
+
+impl Cmd {
+    /// The commands listed by the man page and completed by the completion scripts, which
+    /// leaves out the hidden ones.
+    fn commands() -> Vec<Command> {
+        vec![
+            Command::new::<features::add::Add>("add", features::add::commands()),
+            Command::new::<features::apply::Apply>("apply", vec![]),
+            Command::new::<features::completions::Completions>("completions", vec![]),
+            Command::new::<features::docs::Docs>("docs", vec![]),
+            Command::new::<features::export_spec::ExportSpec>("export-spec", vec![]),
+            Command::new::<features::graph::Graph>("graph", vec![]),
+            Command::new::<features::init::Init>("init", vec![]),
+            Command::new::<features::man::Man>("man", vec![]),
+            Command::new::<features::migrate::Migrate>("migrate", vec![]),
+            Command::new::<features::new::New>("new", vec![]),
+            Command::new::<features::status::Status>("status", vec![]),
+            Command::new::<features::upgrade::Upgrade>("upgrade", vec![]),
+            Command::new::<features::verify::Verify>("verify", vec![]),
+        ]
+    }
+
+    /// Whether the command writes to the project it runs in, which guards it against
+    /// dirty worktrees and lets it commit what it wrote. `new` writes a project of its own,
+    /// which it verifies and commits itself.
+    fn writes_project(&self) -> bool {
+        !matches!(
+            self,
+            Cmd::CompleteFeatures(_)
+                | Cmd::Completions(_)
+                | Cmd::Graph(_)
+                | Cmd::Man(_)
+                | Cmd::New(_)
+                | Cmd::Status(_)
+                | Cmd::Verify(_)
+        )
+    }
+}
+
+impl Run for Cmd {
+    fn run(&self) -> Report {
+        match self {
+            Cmd::Add(add) => add.run(),
+            Cmd::Apply(apply) => apply.run(),
+            Cmd::CompleteFeatures(complete_features) => complete_features.run(),
+            Cmd::Completions(completions) => completions.run(),
+            Cmd::Docs(docs) => docs.run(),
+            Cmd::ExportSpec(export_spec) => export_spec.run(),
+            Cmd::Graph(graph) => graph.run(),
+            Cmd::Init(init) => init.run(),
+            Cmd::Man(man) => man.run(),
+            Cmd::Migrate(migrate) => migrate.run(),
+            Cmd::New(new) => new.run(),
+            Cmd::Status(status) => status.run(),
+            Cmd::Upgrade(upgrade) => upgrade.run(),
+            Cmd::Verify(verify) => verify.run(),
+        }
+    }
+}
+
+/// The command as typed, without the options deciding how it's run, describing commits.
+fn commit_subject(args: impl Iterator<Item = String>) -> String {
+    let mut words = vec![String::from("cargo project")];
+    let mut args = args.skip(1).peekable();
+
+    // Called via cargo, the subcommand name comes first
+    args.next_if(|arg| arg == "project");
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--allow-dirty" | "--commit" | "--verify" => continue,
+            "--message-format" => {
+                args.next();
+            }
+            _ if arg.starts_with("--message-format=") => continue,
+            _ => words.push(arg),
+        }
+    }
+
+    words.join(" ")
+}
+
+/// Commits the files `report` lists to the repository of the project in `root`, noting the
+/// outcome in the report.
+fn commit_changes(mut report: Report, root: &str) -> Report {
+    if !report.success {
+        return report;
+    }
+
+    // Reported paths are relative to the current directory, git commits them from `root`
+    let relative = |paths: &[String]| {
+        paths
+            .iter()
+            .map(|path| match Path::new(path).strip_prefix(root) {
+                Ok(relative) => relative.display().to_string(),
+                Err(_) => path.clone(),
+            })
+            .collect::<Vec<_>>()
+    };
+    let mut changes = report.clone();
+    changes.created = relative(&report.created);
+    changes.modified = relative(&report.modified);
+
+    let usecase = VcsUsecase::new(root);
+    let committed = block_on(usecase.commit_changes(&commit_subject(env::args()), &changes));
+    match committed.success {
+        true => report.notes.push(committed.message),
+        false => report.warnings.push(committed.message),
+    }
+
+    report
 }
 
 fn main() {
-    let project = Project::from_args();
+    let mut project = Project::from_args();
+    if let Cmd::New(new) = &mut project.cmd {
+        new.verify = project.options.verify;
+        new.commit = project.options.commit;
+    }
+    let writes_project = project.cmd.writes_project();
+    let usecase = VcsUsecase::new(".");
+
+    let clean = match writes_project && !project.options.allow_dirty {
+        true => block_on(usecase.check_clean()),
+        false => Report::success(""),
+    };
+
+    let report = match clean.success {
+        true => {
+            let mut report = project.cmd.run();
+
+            if writes_project && project.options.verify {
+                report = verify_changes(report, ".");
+            }
+
+            if writes_project && project.options.commit {
+                report = commit_changes(report, ".");
+            }
+
+            report
+        }
+        false => {
+            let mut clean = clean;
+            if clean.error.is_some() {
+                clean
+                    .notes
+                    .push(String::from("commit the changes or pass --allow-dirty"));
+            }
+            clean
+        }
+    };
+
+    println!("{}", project.options.message_format.render(&report));
+
+    if !report.success {
+        process::exit(1);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(command: &str) -> impl Iterator<Item = String> + '_ {
+        command.split(' ').map(String::from)
+    }
+
+    #[test]
+    fn test_commands() {
+        let mut help = Vec::new();
+        Project::clap()
+            .set_term_width(0)
+            .template("{subcommands}")
+            .write_help(&mut help)
+            .unwrap();
+        let listed = String::from_utf8_lossy(&help)
+            .lines()
+            .filter_map(|line| line.split_whitespace().next())
+            .filter(|name| *name != "help")
+            .map(String::from)
+            .collect::<Vec<_>>();
+
+        let commands = Cmd::commands();
+        let names = commands
+            .iter()
+            .map(|command| command.name.to_string())
+            .collect::<Vec<_>>();
+
+        assert_eq!(listed, names);
+        assert!(commands.iter().all(|command| !command.about().is_empty()));
+    }
 
-    match project.cmd {
-        Cmd::Add(add) => add.run(),
-        Cmd::New(new) => new.run(),
+    #[test]
+    fn test_commit_subject() {
+        assert_eq!(
+            "cargo project add feature notes",
+            commit_subject(args("cargo-project project add feature notes --commit"))
+        );
+        assert_eq!(
+            "cargo project docs",
+            commit_subject(args(
+                "cargo-project --message-format json docs --allow-dirty --commit --verify"
+            ))
+        );
     }
 }